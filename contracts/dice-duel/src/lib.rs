@@ -3,15 +3,17 @@
 //! # Dice Duel
 //!
 //! A two-player dice game where each player rolls two dice.
-//! The player with the highest total wins (ties go to player 1).
+//! The player with the highest total wins the roll; tied rolls are settled
+//! by the game's `TiePolicy`. Games are played as a best-of-N series of
+//! rolls; the first player to win a majority of the rolls wins the game.
 //!
 //! **Game Hub Integration:**
 //! This game is Game Hub-aware and enforces all games to be played through the
 //! Game Hub contract. Games cannot be started or completed without points involvement.
 
 use soroban_sdk::{
-    Address, Bytes, BytesN, Env, IntoVal, contract, contractclient, contracterror, contractimpl,
-    contracttype, vec
+    Address, Bytes, BytesN, Env, IntoVal, Vec, contract, contractclient, contracterror,
+    contractimpl, contracttype, vec
 };
 
 // Import GameHub contract interface
@@ -48,12 +50,42 @@ pub enum Error {
     AlreadyRolled = 3,
     BothPlayersNotRolled = 4,
     GameAlreadyEnded = 5,
+    InvalidSeries = 6,
 }
 
 // ============================================================================
 // Data Types
 // ============================================================================
 
+/// What happens when both players roll the same total.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TiePolicy {
+    /// The tied roll goes to player 1, as in the original single-roll game.
+    Player1,
+    /// Sudden death: the tied roll does not count and both players roll again.
+    Reroll,
+    /// The tied roll is drawn. It counts towards the series without a point;
+    /// once every roll is played, the leader wins or the game is a draw.
+    /// The Game Hub has no draw result, so a drawn game is not reported and
+    /// neither player is paid.
+    Draw,
+    /// The higher single die wins the roll; identical dice are re-rolled.
+    HighDie,
+}
+
+/// Dice and winner of one roll of a series.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RollResult {
+    pub player1_die1: u32,
+    pub player1_die2: u32,
+    pub player2_die1: u32,
+    pub player2_die2: u32,
+    /// None for a tied roll.
+    pub winner: Option<Address>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Game {
@@ -68,6 +100,17 @@ pub struct Game {
     pub player2_die1: Option<u32>,
     pub player2_die2: Option<u32>,
     pub winner: Option<Address>,
+    /// Number of rolls in the series (odd; 1 = single roll).
+    pub series_length: u32,
+    /// Current roll number, starting at 1.
+    pub roll: u32,
+    pub player1_series_points: u32,
+    pub player2_series_points: u32,
+    /// Results of the rolls revealed so far, oldest first.
+    pub rolls: Vec<RollResult>,
+    pub tie_policy: TiePolicy,
+    /// The game ended without a winner (`TiePolicy::Draw` only).
+    pub is_draw: bool,
 }
 
 #[contracttype]
@@ -132,6 +175,9 @@ impl DiceDuelContract {
     /// * `player2` - Address of second player
     /// * `player1_points` - Points amount committed by player 1
     /// * `player2_points` - Points amount committed by player 2
+    /// * `series_length` - Rolls in the series (odd, e.g. 5 for best of 5)
+    /// * `tie_policy` - How tied rolls are settled
+    #[allow(clippy::too_many_arguments)]
     pub fn start_game(
         env: Env,
         session_id: u32,
@@ -139,15 +185,35 @@ impl DiceDuelContract {
         player2: Address,
         player1_points: i128,
         player2_points: i128,
+        series_length: u32,
+        tie_policy: TiePolicy,
     ) -> Result<(), Error> {
         // Prevent self-play: Player 1 and Player 2 must be different
         if player1 == player2 {
             panic!("Cannot play against yourself: Player 1 and Player 2 must be different addresses");
         }
 
+        // An odd series always has a majority winner
+        if series_length.is_multiple_of(2) {
+            return Err(Error::InvalidSeries);
+        }
+
         // Require authentication from both players (they consent to committing points)
-        player1.require_auth_for_args(vec![&env, session_id.into_val(&env), player1_points.into_val(&env)]);
-        player2.require_auth_for_args(vec![&env, session_id.into_val(&env), player2_points.into_val(&env)]);
+        // Both players also sign the series format they agreed to play
+        player1.require_auth_for_args(vec![
+            &env,
+            session_id.into_val(&env),
+            player1_points.into_val(&env),
+            series_length.into_val(&env),
+            tie_policy.into_val(&env),
+        ]);
+        player2.require_auth_for_args(vec![
+            &env,
+            session_id.into_val(&env),
+            player2_points.into_val(&env),
+            series_length.into_val(&env),
+            tie_policy.into_val(&env),
+        ]);
 
         // Get GameHub address
         let game_hub_addr: Address = env
@@ -183,6 +249,13 @@ impl DiceDuelContract {
             player2_die1: None,
             player2_die2: None,
             winner: None,
+            series_length,
+            roll: 1,
+            player1_series_points: 0,
+            player2_series_points: 0,
+            rolls: Vec::new(&env),
+            tie_policy,
+            is_draw: false,
         };
 
         // Store game in temporary storage with 30-day TTL
@@ -199,8 +272,8 @@ impl DiceDuelContract {
         Ok(())
     }
 
-    /// Commit a roll for the current game.
-    /// Both players must roll before the winner can be revealed.
+    /// Commit a roll for the current roll of the series.
+    /// Both players must roll before the roll can be revealed.
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
@@ -217,7 +290,7 @@ impl DiceDuelContract {
             .ok_or(Error::GameNotFound)?;
 
         // Check game is still active (no winner yet)
-        if game.winner.is_some() {
+        if game.winner.is_some() || game.is_draw {
             return Err(Error::GameAlreadyEnded);
         }
 
@@ -242,16 +315,22 @@ impl DiceDuelContract {
         Ok(())
     }
 
-    /// Reveal the winner of the game and submit outcome to GameHub.
+    /// Reveal the current roll of the series, and submit the outcome to
+    /// GameHub once the series is decided.
     /// Can only be called after both players have rolled.
-    /// This generates dice rolls for both players, determines the winner, and ends the session.
+    /// This generates dice for both players and awards the roll's series
+    /// point, settling a tied roll by the game's `TiePolicy`. If the series
+    /// is still undecided, both players roll again for the next roll;
+    /// otherwise the game ends and its winner, if any, is reported.
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
     ///
     /// # Returns
-    /// * `Address` - Address of the winning player
-    pub fn reveal_winner(env: Env, session_id: u32) -> Result<Address, Error> {
+    /// * `Option<Address>` - Address of the roll winner (check game.winner for
+    ///   the series), or `None` if the roll was tied; the game winner once
+    ///   the game has ended (`None` for a draw)
+    pub fn reveal_winner(env: Env, session_id: u32) -> Result<Option<Address>, Error> {
         // Get game from temporary storage
         let key = DataKey::Game(session_id);
         let mut game: Game = env
@@ -261,8 +340,8 @@ impl DiceDuelContract {
             .ok_or(Error::GameNotFound)?;
 
         // Check if game already ended (has a winner)
-        if let Some(winner) = &game.winner {
-            return Ok(winner.clone());
+        if game.winner.is_some() || game.is_draw {
+            return Ok(game.winner);
         }
 
         // Check both players have rolled
@@ -281,6 +360,10 @@ impl DiceDuelContract {
         seed_bytes.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
         seed_bytes.append(&game.player1.to_string().to_bytes());
         seed_bytes.append(&game.player2.to_string().to_bytes());
+        if game.roll > 1 {
+            // Later rolls of a series draw fresh dice
+            seed_bytes.append(&Bytes::from_array(&env, &game.roll.to_be_bytes()));
+        }
         let base_seed = env.crypto().keccak256(&seed_bytes);
 
         // Roll dice for both players using unique seeds
@@ -309,19 +392,70 @@ impl DiceDuelContract {
         game.player2_die1 = Some(player2_die1);
         game.player2_die2 = Some(player2_die2);
 
-        // Determine winner (if tie, player1 wins)
+        // Determine the roll winner, settling a tie by the game's policy
         let player1_total = player1_die1 + player1_die2;
         let player2_total = player2_die1 + player2_die2;
-        let winner = if player1_total >= player2_total {
-            game.player1.clone()
+        let (player1_score, player2_score) = match game.tie_policy {
+            TiePolicy::HighDie if player1_total == player2_total => (
+                player1_die1.max(player1_die2),
+                player2_die1.max(player2_die2),
+            ),
+            _ => (player1_total, player2_total),
+        };
+        let player1_takes_tie =
+            player1_score == player2_score && game.tie_policy == TiePolicy::Player1;
+        let roll_winner = if player1_score > player2_score || player1_takes_tie {
+            game.player1_series_points += 1;
+            Some(game.player1.clone())
+        } else if player2_score > player1_score {
+            game.player2_series_points += 1;
+            Some(game.player2.clone())
         } else {
-            game.player2.clone()
+            None
         };
+        game.rolls.push_back(RollResult {
+            player1_die1,
+            player1_die2,
+            player2_die1,
+            player2_die2,
+            winner: roll_winner.clone(),
+        });
+
+        // Keep rolling until one player has won a majority of the series or,
+        // with drawn rolls, until every roll of the series has been played
+        let points_to_win = game.series_length / 2 + 1;
+        let series_played = game.tie_policy == TiePolicy::Draw
+            && game.rolls.len() >= game.series_length;
+        if game.player1_series_points < points_to_win
+            && game.player2_series_points < points_to_win
+            && !series_played
+        {
+            game.roll += 1;
+            game.player1_rolled = false;
+            game.player2_rolled = false;
+            env.storage().temporary().set(&key, &game);
+            return Ok(roll_winner);
+        }
 
-        // Update game with winner (this marks the game as ended)
-        game.winner = Some(winner.clone());
+        // Update game with the series leader (this marks the game as ended)
+        let winner = if game.player1_series_points > game.player2_series_points {
+            Some(game.player1.clone())
+        } else if game.player2_series_points > game.player1_series_points {
+            Some(game.player2.clone())
+        } else {
+            None
+        };
+        game.winner = winner.clone();
+        game.is_draw = winner.is_none();
         env.storage().temporary().set(&key, &game);
 
+        // The hub only records a win or a loss, so a draw is not reported:
+        // the hub session is left to expire rather than paying either player.
+        // game.is_draw records the outcome.
+        let Some(winner) = winner else {
+            return Ok(None);
+        };
+
         // Get GameHub address
         let game_hub_addr: Address = env
             .storage()
@@ -335,10 +469,10 @@ impl DiceDuelContract {
         // Call GameHub to end the session
         // This unlocks points and updates standings
         // Event emitted by the Game Hub contract (GameEnded)
-        let player1_won = winner == game.player1; // true if player1 won, false if player2 won
+        let player1_won = winner == game.player1;
         game_hub.end_game(&session_id, &player1_won);
 
-        Ok(winner)
+        Ok(Some(winner))
     }
 
     /// Get game information.
@@ -3,9 +3,9 @@
 // Unit tests for the dice-duel contract using a simple mock GameHub.
 // These tests verify game logic independently of the full GameHub system.
 
-use crate::{DiceDuelContract, DiceDuelContractClient, Error};
-use soroban_sdk::testutils::{Address as _, Ledger as _};
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env};
+use crate::{DiceDuelContract, DiceDuelContractClient, Error, TiePolicy};
+use soroban_sdk::testutils::{Address as _, AuthorizedFunction, Ledger as _};
+use soroban_sdk::{contract, contractimpl, vec, Address, BytesN, Env, IntoVal};
 
 // ============================================================================
 // Mock GameHub for Unit Testing
@@ -28,8 +28,25 @@ impl MockGameHub {
         // Mock implementation - does nothing
     }
 
-    pub fn end_game(_env: Env, _session_id: u32, _player1_won: bool) {
-        // Mock implementation - does nothing
+    /// Counts reported results per session and keeps the last one.
+    pub fn end_game(env: Env, session_id: u32, player1_won: bool) {
+        let ends: u32 = env.storage().instance().get(&session_id).unwrap_or(0);
+        env.storage().instance().set(&session_id, &(ends + 1));
+        env.storage()
+            .instance()
+            .set(&(session_id, ends), &player1_won);
+    }
+
+    pub fn end_count(env: Env, session_id: u32) -> u32 {
+        env.storage().instance().get(&session_id).unwrap_or(0)
+    }
+
+    pub fn player1_won(env: Env, session_id: u32) -> bool {
+        let ends = Self::end_count(env.clone(), session_id);
+        env.storage()
+            .instance()
+            .get(&(session_id, ends - 1))
+            .unwrap()
     }
 
     pub fn add_game(_env: Env, _game_address: Address) {
@@ -129,7 +146,7 @@ fn test_complete_game() {
     let points = 100_0000000;
 
     // Start game
-    client.start_game(&session_id, &player1, &player2, &points, &points);
+    client.start_game(&session_id, &player1, &player2, &points, &points, &1, &TiePolicy::Reroll);
 
     // Verify initial state
     let game = client.get_game(&session_id);
@@ -145,12 +162,14 @@ fn test_complete_game() {
     assert!(game.player2_die1.is_none());
     assert!(game.player2_die2.is_none());
 
-    // Players roll
-    client.roll(&session_id, &player1);
-    client.roll(&session_id, &player2);
-
-    // Reveal winner
-    let winner = client.reveal_winner(&session_id);
+    // Players roll, re-rolling any tied roll, and reveal the winner
+    let winner = loop {
+        client.roll(&session_id, &player1);
+        client.roll(&session_id, &player2);
+        if let Some(winner) = client.reveal_winner(&session_id) {
+            break winner;
+        }
+    };
     assert!(winner == player1 || winner == player2);
 
     // Verify dice values and winner stored
@@ -181,7 +200,7 @@ fn test_cannot_roll_twice() {
     let session_id = 2u32;
     let points = 100_0000000;
 
-    client.start_game(&session_id, &player1, &player2, &points, &points);
+    client.start_game(&session_id, &player1, &player2, &points, &points, &1, &TiePolicy::Reroll);
 
     client.roll(&session_id, &player1);
     let result = client.try_roll(&session_id, &player1);
@@ -195,7 +214,7 @@ fn test_cannot_reveal_before_both_roll() {
     let session_id = 3u32;
     let points = 100_0000000;
 
-    client.start_game(&session_id, &player1, &player2, &points, &points);
+    client.start_game(&session_id, &player1, &player2, &points, &points, &1, &TiePolicy::Reroll);
     client.roll(&session_id, &player1);
 
     let result = client.try_reveal_winner(&session_id);
@@ -209,7 +228,7 @@ fn test_non_player_cannot_roll() {
     let session_id = 4u32;
     let points = 100_0000000;
 
-    client.start_game(&session_id, &player1, &player2, &points, &points);
+    client.start_game(&session_id, &player1, &player2, &points, &points, &1, &TiePolicy::Reroll);
 
     let non_player = Address::generate(&_env);
     let result = client.try_roll(&session_id, &non_player);
@@ -223,7 +242,8 @@ fn test_cannot_roll_after_game_ended() {
     let session_id = 5u32;
     let points = 100_0000000;
 
-    client.start_game(&session_id, &player1, &player2, &points, &points);
+    // A single drawn roll always ends the game
+    client.start_game(&session_id, &player1, &player2, &points, &points, &1, &TiePolicy::Draw);
     client.roll(&session_id, &player1);
     client.roll(&session_id, &player2);
     client.reveal_winner(&session_id);
@@ -232,6 +252,146 @@ fn test_cannot_roll_after_game_ended() {
     assert_dice_duel_error(&result, Error::GameAlreadyEnded);
 }
 
+// ============================================================================
+// Series Tests
+// ============================================================================
+
+#[test]
+fn test_best_of_five_series() {
+    let (env, client, hub, player1, player2) = setup_test();
+
+    let session_id = 6u32;
+    let points = 100_0000000;
+
+    assert_dice_duel_error(
+        &client.try_start_game(&session_id, &player1, &player2, &points, &points, &4, &TiePolicy::Reroll),
+        Error::InvalidSeries,
+    );
+    client.start_game(&session_id, &player1, &player2, &points, &points, &5, &TiePolicy::Reroll);
+
+    // Both players sign off on the series format along with their points
+    let auths = env.auths();
+    assert_eq!(auths.len(), 2);
+    for (auth, player) in auths.iter().zip([&player1, &player2]) {
+        assert_eq!(auth.0, *player);
+        let AuthorizedFunction::Contract((_, _, args)) = &auth.1.function else {
+            panic!("expected a contract auth");
+        };
+        assert_eq!(
+            *args,
+            vec![
+                &env,
+                session_id.into_val(&env),
+                points.into_val(&env),
+                5u32.into_val(&env),
+                TiePolicy::Reroll.into_val(&env),
+            ]
+        );
+    }
+
+    let mut reveals = 0;
+    while client.get_game(&session_id).winner.is_none() {
+        client.roll(&session_id, &player1);
+        client.roll(&session_id, &player2);
+        let roll_winner = client.reveal_winner(&session_id);
+        reveals += 1;
+
+        let game = client.get_game(&session_id);
+        assert_eq!(game.rolls.len(), reveals);
+        assert_eq!(game.rolls.last().unwrap().winner, roll_winner);
+        if game.winner.is_none() {
+            // Nothing is reported until the series is decided
+            assert_eq!(hub.end_count(&session_id), 0);
+            assert_eq!(game.roll, reveals + 1);
+            assert!(!game.player1_rolled && !game.player2_rolled);
+        }
+    }
+
+    // Re-rolled ties do not count towards the series
+    let game = client.get_game(&session_id);
+    let decided = game.rolls.iter().filter(|roll| roll.winner.is_some()).count() as u32;
+    assert!((3..=5).contains(&decided));
+    let winner = game.winner.clone().unwrap();
+    let winner_points = if winner == player1 {
+        game.player1_series_points
+    } else {
+        game.player2_series_points
+    };
+    assert_eq!(winner_points, 3);
+    assert_eq!(game.player1_series_points + game.player2_series_points, decided);
+    assert_eq!(hub.end_count(&session_id), 1);
+    assert_dice_duel_error(&client.try_roll(&session_id, &player1), Error::GameAlreadyEnded);
+}
+
+/// Start single-roll games from `first_session_id` until one's first roll
+/// is a tie, returning its session ID
+fn play_until_tie(
+    client: &DiceDuelContractClient,
+    player1: &Address,
+    player2: &Address,
+    first_session_id: u32,
+    tie_policy: TiePolicy,
+) -> u32 {
+    for session_id in first_session_id..first_session_id + 200 {
+        client.start_game(&session_id, player1, player2, &0, &0, &1, &tie_policy);
+        client.roll(&session_id, player1);
+        client.roll(&session_id, player2);
+        client.reveal_winner(&session_id);
+
+        let roll = client.get_game(&session_id).rolls.get(0).unwrap();
+        if roll.player1_die1 + roll.player1_die2 == roll.player2_die1 + roll.player2_die2 {
+            return session_id;
+        }
+    }
+    panic!("no tied roll found");
+}
+
+#[test]
+fn test_tie_policies() {
+    let (_env, client, hub, player1, player2) = setup_test();
+
+    // Player 1: the tied roll goes to player 1, as in the original game
+    let session_id = play_until_tie(&client, &player1, &player2, 700, TiePolicy::Player1);
+    let game = client.get_game(&session_id);
+    assert_eq!(game.rolls.get(0).unwrap().winner, Some(player1.clone()));
+    assert_eq!(game.winner, Some(player1.clone()));
+    assert!(hub.player1_won(&session_id));
+
+    // Sudden death: the tied roll does not count and both players roll again
+    let session_id = play_until_tie(&client, &player1, &player2, 100, TiePolicy::Reroll);
+    let game = client.get_game(&session_id);
+    assert!(game.winner.is_none() && !game.is_draw);
+    assert_eq!(game.roll, 2);
+    assert_eq!(game.player1_series_points + game.player2_series_points, 0);
+    assert_eq!(hub.end_count(&session_id), 0);
+    client.roll(&session_id, &player1);
+
+    // Draw: the game ends without a winner and nothing is reported to the
+    // hub, which has no draw result
+    let session_id = play_until_tie(&client, &player1, &player2, 300, TiePolicy::Draw);
+    let game = client.get_game(&session_id);
+    assert!(game.winner.is_none() && game.is_draw);
+    assert_eq!(client.reveal_winner(&session_id), None);
+    assert_eq!(hub.end_count(&session_id), 0);
+    assert_dice_duel_error(&client.try_roll(&session_id, &player1), Error::GameAlreadyEnded);
+
+    // High die: the higher single die wins a tied total
+    let session_id = play_until_tie(&client, &player1, &player2, 500, TiePolicy::HighDie);
+    let game = client.get_game(&session_id);
+    let roll = game.rolls.get(0).unwrap();
+    let player1_high = roll.player1_die1.max(roll.player1_die2);
+    let player2_high = roll.player2_die1.max(roll.player2_die2);
+    let expected = if player1_high > player2_high {
+        Some(player1.clone())
+    } else if player2_high > player1_high {
+        Some(player2.clone())
+    } else {
+        None
+    };
+    assert_eq!(roll.winner, expected);
+    assert_eq!(game.winner, expected);
+}
+
 #[test]
 fn test_upgrade_function_exists() {
     let (_env, client, _hub, _player1, _player2) = setup_test();
@@ -4,7 +4,15 @@
 //!
 //! A two-player card game where players try to get as close to 21 as possible without going over.
 //! Each player is dealt 2 cards and can choose to "hit" (draw another card) or "stick" (end their turn).
-//! Cards are valued 1-13 where Ace=1, 2-10=face value, Jack/Queen/King=10.
+//! A player dealt a pair may "split" it into two hands, played one after the other.
+//! Cards come from a single 52-card deck per game, reshuffled when it runs out.
+//! Cards are numbered 1-13: 2-10 are worth face value, Jack/Queen/King 10, and an
+//! Ace 11 unless that would bust the hand, in which case it counts as 1.
+//!
+//! Games can optionally be played for XLM: the admin sets a stake, both players
+//! deposit it plus a 0.1% fee, and the winner claims both stakes.
+//! When a betting contract is configured, spectators can bet on each two-player
+//! game in a zk-betting pool opened at the start and settled at the end.
 //!
 //! **Game Hub Integration:**
 //! This game is Game Hub-aware and enforces all games to be played through the
@@ -12,7 +20,7 @@
 
 use soroban_sdk::{
     Address, Bytes, BytesN, Env, IntoVal, contract, contractclient, contracterror,
-    contractimpl, contracttype, vec
+    contractevent, contractimpl, contracttype, token, vec, Vec
 };
 
 // Import GameHub contract interface
@@ -32,6 +40,25 @@ pub trait GameHub {
     fn end_game(env: Env, session_id: u32, player1_won: bool);
 }
 
+// zk-betting hooks: a spectator pool per game, settled when the game ends
+#[contractclient(name = "ZkBettingClient")]
+pub trait ZkBetting {
+    fn open_card_pool(env: Env, session_id: u32, deadline_ts: u64) -> u32;
+
+    fn settle_card_pool(env: Env, session_id: u32, player1_won: Option<bool>);
+}
+
+// zk-groth16-verifier interface, used for proofs about hidden hands
+#[contractclient(name = "ZkVerifierContractClient")]
+pub trait ZkVerifierContract {
+    fn verify_round_proof(
+        env: Env,
+        vk_id: BytesN<32>,
+        proof: Bytes,
+        public_inputs: Vec<BytesN<32>>,
+    ) -> bool;
+}
+
 // ============================================================================
 // Errors
 // ============================================================================
@@ -51,18 +78,135 @@ pub enum Error {
     SelfPlay = 9,
     RoundOverflow = 10,
     InvalidHandData = 11,
+    CannotSplit = 12,
+    InvalidStake = 13,
+    StakeNotConfigured = 14,
+    StakeNotPaid = 15,
+    StakeDepositExpired = 16,
+    DeadlineNotReached = 17,
+    GameCancelled = 18,
+    NothingToClaim = 19,
+    NothingToSweep = 20,
+    SweepTooEarly = 21,
+    WageringNotConfigured = 22,
+    TimeoutNotReached = 23,
+    NoIdlePlayer = 24,
+    InvalidSeries = 25,
+    InsufficientBankroll = 26,
+    InvalidRules = 27,
+    InvalidPlayerCount = 28,
+    NotYourTurn = 29,
+    TurnsNotFinished = 30,
+    GameInProgress = 31,
+    InsuranceNotOffered = 32,
+    AlreadyInsured = 33,
+    SeedNotRevealed = 34,
+    InvalidSeedReveal = 35,
+    ZkVerifierNotConfigured = 36,
+    ZkProofInvalid = 37,
+    TournamentNotFound = 38,
+    TournamentNotReady = 39,
+    AlreadyJoined = 40,
+    HouseGameNotSeeded = 41,
+    PlayerSeedMissing = 42,
+    SeedCommitClosed = 43,
 }
 
 // ============================================================================
-// Events (REMOVED)
+// Events
 // ============================================================================
 //
-// All events have been removed to avoid duplication with GameHub events.
 // Game lifecycle is tracked through GameHub's GameStarted and GameEnded events.
-// Game-specific state (hands, scores) can be queried via get_game().
-//
-// This keeps the event stream clean and makes GameHub the single source of
-// truth for game lifecycle monitoring.
+// Gameplay events let spectators follow a game live without polling
+// get_game(). They carry card counts only, never card values. Each is
+// published as (name, id) topics with the remaining fields as data.
+
+/// ("card", session_id) -> (player, cards_in_hand): a card was dealt
+#[contractevent(topics = ["card"], data_format = "vec")]
+pub struct CardDealt {
+    #[topic]
+    pub session_id: u32,
+    pub player: Address,
+    pub cards_in_hand: u32,
+}
+
+/// ("stuck", session_id) -> player: the player finished their turn
+#[contractevent(topics = ["stuck"], data_format = "single-value")]
+pub struct PlayerStuck {
+    #[topic]
+    pub session_id: u32,
+    pub player: Address,
+}
+
+/// ("busted", session_id) -> player: a hand went over 21
+#[contractevent(topics = ["busted"], data_format = "single-value")]
+pub struct PlayerBusted {
+    #[topic]
+    pub session_id: u32,
+    pub player: Address,
+}
+
+/// ("round", session_id) -> (round, Option<winner>): a round was decided or tied
+#[contractevent(topics = ["round"], data_format = "vec")]
+pub struct RoundEnded {
+    #[topic]
+    pub session_id: u32,
+    pub round: u32,
+    pub winner: Option<Address>,
+}
+
+/// ("abandon", session_id) -> round: an idle game was closed as a no-contest
+#[contractevent(topics = ["abandon"], data_format = "single-value")]
+pub struct GameAbandoned {
+    #[topic]
+    pub session_id: u32,
+    pub round: u32,
+}
+
+/// ("table", session_id) -> winners: a multi-player table was revealed
+#[contractevent(topics = ["table"], data_format = "single-value")]
+pub struct TableRevealed {
+    #[topic]
+    pub session_id: u32,
+    pub winners: Vec<Address>,
+}
+
+/// ("insure", session_id) -> (player, payout): an insurance bet was settled
+#[contractevent(topics = ["insure"], data_format = "vec")]
+pub struct InsuranceSettled {
+    #[topic]
+    pub session_id: u32,
+    pub player: Address,
+    pub payout: i128,
+}
+
+/// ("proof", session_id) -> (player, round, claim): a hand proof was verified
+#[contractevent(topics = ["proof"], data_format = "vec")]
+pub struct HandProven {
+    #[topic]
+    pub session_id: u32,
+    pub player: Address,
+    pub round: u32,
+    pub claim: HandClaim,
+}
+
+/// ("leg", tournament_id) -> (leg, sessions): a tournament leg was paired
+#[contractevent(topics = ["leg"], data_format = "vec")]
+pub struct LegPaired {
+    #[topic]
+    pub tournament_id: u32,
+    pub leg: u32,
+    pub sessions: Vec<u32>,
+}
+
+/// ("champion", tournament_id) -> (player, prize): a tournament was won
+#[contractevent(topics = ["champion"], data_format = "vec")]
+pub struct TournamentWon {
+    #[topic]
+    pub tournament_id: u32,
+    pub player: Address,
+    pub prize: i128,
+}
 
 // ============================================================================
 // Data Types
@@ -81,14 +225,309 @@ pub struct Game {
     pub player2_stuck: bool,
     pub winner: Option<Address>,
     pub round: u32,
+    /// Second hand after a split; empty if the player has not split.
+    pub player1_split_hand: Bytes,
+    pub player2_split_hand: Bytes,
+    /// The first hand is finished and hits/sticks now go to the split hand.
+    pub player1_on_split: bool,
+    pub player2_on_split: bool,
+    /// Cards dealt from the deck, bit `(rank - 1) * 4 + suit` per card.
+    pub deck: u64,
+    /// XLM wager per player in stroops; 0 for a points-only game.
+    pub stake_amount_stroops: i128,
+    pub stake_fee_bps: u32,
+    /// Deposits are refused after this timestamp (0 = no stake).
+    pub stake_deadline_ts: u64,
+    pub player1_stake_paid: bool,
+    pub player2_stake_paid: bool,
+    pub is_cancelled: bool,
+    /// Timestamp of the last hit, stick, split or deposit.
+    pub last_action_ts: u64,
+    /// Round wins needed to take the game (1 = single round).
+    pub rounds_to_win: u32,
+    pub player1_round_wins: u32,
+    pub player2_round_wins: u32,
+    /// Winner of each decided round, in order; drawn rounds are not recorded.
+    pub round_winners: Vec<Address>,
+    /// Tied rounds so far, counted against `TiePolicy::RedealLimit`.
+    pub tied_rounds: u32,
+    /// The game ended in a tie with no winner.
+    pub is_push: bool,
+    /// player2 is this contract, playing fixed dealer rules from the house bankroll.
+    pub is_house_game: bool,
+    /// Rules fixed when the game started.
+    pub rules: GameRules,
+    /// The deciding round was awarded at deal time for a natural (two-card 21).
+    pub won_on_natural: bool,
+    /// Betting contract holding the spectator pool on this game, if betting
+    /// was configured at the start, and the pool's id there.
+    pub betting_contract: Option<Address>,
+    pub betting_pool_id: Option<u32>,
+    /// Insurance player1 bought against the house dealer this round in
+    /// stroops (0 = none), paid 2:1 if the dealer finishes the round on 21.
+    pub insurance: i128,
+    /// Busted hands and naturals won this game, added to player stats at the end.
+    pub player1_busts: u32,
+    pub player2_busts: u32,
+    pub player1_naturals: u32,
+    pub player2_naturals: u32,
+    /// Operator seed chain (see `commit_seed`): the last revealed link, or
+    /// the committed tip before the first reveal. None for unseeded games.
+    pub seed_link: Option<BytesN<32>>,
+    /// Draw in a seeded game waiting for the operator's next link.
+    pub pending_draw: PendingDraw,
+    /// Players' seed contributions to a seeded game (see
+    /// `commit_player_seed`). The house contributes none.
+    pub player1_seed: PlayerSeed,
+    pub player2_seed: PlayerSeed,
+    /// Random salt mixed into hand commitments (see `hand_commitment`).
+    /// Hidden from `get_game` while the game is in play.
+    pub hand_salt: BytesN<32>,
+}
+
+/// A card draw in a seeded game, made when the operator reveals the next
+/// link of its seed chain.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PendingDraw {
+    /// No draw is waiting.
+    None,
+    /// The round's opening hands.
+    Deal,
+    /// A hit by this player.
+    Hit(Address),
+    /// A split by this player.
+    Split(Address),
+    /// The house dealer drawing to 17.
+    Dealer,
+}
+
+/// A player's contribution to a seeded game's cards: `keccak256(secret)`
+/// committed before the operator's first reveal, then the secret itself.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PlayerSeed {
+    Missing,
+    Committed(BytesN<32>),
+    Revealed(BytesN<32>),
+}
+
+/// How Aces are counted.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AceMode {
+    /// One Ace counts as 11 unless the hand would bust (default).
+    Soft,
+    /// Aces always count as 1.
+    Hard,
+}
+
+/// Rules for a single game, passed to `start_game` so variants can share a deployment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameRules {
+    pub ace_mode: AceMode,
+    /// An unsplit hand reaching this many cards without busting wins the
+    /// round (e.g. 5 for a five-card Charlie); 0 disables the rule.
+    pub charlie_cards: u32,
+    /// A hand reaching this many cards is stuck automatically (3 or more,
+    /// and no fewer than `charlie_cards`); 0 means no limit.
+    pub max_hand_cards: u32,
+    pub tie_policy: TiePolicy,
+    /// Series length: the first player to win a majority of `max_rounds`
+    /// decided rounds takes the game (1 = single round).
+    pub max_rounds: u32,
+    /// Time a player may idle before the opponent can claim the game.
+    pub timeout_seconds: u64,
+}
+
+/// What happens when a round ends in a tie.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TiePolicy {
+    /// Deal a new round (default).
+    Redeal,
+    /// End the game as a push with no winner.
+    Push,
+    /// Redeal up to the given number of times, then push.
+    RedealLimit(u32),
+}
+
+/// Spectator view of a game: card counts instead of cards, safe to show
+/// while the game is in play.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PublicGame {
+    pub player1: Address,
+    pub player2: Address,
+    pub round: u32,
+    pub player1_stuck: bool,
+    pub player2_stuck: bool,
+    pub player1_cards: u32,
+    pub player2_cards: u32,
+    /// Cards in the split hand; 0 if the player has not split.
+    pub player1_split_cards: u32,
+    pub player2_split_cards: u32,
+    pub player1_round_wins: u32,
+    pub player2_round_wins: u32,
+    pub winner: Option<Address>,
+    pub is_push: bool,
+    pub is_cancelled: bool,
+}
+
+/// A single-elimination tournament played as a series of two-player games.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tournament {
+    /// Entrants in seeding order; leg 1 pairs them first-second, third-fourth, ...
+    pub players: Vec<Address>,
+    /// Entrants who have joined (and paid the entry stake, if any).
+    pub joined: Vec<Address>,
+    pub entry_stake_stroops: i128,
+    /// Entry stakes pooled for the champion.
+    pub prize_pool_stroops: i128,
+    /// Players still in, in bracket order.
+    pub alive: Vec<Address>,
+    /// Current leg (0 = not started).
+    pub leg: u32,
+    /// Game sessions of the current leg, one per pairing.
+    pub sessions: Vec<u32>,
+    /// First session ID tried for the next leg game; used IDs are skipped.
+    pub next_session_id: u32,
+    pub champion: Option<Address>,
+    /// Entrants must all join by this timestamp, or anyone may cancel the
+    /// tournament and refund the entry stakes.
+    pub join_deadline_ts: u64,
+    pub is_cancelled: bool,
+}
+
+/// Statement about a hidden hand proven with `prove_hand`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HandClaim {
+    /// The hand is worth 21 or less.
+    NotBusted,
+    /// The hand is worth exactly this value.
+    Value(u32),
+}
+
+/// Kind of action recorded in a game's hand history.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HistoryAction {
+    /// A card dealt into a starting hand (or a hand created by a split).
+    Deal,
+    Hit,
+    Stick,
+    Split,
+    Bust,
+    /// New hands dealt for the next round.
+    Redeal,
+    /// A round was decided (player is the winner) or tied (no player).
+    RoundResult,
+}
+
+/// One entry of a game's append-only hand history.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HistoryEntry {
+    pub action: HistoryAction,
+    pub round: u32,
+    pub player: Option<Address>,
+    /// Card dealt (1-13); 0 for actions without a card.
+    pub card: u32,
+    pub timestamp: u64,
+}
+
+/// One seat at a multi-player table.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Seat {
+    pub player: Address,
+    pub hand: Bytes,
+    pub stuck: bool,
+    pub busted: bool,
+}
+
+/// A 3-4 player table. Seats act in order, each hitting until they stick
+/// or bust; the best live hands win. Tables are unstaked and points-free.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Table {
+    pub seats: Vec<Seat>,
+    /// Index of the seat to act; equal to the seat count once all are done.
+    pub turn: u32,
+    pub deck: u64,
+    /// Seats sharing the best live hand, set at reveal (empty if all bust).
+    pub winners: Option<Vec<Address>>,
+    pub last_action_ts: u64,
+    /// Random salt mixed into hand commitments; hidden until reveal.
+    pub hand_salt: BytesN<32>,
+}
+
+/// Winnings recorded at settlement, claimed by the winner.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingPayout {
+    pub winner: Address,
+    pub amount_stroops: i128,
+}
+
+/// Lifetime record of a player across finished games.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PlayerStats {
+    pub wins: u32,
+    pub losses: u32,
+    pub busts: u32,
+    /// Rounds won with a natural (two-card 21).
+    pub blackjacks: u32,
+    pub rounds_played: u32,
+}
+
+/// Compact record of a finished game, kept after the game itself expires.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameResult {
+    pub player1: Address,
+    pub player2: Address,
+    /// Final main-hand values (a bust counts above 21).
+    pub player1_value: u32,
+    pub player2_value: u32,
+    /// None for a push.
+    pub winner: Option<Address>,
+    pub rounds: u32,
+    pub player1_round_wins: u32,
+    pub player2_round_wins: u32,
+    pub finished_ts: u64,
 }
 
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
     Game(u32),
+    Table(u32),
+    History(u32, u32),
+    HistoryLen(u32),
     GameHubAddress,
     Admin,
+    PendingPayout(u32),
+    TreasuryAddress,
+    XlmToken,
+    FeeAccrued,
+    LastSweepTs,
+    OutstandingLiabilities,
+    ActionTimeout,
+    TiePolicy,
+    HouseBankroll,
+    BettingContract,
+    RoundCap,
+    PlayerStats(Address),
+    GameResult(u32),
+    SeedCommit(u32),
+    ZkVerifier,
+    ZkVkId,
+    Tournament(u32),
 }
 
 // ============================================================================
@@ -101,11 +540,51 @@ pub enum DataKey {
 /// 30 days = 30 * 24 * 60 * 60 / 5 = 518,400 ledgers
 const GAME_TTL_LEDGERS: u32 = 518_400;
 
+/// Stake fee charged to each player: 0.1% in basis points
+const STAKE_FEE_BPS: u32 = 10;
+
+/// Time players have to deposit once a stake is set
+const STAKE_DEPOSIT_WINDOW_SECONDS: u64 = 60;
+
+/// Time entrants have to join a tournament before it can be cancelled
+const TOURNAMENT_JOIN_WINDOW_SECONDS: u64 = 86_400;
+
+/// Default time a player may idle before the opponent can claim the game
+const DEFAULT_ACTION_TIMEOUT_SECONDS: u64 = 300;
+
+/// Insurance pays 2:1, so the house reserves twice the insured amount
+const INSURANCE_PAYOUT_MULTIPLIER: i128 = 2;
+
+/// Default number of rounds after which a tie ends the game as a push
+const DEFAULT_ROUND_CAP: u32 = 20;
+
+/// Minimum time between treasury sweeps
+const FEE_SWEEP_INTERVAL_SECONDS: u64 = 86_400;
+
+/// Bonus on the stake for winning with a natural, paid from the house bankroll: 50% (3:2)
+const NATURAL_BONUS_BPS: i128 = 5_000;
+
+/// The house dealer draws until reaching this hand value
+const DEALER_STAND_VALUE: u32 = 17;
+
+/// Hand history entries per page returned by `get_history`
+const HISTORY_PAGE_SIZE: u32 = 20;
+
+/// Seats allowed at a multi-player table
+const MIN_TABLE_SEATS: u32 = 3;
+const MAX_TABLE_SEATS: u32 = 4;
+
+/// Cards in a deck: 13 ranks in 4 suits
+const DECK_SIZE: u32 = 52;
+
+/// Deck bitset with every card dealt
+const FULL_DECK: u64 = (1 << DECK_SIZE) - 1;
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
-/// Convert card number to point value.
+/// Convert card number to its hard point value.
 /// Cards 1-13 where: Ace=1, 2-10=face value, Jack/Queen/King=10
 fn card_value(card: u32) -> u32 {
     if card >= 10 {
@@ -115,23 +594,195 @@ fn card_value(card: u32) -> u32 {
     }
 }
 
-/// Calculate the total value of a hand (stored as Bytes where each byte is a card 1-13)
-fn calculate_hand_value(hand: &Bytes) -> Result<u32, Error> {
+/// Calculate the total value of a hand (stored as Bytes where each byte is a card 1-13).
+/// With `AceMode::Soft`, one Ace counts as 11 (a "soft" hand) when that keeps
+/// the total at 21 or below; a second 11 would always bust, so the rest count as 1.
+fn calculate_hand_value(hand: &Bytes, ace_mode: AceMode) -> Result<u32, Error> {
     let mut total = 0u32;
+    let mut has_ace = false;
     for i in 0..hand.len() {
         let card = hand.get(i).ok_or(Error::InvalidHandData)? as u32;
+        has_ace |= card == 1;
         total = total
             .checked_add(card_value(card))
             .ok_or(Error::InvalidHandData)?;
     }
+    if ace_mode == AceMode::Soft && has_ace && total + 10 <= 21 {
+        total += 10;
+    }
     Ok(total)
 }
 
-/// Deal a card (1-13) using deterministic PRNG
+/// Outcome of one hand against another for the first hand's owner:
+/// 1 win, -1 loss, 0 tie. A bust hand loses to any live hand.
+fn hand_outcome(hand: &Bytes, other: &Bytes, ace_mode: AceMode) -> Result<i32, Error> {
+    let value = calculate_hand_value(hand, ace_mode)?;
+    let other_value = calculate_hand_value(other, ace_mode)?;
+    let outcome = match (value > 21, other_value > 21) {
+        (true, true) => 0,
+        (true, false) => -1,
+        (false, true) => 1,
+        (false, false) => (value > other_value) as i32 - (other_value > value) as i32,
+    };
+    Ok(outcome)
+}
+
+/// Publish a `card` event: a card was dealt, leaving the hand at `cards_in_hand`
+fn emit_card(env: &Env, session_id: u32, player: &Address, cards_in_hand: u32) {
+    CardDealt {
+        session_id,
+        player: player.clone(),
+        cards_in_hand,
+    }
+    .publish(env);
+}
+
+/// Append an entry to the session's hand history. History is kept in
+/// persistent storage so it outlives the game for audits.
+fn log_action(
+    env: &Env,
+    session_id: u32,
+    round: u32,
+    action: HistoryAction,
+    player: Option<Address>,
+    card: u32,
+) {
+    let len_key = DataKey::HistoryLen(session_id);
+    let len: u32 = env.storage().persistent().get(&len_key).unwrap_or(0);
+    let page_key = DataKey::History(session_id, len / HISTORY_PAGE_SIZE);
+    let mut page: Vec<HistoryEntry> = env
+        .storage()
+        .persistent()
+        .get(&page_key)
+        .unwrap_or(Vec::new(env));
+    page.push_back(HistoryEntry {
+        action,
+        round,
+        player,
+        card,
+        timestamp: env.ledger().timestamp(),
+    });
+
+    env.storage().persistent().set(&page_key, &page);
+    env.storage().persistent().set(&len_key, &(len + 1));
+    env.storage()
+        .persistent()
+        .extend_ttl(&page_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+    env.storage()
+        .persistent()
+        .extend_ttl(&len_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+}
+
+/// Log every card of both starting hands
+fn log_deal(env: &Env, session_id: u32, game: &Game) {
+    for (player, hand) in [
+        (&game.player1, &game.player1_hand),
+        (&game.player2, &game.player2_hand),
+    ] {
+        for card in hand.iter() {
+            log_action(env, session_id, game.round, HistoryAction::Deal, Some(player.clone()), card as u32);
+        }
+    }
+}
+
+/// A two-card 21
+fn is_natural(hand: &Bytes, ace_mode: AceMode) -> Result<bool, Error> {
+    Ok(hand.len() == 2 && calculate_hand_value(hand, ace_mode)? == 21)
+}
+
+/// The player dealt a natural when the opponent was not, as `player1_won`
+fn natural_winner(game: &Game) -> Result<Option<bool>, Error> {
+    let player1_natural = is_natural(&game.player1_hand, game.rules.ace_mode)?;
+    let player2_natural = is_natural(&game.player2_hand, game.rules.ace_mode)?;
+    Ok(if player1_natural != player2_natural {
+        Some(player1_natural)
+    } else {
+        None
+    })
+}
+
+/// The player's hands still in play: the main hand plus the split hand, if any.
+fn player_hands(env: &Env, hand: &Bytes, split_hand: &Bytes) -> soroban_sdk::Vec<Bytes> {
+    let mut hands = vec![env, hand.clone()];
+    if !split_hand.is_empty() {
+        hands.push_back(split_hand.clone());
+    }
+    hands
+}
+
+/// Commitment to a hand shown in place of the raw cards while a game is in
+/// play: keccak256(salt || index || cards), where `salt` is the game's hidden
+/// `hand_salt` and `index` is the round of a game or the seat at a table.
+/// Without the salt a viewer cannot brute-force the few possible hands.
+/// An empty hand stays empty.
+///
+/// This keeps opponent cards out of the contract's read API; it is not a
+/// secrecy guarantee, since ledger entries can be read directly.
+fn hand_commitment(env: &Env, salt: &BytesN<32>, index: u32, hand: &Bytes) -> Bytes {
+    if hand.is_empty() {
+        return Bytes::new(env);
+    }
+    let mut preimage = Bytes::from(salt.clone());
+    preimage.append(&Bytes::from_array(env, &index.to_be_bytes()));
+    preimage.append(hand);
+    Bytes::from(env.crypto().keccak256(&preimage))
+}
+
+/// Encode a u32 as a big-endian 32-byte proof public input
+fn u32_to_bytes32(env: &Env, value: u32) -> BytesN<32> {
+    let mut raw = [0u8; 32];
+    raw[28..].copy_from_slice(&value.to_be_bytes());
+    BytesN::from_array(env, &raw)
+}
+
+/// Hash a card seed, mixing in the operator's last revealed seed link and
+/// the players' revealed seeds for seeded games, so cards cannot be computed
+/// from public data alone
+fn card_seed(env: &Env, mut seed_bytes: Bytes, game: &Game) -> BytesN<32> {
+    if let Some(link) = &game.seed_link {
+        seed_bytes.append(&Bytes::from(link.clone()));
+    }
+    for seed in [&game.player1_seed, &game.player2_seed] {
+        if let PlayerSeed::Revealed(secret) = seed {
+            seed_bytes.append(&Bytes::from(secret.clone()));
+        }
+    }
+    env.crypto().keccak256(&seed_bytes).into()
+}
+
+/// Every player of a seeded game has committed a seed, and with `revealed`,
+/// revealed it too. The house contributes none.
+fn player_seeds_ready(game: &Game, revealed: bool) -> bool {
+    let ready = |seed: &PlayerSeed| match seed {
+        PlayerSeed::Missing => false,
+        PlayerSeed::Committed(_) => !revealed,
+        PlayerSeed::Revealed(_) => true,
+    };
+    ready(&game.player1_seed) && (game.is_house_game || ready(&game.player2_seed))
+}
+
+/// Deal a card (1-13) from the cards left in `deck` using deterministic PRNG,
+/// marking it dealt. An exhausted deck is reshuffled first.
 /// The seed is passed in (as Hash from keccak256)
-fn deal_card(env: &Env, seed: BytesN<32>) -> u8 {
+fn deal_card(env: &Env, seed: BytesN<32>, deck: &mut u64) -> u8 {
+    if *deck == FULL_DECK {
+        *deck = 0;
+    }
+
     env.prng().seed(seed.into());
-    env.prng().gen_range::<u64>(1..=13) as u8
+    let remaining = DECK_SIZE - deck.count_ones();
+    let mut pick = env.prng().gen_range::<u64>(0..remaining as u64) as u32;
+    for index in 0..DECK_SIZE {
+        if *deck & (1 << index) != 0 {
+            continue;
+        }
+        if pick == 0 {
+            *deck |= 1 << index;
+            return (index / 4 + 1) as u8;
+        }
+        pick -= 1;
+    }
+    unreachable!("pick is below the number of cards left")
 }
 
 // ============================================================================
@@ -158,7 +809,8 @@ impl TwentyOneContract {
 
     /// Start a new game between two players with points.
     /// This creates a session in the Game Hub and locks points before starting the game.
-    /// Each player is dealt 2 cards to start.
+    /// Each player is dealt 2 cards to start; a natural (two-card 21) dealt
+    /// to only one player wins the round immediately.
     ///
     /// **CRITICAL:** This method requires authorization from THIS contract (not players).
     /// The Game Hub will call `game_id.require_auth()` which checks this contract's address.
@@ -169,6 +821,8 @@ impl TwentyOneContract {
     /// * `player2` - Address of second player
     /// * `player1_points` - Points amount committed by player 1
     /// * `player2_points` - Points amount committed by player 2
+    /// * `rules` - Rules for this game; `None` uses the contract defaults.
+    ///   Both players authorize the rules along with their points.
     pub fn start_game(
         env: Env,
         session_id: u32,
@@ -176,15 +830,84 @@ impl TwentyOneContract {
         player2: Address,
         player1_points: i128,
         player2_points: i128,
+        rules: Option<GameRules>,
     ) -> Result<(), Error> {
         // Prevent self-play: Player 1 and Player 2 must be different
         if player1 == player2 {
             return Err(Error::SelfPlay);
         }
 
-        // Require authentication from both players (they consent to committing points)
-        player1.require_auth_for_args(vec![&env, session_id.into_val(&env), player1_points.into_val(&env)]);
-        player2.require_auth_for_args(vec![&env, session_id.into_val(&env), player2_points.into_val(&env)]);
+        // Require authentication from both players (they consent to committing
+        // points under these rules)
+        player1.require_auth_for_args(vec![
+            &env,
+            session_id.into_val(&env),
+            player1_points.into_val(&env),
+            rules.into_val(&env),
+        ]);
+        player2.require_auth_for_args(vec![
+            &env,
+            session_id.into_val(&env),
+            player2_points.into_val(&env),
+            rules.into_val(&env),
+        ]);
+
+        Self::open_game(env, session_id, player1, player2, player1_points, player2_points, rules)
+    }
+
+    /// Start a single-player game against the house. player2 is this contract,
+    /// which stands on its two cards until the player sticks and then draws to
+    /// 17 in `reveal_winner`. Stakes are matched from the house bankroll.
+    ///
+    /// # Arguments
+    /// * `session_id` - Unique session identifier (u32)
+    /// * `player` - Address of the player
+    /// * `player_points` - Points amount committed by the player
+    /// * `rules` - Rules for this game; `None` uses the contract defaults
+    pub fn start_house_game(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        player_points: i128,
+        rules: Option<GameRules>,
+    ) -> Result<(), Error> {
+        player.require_auth_for_args(vec![
+            &env,
+            session_id.into_val(&env),
+            player_points.into_val(&env),
+            rules.into_val(&env),
+        ]);
+
+        let house = env.current_contract_address();
+        Self::open_game(env, session_id, player, house, player_points, 0, rules)
+    }
+
+    /// Create the hub session, deal the opening hands and store the game.
+    /// A natural dealt to one player wins the first round immediately. When
+    /// player2 is this contract the game is played against the house.
+    fn open_game(
+        env: Env,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+        rules: Option<GameRules>,
+    ) -> Result<(), Error> {
+        // A live session keeps its game (and the stakes it holds)
+        if env.storage().temporary().has(&DataKey::Game(session_id)) {
+            return Err(Error::GameInProgress);
+        }
+        let rules = rules.unwrap_or_else(|| Self::default_rules(&env));
+        if rules.max_rounds == 0 || rules.charlie_cards == 1 || rules.charlie_cards == 2 {
+            return Err(Error::InvalidRules);
+        }
+        if rules.max_hand_cards > 0
+            && (rules.max_hand_cards < 3 || rules.max_hand_cards < rules.charlie_cards)
+        {
+            return Err(Error::InvalidRules);
+        }
+        let is_house_game = player2 == env.current_contract_address();
 
         // Get GameHub address
         let game_hub_addr: Address = env
@@ -207,40 +930,71 @@ impl TwentyOneContract {
             &player2_points,
         );
 
-        // Generate deterministic seed for card dealing
-        // Seed components (all deterministic and identical between sim/submit):
-        // 1. Session ID - unique per game
-        // 2. Player addresses - both players contribute
-        //
-        // Note: We do NOT include ledger sequence or timestamp because those differ
-        // between simulation and submission.
-        let mut seed_bytes = Bytes::new(&env);
-        seed_bytes.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
-        seed_bytes.append(&player1.to_string().to_bytes());
-        seed_bytes.append(&player2.to_string().to_bytes());
-        let base_seed = env.crypto().keccak256(&seed_bytes);
+        // Open a spectator pool on two-player games when betting is configured;
+        // bets close after the game's action timeout. Best effort, so a
+        // failing betting contract never blocks the game.
+        let (betting_contract, betting_pool_id) = match env
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::BettingContract)
+        {
+            Some(betting) if !is_house_game => {
+                let deadline_ts = env.ledger().timestamp().saturating_add(rules.timeout_seconds);
+                match ZkBettingClient::new(&env, &betting)
+                    .try_open_card_pool(&session_id, &deadline_ts)
+                {
+                    Ok(Ok(pool_id)) => (Some(betting), Some(pool_id)),
+                    _ => (None, None),
+                }
+            }
+            _ => (None, None),
+        };
+
+        // A seeded game waits for the operator's first reveal to be dealt
+        let seed_key = DataKey::SeedCommit(session_id);
+        let seed_link: Option<BytesN<32>> = env.storage().temporary().get(&seed_key);
+        env.storage().temporary().remove(&seed_key);
+
+        // Drawn before any card, since dealing reseeds the PRNG from public data
+        let hand_salt: BytesN<32> = env.prng().gen();
 
         // Deal initial hands (2 cards each)
         // Use different seeds for each card to ensure variety
         let mut player1_hand = Bytes::new(&env);
         let mut player2_hand = Bytes::new(&env);
+        let mut deck = 0u64;
+
+        if seed_link.is_none() {
+            // Generate deterministic seed for card dealing
+            // Seed components (all deterministic and identical between sim/submit):
+            // 1. Session ID - unique per game
+            // 2. Player addresses - both players contribute
+            //
+            // Note: We do NOT include ledger sequence or timestamp because those differ
+            // between simulation and submission.
+            let mut seed_bytes = Bytes::new(&env);
+            seed_bytes.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
+            seed_bytes.append(&player1.to_string().to_bytes());
+            seed_bytes.append(&player2.to_string().to_bytes());
+            let base_seed = env.crypto().keccak256(&seed_bytes);
 
-        // Deal 2 cards to player1
-        for i in 0..2 {
-            let mut card_seed_bytes = Bytes::new(&env);
-            card_seed_bytes.append(&Bytes::from(base_seed.clone()));
-            card_seed_bytes.append(&Bytes::from_array(&env, &[i, 1])); // [card_index, player]
-            let card_seed = env.crypto().keccak256(&card_seed_bytes);
-            player1_hand.push_back(deal_card(&env, card_seed.into()));
-        }
+            // Deal 2 cards to player1
+            for i in 0..2 {
+                let mut card_seed_bytes = Bytes::new(&env);
+                card_seed_bytes.append(&Bytes::from(base_seed.clone()));
+                card_seed_bytes.append(&Bytes::from_array(&env, &[i, 1])); // [card_index, player]
+                let card_seed = env.crypto().keccak256(&card_seed_bytes);
+                player1_hand.push_back(deal_card(&env, card_seed.into(), &mut deck));
+            }
 
-        // Deal 2 cards to player2
-        for i in 0..2 {
-            let mut card_seed_bytes = Bytes::new(&env);
-            card_seed_bytes.append(&Bytes::from(base_seed.clone()));
-            card_seed_bytes.append(&Bytes::from_array(&env, &[i, 2])); // [card_index, player]
-            let card_seed = env.crypto().keccak256(&card_seed_bytes);
-            player2_hand.push_back(deal_card(&env, card_seed.into()));
+            // Deal 2 cards to player2
+            for i in 0..2 {
+                let mut card_seed_bytes = Bytes::new(&env);
+                card_seed_bytes.append(&Bytes::from(base_seed.clone()));
+                card_seed_bytes.append(&Bytes::from_array(&env, &[i, 2])); // [card_index, player]
+                let card_seed = env.crypto().keccak256(&card_seed_bytes);
+                player2_hand.push_back(deal_card(&env, card_seed.into(), &mut deck));
+            }
         }
 
         // Create game
@@ -252,9 +1006,46 @@ impl TwentyOneContract {
             player1_hand,
             player2_hand,
             player1_stuck: false,
-            player2_stuck: false,
+            player2_stuck: is_house_game,
             winner: None,
             round: 1,
+            player1_split_hand: Bytes::new(&env),
+            player2_split_hand: Bytes::new(&env),
+            player1_on_split: false,
+            player2_on_split: false,
+            deck,
+            stake_amount_stroops: 0,
+            stake_fee_bps: 0,
+            stake_deadline_ts: 0,
+            player1_stake_paid: false,
+            player2_stake_paid: false,
+            is_cancelled: false,
+            last_action_ts: env.ledger().timestamp(),
+            rounds_to_win: rules.max_rounds / 2 + 1,
+            player1_round_wins: 0,
+            player2_round_wins: 0,
+            round_winners: Vec::new(&env),
+            tied_rounds: 0,
+            is_push: false,
+            is_house_game,
+            rules,
+            won_on_natural: false,
+            betting_contract,
+            betting_pool_id,
+            insurance: 0,
+            player1_busts: 0,
+            player2_busts: 0,
+            player1_naturals: 0,
+            player2_naturals: 0,
+            pending_draw: if seed_link.is_some() {
+                PendingDraw::Deal
+            } else {
+                PendingDraw::None
+            },
+            seed_link,
+            player1_seed: PlayerSeed::Missing,
+            player2_seed: PlayerSeed::Missing,
+            hand_salt,
         };
 
         // Store game in temporary storage with 30-day TTL
@@ -266,13 +1057,25 @@ impl TwentyOneContract {
             .temporary()
             .extend_ttl(&game_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
 
+        if game.seed_link.is_some() {
+            return Ok(());
+        }
+
         // Event emitted by GameHub contract (GameStarted)
+        emit_card(&env, session_id, &game.player1, game.player1_hand.len());
+        emit_card(&env, session_id, &game.player2, game.player2_hand.len());
+        log_deal(&env, session_id, &game);
+
+        if let Some(player1_won) = natural_winner(&game)? {
+            Self::award_natural(&env, session_id, game, player1_won)?;
+        }
 
         Ok(())
     }
 
     /// Player draws another card ("hit").
-    /// If the player's hand value exceeds 21, they bust and lose immediately.
+    /// If the player's hand value exceeds 21, they bust and lose the round immediately.
+    /// In a seeded game the card is drawn when the operator reveals the next link.
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
@@ -293,6 +1096,8 @@ impl TwentyOneContract {
             return Err(Error::GameAlreadyEnded);
         }
 
+        Self::require_stakes_paid(&game)?;
+
         // Check player hasn't stuck yet
         let is_player1 = player == game.player1;
         let is_player2 = player == game.player2;
@@ -308,79 +1113,288 @@ impl TwentyOneContract {
         if is_player2 && game.player2_stuck {
             return Err(Error::AlreadyStuck);
         }
+        game.last_action_ts = env.ledger().timestamp();
+
+        if game.seed_link.is_some() {
+            // Seeded games draw when the operator reveals the next link
+            game.pending_draw = PendingDraw::Hit(player);
+            env.storage().temporary().set(&key, &game);
+            return Ok(());
+        }
+
+        Self::draw_hit(&env, session_id, game, player)
+    }
+
+    /// Draw a card for a hit and apply a bust, Charlie or full hand.
+    fn draw_hit(env: &Env, session_id: u32, mut game: Game, player: Address) -> Result<(), Error> {
+        let is_player1 = player == game.player1;
+        let on_split = if is_player1 {
+            game.player1_on_split
+        } else {
+            game.player2_on_split
+        };
 
         // Generate seed for new card based on current hand size
-        let mut seed_bytes = Bytes::new(&env);
-        seed_bytes.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
+        let mut seed_bytes = Bytes::new(env);
+        seed_bytes.append(&Bytes::from_array(env, &session_id.to_be_bytes()));
         seed_bytes.append(&player.to_string().to_bytes());
 
-        let card_count = if is_player1 {
-            game.player1_hand.len()
-        } else {
-            game.player2_hand.len()
+        let card_count = match (is_player1, on_split) {
+            (true, false) => game.player1_hand.len(),
+            (true, true) => game.player1_split_hand.len(),
+            (false, false) => game.player2_hand.len(),
+            (false, true) => game.player2_split_hand.len(),
         };
 
-        seed_bytes.append(&Bytes::from_array(&env, &(card_count as u32).to_be_bytes()));
-        seed_bytes.append(&Bytes::from_array(&env, &game.round.to_be_bytes()));
+        seed_bytes.append(&Bytes::from_array(env, &(card_count as u32).to_be_bytes()));
+        seed_bytes.append(&Bytes::from_array(env, &game.round.to_be_bytes()));
+        if on_split {
+            // Keep split-hand draws distinct from main-hand draws of the same size
+            seed_bytes.push_back(1);
+        }
+
+        let card_seed = card_seed(env, seed_bytes, &game);
+        let new_card = deal_card(env, card_seed, &mut game.deck);
+        emit_card(env, session_id, &player, card_count + 1);
+        log_action(env, session_id, game.round, HistoryAction::Hit, Some(player.clone()), new_card as u32);
 
-        let card_seed = env.crypto().keccak256(&seed_bytes);
-        let new_card = deal_card(&env, card_seed.into());
+        let has_split = if is_player1 {
+            !game.player1_split_hand.is_empty()
+        } else {
+            !game.player2_split_hand.is_empty()
+        };
+        if has_split {
+            return Self::hit_split_hand(env, session_id, game, is_player1, new_card);
+        }
 
         // Add card to player's hand
         if is_player1 {
             game.player1_hand.push_back(new_card);
 
             // Check if player busted
-            let hand_value = calculate_hand_value(&game.player1_hand)?;
+            let hand_value = calculate_hand_value(&game.player1_hand, game.rules.ace_mode)?;
             if hand_value > 21 {
-                // Player 1 busted, player 2 wins
-                // Call GameHub FIRST (before setting winner)
-                Self::end_game_with_hub(&env, session_id, false)?;
-
-                // Only set winner AFTER GameHub succeeds
-                game.winner = Some(game.player2.clone());
-                env.storage().temporary().set(&key, &game);
+                log_action(env, session_id, game.round, HistoryAction::Bust, Some(player.clone()), 0);
+                PlayerBusted { session_id, player }.publish(env);
+                game.player1_busts += 1;
 
+                // Player 1 busted, player 2 wins the round
                 // Return Ok - caller should check game.winner to see if game ended
+                Self::finish_round(env, session_id, game, false)?;
                 return Ok(());
             }
         } else {
             game.player2_hand.push_back(new_card);
 
             // Check if player busted
-            let hand_value = calculate_hand_value(&game.player2_hand)?;
+            let hand_value = calculate_hand_value(&game.player2_hand, game.rules.ace_mode)?;
             if hand_value > 21 {
-                // Player 2 busted, player 1 wins
-                // Call GameHub FIRST (before setting winner)
-                Self::end_game_with_hub(&env, session_id, true)?;
-
-                // Only set winner AFTER GameHub succeeds
-                game.winner = Some(game.player1.clone());
-                env.storage().temporary().set(&key, &game);
+                log_action(env, session_id, game.round, HistoryAction::Bust, Some(player.clone()), 0);
+                PlayerBusted { session_id, player }.publish(env);
+                game.player2_busts += 1;
 
+                // Player 2 busted, player 1 wins the round
                 // Return Ok - caller should check game.winner to see if game ended
+                Self::finish_round(env, session_id, game, true)?;
                 return Ok(());
             }
         }
 
+        let hand_len = if is_player1 {
+            game.player1_hand.len()
+        } else {
+            game.player2_hand.len()
+        };
+        if game.rules.charlie_cards > 0 && hand_len >= game.rules.charlie_cards {
+            // Charlie rule: enough cards without busting wins the round
+            Self::finish_round(env, session_id, game, is_player1)?;
+            return Ok(());
+        }
+        if game.rules.max_hand_cards > 0 && hand_len >= game.rules.max_hand_cards {
+            // A full hand cannot take more cards
+            Self::mark_stuck(env, session_id, &mut game, &player)?;
+        }
+
         // Store updated game
-        env.storage().temporary().set(&key, &game);
+        env.storage().temporary().set(&DataKey::Game(session_id), &game);
 
         Ok(())
     }
 
-    /// Player chooses to stick (end their turn with current hand).
-    /// If both players have stuck, the game can be revealed.
-    ///
-    /// # Arguments
-    /// * `session_id` - The session ID of the game
-    /// * `player` - Address of the player sticking
-    pub fn stick(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
-        player.require_auth();
+    /// Hit on a split hand. A bust only finishes that hand; the player loses
+    /// the round once both hands are bust.
+    fn hit_split_hand(
+        env: &Env,
+        session_id: u32,
+        mut game: Game,
+        is_player1: bool,
+        new_card: u8,
+    ) -> Result<(), Error> {
+        let ace_mode = game.rules.ace_mode;
+        let player = if is_player1 {
+            game.player1.clone()
+        } else {
+            game.player2.clone()
+        };
+        let (hand, split_hand, on_split, stuck) = if is_player1 {
+            (
+                &mut game.player1_hand,
+                &mut game.player1_split_hand,
+                &mut game.player1_on_split,
+                &mut game.player1_stuck,
+            )
+        } else {
+            (
+                &mut game.player2_hand,
+                &mut game.player2_split_hand,
+                &mut game.player2_on_split,
+                &mut game.player2_stuck,
+            )
+        };
 
-        // Get game from temporary storage
-        let key = DataKey::Game(session_id);
-        let mut game: Game = env
+        let active = if *on_split { &mut *split_hand } else { &mut *hand };
+        active.push_back(new_card);
+        let full = game.rules.max_hand_cards > 0 && active.len() >= game.rules.max_hand_cards;
+        let busted = calculate_hand_value(active, ace_mode)? > 21;
+        if busted {
+            log_action(env, session_id, game.round, HistoryAction::Bust, Some(player.clone()), 0);
+            PlayerBusted { session_id, player: player.clone() }.publish(env);
+            if *on_split {
+                *stuck = true;
+            } else {
+                *on_split = true;
+            }
+        }
+
+        let all_bust = *stuck
+            && calculate_hand_value(hand, ace_mode)? > 21
+            && calculate_hand_value(split_hand, ace_mode)? > 21;
+        if busted {
+            if is_player1 {
+                game.player1_busts += 1;
+            } else {
+                game.player2_busts += 1;
+            }
+        }
+        if all_bust {
+            Self::finish_round(env, session_id, game, !is_player1)?;
+            return Ok(());
+        }
+        if full && !busted {
+            // A full hand cannot take more cards
+            Self::mark_stuck(env, session_id, &mut game, &player)?;
+        }
+
+        env.storage().temporary().set(&DataKey::Game(session_id), &game);
+
+        Ok(())
+    }
+
+    /// Split an initial pair into two hands, each topped up with a new card.
+    /// The first hand is played out (hit/stick) before the second. In a
+    /// seeded game the cards are drawn when the operator reveals the next link.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player splitting
+    pub fn split(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.winner.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        Self::require_stakes_paid(&game)?;
+
+        let is_player1 = player == game.player1;
+        if !is_player1 && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+
+        let (hand, split_hand, stuck) = if is_player1 {
+            (&game.player1_hand, &game.player1_split_hand, game.player1_stuck)
+        } else {
+            (&game.player2_hand, &game.player2_split_hand, game.player2_stuck)
+        };
+
+        if stuck {
+            return Err(Error::AlreadyStuck);
+        }
+        if hand.len() != 2 || hand.get(0) != hand.get(1) || !split_hand.is_empty() {
+            return Err(Error::CannotSplit);
+        }
+
+        game.last_action_ts = env.ledger().timestamp();
+        if game.seed_link.is_some() {
+            // Seeded games draw when the operator reveals the next link
+            game.pending_draw = PendingDraw::Split(player);
+            env.storage().temporary().set(&key, &game);
+            return Ok(());
+        }
+
+        Self::draw_split(&env, session_id, game, player)
+    }
+
+    /// Split the player's pair and deal the second card of each hand.
+    fn draw_split(
+        env: &Env,
+        session_id: u32,
+        mut game: Game,
+        player: Address,
+    ) -> Result<(), Error> {
+        let round = game.round;
+        let mut seed_bytes = Bytes::new(env);
+        seed_bytes.append(&Bytes::from_array(env, &session_id.to_be_bytes()));
+        seed_bytes.append(&player.to_string().to_bytes());
+        seed_bytes.append(&Bytes::from_array(env, &round.to_be_bytes()));
+        seed_bytes.append(&Bytes::from_slice(env, b"split"));
+        let base_seed = card_seed(env, seed_bytes, &game);
+
+        let (hand, split_hand) = if player == game.player1 {
+            (&mut game.player1_hand, &mut game.player1_split_hand)
+        } else {
+            (&mut game.player2_hand, &mut game.player2_split_hand)
+        };
+        let pair_card = hand.get(1).ok_or(Error::InvalidHandData)?;
+        hand.pop_back();
+        split_hand.push_back(pair_card);
+
+        // Deal the second card of each hand
+        log_action(env, session_id, round, HistoryAction::Split, Some(player.clone()), 0);
+        for (i, target) in [hand, split_hand].into_iter().enumerate() {
+            let mut card_seed_bytes = Bytes::from(base_seed.clone());
+            card_seed_bytes.push_back(i as u8);
+            let card_seed = env.crypto().keccak256(&card_seed_bytes);
+            let card = deal_card(env, card_seed.into(), &mut game.deck);
+            target.push_back(card);
+            emit_card(env, session_id, &player, target.len());
+            log_action(env, session_id, round, HistoryAction::Deal, Some(player.clone()), card as u32);
+        }
+
+        env.storage().temporary().set(&DataKey::Game(session_id), &game);
+
+        Ok(())
+    }
+
+    /// Player chooses to stick (end their turn with current hand).
+    /// After a split, the first stick moves play to the second hand.
+    /// If both players have stuck, the game can be revealed.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player sticking
+    pub fn stick(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
+
+        // Get game from temporary storage
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
             .storage()
             .temporary()
             .get(&key)
@@ -390,170 +1404,2206 @@ impl TwentyOneContract {
         if game.winner.is_some() {
             return Err(Error::GameAlreadyEnded);
         }
+        Self::require_stakes_paid(&game)?;
 
-        // Mark player as stuck
-        if player == game.player1 {
+        Self::mark_stuck(&env, session_id, &mut game, &player)?;
+
+        // Store updated game
+        env.storage().temporary().set(&key, &game);
+
+        Ok(())
+    }
+
+    /// Stick the player's current hand: moves a split player on to the
+    /// second hand, otherwise marks the player as stuck.
+    fn mark_stuck(
+        env: &Env,
+        session_id: u32,
+        game: &mut Game,
+        player: &Address,
+    ) -> Result<(), Error> {
+        if *player == game.player1 {
             if game.player1_stuck {
                 return Err(Error::AlreadyStuck);
             }
-            game.player1_stuck = true;
-        } else if player == game.player2 {
+            if !game.player1_split_hand.is_empty() && !game.player1_on_split {
+                game.player1_on_split = true;
+            } else {
+                game.player1_stuck = true;
+                PlayerStuck { session_id, player: player.clone() }.publish(env);
+            }
+        } else if *player == game.player2 {
             if game.player2_stuck {
                 return Err(Error::AlreadyStuck);
             }
-            game.player2_stuck = true;
+            if !game.player2_split_hand.is_empty() && !game.player2_on_split {
+                game.player2_on_split = true;
+            } else {
+                game.player2_stuck = true;
+                PlayerStuck { session_id, player: player.clone() }.publish(env);
+            }
         } else {
             return Err(Error::NotPlayer);
         }
+        game.last_action_ts = env.ledger().timestamp();
+        log_action(env, session_id, game.round, HistoryAction::Stick, Some(player.clone()), 0);
+
+        Ok(())
+    }
+
+    /// Reveal the winner of the round and submit outcome to GameHub once
+    /// the series is decided.
+    /// Can only be called after both players have stuck.
+    /// This calculates hand values, determines the winner (closest to 21),
+    /// and handles ties according to the `TiePolicy`: by dealing new hands
+    /// or ending the game as a push. With split hands, every hand is compared
+    /// against each of the opponent's and the wins and losses are summed. In
+    /// a best-of-N series an undecided game continues with a freshly dealt
+    /// round.
+    ///
+    /// Only the players or the admin may reveal, until both players have
+    /// been stuck for the game's action timeout; after that anyone can, so
+    /// a finished game cannot be left unrevealed. In a seeded house game the
+    /// dealer's draws wait for the operator's next link, and the round is
+    /// decided by `reveal_seed`.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `caller` - Address revealing the winner
+    ///
+    /// # Returns
+    /// * `Option<Address>` - Address of the round winner (check game.winner for
+    ///   the game), or `None` if the round was tied or waits for the dealer
+    pub fn reveal_winner(
+        env: Env,
+        session_id: u32,
+        caller: Address,
+    ) -> Result<Option<Address>, Error> {
+        caller.require_auth();
+
+        // Get game from temporary storage
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        // Check if game already ended (has a winner or was pushed)
+        if game.winner.is_some() || game.is_push {
+            return Ok(game.winner);
+        }
+
+        Self::require_stakes_paid(&game)?;
+
+        // Check both players have stuck
+        if !game.player1_stuck || !game.player2_stuck {
+            return Err(Error::BothPlayersNotStuck);
+        }
+
+        let is_participant = caller == game.player1
+            || caller == game.player2
+            || caller == Self::get_admin(env.clone());
+        let reveal_open_at = game.last_action_ts.saturating_add(game.rules.timeout_seconds);
+        if !is_participant && env.ledger().timestamp() < reveal_open_at {
+            return Err(Error::NotPlayer);
+        }
+
+        if game.is_house_game
+            && game.seed_link.is_some()
+            && calculate_hand_value(&game.player2_hand, game.rules.ace_mode)? < DEALER_STAND_VALUE
+        {
+            game.pending_draw = PendingDraw::Dealer;
+            env.storage().temporary().set(&key, &game);
+            return Ok(None);
+        }
+
+        Self::settle_round(&env, session_id, game)
+    }
+
+    /// Decide a round once both players have stuck, playing out the house
+    /// dealer first in house games.
+    fn settle_round(env: &Env, session_id: u32, mut game: Game) -> Result<Option<Address>, Error> {
+        if game.is_house_game {
+            Self::play_dealer(env, session_id, &mut game);
+        }
+
+        // Net hand outcomes for player 1 (closest to 21 without going over wins).
+        // Note: an unsplit bust is already handled in hit(); only one of two
+        // split hands can be bust here.
+        let mut player1_net = 0i32;
+        let player2_hands = player_hands(env, &game.player2_hand, &game.player2_split_hand);
+        for hand in player_hands(env, &game.player1_hand, &game.player1_split_hand).iter() {
+            for other in player2_hands.iter() {
+                player1_net += hand_outcome(&hand, &other, game.rules.ace_mode)?;
+            }
+        }
+
+        if player1_net == 0 {
+            // Past the round cap a tie is a push whatever the tie policy, so
+            // repeated ties cannot keep points locked forever
+            let redeal = game.round < Self::get_round_cap(env.clone())
+                && match game.rules.tie_policy {
+                    TiePolicy::Redeal => true,
+                    TiePolicy::Push => false,
+                    TiePolicy::RedealLimit(max_redeals) => game.tied_rounds < max_redeals,
+                };
+            game.tied_rounds += 1;
+            Self::settle_insurance(env, session_id, &mut game)?;
+            RoundEnded {
+                session_id,
+                round: game.round,
+                winner: None,
+            }
+            .publish(env);
+            log_action(env, session_id, game.round, HistoryAction::RoundResult, None, 0);
+
+            if redeal {
+                // Draw - deal new hands and continue
+                Self::deal_round(env, session_id, &mut game)?;
+                if let Some(player1_won) = natural_winner(&game)? {
+                    Self::award_natural(env, session_id, game, player1_won)?;
+                } else {
+                    env.storage().temporary().set(&DataKey::Game(session_id), &game);
+                }
+            } else {
+                Self::push_game(env, session_id, game)?;
+            }
+
+            return Ok(None);
+        }
+
+        // Player with the positive net is closer to 21
+        Self::finish_round(env, session_id, game, player1_net > 0).map(Some)
+    }
+
+    /// House dealer rules: draw until the hand is worth at least 17.
+    fn play_dealer(env: &Env, session_id: u32, game: &mut Game) {
+        while calculate_hand_value(&game.player2_hand, game.rules.ace_mode).unwrap_or(0)
+            < DEALER_STAND_VALUE {
+            let mut seed_bytes = Bytes::new(env);
+            seed_bytes.append(&Bytes::from_array(env, &session_id.to_be_bytes()));
+            seed_bytes.append(&game.player2.to_string().to_bytes());
+            seed_bytes.append(&Bytes::from_array(
+                env,
+                &game.player2_hand.len().to_be_bytes(),
+            ));
+            seed_bytes.append(&Bytes::from_array(env, &game.round.to_be_bytes()));
+            let card_seed = card_seed(env, seed_bytes, game);
+            let card = deal_card(env, card_seed, &mut game.deck);
+            game.player2_hand.push_back(card);
+            emit_card(env, session_id, &game.player2, game.player2_hand.len());
+            log_action(env, session_id, game.round, HistoryAction::Hit, Some(game.player2.clone()), card as u32);
+        }
+        if calculate_hand_value(&game.player2_hand, game.rules.ace_mode).unwrap_or(0) > 21 {
+            log_action(env, session_id, game.round, HistoryAction::Bust, Some(game.player2.clone()), 0);
+            PlayerBusted { session_id, player: game.player2.clone() }.publish(env);
+        }
+    }
+
+    /// End a tied game as a push: no winner, and each player's stake is
+    /// returned (fees are kept as the game was played).
+    fn push_game(env: &Env, session_id: u32, mut game: Game) -> Result<(), Error> {
+        if game.stake_amount_stroops > 0 {
+            let xlm = token::Client::new(env, &Self::xlm_token(env));
+            xlm.transfer(
+                &env.current_contract_address(),
+                &game.player1,
+                &game.stake_amount_stroops,
+            );
+            Self::release_liability(env, game.stake_amount_stroops);
+            if game.is_house_game {
+                Self::credit_bankroll(env, game.stake_amount_stroops);
+            } else {
+                xlm.transfer(
+                    &env.current_contract_address(),
+                    &game.player2,
+                    &game.stake_amount_stroops,
+                );
+                Self::release_liability(env, game.stake_amount_stroops);
+            }
+
+            Self::accrue_stake_fees(env, &game);
+        }
+
+        game.is_push = true;
+        env.storage().temporary().set(&DataKey::Game(session_id), &game);
+        Self::record_stats(env, &game, None);
+
+        Self::close_without_winner(env, session_id, &game);
+
+        Ok(())
+    }
+
+    /// Record a decided round. Once a player reaches `rounds_to_win` the game
+    /// is reported to the Game Hub; otherwise the next round is dealt.
+    ///
+    /// # Returns
+    /// * `Address` - Address of the round winner
+    fn finish_round(
+        env: &Env,
+        session_id: u32,
+        mut game: Game,
+        player1_won: bool,
+    ) -> Result<Address, Error> {
+        Self::settle_insurance(env, session_id, &mut game)?;
+
+        let winner = if player1_won {
+            game.player1_round_wins += 1;
+            game.player1.clone()
+        } else {
+            game.player2_round_wins += 1;
+            game.player2.clone()
+        };
+        game.round_winners.push_back(winner.clone());
+        RoundEnded {
+            session_id,
+            round: game.round,
+            winner: Some(winner.clone()),
+        }
+        .publish(env);
+        log_action(env, session_id, game.round, HistoryAction::RoundResult, Some(winner.clone()), 0);
+
+        let wins = if player1_won {
+            game.player1_round_wins
+        } else {
+            game.player2_round_wins
+        };
+        if wins >= game.rounds_to_win {
+            // Call GameHub FIRST (before setting winner)
+            Self::end_game_with_hub(env, session_id, &game, player1_won)?;
+
+            // Only update game with winner AFTER GameHub succeeds
+            game.winner = Some(winner.clone());
+        } else {
+            Self::deal_round(env, session_id, &mut game)?;
+            if let Some(player1_won) = natural_winner(&game)? {
+                Self::award_natural(env, session_id, game, player1_won)?;
+                return Ok(winner);
+            }
+        }
+
+        env.storage().temporary().set(&DataKey::Game(session_id), &game);
+
+        Ok(winner)
+    }
+
+    /// Rules for games started without their own: soft Aces, no Charlie rule,
+    /// single round, with the contract's tie policy and action timeout.
+    fn default_rules(env: &Env) -> GameRules {
+        GameRules {
+            ace_mode: AceMode::Soft,
+            charlie_cards: 0,
+            max_hand_cards: 0,
+            tie_policy: Self::get_tie_policy(env.clone()),
+            max_rounds: 1,
+            timeout_seconds: Self::get_action_timeout(env.clone()),
+        }
+    }
+
+    /// Award a freshly dealt round to the player holding a natural.
+    fn award_natural(
+        env: &Env,
+        session_id: u32,
+        mut game: Game,
+        player1_won: bool,
+    ) -> Result<Address, Error> {
+        game.won_on_natural = true;
+        if player1_won {
+            game.player1_naturals += 1;
+        } else {
+            game.player2_naturals += 1;
+        }
+        Self::finish_round(env, session_id, game, player1_won)
+    }
+
+    /// Start the next round: reset turn state and deal two fresh cards each
+    /// (seeded games are dealt when the operator reveals the next link).
+    fn deal_round(env: &Env, session_id: u32, game: &mut Game) -> Result<(), Error> {
+        game.round = game.round.checked_add(1).ok_or(Error::RoundOverflow)?;
+        game.last_action_ts = env.ledger().timestamp();
+
+        game.won_on_natural = false;
+
+        // Reset stuck flags (the house only plays once the player sticks)
+        game.player1_stuck = false;
+        game.player2_stuck = game.is_house_game;
+
+        // Clear hands
+        game.player1_hand = Bytes::new(env);
+        game.player2_hand = Bytes::new(env);
+        game.player1_split_hand = Bytes::new(env);
+        game.player2_split_hand = Bytes::new(env);
+        game.player1_on_split = false;
+        game.player2_on_split = false;
+        log_action(env, session_id, game.round, HistoryAction::Redeal, None, 0);
+
+        // Seeded games are dealt by the operator's next reveal
+        if game.seed_link.is_some() {
+            game.pending_draw = PendingDraw::Deal;
+        } else {
+            Self::deal_hands(env, session_id, game);
+        }
+
+        Ok(())
+    }
+
+    /// Deal two cards to each player for the current round.
+    fn deal_hands(env: &Env, session_id: u32, game: &mut Game) {
+        let mut seed_bytes = Bytes::new(env);
+        seed_bytes.append(&Bytes::from_array(env, &session_id.to_be_bytes()));
+        seed_bytes.append(&game.player1.to_string().to_bytes());
+        seed_bytes.append(&game.player2.to_string().to_bytes());
+        seed_bytes.append(&Bytes::from_array(env, &game.round.to_be_bytes()));
+        let base_seed = card_seed(env, seed_bytes, game);
+
+        // Deal 2 cards to player1
+        for i in 0..2 {
+            let mut card_seed_bytes = Bytes::new(env);
+            card_seed_bytes.append(&Bytes::from(base_seed.clone()));
+            card_seed_bytes.append(&Bytes::from_array(env, &[i, 1])); // [card_index, player]
+            let card_seed = env.crypto().keccak256(&card_seed_bytes);
+            game.player1_hand.push_back(deal_card(env, card_seed.into(), &mut game.deck));
+        }
+
+        // Deal 2 cards to player2
+        for i in 0..2 {
+            let mut card_seed_bytes = Bytes::new(env);
+            card_seed_bytes.append(&Bytes::from(base_seed.clone()));
+            card_seed_bytes.append(&Bytes::from_array(env, &[i, 2])); // [card_index, player]
+            let card_seed = env.crypto().keccak256(&card_seed_bytes);
+            game.player2_hand.push_back(deal_card(env, card_seed.into(), &mut game.deck));
+        }
+        emit_card(env, session_id, &game.player1, game.player1_hand.len());
+        emit_card(env, session_id, &game.player2, game.player2_hand.len());
+        log_deal(env, session_id, game);
+    }
+
+    /// Commit the operator's seed chain for a game before it starts. The
+    /// operator picks a secret `s` and commits `tip = keccak256^n(s)`, then
+    /// reveals the chain back from the tip one link per draw (`reveal_seed`):
+    /// a round's deal, a hit, a split, or the house dealer's draws. Each
+    /// player also commits a secret before the first link and reveals it
+    /// after (`commit_player_seed`, `reveal_player_seed`). Every card seed
+    /// mixes the deterministic session seed with the current link and the
+    /// players' secrets, so players cannot compute a card before its link is
+    /// revealed and the operator, bound by the tip before any player secret
+    /// is known, cannot pick them. No ledger data is used, so simulation and
+    /// submission deal the same cards.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game to be started
+    /// * `tip` - The last hash of the operator's chain
+    pub fn commit_seed(env: Env, session_id: u32, tip: BytesN<32>) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        if env.storage().temporary().has(&DataKey::Game(session_id)) {
+            return Err(Error::GameInProgress);
+        }
+
+        let key = DataKey::SeedCommit(session_id);
+        env.storage().temporary().set(&key, &tip);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+
+        Ok(())
+    }
+
+    /// Reveal the next link of the operator's seed chain and make the draw
+    /// waiting on it. The link must hash (keccak256) to the previous one.
+    /// The first link needs every player's seed commitment, and the first
+    /// deal then waits for the players to reveal their seeds.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `link` - The preimage of the current link
+    pub fn reveal_seed(env: Env, session_id: u32, link: BytesN<32>) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.winner.is_some() || game.is_push {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if game.is_cancelled {
+            return Err(Error::GameCancelled);
+        }
+        let expected = game.seed_link.clone().ok_or(Error::InvalidSeedReveal)?;
+        let hashed: BytesN<32> = env.crypto().keccak256(&Bytes::from(link.clone())).into();
+        if hashed != expected {
+            return Err(Error::InvalidSeedReveal);
+        }
+        let draw = core::mem::replace(&mut game.pending_draw, PendingDraw::None);
+        if draw == PendingDraw::None {
+            return Err(Error::InvalidSeedReveal);
+        }
+        if !player_seeds_ready(&game, false) {
+            return Err(Error::PlayerSeedMissing);
+        }
+
+        game.seed_link = Some(link);
+        game.last_action_ts = env.ledger().timestamp();
+        match draw {
+            PendingDraw::Deal => Self::deal_seeded_round(&env, session_id, game),
+            PendingDraw::Hit(player) => Self::draw_hit(&env, session_id, game, player),
+            PendingDraw::Split(player) => Self::draw_split(&env, session_id, game, player),
+            PendingDraw::Dealer => Self::settle_round(&env, session_id, game).map(|_| ()),
+            PendingDraw::None => Ok(()),
+        }
+    }
+
+    /// Commit a player's seed for a seeded game as `keccak256(secret)`.
+    /// Each player commits before the operator reveals the first link.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the committing player
+    /// * `commitment` - keccak256 of the player's 32-byte secret
+    pub fn commit_player_seed(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        commitment: BytesN<32>,
+    ) -> Result<(), Error> {
+        player.require_auth();
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        let seed = if player == game.player1 {
+            &mut game.player1_seed
+        } else if player == game.player2 && !game.is_house_game {
+            &mut game.player2_seed
+        } else {
+            return Err(Error::NotPlayer);
+        };
+        // Open until the operator's first reveal takes the round 1 deal
+        let first_deal_pending = game.round == 1 && game.pending_draw == PendingDraw::Deal;
+        if *seed != PlayerSeed::Missing || !first_deal_pending {
+            return Err(Error::SeedCommitClosed);
+        }
+
+        *seed = PlayerSeed::Committed(commitment);
+        env.storage().temporary().set(&key, &game);
+
+        Ok(())
+    }
+
+    /// Reveal a player's committed seed once the operator has revealed the
+    /// first link. The last player to reveal deals the first round.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the revealing player
+    /// * `secret` - The secret behind the player's commitment
+    pub fn reveal_player_seed(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        secret: BytesN<32>,
+    ) -> Result<(), Error> {
+        player.require_auth();
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.winner.is_some() || game.is_push {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if game.is_cancelled {
+            return Err(Error::GameCancelled);
+        }
+        if game.pending_draw != PendingDraw::None {
+            return Err(Error::SeedNotRevealed);
+        }
+        let hashed: BytesN<32> = env.crypto().keccak256(&Bytes::from(secret.clone())).into();
+        let seed = if player == game.player1 {
+            &mut game.player1_seed
+        } else if player == game.player2 {
+            &mut game.player2_seed
+        } else {
+            return Err(Error::NotPlayer);
+        };
+        match seed {
+            PlayerSeed::Missing => return Err(Error::PlayerSeedMissing),
+            PlayerSeed::Committed(commitment) if *commitment == hashed => {}
+            _ => return Err(Error::InvalidSeedReveal),
+        }
+        *seed = PlayerSeed::Revealed(secret);
+
+        game.last_action_ts = env.ledger().timestamp();
+        Self::deal_seeded_round(&env, session_id, game)
+    }
+
+    /// Deal a seeded game's round once the operator's link and every
+    /// player's seed are revealed, awarding a natural straight away.
+    fn deal_seeded_round(env: &Env, session_id: u32, mut game: Game) -> Result<(), Error> {
+        let key = DataKey::Game(session_id);
+        if player_seeds_ready(&game, true) {
+            Self::deal_hands(env, session_id, &mut game);
+            if let Some(player1_won) = natural_winner(&game)? {
+                Self::award_natural(env, session_id, game, player1_won)?;
+                return Ok(());
+            }
+        }
+
+        env.storage().temporary().set(&key, &game);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+
+        Ok(())
+    }
+
+    /// Award the game to a player who has stuck when the opponent has neither
+    /// hit nor stuck within the action timeout. Callable by anyone; the result
+    /// is reported to the Game Hub as a normal win.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    ///
+    /// # Returns
+    /// * `Address` - Address of the winning player
+    pub fn claim_timeout(env: Env, session_id: u32) -> Result<Address, Error> {
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.winner.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        Self::require_stakes_paid(&game)?;
+
+        // Exactly one player must still owe a move
+        if game.player1_stuck == game.player2_stuck {
+            return Err(Error::NoIdlePlayer);
+        }
+
+        let timeout = game.rules.timeout_seconds;
+        if env.ledger().timestamp() < game.last_action_ts.saturating_add(timeout) {
+            return Err(Error::TimeoutNotReached);
+        }
+
+        let player1_won = game.player1_stuck;
+        Self::settle_insurance(&env, session_id, &mut game)?;
+        Self::end_game_with_hub(&env, session_id, &game, player1_won)?;
+
+        let winner = if player1_won {
+            game.player1.clone()
+        } else {
+            game.player2.clone()
+        };
+        game.winner = Some(winner.clone());
+        env.storage().temporary().set(&key, &game);
+
+        Ok(winner)
+    }
+
+    /// Get game information.
+    /// While the game is in play each hand is replaced by its commitment
+    /// (see `hand_commitment`) and the salt is hidden; raw cards and the salt
+    /// are shown once the game ends. The dealt-card bitmap is never shown.
+    /// Players read their own cards with `get_hands`.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    ///
+    /// # Returns
+    /// * `Game` - The game state (includes hands and winner after game ends)
+    pub fn get_game(env: Env, session_id: u32) -> Result<Game, Error> {
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        game.deck = 0;
+        if game.winner.is_none() && !game.is_push {
+            let salt = game.hand_salt.clone();
+            game.player1_hand = hand_commitment(&env, &salt, game.round, &game.player1_hand);
+            game.player2_hand = hand_commitment(&env, &salt, game.round, &game.player2_hand);
+            game.player1_split_hand =
+                hand_commitment(&env, &salt, game.round, &game.player1_split_hand);
+            game.player2_split_hand =
+                hand_commitment(&env, &salt, game.round, &game.player2_split_hand);
+            game.hand_salt = BytesN::from_array(&env, &[0u8; 32]);
+        }
+
+        Ok(game)
+    }
+
+    /// Get a spectator-safe summary of a game: turn state, card counts and
+    /// result, never the cards themselves.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    ///
+    /// # Returns
+    /// * `PublicGame` - The game summary
+    pub fn get_public_game(env: Env, session_id: u32) -> Result<PublicGame, Error> {
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+
+        Ok(PublicGame {
+            player1: game.player1,
+            player2: game.player2,
+            round: game.round,
+            player1_stuck: game.player1_stuck,
+            player2_stuck: game.player2_stuck,
+            player1_cards: game.player1_hand.len(),
+            player2_cards: game.player2_hand.len(),
+            player1_split_cards: game.player1_split_hand.len(),
+            player2_split_cards: game.player2_split_hand.len(),
+            player1_round_wins: game.player1_round_wins,
+            player2_round_wins: game.player2_round_wins,
+            winner: game.winner,
+            is_push: game.is_push,
+            is_cancelled: game.is_cancelled,
+        })
+    }
+
+    /// Get a player's lifetime stats across finished games (pushes count
+    /// rounds and busts but neither a win nor a loss).
+    ///
+    /// # Arguments
+    /// * `player` - Address of the player
+    ///
+    /// # Returns
+    /// * `PlayerStats` - The player's stats (all zero for a new player)
+    pub fn get_player_stats(env: Env, player: Address) -> PlayerStats {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PlayerStats(player))
+            .unwrap_or_default()
+    }
+
+    /// Get the archived result of a finished game. Results are written when a
+    /// game ends with a winner, a push or a cancellation (no winner) and
+    /// outlive the game itself.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    ///
+    /// # Returns
+    /// * `GameResult` - Players, final hand values, winner and rounds played
+    pub fn get_game_result(env: Env, session_id: u32) -> Result<GameResult, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::GameResult(session_id))
+            .ok_or(Error::GameNotFound)
+    }
+
+    /// Get a page of the game's hand history, oldest first. Available once
+    /// the game has ended (or expired), since entries include card values.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `page` - Page number, starting at 0
+    ///
+    /// # Returns
+    /// * `Vec<HistoryEntry>` - Up to 20 entries; empty past the last page
+    pub fn get_history(env: Env, session_id: u32, page: u32) -> Result<Vec<HistoryEntry>, Error> {
+        let game: Option<Game> = env.storage().temporary().get(&DataKey::Game(session_id));
+        if let Some(game) = game {
+            if game.winner.is_none() && !game.is_push && !game.is_cancelled {
+                return Err(Error::GameInProgress);
+            }
+        }
+
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::History(session_id, page))
+            .unwrap_or(Vec::new(&env)))
+    }
+
+    /// Get the caller's own hands: the main hand, then the split hand if the
+    /// player has split.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player
+    ///
+    /// # Returns
+    /// * `Vec<Bytes>` - The player's hands, one card (1-13) per byte
+    pub fn get_hands(env: Env, session_id: u32, player: Address) -> Result<Vec<Bytes>, Error> {
+        player.require_auth();
+
+        let key = DataKey::Game(session_id);
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if player == game.player1 {
+            Ok(player_hands(&env, &game.player1_hand, &game.player1_split_hand))
+        } else if player == game.player2 {
+            Ok(player_hands(&env, &game.player2_hand, &game.player2_split_hand))
+        } else {
+            Err(Error::NotPlayer)
+        }
+    }
+
+    /// Get the salt of a game's hand commitments, needed to open a
+    /// commitment or prove a claim about it with `prove_hand`. Only the
+    /// players may query it.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player
+    pub fn get_hand_salt(env: Env, session_id: u32, player: Address) -> Result<BytesN<32>, Error> {
+        player.require_auth();
+
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+
+        if player != game.player1 && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+        Ok(game.hand_salt)
+    }
+
+    /// Get the current hand value for a player. Only the player may query
+    /// their own hand.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player
+    ///
+    /// # Returns
+    /// * `u32` - The total value of the player's hand
+    pub fn get_hand_value(env: Env, session_id: u32, player: Address) -> Result<u32, Error> {
+        player.require_auth();
+
+        let key = DataKey::Game(session_id);
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if player == game.player1 {
+            calculate_hand_value(&game.player1_hand, game.rules.ace_mode)
+        } else if player == game.player2 {
+            calculate_hand_value(&game.player2_hand, game.rules.ace_mode)
+        } else {
+            Err(Error::NotPlayer)
+        }
+    }
+
+    /// Prove a claim about the player's hidden main hand without revealing
+    /// the cards, for disputes in hidden-hand mode. The proof is checked by
+    /// the configured zk-groth16-verifier against public inputs built here:
+    /// the hand's commitment (as shown by `get_game`), session ID, round,
+    /// claim kind (0 = not busted, 1 = value) and claimed value. A verified
+    /// claim is published as a `HandProven` event for the other side of the
+    /// dispute; nothing is stored.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the proving player
+    /// * `claim` - The statement proven about the hand
+    /// * `proof` - Groth16 proof bytes
+    pub fn prove_hand(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        claim: HandClaim,
+        proof: Bytes,
+    ) -> Result<(), Error> {
+        player.require_auth();
+
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+
+        let hand = if player == game.player1 {
+            &game.player1_hand
+        } else if player == game.player2 {
+            &game.player2_hand
+        } else {
+            return Err(Error::NotPlayer);
+        };
+
+        let verifier_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::ZkVerifier)
+            .ok_or(Error::ZkVerifierNotConfigured)?;
+        let vk_id: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ZkVkId)
+            .ok_or(Error::ZkVerifierNotConfigured)?;
+
+        let commitment = hand_commitment(&env, &game.hand_salt, game.round, hand);
+        let commitment = BytesN::<32>::try_from(commitment).map_err(|_| Error::InvalidHandData)?;
+        let (claim_kind, claim_value) = match claim {
+            HandClaim::NotBusted => (0, 21),
+            HandClaim::Value(value) => (1, value),
+        };
+        let public_inputs = vec![
+            &env,
+            commitment,
+            u32_to_bytes32(&env, session_id),
+            u32_to_bytes32(&env, game.round),
+            u32_to_bytes32(&env, claim_kind),
+            u32_to_bytes32(&env, claim_value),
+        ];
+
+        let verifier = ZkVerifierContractClient::new(&env, &verifier_addr);
+        if !verifier.verify_round_proof(&vk_id, &proof, &public_inputs) {
+            return Err(Error::ZkProofInvalid);
+        }
+
+        HandProven {
+            session_id,
+            player,
+            round: game.round,
+            claim,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // Multi-player Tables
+    // ========================================================================
+
+    /// Start a table for 3-4 players, each dealt 2 cards. Seats act in the
+    /// order given.
+    ///
+    /// Tables are for unstaked, points-free play. The Game Hub tracks two
+    /// players per session, so tables are not registered with it: no points
+    /// are locked and no result is reported. The result is the table's
+    /// `winners`.
+    ///
+    /// # Arguments
+    /// * `session_id` - Unique session identifier (u32)
+    /// * `players` - Addresses of the players, in turn order
+    pub fn start_table(env: Env, session_id: u32, players: Vec<Address>) -> Result<(), Error> {
+        if players.len() < MIN_TABLE_SEATS || players.len() > MAX_TABLE_SEATS {
+            return Err(Error::InvalidPlayerCount);
+        }
+        for i in 0..players.len() {
+            for j in (i + 1)..players.len() {
+                if players.get_unchecked(i) == players.get_unchecked(j) {
+                    return Err(Error::SelfPlay);
+                }
+            }
+        }
+
+        let key = DataKey::Table(session_id);
+        if env.storage().temporary().has(&key) {
+            return Err(Error::GameInProgress);
+        }
+
+        let mut seed_bytes = Bytes::new(&env);
+        seed_bytes.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
+        for player in players.iter() {
+            player.require_auth_for_args(vec![&env, session_id.into_val(&env)]);
+            seed_bytes.append(&player.to_string().to_bytes());
+        }
+        let base_seed = env.crypto().keccak256(&seed_bytes);
+
+        // Drawn before any card, since dealing reseeds the PRNG from public data
+        let hand_salt: BytesN<32> = env.prng().gen();
+
+        // Deal 2 cards to each seat
+        let mut deck = 0u64;
+        let mut seats = Vec::new(&env);
+        for (seat, player) in players.iter().enumerate() {
+            let mut hand = Bytes::new(&env);
+            for i in 0..2 {
+                let mut card_seed_bytes = Bytes::new(&env);
+                card_seed_bytes.append(&Bytes::from(base_seed.clone()));
+                card_seed_bytes.append(&Bytes::from_array(&env, &[i, seat as u8 + 1])); // [card_index, seat]
+                let card_seed = env.crypto().keccak256(&card_seed_bytes);
+                hand.push_back(deal_card(&env, card_seed.into(), &mut deck));
+            }
+            emit_card(&env, session_id, &player, hand.len());
+            seats.push_back(Seat {
+                player,
+                hand,
+                stuck: false,
+                busted: false,
+            });
+        }
+
+        let table = Table {
+            seats,
+            turn: 0,
+            deck,
+            winners: None,
+            last_action_ts: env.ledger().timestamp(),
+            hand_salt,
+        };
+        env.storage().temporary().set(&key, &table);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+
+        Ok(())
+    }
+
+    /// Draw a card for the seat whose turn it is. A bust ends that seat's turn.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the table
+    /// * `player` - Address of the player drawing a card
+    pub fn table_hit(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
+
+        let key = DataKey::Table(session_id);
+        let mut table = Self::load_table(&env, session_id)?;
+        let mut seat = Self::current_seat(&table, &player)?;
+
+        let mut seed_bytes = Bytes::new(&env);
+        seed_bytes.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
+        seed_bytes.append(&player.to_string().to_bytes());
+        seed_bytes.append(&Bytes::from_array(&env, &seat.hand.len().to_be_bytes()));
+        let card_seed = env.crypto().keccak256(&seed_bytes);
+        seat.hand.push_back(deal_card(&env, card_seed.into(), &mut table.deck));
+        emit_card(&env, session_id, &player, seat.hand.len());
+
+        let turn = table.turn;
+        if calculate_hand_value(&seat.hand, AceMode::Soft)? > 21 {
+            PlayerBusted { session_id, player }.publish(&env);
+            seat.busted = true;
+            table.turn += 1;
+        }
+
+        table.seats.set(turn, seat);
+        table.last_action_ts = env.ledger().timestamp();
+        env.storage().temporary().set(&key, &table);
+
+        Ok(())
+    }
+
+    /// End the current seat's turn with its hand as it stands.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the table
+    /// * `player` - Address of the player sticking
+    pub fn table_stick(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
+
+        let key = DataKey::Table(session_id);
+        let mut table = Self::load_table(&env, session_id)?;
+        let mut seat = Self::current_seat(&table, &player)?;
+
+        seat.stuck = true;
+        table.seats.set(table.turn, seat);
+        table.turn += 1;
+        table.last_action_ts = env.ledger().timestamp();
+        PlayerStuck { session_id, player }.publish(&env);
+        env.storage().temporary().set(&key, &table);
+
+        Ok(())
+    }
+
+    /// Reveal a table once every seat has stuck or bust. The seats sharing the
+    /// best live hand win.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the table
+    ///
+    /// # Returns
+    /// * `Vec<Address>` - The winning players (empty if every seat bust)
+    pub fn reveal_table(env: Env, session_id: u32) -> Result<Vec<Address>, Error> {
+        let key = DataKey::Table(session_id);
+        let mut table: Table = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if let Some(winners) = table.winners {
+            return Ok(winners);
+        }
+        if table.turn < table.seats.len() {
+            return Err(Error::TurnsNotFinished);
+        }
+
+        let mut best = 0u32;
+        for seat in table.seats.iter() {
+            if !seat.busted {
+                best = best.max(calculate_hand_value(&seat.hand, AceMode::Soft)?);
+            }
+        }
+        let mut winners = Vec::new(&env);
+        for seat in table.seats.iter() {
+            if !seat.busted && calculate_hand_value(&seat.hand, AceMode::Soft)? == best {
+                winners.push_back(seat.player);
+            }
+        }
+
+        table.winners = Some(winners.clone());
+        env.storage().temporary().set(&key, &table);
+        TableRevealed {
+            session_id,
+            winners: winners.clone(),
+        }
+        .publish(&env);
+
+        Ok(winners)
+    }
+
+    /// Get table information. Hands are shown as commitments, keyed by seat,
+    /// until the table is revealed, as in `get_game`.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the table
+    pub fn get_table(env: Env, session_id: u32) -> Result<Table, Error> {
+        let mut table: Table = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Table(session_id))
+            .ok_or(Error::GameNotFound)?;
+
+        table.deck = 0;
+        if table.winners.is_none() {
+            for i in 0..table.seats.len() {
+                let mut seat = table.seats.get_unchecked(i);
+                seat.hand = hand_commitment(&env, &table.hand_salt, i, &seat.hand);
+                table.seats.set(i, seat);
+            }
+            table.hand_salt = BytesN::from_array(&env, &[0u8; 32]);
+        }
+
+        Ok(table)
+    }
+
+    /// Get the caller's own hand at a table, as in `get_hands`. Only the
+    /// seated player may query it.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the table
+    /// * `player` - Address of the seated player
+    ///
+    /// # Returns
+    /// * `Bytes` - The seat's hand, one card (1-13) per byte
+    pub fn get_table_hand(env: Env, session_id: u32, player: Address) -> Result<Bytes, Error> {
+        player.require_auth();
+
+        let table: Table = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Table(session_id))
+            .ok_or(Error::GameNotFound)?;
+        table
+            .seats
+            .iter()
+            .find(|seat| seat.player == player)
+            .map(|seat| seat.hand)
+            .ok_or(Error::NotPlayer)
+    }
+
+    // ========================================================================
+    // Tournaments
+    // ========================================================================
+
+    /// Create a single-elimination tournament for 2, 4, 8 or 16 players.
+    /// Each entrant joins with `join_tournament` within a day, paying the
+    /// entry stake into the prize pool; otherwise `cancel_tournament`
+    /// refunds the stakes. Leg games are ordinary sessions reported to the
+    /// Game Hub, with session IDs allocated from `first_session_id`.
+    /// Tournaments are kept in persistent storage, as they outlast their
+    /// leg games.
+    ///
+    /// # Arguments
+    /// * `tournament_id` - Unique tournament identifier
+    /// * `players` - Entrants in seeding order
+    /// * `entry_stake_stroops` - XLM each entrant pays in (0 = free)
+    /// * `first_session_id` - Session ID of the first leg game
+    pub fn create_tournament(
+        env: Env,
+        tournament_id: u32,
+        players: Vec<Address>,
+        entry_stake_stroops: i128,
+        first_session_id: u32,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        let key = DataKey::Tournament(tournament_id);
+        if env.storage().persistent().has(&key) {
+            return Err(Error::GameInProgress);
+        }
+        if players.len() < 2 || players.len() > 16 || !players.len().is_power_of_two() {
+            return Err(Error::InvalidPlayerCount);
+        }
+        for i in 0..players.len() {
+            for j in (i + 1)..players.len() {
+                if players.get_unchecked(i) == players.get_unchecked(j) {
+                    return Err(Error::SelfPlay);
+                }
+            }
+        }
+        if entry_stake_stroops < 0 {
+            return Err(Error::InvalidStake);
+        }
+        if entry_stake_stroops > 0 && !env.storage().instance().has(&DataKey::XlmToken) {
+            return Err(Error::WageringNotConfigured);
+        }
+
+        let tournament = Tournament {
+            alive: players.clone(),
+            players,
+            joined: Vec::new(&env),
+            entry_stake_stroops,
+            prize_pool_stroops: 0,
+            leg: 0,
+            sessions: Vec::new(&env),
+            next_session_id: first_session_id,
+            champion: None,
+            join_deadline_ts: env
+                .ledger()
+                .timestamp()
+                .saturating_add(TOURNAMENT_JOIN_WINDOW_SECONDS),
+            is_cancelled: false,
+        };
+        Self::save_tournament(&env, tournament_id, &tournament);
+
+        Ok(())
+    }
+
+    /// Join a tournament before its join deadline, paying the entry stake.
+    ///
+    /// # Arguments
+    /// * `tournament_id` - The tournament to join
+    /// * `player` - Address of the entrant
+    pub fn join_tournament(env: Env, tournament_id: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
+
+        let mut tournament = Self::get_tournament(env.clone(), tournament_id)?;
+
+        if tournament.is_cancelled {
+            return Err(Error::GameCancelled);
+        }
+        if !tournament.players.contains(&player) {
+            return Err(Error::NotPlayer);
+        }
+        if tournament.joined.contains(&player) {
+            return Err(Error::AlreadyJoined);
+        }
+        if env.ledger().timestamp() > tournament.join_deadline_ts {
+            return Err(Error::StakeDepositExpired);
+        }
+
+        if tournament.entry_stake_stroops > 0 {
+            let xlm = token::Client::new(&env, &Self::xlm_token(&env));
+            xlm.transfer(&player, env.current_contract_address(), &tournament.entry_stake_stroops);
+            Self::add_liability(&env, tournament.entry_stake_stroops);
+            tournament.prize_pool_stroops += tournament.entry_stake_stroops;
+        }
+        tournament.joined.push_back(player);
+        Self::save_tournament(&env, tournament_id, &tournament);
+
+        Ok(())
+    }
+
+    /// Cancel a tournament that not every entrant joined by its join
+    /// deadline, refunding the entry stakes paid. Callable by anyone.
+    ///
+    /// # Arguments
+    /// * `tournament_id` - The tournament to cancel
+    pub fn cancel_tournament(env: Env, tournament_id: u32) -> Result<(), Error> {
+        let mut tournament = Self::get_tournament(env.clone(), tournament_id)?;
+
+        if tournament.is_cancelled {
+            return Err(Error::GameCancelled);
+        }
+        if tournament.leg > 0 || tournament.joined.len() == tournament.players.len() {
+            return Err(Error::GameInProgress);
+        }
+        if env.ledger().timestamp() <= tournament.join_deadline_ts {
+            return Err(Error::DeadlineNotReached);
+        }
+
+        Self::refund_tournament_entries(&env, tournament_id, &mut tournament);
+        Ok(())
+    }
+
+    /// Cancel a tournament that has already started, refunding every entry
+    /// stake from the prize pool. For tournaments that cannot finish, e.g.
+    /// when the Game Hub refuses a leg session. Admin only.
+    ///
+    /// # Arguments
+    /// * `tournament_id` - The tournament to refund
+    pub fn refund_tournament(env: Env, tournament_id: u32) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        let mut tournament = Self::get_tournament(env.clone(), tournament_id)?;
+
+        if tournament.is_cancelled {
+            return Err(Error::GameCancelled);
+        }
+        if tournament.champion.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+
+        Self::refund_tournament_entries(&env, tournament_id, &mut tournament);
+        Ok(())
+    }
+
+    /// Return each joined entrant's stake and mark the tournament cancelled
+    fn refund_tournament_entries(env: &Env, tournament_id: u32, tournament: &mut Tournament) {
+        if tournament.entry_stake_stroops > 0 {
+            let xlm = token::Client::new(env, &Self::xlm_token(env));
+            for player in tournament.joined.iter() {
+                xlm.transfer(
+                    &env.current_contract_address(),
+                    &player,
+                    &tournament.entry_stake_stroops,
+                );
+                Self::release_liability(env, tournament.entry_stake_stroops);
+            }
+        }
+        tournament.prize_pool_stroops = 0;
+        tournament.is_cancelled = true;
+        Self::save_tournament(env, tournament_id, tournament);
+    }
+
+    /// Move a tournament forward. Starts leg 1 once every entrant has joined;
+    /// after that, once every game of the current leg has ended, pairs the
+    /// winners in bracket order for the next leg, or pays the prize pool to
+    /// the last player standing. Leg outcomes are read from the archived game
+    /// results, so a leg can be advanced after its game has expired. A leg
+    /// game that ended without a winner (push or cancellation) sends the
+    /// higher seed (its player1) through. Leg games skip session IDs already
+    /// used on this contract. Callable by anyone.
+    ///
+    /// # Arguments
+    /// * `tournament_id` - The tournament to advance
+    ///
+    /// # Returns
+    /// * `Option<Address>` - The champion, once the tournament is won
+    pub fn advance_tournament(env: Env, tournament_id: u32) -> Result<Option<Address>, Error> {
+        let mut tournament = Self::get_tournament(env.clone(), tournament_id)?;
+
+        if tournament.champion.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if tournament.is_cancelled {
+            return Err(Error::GameCancelled);
+        }
+
+        if tournament.leg == 0 {
+            if tournament.joined.len() < tournament.players.len() {
+                return Err(Error::TournamentNotReady);
+            }
+        } else {
+            let mut winners = Vec::new(&env);
+            for (i, session_id) in tournament.sessions.iter().enumerate() {
+                let result: GameResult = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::GameResult(session_id))
+                    .ok_or(Error::TournamentNotReady)?;
+                // Only this leg's pairing counts as its result
+                let pairing = i as u32 * 2;
+                if result.player1 != tournament.alive.get_unchecked(pairing)
+                    || result.player2 != tournament.alive.get_unchecked(pairing + 1)
+                {
+                    return Err(Error::TournamentNotReady);
+                }
+                winners.push_back(result.winner.unwrap_or(result.player1));
+            }
+            tournament.alive = winners;
+        }
+
+        if tournament.alive.len() == 1 {
+            let champion = tournament.alive.get_unchecked(0);
+            let prize = tournament.prize_pool_stroops;
+            if prize > 0 {
+                let xlm = token::Client::new(&env, &Self::xlm_token(&env));
+                xlm.transfer(&env.current_contract_address(), &champion, &prize);
+                Self::release_liability(&env, prize);
+            }
+            tournament.prize_pool_stroops = 0;
+            tournament.champion = Some(champion.clone());
+            Self::save_tournament(&env, tournament_id, &tournament);
+            TournamentWon {
+                tournament_id,
+                player: champion.clone(),
+                prize,
+            }
+            .publish(&env);
+            return Ok(Some(champion));
+        }
+
+        // Pair the players still in for the next leg
+        tournament.leg += 1;
+        tournament.sessions = Vec::new(&env);
+        for i in (0..tournament.alive.len()).step_by(2) {
+            let mut session_id = tournament.next_session_id;
+            while env.storage().temporary().has(&DataKey::Game(session_id))
+                || env.storage().persistent().has(&DataKey::GameResult(session_id))
+            {
+                session_id = session_id.checked_add(1).ok_or(Error::RoundOverflow)?;
+            }
+            Self::open_game(
+                env.clone(),
+                session_id,
+                tournament.alive.get_unchecked(i),
+                tournament.alive.get_unchecked(i + 1),
+                0,
+                0,
+                None,
+            )?;
+            tournament.sessions.push_back(session_id);
+            tournament.next_session_id = session_id.checked_add(1).ok_or(Error::RoundOverflow)?;
+        }
+
+        Self::save_tournament(&env, tournament_id, &tournament);
+        LegPaired {
+            tournament_id,
+            leg: tournament.leg,
+            sessions: tournament.sessions.clone(),
+        }
+        .publish(&env);
+
+        Ok(None)
+    }
+
+    /// Get tournament information.
+    ///
+    /// # Arguments
+    /// * `tournament_id` - The tournament ID
+    pub fn get_tournament(env: Env, tournament_id: u32) -> Result<Tournament, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Tournament(tournament_id))
+            .ok_or(Error::TournamentNotFound)
+    }
+
+    fn save_tournament(env: &Env, tournament_id: u32, tournament: &Tournament) {
+        let key = DataKey::Tournament(tournament_id);
+        env.storage().persistent().set(&key, tournament);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+    }
+
+    fn load_table(env: &Env, session_id: u32) -> Result<Table, Error> {
+        let table: Table = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Table(session_id))
+            .ok_or(Error::GameNotFound)?;
+        if table.winners.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        Ok(table)
+    }
+
+    /// The seat whose turn it is, which must belong to `player`
+    fn current_seat(table: &Table, player: &Address) -> Result<Seat, Error> {
+        if !table.seats.iter().any(|seat| seat.player == *player) {
+            return Err(Error::NotPlayer);
+        }
+        match table.seats.get(table.turn) {
+            Some(seat) if seat.player == *player => Ok(seat),
+            Some(_) => Err(Error::NotYourTurn),
+            None => Err(Error::AlreadyStuck),
+        }
+    }
+
+    // ========================================================================
+    // Stake Wagering
+    // ========================================================================
+
+    /// Configure a stake for a game before play begins.
+    /// Stake amount is the base wager (e.g. 1 XLM). Each player deposits stake + 0.1% fee
+    /// within 60 seconds, and the winner claims 2x the stake. A house game
+    /// can only be staked if it is seeded (see `commit_seed`).
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `stake_amount_stroops` - Wager per player in stroops
+    pub fn set_game_stake(
+        env: Env,
+        session_id: u32,
+        stake_amount_stroops: i128,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        if !env.storage().instance().has(&DataKey::XlmToken) {
+            return Err(Error::WageringNotConfigured);
+        }
+        if stake_amount_stroops <= 0 {
+            return Err(Error::InvalidStake);
+        }
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.winner.is_some() || game.is_push {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if game.is_cancelled {
+            return Err(Error::GameCancelled);
+        }
+        if game.stake_amount_stroops > 0 {
+            if game.stake_amount_stroops != stake_amount_stroops {
+                return Err(Error::InvalidStake);
+            }
+            return Ok(());
+        }
+
+        game.stake_amount_stroops = stake_amount_stroops;
+        game.stake_fee_bps = STAKE_FEE_BPS;
+        game.stake_deadline_ts = env
+            .ledger()
+            .timestamp()
+            .saturating_add(STAKE_DEPOSIT_WINDOW_SECONDS);
+
+        if game.is_house_game {
+            // Unseeded cards are computable from public data, so the house
+            // only wagers on games dealt from an operator seed chain
+            if game.seed_link.is_none() {
+                return Err(Error::HouseGameNotSeeded);
+            }
+            // The house matches the stake from its bankroll, which is already
+            // counted in outstanding liabilities
+            let bankroll = Self::get_house_bankroll(env.clone());
+            if bankroll < stake_amount_stroops {
+                return Err(Error::InsufficientBankroll);
+            }
+            env.storage()
+                .instance()
+                .set(&DataKey::HouseBankroll, &(bankroll - stake_amount_stroops));
+            game.player2_stake_paid = true;
+        }
+
+        env.storage().temporary().set(&key, &game);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+
+        Ok(())
+    }
+
+    /// Player deposit for staked games.
+    /// Required amount is stake + 0.1% fee, transferred to this contract.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the depositing player
+    pub fn deposit_stake(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.stake_amount_stroops <= 0 {
+            return Err(Error::StakeNotConfigured);
+        }
+        if game.is_cancelled {
+            return Err(Error::GameCancelled);
+        }
+        if env.ledger().timestamp() > game.stake_deadline_ts {
+            return Err(Error::StakeDepositExpired);
+        }
+
+        let is_player1 = player == game.player1;
+        if !is_player1 && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+
+        // Repeat deposits are no-ops
+        if (is_player1 && game.player1_stake_paid) || (!is_player1 && game.player2_stake_paid) {
+            return Ok(());
+        }
+
+        let fee = Self::calc_fee(game.stake_amount_stroops, game.stake_fee_bps);
+        let xlm = token::Client::new(&env, &Self::xlm_token(&env));
+        xlm.transfer(
+            &player,
+            env.current_contract_address(),
+            &(game.stake_amount_stroops + fee),
+        );
+        Self::add_liability(&env, game.stake_amount_stroops);
+
+        if is_player1 {
+            game.player1_stake_paid = true;
+        } else {
+            game.player2_stake_paid = true;
+        }
+        game.last_action_ts = env.ledger().timestamp();
+
+        env.storage().temporary().set(&key, &game);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+
+        Ok(())
+    }
+
+    /// Buy insurance against the house dealer making 21 when the dealer's up
+    /// card is an Ace. Offered on staked house games before the player acts
+    /// in the round, for up to half the stake. Pays 2:1 from the house
+    /// bankroll if the dealer finishes the round on 21; otherwise the bet
+    /// goes to the bankroll. Two-player games never offer insurance, so the
+    /// offer cannot give away the opponent's hidden first card.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the insuring player
+    /// * `amount_stroops` - Insurance bet in stroops
+    pub fn buy_insurance(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        amount_stroops: i128,
+    ) -> Result<(), Error> {
+        player.require_auth();
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.winner.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if player != game.player1 {
+            return Err(Error::NotPlayer);
+        }
+        if !game.is_house_game || game.stake_amount_stroops <= 0 {
+            return Err(Error::InsuranceNotOffered);
+        }
+        Self::require_stakes_paid(&game)?;
+
+        if game.player1_stuck
+            || game.player1_hand.len() != 2
+            || !game.player1_split_hand.is_empty()
+            || game.player2_hand.first() != Some(1)
+        {
+            return Err(Error::InsuranceNotOffered);
+        }
+        if game.insurance > 0 {
+            return Err(Error::AlreadyInsured);
+        }
+        if amount_stroops <= 0 || amount_stroops > game.stake_amount_stroops / 2 {
+            return Err(Error::InvalidStake);
+        }
+
+        // Reserve the 2:1 payout from the bankroll, which is already counted
+        // in outstanding liabilities
+        let reserve = amount_stroops * INSURANCE_PAYOUT_MULTIPLIER;
+        let bankroll = Self::get_house_bankroll(env.clone());
+        if bankroll < reserve {
+            return Err(Error::InsufficientBankroll);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::HouseBankroll, &(bankroll - reserve));
+
+        let xlm = token::Client::new(&env, &Self::xlm_token(&env));
+        xlm.transfer(&player, env.current_contract_address(), &amount_stroops);
+        Self::add_liability(&env, amount_stroops);
+
+        game.insurance = amount_stroops;
+        env.storage().temporary().set(&key, &game);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+
+        Ok(())
+    }
+
+    /// Settle the round's insurance bet: if the dealer has a hand of exactly
+    /// 21 the player gets the bet back plus 2:1, otherwise the bet and the
+    /// reserved payout go to the bankroll.
+    fn settle_insurance(env: &Env, session_id: u32, game: &mut Game) -> Result<(), Error> {
+        let insurance = game.insurance;
+        if insurance <= 0 {
+            return Ok(());
+        }
+
+        let mut dealer_made_21 = false;
+        for hand in player_hands(env, &game.player2_hand, &game.player2_split_hand).iter() {
+            dealer_made_21 |= calculate_hand_value(&hand, game.rules.ace_mode)? == 21;
+        }
+
+        let total = insurance * (INSURANCE_PAYOUT_MULTIPLIER + 1);
+        let payout = if dealer_made_21 {
+            let xlm = token::Client::new(env, &Self::xlm_token(env));
+            xlm.transfer(&env.current_contract_address(), &game.player1, &total);
+            Self::release_liability(env, total);
+            total
+        } else {
+            Self::credit_bankroll(env, total);
+            0
+        };
+        InsuranceSettled {
+            session_id,
+            player: game.player1.clone(),
+            payout,
+        }
+        .publish(env);
+
+        game.insurance = 0;
+        Ok(())
+    }
+
+    /// Cancel a staked game whose deposit window passed without both deposits.
+    /// A player who did deposit gets the full amount (stake + fee) back.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn expire_stake(env: Env, session_id: u32) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.winner.is_some() || game.is_push {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if game.is_cancelled {
+            return Err(Error::GameCancelled);
+        }
+        if game.stake_amount_stroops <= 0 {
+            return Err(Error::StakeNotConfigured);
+        }
+        if env.ledger().timestamp() <= game.stake_deadline_ts
+            || (game.player1_stake_paid && game.player2_stake_paid)
+        {
+            return Err(Error::DeadlineNotReached);
+        }
+
+        Self::refund_deposits(&env, &mut game);
+        game.is_cancelled = true;
+
+        env.storage().temporary().set(&key, &game);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+
+        Self::close_without_winner(&env, session_id, &game);
+
+        Ok(())
+    }
+
+    /// Close a game nobody is playing as a no-contest once both players have
+    /// been idle for the game's action timeout. Paid deposits (stake + fee)
+    /// are refunded; the Game Hub session is left to expire.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn abandon_game(env: Env, session_id: u32) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.winner.is_some() || game.is_push {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if game.is_cancelled {
+            return Err(Error::GameCancelled);
+        }
+        let idle_until = game.last_action_ts.saturating_add(game.rules.timeout_seconds);
+        if env.ledger().timestamp() < idle_until {
+            return Err(Error::TimeoutNotReached);
+        }
+
+        Self::refund_deposits(&env, &mut game);
+        game.is_cancelled = true;
+
+        env.storage().temporary().set(&key, &game);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+
+        GameAbandoned {
+            session_id,
+            round: game.round,
+        }
+        .publish(&env);
+        Self::close_without_winner(&env, session_id, &game);
+
+        Ok(())
+    }
+
+    /// Return every paid deposit (stake + fee) of a game that will not be
+    /// played out; the house's stake goes back to the bankroll.
+    fn refund_deposits(env: &Env, game: &mut Game) {
+        if !game.player1_stake_paid && !game.player2_stake_paid {
+            return;
+        }
+        let fee = Self::calc_fee(game.stake_amount_stroops, game.stake_fee_bps);
+        let xlm = token::Client::new(env, &Self::xlm_token(env));
+        if game.player1_stake_paid {
+            xlm.transfer(
+                &env.current_contract_address(),
+                &game.player1,
+                &(game.stake_amount_stroops + fee),
+            );
+            Self::release_liability(env, game.stake_amount_stroops);
+        }
+        if game.player2_stake_paid {
+            if game.is_house_game {
+                Self::credit_bankroll(env, game.stake_amount_stroops);
+            } else {
+                xlm.transfer(
+                    &env.current_contract_address(),
+                    &game.player2,
+                    &(game.stake_amount_stroops + fee),
+                );
+                Self::release_liability(env, game.stake_amount_stroops);
+            }
+        }
+
+        // An insurance bet goes back to the player and its reserve to the bankroll
+        if game.insurance > 0 {
+            xlm.transfer(&env.current_contract_address(), &game.player1, &game.insurance);
+            Self::release_liability(env, game.insurance);
+            Self::credit_bankroll(env, game.insurance * INSURANCE_PAYOUT_MULTIPLIER);
+        }
+
+        game.player1_stake_paid = false;
+        game.player2_stake_paid = false;
+        game.insurance = 0;
+    }
+
+    /// Close a game that ended without a winner. The result is archived and
+    /// the game's betting pool, if any, is refunded. The hub only records a
+    /// win or a loss, so nothing is reported and the hub session is left to
+    /// expire rather than crediting player 2 with a win.
+    fn close_without_winner(env: &Env, session_id: u32, game: &Game) {
+        Self::archive_result(env, session_id, game, None);
+        Self::settle_betting_pool(env, session_id, game, None);
+    }
+
+    /// Add a finished game to both players' lifetime stats. The house has none.
+    fn record_stats(env: &Env, game: &Game, player1_won: Option<bool>) {
+        for (player, won, busts, naturals) in [
+            (&game.player1, player1_won, game.player1_busts, game.player1_naturals),
+            (
+                &game.player2,
+                player1_won.map(|player1_won| !player1_won),
+                game.player2_busts,
+                game.player2_naturals,
+            ),
+        ] {
+            if *player == env.current_contract_address() {
+                continue;
+            }
+            let key = DataKey::PlayerStats(player.clone());
+            let mut stats: PlayerStats = env.storage().persistent().get(&key).unwrap_or_default();
+            match won {
+                Some(true) => stats.wins += 1,
+                Some(false) => stats.losses += 1,
+                None => {}
+            }
+            stats.busts += busts;
+            stats.blackjacks += naturals;
+            stats.rounds_played += game.round;
+
+            env.storage().persistent().set(&key, &stats);
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+        }
+    }
+
+    /// Write the finished game's result record to persistent storage.
+    fn archive_result(env: &Env, session_id: u32, game: &Game, player1_won: Option<bool>) {
+        let hand_value = |hand: &Bytes| calculate_hand_value(hand, game.rules.ace_mode).unwrap_or(0);
+        let result = GameResult {
+            player1: game.player1.clone(),
+            player2: game.player2.clone(),
+            player1_value: hand_value(&game.player1_hand),
+            player2_value: hand_value(&game.player2_hand),
+            winner: player1_won.map(|player1_won| {
+                if player1_won {
+                    game.player1.clone()
+                } else {
+                    game.player2.clone()
+                }
+            }),
+            rounds: game.round,
+            player1_round_wins: game.player1_round_wins,
+            player2_round_wins: game.player2_round_wins,
+            finished_ts: env.ledger().timestamp(),
+        };
 
-        // Store updated game
-        env.storage().temporary().set(&key, &game);
+        let key = DataKey::GameResult(session_id);
+        env.storage().persistent().set(&key, &result);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+    }
 
-        Ok(())
+    /// Report the result to the betting contract holding the game's pool:
+    /// settled for the winner, or refunded when there is none. Best effort,
+    /// so a failing betting contract never blocks the end of the game.
+    fn settle_betting_pool(env: &Env, session_id: u32, game: &Game, player1_won: Option<bool>) {
+        if let Some(betting) = &game.betting_contract {
+            let _ =
+                ZkBettingClient::new(env, betting).try_settle_card_pool(&session_id, &player1_won);
+        }
     }
 
-    /// Reveal the winner of the game and submit outcome to GameHub.
-    /// Can only be called after both players have stuck.
-    /// This calculates hand values, determines the winner (closest to 21),
-    /// and handles draws by dealing new hands.
+    /// Withdraw the payout recorded for the winner of a staked game.
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
     ///
     /// # Returns
-    /// * `Address` - Address of the winning player
-    pub fn reveal_winner(env: Env, session_id: u32) -> Result<Address, Error> {
-        // Get game from temporary storage
-        let key = DataKey::Game(session_id);
-        let mut game: Game = env
+    /// * `i128` - The amount paid out in stroops
+    pub fn claim_winnings(env: Env, session_id: u32) -> Result<i128, Error> {
+        let payout_key = DataKey::PendingPayout(session_id);
+        let payout: PendingPayout = env
             .storage()
-            .temporary()
-            .get(&key)
-            .ok_or(Error::GameNotFound)?;
+            .persistent()
+            .get(&payout_key)
+            .ok_or(Error::NothingToClaim)?;
 
-        // Check if game already ended (has a winner)
-        if let Some(winner) = &game.winner {
-            return Ok(winner.clone());
-        }
+        payout.winner.require_auth();
 
-        // Check both players have stuck
-        if !game.player1_stuck || !game.player2_stuck {
-            return Err(Error::BothPlayersNotStuck);
-        }
+        // Remove before transferring so a re-entrant claim finds nothing.
+        env.storage().persistent().remove(&payout_key);
+        Self::release_liability(&env, payout.amount_stroops);
 
-        // Calculate hand values
-        let player1_value = calculate_hand_value(&game.player1_hand)?;
-        let player2_value = calculate_hand_value(&game.player2_hand)?;
+        let xlm = token::Client::new(&env, &Self::xlm_token(&env));
+        xlm.transfer(&env.current_contract_address(), &payout.winner, &payout.amount_stroops);
 
-        // Determine winner (closest to 21 without going over)
-        // Note: Bust conditions are already handled in hit(), so values should be <= 21
-        let winner = if player1_value > player2_value {
-            // Player 1 is closer to 21
-            game.player1.clone()
-        } else if player2_value > player1_value {
-            // Player 2 is closer to 21
-            game.player2.clone()
-        } else {
-            // Draw - deal new hands and continue
-            game.round = game.round.checked_add(1).ok_or(Error::RoundOverflow)?;
+        Ok(payout.amount_stroops)
+    }
 
-            // Reset stuck flags
-            game.player1_stuck = false;
-            game.player2_stuck = false;
+    /// Transfer accrued stake fees to the treasury at most once every 24 hours.
+    /// Never touches stakes held for live games or unclaimed winnings.
+    ///
+    /// # Returns
+    /// * `i128` - The amount swept in stroops
+    pub fn sweep_treasury(env: Env) -> Result<i128, Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
 
-            // Clear hands
-            game.player1_hand = Bytes::new(&env);
-            game.player2_hand = Bytes::new(&env);
+        let now_ts = env.ledger().timestamp();
+        let last_sweep = Self::get_last_sweep_ts(env.clone());
+        if last_sweep > 0 && now_ts.saturating_sub(last_sweep) < FEE_SWEEP_INTERVAL_SECONDS {
+            return Err(Error::SweepTooEarly);
+        }
 
-            // Deal new hands (2 cards each)
-            let mut seed_bytes = Bytes::new(&env);
-            seed_bytes.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
-            seed_bytes.append(&game.player1.to_string().to_bytes());
-            seed_bytes.append(&game.player2.to_string().to_bytes());
-            seed_bytes.append(&Bytes::from_array(&env, &game.round.to_be_bytes()));
-            let base_seed = env.crypto().keccak256(&seed_bytes);
+        let accrued_fee = Self::get_fee_accrued(env.clone());
+        let xlm = token::Client::new(&env, &Self::xlm_token(&env));
+        let available = xlm.balance(&env.current_contract_address())
+            - Self::get_outstanding_liabilities(env.clone());
+        let sweepable = accrued_fee.min(available);
+        if sweepable <= 0 {
+            return Err(Error::NothingToSweep);
+        }
 
-            // Deal 2 cards to player1
-            for i in 0..2 {
-                let mut card_seed_bytes = Bytes::new(&env);
-                card_seed_bytes.append(&Bytes::from(base_seed.clone()));
-                card_seed_bytes.append(&Bytes::from_array(&env, &[i, 1])); // [card_index, player]
-                let card_seed = env.crypto().keccak256(&card_seed_bytes);
-                game.player1_hand.push_back(deal_card(&env, card_seed.into()));
-            }
+        let treasury: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TreasuryAddress)
+            .ok_or(Error::WageringNotConfigured)?;
+        xlm.transfer(&env.current_contract_address(), &treasury, &sweepable);
 
-            // Deal 2 cards to player2
-            for i in 0..2 {
-                let mut card_seed_bytes = Bytes::new(&env);
-                card_seed_bytes.append(&Bytes::from(base_seed.clone()));
-                card_seed_bytes.append(&Bytes::from_array(&env, &[i, 2])); // [card_index, player]
-                let card_seed = env.crypto().keccak256(&card_seed_bytes);
-                game.player2_hand.push_back(deal_card(&env, card_seed.into()));
-            }
+        env.storage()
+            .instance()
+            .set(&DataKey::FeeAccrued, &(accrued_fee - sweepable));
+        env.storage().instance().set(&DataKey::LastSweepTs, &now_ts);
 
-            // Store updated game and return error to indicate draw
-            env.storage().temporary().set(&key, &game);
+        Ok(sweepable)
+    }
 
-            return Err(Error::Draw);
-        };
+    /// Add XLM to the house bankroll that matches stakes in house games.
+    ///
+    /// # Arguments
+    /// * `from` - Address funding the bankroll
+    /// * `amount_stroops` - Amount to add in stroops
+    pub fn fund_house(env: Env, from: Address, amount_stroops: i128) -> Result<(), Error> {
+        from.require_auth();
 
-        // Call GameHub FIRST (before setting winner)
-        let player1_won = winner == game.player1;
-        Self::end_game_with_hub(&env, session_id, player1_won)?;
+        if amount_stroops <= 0 {
+            return Err(Error::InvalidStake);
+        }
 
-        // Only update game with winner AFTER GameHub succeeds
-        game.winner = Some(winner.clone());
-        env.storage().temporary().set(&key, &game);
+        let xlm = token::Client::new(&env, &Self::xlm_token(&env));
+        xlm.transfer(&from, env.current_contract_address(), &amount_stroops);
+        Self::credit_bankroll(&env, amount_stroops);
+        Self::add_liability(&env, amount_stroops);
 
-        Ok(winner)
+        Ok(())
     }
 
-    /// Get game information.
+    /// Withdraw XLM from the house bankroll. Stakes reserved for live house
+    /// games are not part of the bankroll and cannot be withdrawn.
     ///
     /// # Arguments
-    /// * `session_id` - The session ID of the game
-    ///
-    /// # Returns
-    /// * `Game` - The game state (includes hands and winner after game ends)
-    pub fn get_game(env: Env, session_id: u32) -> Result<Game, Error> {
-        let key = DataKey::Game(session_id);
+    /// * `to` - Address receiving the funds
+    /// * `amount_stroops` - Amount to withdraw in stroops
+    pub fn withdraw_house(env: Env, to: Address, amount_stroops: i128) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        let bankroll = Self::get_house_bankroll(env.clone());
+        if amount_stroops <= 0 || amount_stroops > bankroll {
+            return Err(Error::InsufficientBankroll);
+        }
+
         env.storage()
-            .temporary()
-            .get(&key)
-            .ok_or(Error::GameNotFound)
+            .instance()
+            .set(&DataKey::HouseBankroll, &(bankroll - amount_stroops));
+        Self::release_liability(&env, amount_stroops);
+
+        let xlm = token::Client::new(&env, &Self::xlm_token(&env));
+        xlm.transfer(&env.current_contract_address(), &to, &amount_stroops);
+
+        Ok(())
     }
 
-    /// Get the current hand value for a player.
-    ///
-    /// # Arguments
-    /// * `session_id` - The session ID of the game
-    /// * `player` - Address of the player
-    ///
-    /// # Returns
-    /// * `u32` - The total value of the player's hand
-    pub fn get_hand_value(env: Env, session_id: u32, player: Address) -> Result<u32, Error> {
-        let key = DataKey::Game(session_id);
-        let game: Game = env
-            .storage()
-            .temporary()
-            .get(&key)
-            .ok_or(Error::GameNotFound)?;
+    /// Get the house bankroll available to match stakes, in stroops.
+    pub fn get_house_bankroll(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::HouseBankroll)
+            .unwrap_or(0)
+    }
 
-        if player == game.player1 {
-            calculate_hand_value(&game.player1_hand)
-        } else if player == game.player2 {
-            calculate_hand_value(&game.player2_hand)
-        } else {
-            Err(Error::NotPlayer)
-        }
+    /// Get the payout waiting to be claimed for a staked game.
+    pub fn get_pending_payout(env: Env, session_id: u32) -> Result<PendingPayout, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingPayout(session_id))
+            .ok_or(Error::NothingToClaim)
+    }
+
+    /// Get stake fees accrued and not yet swept, in stroops.
+    pub fn get_fee_accrued(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::FeeAccrued)
+            .unwrap_or(0)
+    }
+
+    /// Get stakes held for live games plus unclaimed winnings, in stroops.
+    pub fn get_outstanding_liabilities(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::OutstandingLiabilities)
+            .unwrap_or(0)
+    }
+
+    /// Get the timestamp of the last treasury sweep (0 if never swept).
+    pub fn get_last_sweep_ts(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::LastSweepTs)
+            .unwrap_or(0)
     }
 
     // ========================================================================
     // Internal Helper Functions
     // ========================================================================
 
-    /// Helper to end game with the Game Hub
-    fn end_game_with_hub(env: &Env, session_id: u32, player1_won: bool) -> Result<(), Error> {
+    fn xlm_token(env: &Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::XlmToken)
+            .expect("XLM token not set")
+    }
+
+    fn add_liability(env: &Env, amount_stroops: i128) {
+        let liabilities = Self::get_outstanding_liabilities(env.clone()) + amount_stroops;
+        env.storage()
+            .instance()
+            .set(&DataKey::OutstandingLiabilities, &liabilities);
+    }
+
+    fn release_liability(env: &Env, amount_stroops: i128) {
+        let liabilities = Self::get_outstanding_liabilities(env.clone());
+        env.storage().instance().set(
+            &DataKey::OutstandingLiabilities,
+            &(liabilities - amount_stroops).max(0),
+        );
+    }
+
+    /// Record the fees paid on a settled game's deposits (the house pays none)
+    fn accrue_stake_fees(env: &Env, game: &Game) {
+        let fee = Self::calc_fee(game.stake_amount_stroops, game.stake_fee_bps);
+        let payers = if game.is_house_game { 1 } else { 2 };
+        let accrued = Self::get_fee_accrued(env.clone()) + fee * payers;
+        env.storage().instance().set(&DataKey::FeeAccrued, &accrued);
+    }
+
+    /// Take the natural bonus out of the house bankroll, capped at what it
+    /// holds. Hub points have no bonus: the hub only records win or loss.
+    fn natural_bonus(env: &Env, game: &Game) -> i128 {
+        if !game.won_on_natural {
+            return 0;
+        }
+        let bankroll = Self::get_house_bankroll(env.clone());
+        let bonus = (game.stake_amount_stroops * NATURAL_BONUS_BPS / 10_000).min(bankroll);
+        env.storage()
+            .instance()
+            .set(&DataKey::HouseBankroll, &(bankroll - bonus));
+        bonus
+    }
+
+    fn credit_bankroll(env: &Env, amount_stroops: i128) {
+        let bankroll = Self::get_house_bankroll(env.clone()) + amount_stroops;
+        env.storage().instance().set(&DataKey::HouseBankroll, &bankroll);
+    }
+
+    /// Fee on a stake, rounded up to the next stroop
+    fn calc_fee(stake_amount_stroops: i128, fee_bps: u32) -> i128 {
+        ((stake_amount_stroops * fee_bps as i128) + 9_999) / 10_000
+    }
+
+    /// Reject play on cancelled games, on staked games until both deposits are
+    /// in, and on seeded games until the round has been dealt or while a draw
+    /// waits for the operator's next link
+    fn require_stakes_paid(game: &Game) -> Result<(), Error> {
+        if game.is_push {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if game.is_cancelled {
+            return Err(Error::GameCancelled);
+        }
+        if game.stake_amount_stroops > 0 && (!game.player1_stake_paid || !game.player2_stake_paid) {
+            return Err(Error::StakeNotPaid);
+        }
+        if game.seed_link.is_some()
+            && (game.player1_hand.is_empty() || game.pending_draw != PendingDraw::None)
+        {
+            return Err(Error::SeedNotRevealed);
+        }
+        Ok(())
+    }
+
+    /// Helper to end game with the Game Hub, recording the winner's payout
+    /// and the stake fees for staked games
+    fn end_game_with_hub(
+        env: &Env,
+        session_id: u32,
+        game: &Game,
+        player1_won: bool,
+    ) -> Result<(), Error> {
+        if game.stake_amount_stroops > 0 {
+            if game.is_house_game && !player1_won {
+                // The house keeps the pot in its bankroll
+                Self::credit_bankroll(env, game.stake_amount_stroops * 2);
+            } else {
+                let winner = if player1_won {
+                    game.player1.clone()
+                } else {
+                    game.player2.clone()
+                };
+                let payout = PendingPayout {
+                    winner,
+                    amount_stroops: game.stake_amount_stroops * 2 + Self::natural_bonus(env, game),
+                };
+                let payout_key = DataKey::PendingPayout(session_id);
+                env.storage().persistent().set(&payout_key, &payout);
+                env.storage()
+                    .persistent()
+                    .extend_ttl(&payout_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+            }
+
+            Self::accrue_stake_fees(env, game);
+        }
+
+        Self::settle_betting_pool(env, session_id, game, Some(player1_won));
+        Self::record_stats(env, game, Some(player1_won));
+        Self::archive_result(env, session_id, game, Some(player1_won));
+
         // Get GameHub address
         let game_hub_addr: Address = env
             .storage()
@@ -630,6 +3680,154 @@ impl TwentyOneContract {
             .set(&DataKey::GameHubAddress, &new_hub);
     }
 
+    /// Get the betting contract that spectator pools are opened on
+    ///
+    /// # Returns
+    /// * `Option<Address>` - The zk-betting contract address, if configured
+    pub fn get_betting_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::BettingContract)
+    }
+
+    /// Open a spectator pool on the zk-betting contract for every two-player
+    /// game started from now on, settled when the game ends. The betting
+    /// contract must register this contract with `set_card_game_contract`.
+    ///
+    /// # Arguments
+    /// * `betting` - The zk-betting contract address
+    pub fn set_betting_contract(env: Env, betting: Address) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::BettingContract, &betting);
+    }
+
+    /// Enable XLM wagering by setting the token and the wallet fees are swept to
+    ///
+    /// # Arguments
+    /// * `xlm_token` - SAC contract address for native XLM
+    /// * `treasury` - Wallet that receives swept stake fees
+    pub fn set_wagering(env: Env, xlm_token: Address, treasury: Address) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::XlmToken, &xlm_token);
+        env.storage()
+            .instance()
+            .set(&DataKey::TreasuryAddress, &treasury);
+    }
+
+    /// Get how long a player may idle before the opponent can claim the game,
+    /// for games started without their own rules
+    ///
+    /// # Returns
+    /// * `u64` - The timeout in seconds
+    pub fn get_action_timeout(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ActionTimeout)
+            .unwrap_or(DEFAULT_ACTION_TIMEOUT_SECONDS)
+    }
+
+    /// Set how long a player may idle before the opponent can claim the game,
+    /// for games started without their own rules
+    ///
+    /// # Arguments
+    /// * `timeout_seconds` - The timeout in seconds
+    pub fn set_action_timeout(env: Env, timeout_seconds: u64) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ActionTimeout, &timeout_seconds);
+    }
+
+    /// Get how tied rounds are resolved in games started without their own rules
+    ///
+    /// # Returns
+    /// * `TiePolicy` - The tie policy (defaults to `Redeal`)
+    pub fn get_tie_policy(env: Env) -> TiePolicy {
+        env.storage()
+            .instance()
+            .get(&DataKey::TiePolicy)
+            .unwrap_or(TiePolicy::Redeal)
+    }
+
+    /// Set how tied rounds are resolved in games started without their own rules
+    ///
+    /// # Arguments
+    /// * `policy` - Redeal, push, or redeal a bounded number of times then push
+    pub fn set_tie_policy(env: Env, policy: TiePolicy) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::TiePolicy, &policy);
+    }
+
+    /// Get the round after which a tied round ends the game as a push
+    ///
+    /// # Returns
+    /// * `u32` - The round cap (defaults to 20)
+    pub fn get_round_cap(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RoundCap)
+            .unwrap_or(DEFAULT_ROUND_CAP)
+    }
+
+    /// Set the round after which a tied round ends the game as a push,
+    /// instead of dealing another round. Applies to
+    /// every game, overriding redeal tie policies.
+    ///
+    /// # Arguments
+    /// * `round_cap` - The last round that may be redealt on a tie
+    pub fn set_round_cap(env: Env, round_cap: u32) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::RoundCap, &round_cap);
+    }
+
+    /// Set the zk-groth16-verifier contract and verification key used by
+    /// `prove_hand`
+    ///
+    /// # Arguments
+    /// * `verifier` - The verifier contract address
+    /// * `vk_id` - ID of the hand-claim circuit's verification key
+    pub fn set_zk_verifier(env: Env, verifier: Address, vk_id: BytesN<32>) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        env.storage().instance().set(&DataKey::ZkVerifier, &verifier);
+        env.storage().instance().set(&DataKey::ZkVkId, &vk_id);
+    }
+
     /// Update the contract WASM hash (upgrade contract)
     ///
     /// # Arguments
@@ -7,9 +7,15 @@
 // For full integration tests with the real GameHub contract, see:
 // contracts/game_hub/src/tests/twenty_one_integration.rs
 
-use crate::{Error, TwentyOneContract, TwentyOneContractClient};
-use soroban_sdk::testutils::{Address as _, Ledger as _};
-use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env};
+use crate::{
+    AceMode, DataKey, Error, Game, GameResult, GameRules, HandClaim, HistoryAction,
+    HistoryEntry, PlayerStats, PublicGame, Table, TiePolicy, Tournament, TwentyOneContract,
+    TwentyOneContractClient,
+};
+use soroban_sdk::testutils::{Address as _, AuthorizedFunction, Events as _, Ledger as _};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, vec, Address, Bytes, BytesN, Env, IntoVal, Vec,
+};
 
 // ============================================================================
 // Mock GameHub for Unit Testing
@@ -41,6 +47,54 @@ impl MockGameHub {
     }
 }
 
+// ============================================================================
+// Mock zk-betting hooks
+// ============================================================================
+
+#[contract]
+pub struct MockBetting;
+
+#[contractimpl]
+impl MockBetting {
+    pub fn open_card_pool(_env: Env, session_id: u32, _deadline_ts: u64) -> u32 {
+        session_id + 1000
+    }
+
+    /// Records the reported result: 1 = player1, 2 = player2, 0 = refunded.
+    pub fn settle_card_pool(env: Env, session_id: u32, player1_won: Option<bool>) {
+        let result: u32 = match player1_won {
+            Some(true) => 1,
+            Some(false) => 2,
+            None => 0,
+        };
+        env.storage().instance().set(&session_id, &result);
+    }
+
+    pub fn result(env: Env, session_id: u32) -> Option<u32> {
+        env.storage().instance().get(&session_id)
+    }
+}
+
+// ============================================================================
+// Mock zk-groth16-verifier
+// ============================================================================
+
+#[contract]
+pub struct MockVerifier;
+
+#[contractimpl]
+impl MockVerifier {
+    /// Accepts any non-empty proof with the five hand-claim public inputs.
+    pub fn verify_round_proof(
+        _env: Env,
+        _vk_id: BytesN<32>,
+        proof: Bytes,
+        public_inputs: Vec<BytesN<32>>,
+    ) -> bool {
+        !proof.is_empty() && public_inputs.len() == 5
+    }
+}
+
 // ============================================================================
 // Test Helpers
 // ============================================================================
@@ -140,17 +194,58 @@ fn assert_twenty_one_error<T, E>(
     }
 }
 
-/// Helper to calculate hand value from Bytes
+/// Helper to calculate hand value from Bytes, with one Ace as 11 when it fits
 fn calculate_hand_value_helper(hand: &Bytes) -> u32 {
     let mut total = 0u32;
+    let mut has_ace = false;
     for i in 0..hand.len() {
         let card = hand.get(i).unwrap() as u32;
         let value = if card >= 10 { 10 } else { card };
+        has_ace |= card == 1;
         total += value;
     }
+    if has_ace && total + 10 <= 21 {
+        total += 10;
+    }
     total
 }
 
+/// Read the stored game with raw hands, bypassing `get_game`'s commitments
+fn stored_game(client: &TwentyOneContractClient, session_id: u32) -> Game {
+    client.env.as_contract(&client.address, || {
+        client.env.storage().temporary().get(&DataKey::Game(session_id)).unwrap()
+    })
+}
+
+/// Commit a one-link operator seed chain for a game about to start and
+/// return the link that deals its first round.
+fn commit_test_seed(env: &Env, client: &TwentyOneContractClient, session_id: u32) -> BytesN<32> {
+    let link = BytesN::from_array(env, &[session_id as u8; 32]);
+    let tip: BytesN<32> = env.crypto().keccak256(&Bytes::from(link.clone())).into();
+    client.commit_seed(&session_id, &tip);
+    link
+}
+
+/// Deal a seeded game's first round: each player commits a seed, the
+/// operator reveals `link` and the players reveal their seeds.
+fn deal_test_seed(
+    env: &Env,
+    client: &TwentyOneContractClient,
+    session_id: u32,
+    link: &BytesN<32>,
+    players: &[&Address],
+) {
+    let secret = BytesN::from_array(env, &[0x11; 32]);
+    let commitment: BytesN<32> = env.crypto().keccak256(&Bytes::from(secret.clone())).into();
+    for player in players {
+        client.commit_player_seed(&session_id, player, &commitment);
+    }
+    client.reveal_seed(&session_id, link);
+    for player in players {
+        client.reveal_player_seed(&session_id, player, &secret);
+    }
+}
+
 // ============================================================================
 // Basic Game Flow Tests
 // ============================================================================
@@ -163,10 +258,10 @@ fn test_complete_game_simple() {
     let points = 100_0000000;
 
     // Start game
-    client.start_game(&session_id, &player1, &player2, &points, &points);
+    client.start_game(&session_id, &player1, &player2, &points, &points, &None);
 
     // Get game to verify initial state
-    let game = client.get_game(&session_id);
+    let game = stored_game(&client, session_id);
     assert!(game.winner.is_none()); // Game is still active
     assert_eq!(game.player1, player1);
     assert_eq!(game.player2, player2);
@@ -182,11 +277,11 @@ fn test_complete_game_simple() {
     client.stick(&session_id, &player2);
 
     // Reveal winner
-    let winner = client.reveal_winner(&session_id);
+    let winner = client.reveal_winner(&session_id, &player1).unwrap();
     assert!(winner == player1 || winner == player2);
 
     // Verify game is ended
-    let final_game = client.get_game(&session_id);
+    let final_game = stored_game(&client, session_id);
     assert!(final_game.winner.is_some());
     assert_eq!(final_game.winner.unwrap(), winner);
 }
@@ -196,9 +291,9 @@ fn test_initial_cards_dealt() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 2u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
-    let game = client.get_game(&session_id);
+    let game = stored_game(&client, session_id);
 
     // Each player should have exactly 2 cards
     assert_eq!(game.player1_hand.len(), 2);
@@ -220,7 +315,7 @@ fn test_get_hand_value() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 3u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
     // Get hand values
     let player1_value = client.get_hand_value(&session_id, &player1);
@@ -231,7 +326,7 @@ fn test_get_hand_value() {
     assert!(player2_value >= 2 && player2_value <= 20);
 
     // Verify hand value matches calculation
-    let game = client.get_game(&session_id);
+    let game = stored_game(&client, session_id);
     let expected_value1 = calculate_hand_value_helper(&game.player1_hand);
     let expected_value2 = calculate_hand_value_helper(&game.player2_hand);
 
@@ -244,15 +339,15 @@ fn test_hit_adds_card() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 4u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
-    let initial_game = client.get_game(&session_id);
+    let initial_game = stored_game(&client, session_id);
     let initial_hand_size = initial_game.player1_hand.len();
 
     // Player 1 hits
     client.hit(&session_id, &player1);
 
-    let after_hit_game = client.get_game(&session_id);
+    let after_hit_game = stored_game(&client, session_id);
     assert_eq!(after_hit_game.player1_hand.len(), initial_hand_size + 1);
 }
 
@@ -261,7 +356,7 @@ fn test_stick_prevents_further_hits() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 5u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
     // Player 1 sticks
     client.stick(&session_id, &player1);
@@ -276,9 +371,9 @@ fn test_multiple_hits_allowed() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 6u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
-    let initial_game = client.get_game(&session_id);
+    let initial_game = stored_game(&client, session_id);
     let initial_hand_size = initial_game.player1_hand.len();
 
     // Player 1 hits multiple times (be careful not to bust in deterministic test)
@@ -287,13 +382,13 @@ fn test_multiple_hits_allowed() {
 
     // If first hit succeeds (didn't bust), try another
     if result1.is_ok() {
-        let mid_game = client.get_game(&session_id);
+        let mid_game = stored_game(&client, session_id);
         assert_eq!(mid_game.player1_hand.len(), initial_hand_size + 1);
 
         let result2 = client.try_hit(&session_id, &player1);
         // Could succeed or fail (bust), both are valid
         if result2.is_ok() {
-            let final_game = client.get_game(&session_id);
+            let final_game = stored_game(&client, session_id);
             assert_eq!(final_game.player1_hand.len(), initial_hand_size + 2);
         }
     }
@@ -308,24 +403,24 @@ fn test_closer_to_21_wins() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 7u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
     // Both players stick
     client.stick(&session_id, &player1);
     client.stick(&session_id, &player2);
 
-    let winner = client.reveal_winner(&session_id);
+    let winner = client.reveal_winner(&session_id, &player1);
 
     // Get final hand values
-    let game = client.get_game(&session_id);
+    let game = stored_game(&client, session_id);
     let player1_value = calculate_hand_value_helper(&game.player1_hand);
     let player2_value = calculate_hand_value_helper(&game.player2_hand);
 
     // Winner should be closer to 21
     if player1_value > player2_value {
-        assert_eq!(winner, player1);
+        assert_eq!(winner, Some(player1));
     } else if player2_value > player1_value {
-        assert_eq!(winner, player2);
+        assert_eq!(winner, Some(player2));
     }
     // If equal, test will continue (draw handling)
 }
@@ -335,13 +430,13 @@ fn test_reveal_winner_requires_both_stuck() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 8u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
     // Only player1 sticks
     client.stick(&session_id, &player1);
 
     // Try to reveal winner - should fail
-    let result = client.try_reveal_winner(&session_id);
+    let result = client.try_reveal_winner(&session_id, &player1);
     assert_twenty_one_error(&result, Error::BothPlayersNotStuck);
 }
 
@@ -353,8 +448,8 @@ fn test_reveal_winner_requires_both_stuck() {
 fn test_bust_detection() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
-    let session_id = 9u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    let session_id = 53u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
     // Keep hitting until player1 busts
     // Note: With enough hits, player will eventually bust (hand value > 21)
@@ -364,7 +459,7 @@ fn test_bust_detection() {
         client.hit(&session_id, &player1);
 
         // Check if game ended (player busted)
-        let game = client.get_game(&session_id);
+        let game = stored_game(&client, session_id);
         if game.winner.is_some() {
             busted = true;
             assert_eq!(game.winner.unwrap(), player2, "Player 2 should win when player 1 busts");
@@ -384,7 +479,7 @@ fn test_cannot_hit_after_bust() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 10u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
     // Hit until bust (game ends)
     let mut did_bust = false;
@@ -392,7 +487,7 @@ fn test_cannot_hit_after_bust() {
         client.hit(&session_id, &player1);
 
         // Check if game ended (player busted)
-        let game = client.get_game(&session_id);
+        let game = stored_game(&client, session_id);
         if game.winner.is_some() {
             did_bust = true;
             assert_eq!(game.winner.unwrap(), player2);
@@ -416,24 +511,23 @@ fn test_draw_starts_new_round() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 11u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
     // Both players stick immediately
     client.stick(&session_id, &player1);
     client.stick(&session_id, &player2);
 
-    let game = client.get_game(&session_id);
+    let game = stored_game(&client, session_id);
     let player1_value = calculate_hand_value_helper(&game.player1_hand);
     let player2_value = calculate_hand_value_helper(&game.player2_hand);
 
     // Only test draw behavior if hands are equal
     if player1_value == player2_value {
-        // This should return Draw error
-        let result = client.try_reveal_winner(&session_id);
-        assert_twenty_one_error(&result, Error::Draw);
+        // A tie has no round winner
+        assert_eq!(client.reveal_winner(&session_id, &player1), None);
 
         // Verify new round was created
-        let game_after = client.get_game(&session_id);
+        let game_after = stored_game(&client, session_id);
         assert_eq!(game_after.round, 2); // Round should increment
         assert_eq!(game_after.player1_stuck, false); // Flags reset
         assert_eq!(game_after.player2_stuck, false);
@@ -452,7 +546,7 @@ fn test_cannot_stick_twice() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 12u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
     // First stick succeeds
     client.stick(&session_id, &player1);
@@ -468,7 +562,7 @@ fn test_non_player_cannot_hit() {
     let non_player = Address::generate(&env);
 
     let session_id = 13u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
     // Non-player tries to hit
     let result = client.try_hit(&session_id, &non_player);
@@ -481,7 +575,7 @@ fn test_non_player_cannot_stick() {
     let non_player = Address::generate(&env);
 
     let session_id = 14u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
     // Non-player tries to stick
     let result = client.try_stick(&session_id, &non_player);
@@ -494,7 +588,7 @@ fn test_non_player_cannot_get_hand_value() {
     let non_player = Address::generate(&env);
 
     let session_id = 15u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
     // Non-player tries to get hand value
     let result = client.try_get_hand_value(&session_id, &non_player);
@@ -519,9 +613,9 @@ fn test_cannot_stick_nonexistent_game() {
 
 #[test]
 fn test_cannot_reveal_nonexistent_game() {
-    let (_env, client, _hub, _player1, _player2) = setup_test();
+    let (_env, client, _hub, player1, _player2) = setup_test();
 
-    let result = client.try_reveal_winner(&999);
+    let result = client.try_reveal_winner(&999, &player1);
     assert_twenty_one_error(&result, Error::GameNotFound);
 }
 
@@ -538,15 +632,14 @@ fn test_cannot_hit_after_game_ended() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 16u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
     // Both players stick
     client.stick(&session_id, &player1);
     client.stick(&session_id, &player2);
 
     // Reveal winner (may panic if draw, but that's okay)
-    let result = client.try_reveal_winner(&session_id);
-    if result.is_ok() {
+    if client.reveal_winner(&session_id, &player1).is_some() {
         // Game ended successfully
         // Try to hit after game ended
         let hit_result = client.try_hit(&session_id, &player1);
@@ -559,7 +652,7 @@ fn test_cannot_stick_after_game_ended() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 17u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
     // Player 1 sticks
     client.stick(&session_id, &player1);
@@ -568,10 +661,9 @@ fn test_cannot_stick_after_game_ended() {
     client.stick(&session_id, &player2);
 
     // Reveal winner
-    let result = client.try_reveal_winner(&session_id);
-    if result.is_ok() {
+    if client.reveal_winner(&session_id, &player1).is_some() {
         // Game ended, verify stuck flag is set
-        let game = client.get_game(&session_id);
+        let game = stored_game(&client, session_id);
         assert!(game.player1_stuck);
 
         // Try to stick again after game ended
@@ -585,19 +677,16 @@ fn test_reveal_winner_idempotent() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 18u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
     client.stick(&session_id, &player1);
     client.stick(&session_id, &player2);
 
     // First reveal
-    let result1 = client.try_reveal_winner(&session_id);
-    if result1.is_ok() {
-        let winner1 = result1.unwrap().unwrap();
-
+    if let Some(winner1) = client.reveal_winner(&session_id, &player1) {
         // Second reveal should return same winner (idempotent)
-        let winner2 = client.reveal_winner(&session_id);
-        assert_eq!(winner1, winner2);
+        let winner2 = client.reveal_winner(&session_id, &player1);
+        assert_eq!(winner2, Some(winner1));
     }
 }
 
@@ -615,16 +704,16 @@ fn test_multiple_games_independent() {
     let session2 = 21u32;
 
     // Start two games
-    client.start_game(&session1, &player1, &player2, &100_0000000, &100_0000000);
-    client.start_game(&session2, &player3, &player4, &50_0000000, &50_0000000);
+    client.start_game(&session1, &player1, &player2, &100_0000000, &100_0000000, &None);
+    client.start_game(&session2, &player3, &player4, &50_0000000, &50_0000000, &None);
 
     // Play both games independently (use try_ methods to handle potential busts)
     let _ = client.try_hit(&session1, &player1);
     let _ = client.try_hit(&session2, &player3);
 
     // Only continue if games haven't ended from busts
-    let game1_check = client.get_game(&session1);
-    let game2_check = client.get_game(&session2);
+    let game1_check = stored_game(&client, session1);
+    let game2_check = stored_game(&client, session2);
 
     if game1_check.winner.is_none() {
         client.stick(&session1, &player1);
@@ -637,8 +726,8 @@ fn test_multiple_games_independent() {
     }
 
     // Verify both games exist and are independent
-    let game1 = client.get_game(&session1);
-    let game2 = client.get_game(&session2);
+    let game1 = stored_game(&client, session1);
+    let game2 = stored_game(&client, session2);
 
     assert_eq!(game1.player1, player1);
     assert_eq!(game2.player1, player3);
@@ -653,12 +742,12 @@ fn test_multiple_sessions() {
     let session1 = 22u32;
     let session2 = 23u32;
 
-    client.start_game(&session1, &player1, &player2, &100_0000000, &100_0000000);
-    client.start_game(&session2, &player3, &player4, &50_0000000, &50_0000000);
+    client.start_game(&session1, &player1, &player2, &100_0000000, &100_0000000, &None);
+    client.start_game(&session2, &player3, &player4, &50_0000000, &50_0000000, &None);
 
     // Verify both games exist and are independent
-    let game1 = client.get_game(&session1);
-    let game2 = client.get_game(&session2);
+    let game1 = stored_game(&client, session1);
+    let game2 = stored_game(&client, session2);
 
     assert_eq!(game1.player1, player1);
     assert_eq!(game2.player1, player3);
@@ -674,9 +763,9 @@ fn test_asymmetric_points() {
     let points1 = 200_0000000;
     let points2 = 50_0000000;
 
-    client.start_game(&session_id, &player1, &player2, &points1, &points2);
+    client.start_game(&session_id, &player1, &player2, &points1, &points2, &None);
 
-    let game = client.get_game(&session_id);
+    let game = stored_game(&client, session_id);
     assert_eq!(game.player1_points, points1);
     assert_eq!(game.player2_points, points2);
 
@@ -684,7 +773,1224 @@ fn test_asymmetric_points() {
     client.stick(&session_id, &player2);
 
     // Try to reveal winner (may be draw)
-    let _ = client.try_reveal_winner(&session_id);
+    let _ = client.reveal_winner(&session_id, &player1);
+}
+
+// ============================================================================
+// Split Tests
+// ============================================================================
+
+/// Overwrite the stored hands of a started game
+fn set_hands(env: &Env, client: &TwentyOneContractClient, session_id: u32, hands: [&[u8]; 4]) {
+    let mut game = stored_game(client, session_id);
+    game.player1_hand = Bytes::from_slice(env, hands[0]);
+    game.player1_split_hand = Bytes::from_slice(env, hands[1]);
+    game.player2_hand = Bytes::from_slice(env, hands[2]);
+    game.player2_split_hand = Bytes::from_slice(env, hands[3]);
+    env.as_contract(&client.address, || {
+        env.storage().temporary().set(&DataKey::Game(session_id), &game);
+    });
+}
+
+/// Net hand outcomes for player 1, summed over every pair of hands
+fn net_outcome(game: &Game) -> i32 {
+    let p1_hands = [&game.player1_hand, &game.player1_split_hand];
+    let p2_hands = [&game.player2_hand, &game.player2_split_hand];
+
+    let score = |hand: &Bytes| match calculate_hand_value_helper(hand) {
+        value if value > 21 => 0,
+        value => value,
+    };
+    let mut net = 0;
+    // An empty split hand means the player did not split.
+    for hand in p1_hands.iter().filter(|hand| !hand.is_empty()) {
+        for other in p2_hands.iter().filter(|hand| !hand.is_empty()) {
+            net += (score(hand) > score(other)) as i32 - (score(other) > score(hand)) as i32;
+        }
+    }
+    net
+}
+
+#[test]
+fn test_split_pair_plays_two_hands() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 40u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    set_hands(&env, &client, session_id, [&[8, 8], &[], &[10, 9], &[]]);
+
+    assert_twenty_one_error(&client.try_split(&session_id, &player2), Error::CannotSplit);
+    client.split(&session_id, &player1);
+    let game = stored_game(&client, session_id);
+    assert_eq!(game.player1_hand.len(), 2);
+    assert_eq!(game.player1_split_hand.len(), 2);
+    assert_eq!(game.player1_hand.get(0), Some(8));
+    assert_eq!(game.player1_split_hand.get(0), Some(8));
+    assert_twenty_one_error(&client.try_split(&session_id, &player1), Error::CannotSplit);
+
+    // First stick finishes the first hand, the second ends the turn.
+    client.stick(&session_id, &player1);
+    let game = stored_game(&client, session_id);
+    assert!(game.player1_on_split);
+    assert!(!game.player1_stuck);
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+
+    let game = stored_game(&client, session_id);
+    let net = net_outcome(&game);
+    let result = client.reveal_winner(&session_id, &player1);
+    match net {
+        0 => assert_eq!(result, None),
+        n if n > 0 => assert_eq!(result, Some(player1.clone())),
+        _ => assert_eq!(result, Some(player2.clone())),
+    }
+}
+
+#[test]
+fn test_split_player_loses_only_when_both_hands_bust() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 41u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    set_hands(&env, &client, session_id, [&[10, 10, 1], &[10, 10, 1], &[10, 9], &[]]);
+
+    // 21 + any card busts the first hand; play moves to the split hand.
+    client.hit(&session_id, &player1);
+    let game = stored_game(&client, session_id);
+    assert!(game.player1_on_split);
+    assert_eq!(game.winner, None);
+
+    client.hit(&session_id, &player1);
+    let game = stored_game(&client, session_id);
+    assert!(game.player1_stuck);
+    assert_eq!(game.winner, Some(player2));
+}
+
+// ============================================================================
+// Stake Wagering Tests
+// ============================================================================
+
+/// Enable wagering with a fresh XLM asset and fund both players
+fn setup_wagering(
+    env: &Env,
+    client: &TwentyOneContractClient,
+    player1: &Address,
+    player2: &Address,
+) -> (soroban_sdk::token::Client<'static>, Address) {
+    let issuer = Address::generate(env);
+    let xlm_addr = env.register_stellar_asset_contract_v2(issuer).address();
+    let treasury = Address::generate(env);
+    client.set_wagering(&xlm_addr, &treasury);
+
+    let xlm_admin = soroban_sdk::token::StellarAssetClient::new(env, &xlm_addr);
+    xlm_admin.mint(player1, &10_000_000_000);
+    xlm_admin.mint(player2, &10_000_000_000);
+    (soroban_sdk::token::Client::new(env, &xlm_addr), treasury)
+}
+
+#[test]
+fn test_staked_game_pays_winner_and_sweeps_fees() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 54u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    assert_twenty_one_error(
+        &client.try_set_game_stake(&session_id, &100_000_000),
+        Error::WageringNotConfigured,
+    );
+    let (xlm, treasury) = setup_wagering(&env, &client, &player1, &player2);
+    client.set_game_stake(&session_id, &100_000_000);
+
+    client.deposit_stake(&session_id, &player1);
+    assert_twenty_one_error(&client.try_hit(&session_id, &player1), Error::StakeNotPaid);
+    client.deposit_stake(&session_id, &player2);
+    // 10 XLM stake + 0.1% fee each
+    assert_eq!(xlm.balance(&client.address), 200_200_000);
+    assert_eq!(client.get_outstanding_liabilities(), 200_000_000);
+
+    // Player 1 sits on 21, so any hit busts and player 2 wins the pot.
+    set_hands(&env, &client, session_id, [&[10, 10, 1], &[], &[10, 9], &[]]);
+    client.hit(&session_id, &player1);
+    assert_eq!(stored_game(&client, session_id).winner, Some(player2.clone()));
+    assert_eq!(client.get_fee_accrued(), 200_000);
+
+    assert_eq!(client.claim_winnings(&session_id), 200_000_000);
+    assert_eq!(xlm.balance(&player2), 10_099_900_000);
+    assert_twenty_one_error(&client.try_claim_winnings(&session_id), Error::NothingToClaim);
+
+    assert_eq!(client.sweep_treasury(), 200_000);
+    assert_eq!(xlm.balance(&treasury), 200_000);
+    assert_eq!(xlm.balance(&client.address), 0);
+    assert_twenty_one_error(&client.try_sweep_treasury(), Error::SweepTooEarly);
+}
+
+#[test]
+fn test_expire_stake_refunds_lone_depositor() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 44u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    let (xlm, _treasury) = setup_wagering(&env, &client, &player1, &player2);
+    client.set_game_stake(&session_id, &100_000_000);
+    assert_twenty_one_error(&client.try_set_game_stake(&session_id, &50_000_000), Error::InvalidStake);
+
+    client.deposit_stake(&session_id, &player1);
+    assert_twenty_one_error(&client.try_expire_stake(&session_id), Error::DeadlineNotReached);
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 61);
+    assert_twenty_one_error(
+        &client.try_deposit_stake(&session_id, &player2),
+        Error::StakeDepositExpired,
+    );
+    client.expire_stake(&session_id);
+
+    assert_eq!(xlm.balance(&player1), 10_000_000_000);
+    assert_eq!(client.get_outstanding_liabilities(), 0);
+    assert!(stored_game(&client, session_id).is_cancelled);
+    assert_twenty_one_error(&client.try_stick(&session_id, &player2), Error::GameCancelled);
+}
+
+// ============================================================================
+// Game Rules Tests
+// ============================================================================
+
+#[test]
+fn test_game_rules_stored_per_game() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let rules = GameRules {
+        ace_mode: AceMode::Hard,
+        charlie_cards: 5,
+        max_hand_cards: 0,
+        tie_policy: TiePolicy::Push,
+        max_rounds: 3,
+        timeout_seconds: 60,
+    };
+    let bad_rules = GameRules {
+        max_rounds: 0,
+        ..rules.clone()
+    };
+    assert_twenty_one_error(
+        &client.try_start_game(&58, &player1, &player2, &100_0000000, &100_0000000, &Some(bad_rules)),
+        Error::InvalidRules,
+    );
+
+    // Both players sign off on the rules along with their points.
+    let session_id = 58u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &Some(rules.clone()));
+    let auths = env.auths();
+    assert_eq!(auths.len(), 2);
+    for (auth, player) in auths.iter().zip([&player1, &player2]) {
+        assert_eq!(auth.0, *player);
+        let AuthorizedFunction::Contract((_, _, args)) = &auth.1.function else {
+            panic!("expected a contract auth");
+        };
+        assert_eq!(
+            *args,
+            vec![
+                &env,
+                session_id.into_val(&env),
+                100_0000000i128.into_val(&env),
+                Some(rules.clone()).into_val(&env),
+            ]
+        );
+    }
+    let game = stored_game(&client, session_id);
+    assert_eq!(game.rules, rules);
+    assert_eq!(game.rounds_to_win, 2);
+
+    // Hard Aces always count as 1.
+    set_hands(&env, &client, session_id, [&[1, 10], &[], &[10, 7], &[]]);
+    assert_eq!(client.get_hand_value(&session_id, &player1), 11);
+
+    // Five cards without busting win the round under the Charlie rule.
+    set_hands(&env, &client, session_id, [&[1, 2, 2, 2], &[], &[10, 7], &[]]);
+    client.hit(&session_id, &player1);
+    let game = stored_game(&client, session_id);
+    assert_eq!(game.round_winners, vec![&env, player1.clone()]);
+    assert_eq!(game.winner, None);
+
+    // A game started without rules uses the contract defaults.
+    client.start_game(&59, &player1, &player2, &100_0000000, &100_0000000, &None);
+    let rules = stored_game(&client, 59).rules;
+    assert_eq!(rules.ace_mode, AceMode::Soft);
+    assert_eq!(rules.tie_policy, TiePolicy::Redeal);
+    assert_eq!(rules.timeout_seconds, client.get_action_timeout());
+}
+
+#[test]
+fn test_hit_auto_sticks_at_max_hand_size() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let rules = GameRules {
+        ace_mode: AceMode::Soft,
+        charlie_cards: 0,
+        max_hand_cards: 3,
+        tie_policy: TiePolicy::Redeal,
+        max_rounds: 1,
+        timeout_seconds: 60,
+    };
+    for bad_max in [2, 4] {
+        let bad_rules = GameRules {
+            charlie_cards: 5,
+            max_hand_cards: bad_max,
+            ..rules.clone()
+        };
+        assert_twenty_one_error(
+            &client.try_start_game(&76, &player1, &player2, &100_0000000, &100_0000000, &Some(bad_rules)),
+            Error::InvalidRules,
+        );
+    }
+
+    let session_id = 76u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &Some(rules));
+
+    // A pair of Aces cannot bust on a third card, which fills the hand.
+    set_hands(&env, &client, session_id, [&[1, 1], &[], &[10, 7], &[]]);
+    client.hit(&session_id, &player1);
+    let game = stored_game(&client, session_id);
+    assert_eq!(game.player1_hand.len(), 3);
+    assert!(game.player1_stuck);
+    assert_twenty_one_error(&client.try_hit(&session_id, &player1), Error::AlreadyStuck);
+}
+
+// ============================================================================
+// Event Tests
+// ============================================================================
+
+#[test]
+fn test_gameplay_events_carry_counts_only() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 57u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    set_hands(&env, &client, session_id, [&[2, 3], &[], &[10, 10, 5], &[]]);
+
+    client.hit(&session_id, &player1);
+    assert_eq!(
+        env.events().all().filter_by_contract(&client.address),
+        Vec::from_array(
+            &env,
+            [(
+                client.address.clone(),
+                (symbol_short!("card"), session_id).into_val(&env),
+                (player1.clone(), 3u32).into_val(&env),
+            )]
+        )
+    );
+
+    client.stick(&session_id, &player1);
+    assert_eq!(
+        env.events().all().filter_by_contract(&client.address),
+        Vec::from_array(
+            &env,
+            [(
+                client.address.clone(),
+                (symbol_short!("stuck"), session_id).into_val(&env),
+                player1.clone().into_val(&env),
+            )]
+        )
+    );
+
+    // A bust reports the bust and the round result before the game ends.
+    client.hit(&session_id, &player2);
+    assert_eq!(
+        env.events().all().filter_by_contract(&client.address),
+        Vec::from_array(
+            &env,
+            [
+                (
+                    client.address.clone(),
+                    (symbol_short!("card"), session_id).into_val(&env),
+                    (player2.clone(), 4u32).into_val(&env),
+                ),
+                (
+                    client.address.clone(),
+                    (symbol_short!("busted"), session_id).into_val(&env),
+                    player2.clone().into_val(&env),
+                ),
+                (
+                    client.address.clone(),
+                    (symbol_short!("round"), session_id).into_val(&env),
+                    (1u32, Some(player1.clone())).into_val(&env),
+                ),
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_public_game_shows_counts_only() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 63u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    set_hands(&env, &client, session_id, [&[8, 8], &[], &[10, 7], &[]]);
+    client.split(&session_id, &player1);
+    client.stick(&session_id, &player2);
+
+    assert_eq!(
+        client.get_public_game(&session_id),
+        PublicGame {
+            player1: player1.clone(),
+            player2: player2.clone(),
+            round: 1,
+            player1_stuck: false,
+            player2_stuck: true,
+            player1_cards: 2,
+            player2_cards: 2,
+            player1_split_cards: 2,
+            player2_split_cards: 0,
+            player1_round_wins: 0,
+            player2_round_wins: 0,
+            winner: None,
+            is_push: false,
+            is_cancelled: false,
+        }
+    );
+    assert_twenty_one_error(&client.try_get_public_game(&999), Error::GameNotFound);
+}
+
+#[test]
+fn test_player_stats_recorded_at_game_end() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 70u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    set_hands(&env, &client, session_id, [&[10, 7], &[], &[10, 9, 2], &[]]);
+    client.hit(&session_id, &player2);
+
+    assert_eq!(
+        client.get_player_stats(&player1),
+        PlayerStats {
+            wins: 1,
+            losses: 0,
+            busts: 0,
+            blackjacks: 0,
+            rounds_played: 1,
+        }
+    );
+    assert_eq!(
+        client.get_player_stats(&player2),
+        PlayerStats {
+            wins: 0,
+            losses: 1,
+            busts: 1,
+            blackjacks: 0,
+            rounds_played: 1,
+        }
+    );
+    assert_eq!(client.get_player_stats(&Address::generate(&env)), PlayerStats::default());
+}
+
+#[test]
+fn test_game_result_archived_past_game_expiry() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 75u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    assert_twenty_one_error(&client.try_get_game_result(&session_id), Error::GameNotFound);
+    set_hands(&env, &client, session_id, [&[10, 7], &[], &[10, 9, 2], &[]]);
+    client.hit(&session_id, &player2);
+
+    // The result survives the game's temporary entry expiring
+    env.as_contract(&client.address, || {
+        env.storage().temporary().remove(&DataKey::Game(session_id));
+    });
+    let result: GameResult = client.get_game_result(&session_id);
+    assert_eq!(result.player1, player1);
+    assert_eq!(result.player2, player2);
+    assert_eq!(result.winner, Some(player1));
+    assert_eq!((result.player1_value, result.player2_value), (17, 31));
+    assert_eq!((result.rounds, result.player1_round_wins, result.player2_round_wins), (1, 1, 0));
+}
+
+#[test]
+fn test_seeded_game_draws_on_each_reveal() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    // Operator chain revealed back from the tip, one link per draw
+    let hash = |link: &BytesN<32>| -> BytesN<32> {
+        env.crypto().keccak256(&Bytes::from(link.clone())).into()
+    };
+    let last = BytesN::from_array(&env, &[7u8; 32]);
+    let links = [hash(&hash(&last)), hash(&last), last];
+    let tip = hash(&links[0]);
+
+    let session_id = 71u32;
+    client.commit_seed(&session_id, &tip);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    assert_twenty_one_error(&client.try_commit_seed(&session_id, &tip), Error::GameInProgress);
+    assert!(stored_game(&client, session_id).player1_hand.is_empty());
+    assert_twenty_one_error(&client.try_hit(&session_id, &player1), Error::SeedNotRevealed);
+
+    // The first link needs both players' seed commitments
+    assert_twenty_one_error(
+        &client.try_reveal_seed(&session_id, &links[0]),
+        Error::PlayerSeedMissing,
+    );
+    let secret1 = BytesN::from_array(&env, &[1u8; 32]);
+    let secret2 = BytesN::from_array(&env, &[2u8; 32]);
+    client.commit_player_seed(&session_id, &player1, &hash(&secret1));
+    assert_twenty_one_error(
+        &client.try_commit_player_seed(&session_id, &player1, &hash(&secret1)),
+        Error::SeedCommitClosed,
+    );
+    assert_twenty_one_error(
+        &client.try_reveal_player_seed(&session_id, &player1, &secret1),
+        Error::SeedNotRevealed,
+    );
+    client.commit_player_seed(&session_id, &player2, &hash(&secret2));
+
+    assert_twenty_one_error(
+        &client.try_reveal_seed(&session_id, &links[1]),
+        Error::InvalidSeedReveal,
+    );
+    client.reveal_seed(&session_id, &links[0]);
+    assert!(stored_game(&client, session_id).player1_hand.is_empty());
+    assert_twenty_one_error(
+        &client.try_reveal_seed(&session_id, &links[1]),
+        Error::InvalidSeedReveal,
+    );
+
+    // The last player seed reveal deals the round
+    assert_twenty_one_error(
+        &client.try_reveal_player_seed(&session_id, &player1, &secret2),
+        Error::InvalidSeedReveal,
+    );
+    client.reveal_player_seed(&session_id, &player1, &secret1);
+    assert!(stored_game(&client, session_id).player1_hand.is_empty());
+    client.reveal_player_seed(&session_id, &player2, &secret2);
+    let game = stored_game(&client, session_id);
+    assert_eq!(game.player1_hand.len(), 2);
+    assert_eq!(game.player2_hand.len(), 2);
+
+    // A hit waits for the next link, and play pauses until it is revealed
+    set_hands(&env, &client, session_id, [&[2, 3], &[], &[10, 9], &[]]);
+    client.hit(&session_id, &player1);
+    assert_eq!(stored_game(&client, session_id).player1_hand.len(), 2);
+    assert_twenty_one_error(&client.try_stick(&session_id, &player2), Error::SeedNotRevealed);
+    client.reveal_seed(&session_id, &links[1]);
+    assert_eq!(stored_game(&client, session_id).player1_hand.len(), 3);
+
+    // A tied round waits for the next link before the redeal
+    set_hands(&env, &client, session_id, [&[10, 9], &[], &[10, 9], &[]]);
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+    client.reveal_winner(&session_id, &player1);
+    assert!(stored_game(&client, session_id).player1_hand.is_empty());
+
+    client.reveal_seed(&session_id, &links[2]);
+    let game = stored_game(&client, session_id);
+    assert_eq!(game.round, 2);
+    assert_eq!(game.player1_hand.len(), 2);
+}
+
+#[test]
+fn test_seeded_house_dealer_draws_on_reveal() {
+    let (env, client, _hub, player1, _player2) = setup_test();
+
+    let second = BytesN::from_array(&env, &[9u8; 32]);
+    let first: BytesN<32> = env.crypto().keccak256(&Bytes::from(second.clone())).into();
+    let tip: BytesN<32> = env.crypto().keccak256(&Bytes::from(first.clone())).into();
+
+    let session_id = 72u32;
+    client.commit_seed(&session_id, &tip);
+    client.start_house_game(&session_id, &player1, &100_0000000, &None);
+    assert_twenty_one_error(
+        &client.try_commit_player_seed(&session_id, &client.address, &tip),
+        Error::NotPlayer,
+    );
+    deal_test_seed(&env, &client, session_id, &first, &[&player1]);
+
+    // The dealer's draws to 17 wait for the next link
+    set_hands(&env, &client, session_id, [&[10, 9], &[], &[2, 3], &[]]);
+    client.stick(&session_id, &player1);
+    assert_eq!(client.reveal_winner(&session_id, &player1), None);
+    assert_eq!(stored_game(&client, session_id).player2_hand.len(), 2);
+
+    client.reveal_seed(&session_id, &second);
+    let game = stored_game(&client, session_id);
+    assert!(calculate_hand_value_helper(&game.player2_hand) >= 17);
+    assert!(game.winner.is_some());
+}
+
+#[test]
+fn test_prove_hand_publishes_verified_claim() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let proof = Bytes::from_array(&env, &[1u8; 256]);
+
+    let session_id = 74u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    assert_twenty_one_error(
+        &client.try_prove_hand(&session_id, &player1, &HandClaim::NotBusted, &proof),
+        Error::ZkVerifierNotConfigured,
+    );
+
+    let verifier = env.register(MockVerifier, ());
+    client.set_zk_verifier(&verifier, &BytesN::from_array(&env, &[9u8; 32]));
+    assert_twenty_one_error(
+        &client.try_prove_hand(&session_id, &player1, &HandClaim::Value(19), &Bytes::new(&env)),
+        Error::ZkProofInvalid,
+    );
+
+    client.prove_hand(&session_id, &player1, &HandClaim::Value(19), &proof);
+    assert_eq!(
+        env.events().all().filter_by_contract(&client.address),
+        Vec::from_array(
+            &env,
+            [(
+                client.address.clone(),
+                (symbol_short!("proof"), session_id).into_val(&env),
+                (player1.clone(), 1u32, HandClaim::Value(19)).into_val(&env),
+            )]
+        )
+    );
+    let outsider = Address::generate(&env);
+    assert_twenty_one_error(
+        &client.try_prove_hand(&session_id, &outsider, &HandClaim::NotBusted, &proof),
+        Error::NotPlayer,
+    );
+}
+
+// ============================================================================
+// History Tests
+// ============================================================================
+
+#[test]
+fn test_history_records_every_action() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 62u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    let dealt = stored_game(&client, session_id);
+    assert_twenty_one_error(&client.try_get_history(&session_id, &0), Error::GameInProgress);
+
+    client.stick(&session_id, &player1);
+    let mut game = stored_game(&client, session_id);
+    game.player2_hand = Bytes::from_slice(&env, &[10, 10]);
+    env.as_contract(&client.address, || {
+        env.storage().temporary().set(&DataKey::Game(session_id), &game);
+    });
+    client.hit(&session_id, &player2);
+    let hit_card = stored_game(&client, session_id).player2_hand.get(2).unwrap() as u32;
+
+    let ts = env.ledger().timestamp();
+    let entry = |action, player: &Address, card: u32| HistoryEntry {
+        action,
+        round: 1,
+        player: Some(player.clone()),
+        card,
+        timestamp: ts,
+    };
+    let card = |hand: &Bytes, i: u32| hand.get(i).unwrap() as u32;
+    assert_eq!(
+        client.get_history(&session_id, &0),
+        vec![
+            &env,
+            entry(HistoryAction::Deal, &player1, card(&dealt.player1_hand, 0)),
+            entry(HistoryAction::Deal, &player1, card(&dealt.player1_hand, 1)),
+            entry(HistoryAction::Deal, &player2, card(&dealt.player2_hand, 0)),
+            entry(HistoryAction::Deal, &player2, card(&dealt.player2_hand, 1)),
+            entry(HistoryAction::Stick, &player1, 0),
+            entry(HistoryAction::Hit, &player2, hit_card),
+            entry(HistoryAction::Bust, &player2, 0),
+            entry(HistoryAction::RoundResult, &player1, 0),
+        ]
+    );
+    assert!(client.get_history(&session_id, &1).is_empty());
+}
+
+// ============================================================================
+// Hidden Hand Tests
+// ============================================================================
+
+#[test]
+fn test_get_game_hides_hands_until_game_ends() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 49u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    set_hands(&env, &client, session_id, [&[10, 9], &[], &[10, 7], &[]]);
+
+    // Mid-game, hands are 32-byte salted commitments, the salt and dealt
+    // cards are hidden, and players read their own cards.
+    let game = client.get_game(&session_id);
+    assert_eq!(game.player1_hand.len(), 32);
+    assert_ne!(game.player1_hand, game.player2_hand);
+    assert!(game.player1_split_hand.is_empty());
+    assert_eq!(game.deck, 0);
+    assert_eq!(game.hand_salt, BytesN::from_array(&env, &[0u8; 32]));
+
+    let salt = client.get_hand_salt(&session_id, &player1);
+    let mut preimage = Bytes::from(salt.clone());
+    preimage.append(&Bytes::from_array(&env, &1u32.to_be_bytes()));
+    preimage.append(&Bytes::from_slice(&env, &[10, 9]));
+    assert_eq!(game.player1_hand, Bytes::from(env.crypto().keccak256(&preimage)));
+    assert_twenty_one_error(
+        &client.try_get_hand_salt(&session_id, &Address::generate(&env)),
+        Error::NotPlayer,
+    );
+    assert_eq!(
+        client.get_hands(&session_id, &player1),
+        vec![&env, Bytes::from_slice(&env, &[10, 9])]
+    );
+    assert_twenty_one_error(
+        &client.try_get_hands(&session_id, &Address::generate(&env)),
+        Error::NotPlayer,
+    );
+
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+    client.reveal_winner(&session_id, &player1);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.player1_hand, Bytes::from_slice(&env, &[10, 9]));
+    assert_eq!(game.player2_hand, Bytes::from_slice(&env, &[10, 7]));
+    assert_eq!(game.hand_salt, salt);
+    assert_eq!(game.deck, 0);
+}
+
+// ============================================================================
+// Abandon Tests
+// ============================================================================
+
+#[test]
+fn test_insurance_pays_when_dealer_makes_21() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let (xlm, _treasury) = setup_wagering(&env, &client, &player1, &player2);
+    let stake = 10_000_000;
+    client.fund_house(&player2, &(10 * stake));
+
+    // Two-player games never offer insurance, whatever the opponent holds.
+    let session_id = 67u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    client.set_game_stake(&session_id, &stake);
+    client.deposit_stake(&session_id, &player1);
+    client.deposit_stake(&session_id, &player2);
+    set_hands(&env, &client, session_id, [&[10, 7], &[], &[1, 9], &[]]);
+    assert_twenty_one_error(
+        &client.try_buy_insurance(&session_id, &player1, &1_000_000),
+        Error::InsuranceNotOffered,
+    );
+
+    // Dealer finishes on 20: the bet goes to the bankroll
+    let session_id = 68u32;
+    let link = commit_test_seed(&env, &client, session_id);
+    client.start_house_game(&session_id, &player1, &100_0000000, &None);
+    client.set_game_stake(&session_id, &stake);
+    client.deposit_stake(&session_id, &player1);
+    deal_test_seed(&env, &client, session_id, &link, &[&player1]);
+    set_hands(&env, &client, session_id, [&[10, 7], &[], &[10, 9], &[]]);
+
+    assert_twenty_one_error(
+        &client.try_buy_insurance(&session_id, &player1, &1_000_000),
+        Error::InsuranceNotOffered,
+    );
+    set_hands(&env, &client, session_id, [&[10, 7], &[], &[1, 9], &[]]);
+    assert_twenty_one_error(
+        &client.try_buy_insurance(&session_id, &player2, &1_000_000),
+        Error::NotPlayer,
+    );
+    assert_twenty_one_error(
+        &client.try_buy_insurance(&session_id, &player1, &5_000_001),
+        Error::InvalidStake,
+    );
+    let bankroll_before = client.get_house_bankroll();
+    let balance_before = xlm.balance(&player1);
+    client.buy_insurance(&session_id, &player1, &5_000_000);
+    assert_twenty_one_error(
+        &client.try_buy_insurance(&session_id, &player1, &1_000_000),
+        Error::AlreadyInsured,
+    );
+    assert_eq!(client.get_house_bankroll(), bankroll_before - 10_000_000);
+
+    client.stick(&session_id, &player1);
+    client.reveal_winner(&session_id, &player1);
+    assert_eq!(xlm.balance(&player1), balance_before - 5_000_000);
+    assert_eq!(stored_game(&client, session_id).insurance, 0);
+
+    // Dealer finishes on 21: the bet is returned plus 2:1
+    let session_id = 69u32;
+    let link = commit_test_seed(&env, &client, session_id);
+    client.start_house_game(&session_id, &player1, &100_0000000, &None);
+    client.set_game_stake(&session_id, &stake);
+    client.deposit_stake(&session_id, &player1);
+    deal_test_seed(&env, &client, session_id, &link, &[&player1]);
+    set_hands(&env, &client, session_id, [&[10, 7], &[], &[1, 10], &[]]);
+
+    let bankroll_before = client.get_house_bankroll();
+    let balance_before = xlm.balance(&player1);
+    client.buy_insurance(&session_id, &player1, &5_000_000);
+    client.stick(&session_id, &player1);
+    client.reveal_winner(&session_id, &player1);
+    assert_eq!(xlm.balance(&player1), balance_before + 10_000_000);
+    // The house wins the stake but pays the insurance from its reserve.
+    assert_eq!(client.get_house_bankroll(), bankroll_before + 2 * stake - 10_000_000);
+}
+
+#[test]
+fn test_abandon_idle_game_refunds_deposits() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let (xlm, _treasury) = setup_wagering(&env, &client, &player1, &player2);
+
+    let session_id = 60u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    client.set_game_stake(&session_id, &10_000_000);
+    client.deposit_stake(&session_id, &player1);
+    client.deposit_stake(&session_id, &player2);
+    let balance_before = xlm.balance(&player1);
+
+    let timeout = client.get_action_timeout();
+    let start = env.ledger().timestamp();
+    env.ledger().set_timestamp(start + timeout - 1);
+    assert_twenty_one_error(&client.try_abandon_game(&session_id), Error::TimeoutNotReached);
+
+    env.ledger().set_timestamp(start + timeout);
+    client.abandon_game(&session_id);
+
+    let game = stored_game(&client, session_id);
+    assert!(game.is_cancelled);
+    assert_eq!(game.winner, None);
+    assert_eq!(xlm.balance(&player1), balance_before + 10_000_000 + 10_000);
+    assert_eq!(client.get_outstanding_liabilities(), 0);
+    assert_twenty_one_error(&client.try_hit(&session_id, &player1), Error::GameCancelled);
+    assert_twenty_one_error(&client.try_abandon_game(&session_id), Error::GameCancelled);
+}
+
+#[test]
+fn test_betting_pool_opened_and_settled() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let betting = env.register(MockBetting, ());
+    let betting_client = MockBettingClient::new(&env, &betting);
+    client.set_betting_contract(&betting);
+    assert_eq!(client.get_betting_contract(), Some(betting.clone()));
+
+    let session_id = 64u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    let game = stored_game(&client, session_id);
+    assert_eq!(game.betting_contract, Some(betting.clone()));
+    assert_eq!(game.betting_pool_id, Some(1064));
+    assert_eq!(betting_client.result(&session_id), None);
+
+    set_hands(&env, &client, session_id, [&[10, 9], &[], &[10, 7], &[]]);
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+    client.reveal_winner(&session_id, &player1);
+    assert_eq!(betting_client.result(&session_id), Some(1));
+
+    // An abandoned game refunds its pool
+    let session_id = 65u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    env.ledger().set_timestamp(env.ledger().timestamp() + client.get_action_timeout());
+    client.abandon_game(&session_id);
+    assert_eq!(betting_client.result(&session_id), Some(0));
+
+    // House games have no spectator pool
+    let session_id = 66u32;
+    client.start_house_game(&session_id, &player1, &100_0000000, &None);
+    assert_eq!(stored_game(&client, session_id).betting_contract, None);
+}
+
+// ============================================================================
+// Multi-player Table Tests
+// ============================================================================
+
+/// Replace each seat's hand directly in storage
+fn set_table_hands(env: &Env, client: &TwentyOneContractClient, session_id: u32, hands: &[&[u8]]) {
+    env.as_contract(&client.address, || {
+        let key = DataKey::Table(session_id);
+        let mut table: Table = env.storage().temporary().get(&key).unwrap();
+        for (i, hand) in hands.iter().enumerate() {
+            let mut seat = table.seats.get(i as u32).unwrap();
+            seat.hand = Bytes::from_slice(env, hand);
+            table.seats.set(i as u32, seat);
+        }
+        env.storage().temporary().set(&key, &table);
+    });
+}
+
+#[test]
+fn test_table_turn_order_and_winners() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let player3 = Address::generate(&env);
+    let player4 = Address::generate(&env);
+
+    let session_id = 61u32;
+    assert_twenty_one_error(
+        &client.try_start_table(&session_id, &vec![&env, player1.clone(), player2.clone()]),
+        Error::InvalidPlayerCount,
+    );
+    let players = vec![&env, player1.clone(), player2.clone(), player3.clone(), player4.clone()];
+    client.start_table(&session_id, &players);
+    assert_twenty_one_error(&client.try_start_table(&session_id, &players), Error::GameInProgress);
+    assert_eq!(client.get_table(&session_id).seats.get(0).unwrap().hand.len(), 32);
+    set_table_hands(&env, &client, session_id, &[&[10, 9], &[10, 10, 5], &[10, 9], &[10, 7]]);
+
+    // Equal hands commit differently per seat; the salt and deck are hidden.
+    let table = client.get_table(&session_id);
+    assert_ne!(table.seats.get(0).unwrap().hand, table.seats.get(2).unwrap().hand);
+    assert_eq!(table.hand_salt, BytesN::from_array(&env, &[0u8; 32]));
+    assert_eq!(table.deck, 0);
+
+    // Each seat can read only its own cards.
+    assert_eq!(client.get_table_hand(&session_id, &player2), Bytes::from_slice(&env, &[10, 10, 5]));
+    assert_eq!(env.auths()[0].0, player2);
+    let outsider = Address::generate(&env);
+    assert_twenty_one_error(&client.try_get_table_hand(&session_id, &outsider), Error::NotPlayer);
+
+    // Seats act in order.
+    assert_twenty_one_error(&client.try_table_stick(&session_id, &player2), Error::NotYourTurn);
+    client.table_stick(&session_id, &player1);
+    client.table_hit(&session_id, &player2); // busts
+    client.table_stick(&session_id, &player3);
+    assert_twenty_one_error(&client.try_reveal_table(&session_id), Error::TurnsNotFinished);
+    client.table_stick(&session_id, &player4);
+    assert_twenty_one_error(&client.try_table_hit(&session_id, &player4), Error::AlreadyStuck);
+
+    // Seats 1 and 3 share the best live hand.
+    let winners = client.reveal_table(&session_id);
+    assert_eq!(winners, vec![&env, player1.clone(), player3.clone()]);
+
+    let table = client.get_table(&session_id);
+    assert!(table.seats.get(1).unwrap().busted);
+    assert_eq!(table.seats.get(0).unwrap().hand, Bytes::from_slice(&env, &[10, 9]));
+    assert_twenty_one_error(&client.try_table_hit(&session_id, &player1), Error::GameAlreadyEnded);
+}
+
+// ============================================================================
+// Series Tests
+// ============================================================================
+
+#[test]
+fn test_series_finalizes_only_when_decided() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    // Best of three, as both players signed in the game's rules
+    let rules = GameRules {
+        ace_mode: AceMode::Soft,
+        charlie_cards: 0,
+        max_hand_cards: 0,
+        tie_policy: client.get_tie_policy(),
+        max_rounds: 3,
+        timeout_seconds: client.get_action_timeout(),
+    };
+    let session_id = 55u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &Some(rules));
+    assert_eq!(stored_game(&client, session_id).rounds_to_win, 2);
+
+    // Round 1: player 1 stands on 20 against 18.
+    set_hands(&env, &client, session_id, [&[10, 10], &[], &[10, 8], &[]]);
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+    assert_eq!(client.reveal_winner(&session_id, &player1), Some(player1.clone()));
+
+    let game = stored_game(&client, session_id);
+    assert_eq!(game.winner, None);
+    assert_eq!(game.round, 2);
+    assert_eq!(game.player1_round_wins, 1);
+    assert_eq!(game.player1_hand.len(), 2);
+    assert!(!game.player1_stuck);
+
+    // Round 2: player 1 busts.
+    set_hands(&env, &client, session_id, [&[10, 10, 2], &[], &[10, 9], &[]]);
+    client.hit(&session_id, &player1);
+    let game = stored_game(&client, session_id);
+    assert_eq!(game.winner, None);
+    assert_eq!(game.player2_round_wins, 1);
+
+    // Round 3 decides the series.
+    set_hands(&env, &client, session_id, [&[10, 7], &[], &[10, 9], &[]]);
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+    assert_eq!(client.reveal_winner(&session_id, &player1), Some(player2.clone()));
+
+    let game = stored_game(&client, session_id);
+    assert_eq!(game.winner, Some(player2.clone()));
+    assert_eq!(
+        game.round_winners,
+        vec![&env, player1.clone(), player2.clone(), player2.clone()]
+    );
+}
+
+// ============================================================================
+// House Dealer Tests
+// ============================================================================
+
+#[test]
+fn test_house_game_dealer_draws_to_17() {
+    let (env, client, _hub, player1, _player2) = setup_test();
+
+    let session_id = 50u32;
+    client.start_house_game(&session_id, &player1, &100_0000000, &None);
+    let game = stored_game(&client, session_id);
+    assert!(game.is_house_game);
+    assert_eq!(game.player2, client.address);
+    assert!(game.player2_stuck);
+
+    set_hands(&env, &client, session_id, [&[10, 9], &[], &[2, 3], &[]]);
+    client.stick(&session_id, &player1);
+    client.reveal_winner(&session_id, &player1);
+
+    let game = stored_game(&client, session_id);
+    assert!(game.player2_hand.len() > 2);
+    assert!(calculate_hand_value_helper(&game.player2_hand) >= 17);
+}
+
+#[test]
+fn test_house_game_stakes_from_bankroll() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let (xlm, _treasury) = setup_wagering(&env, &client, &player1, &player2);
+    let stake = 10_000_000;
+
+    // Unseeded house games cannot be staked.
+    let session_id = 57u32;
+    client.start_house_game(&session_id, &player1, &100_0000000, &None);
+    assert_twenty_one_error(
+        &client.try_set_game_stake(&session_id, &stake),
+        Error::HouseGameNotSeeded,
+    );
+
+    let session_id = 51u32;
+    let link = commit_test_seed(&env, &client, session_id);
+    client.start_house_game(&session_id, &player1, &100_0000000, &None);
+    assert_twenty_one_error(
+        &client.try_set_game_stake(&session_id, &stake),
+        Error::InsufficientBankroll,
+    );
+    client.fund_house(&player2, &(5 * stake));
+    client.set_game_stake(&session_id, &stake);
+    assert_eq!(client.get_house_bankroll(), 4 * stake);
+    client.deposit_stake(&session_id, &player1);
+    deal_test_seed(&env, &client, session_id, &link, &[&player1]);
+
+    // Dealer stands on 17 and the player's 19 wins the house's stake.
+    set_hands(&env, &client, session_id, [&[10, 9], &[], &[10, 7], &[]]);
+    client.stick(&session_id, &player1);
+    assert_eq!(client.reveal_winner(&session_id, &player1), Some(player1.clone()));
+    assert_eq!(client.claim_winnings(&session_id), 2 * stake);
+
+    // The house wins the next game and keeps the pot.
+    let session_id = 56u32;
+    let link = commit_test_seed(&env, &client, session_id);
+    client.start_house_game(&session_id, &player1, &100_0000000, &None);
+    client.set_game_stake(&session_id, &stake);
+    client.deposit_stake(&session_id, &player1);
+    deal_test_seed(&env, &client, session_id, &link, &[&player1]);
+    set_hands(&env, &client, session_id, [&[10, 6], &[], &[10, 8], &[]]);
+    client.stick(&session_id, &player1);
+    assert_eq!(client.reveal_winner(&session_id, &player1), Some(client.address.clone()));
+    assert_eq!(client.get_house_bankroll(), 5 * stake);
+    assert_eq!(client.get_fee_accrued(), 2 * 10_000);
+
+    // Withdrawals are limited to the bankroll, which sweeps cannot touch.
+    assert_twenty_one_error(
+        &client.try_withdraw_house(&player2, &(6 * stake)),
+        Error::InsufficientBankroll,
+    );
+    client.withdraw_house(&player2, &(5 * stake));
+    assert_eq!(client.get_outstanding_liabilities(), 0);
+    assert_eq!(xlm.balance(&client.address), 2 * 10_000);
+}
+
+// ============================================================================
+// Natural Tests
+// ============================================================================
+
+#[test]
+fn test_natural_wins_round_at_deal() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    // Deals are deterministic per session; find one dealing a lone natural.
+    let game = (100u32..300)
+        .find_map(|session_id| {
+            client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+            Some(stored_game(&client, session_id)).filter(|game| game.winner.is_some())
+        })
+        .expect("no natural dealt");
+
+    assert!(game.won_on_natural);
+    let winner_hand = if game.winner == Some(player1.clone()) {
+        &game.player1_hand
+    } else {
+        &game.player2_hand
+    };
+    assert_eq!(winner_hand.len(), 2);
+    assert_eq!(calculate_hand_value_helper(winner_hand), 21);
+    assert_eq!(game.round_winners.len(), 1);
+
+    let winner = game.winner.unwrap();
+    assert_eq!(client.get_player_stats(&winner).blackjacks, 1);
+    assert_eq!(client.get_player_stats(&winner).wins, 1);
+}
+
+#[test]
+fn test_natural_pays_bonus_from_bankroll() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let _ = setup_wagering(&env, &client, &player1, &player2);
+    let stake = 10_000_000;
+    client.fund_house(&player1, &(10 * stake));
+
+    // Tie each staked game until a redeal hands one player a natural.
+    let (session_id, game) = (300u32..500)
+        .find_map(|session_id| {
+            client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+            if stored_game(&client, session_id).winner.is_some() {
+                return None;
+            }
+            client.set_game_stake(&session_id, &stake);
+            client.deposit_stake(&session_id, &player1);
+            client.deposit_stake(&session_id, &player2);
+            set_hands(&env, &client, session_id, [&[10, 9], &[], &[10, 9], &[]]);
+            client.stick(&session_id, &player1);
+            client.stick(&session_id, &player2);
+            assert_eq!(client.reveal_winner(&session_id, &player1), None);
+            Some((session_id, stored_game(&client, session_id))).filter(|(_, game)| game.won_on_natural)
+        })
+        .expect("no natural dealt");
+
+    let payout = client.get_pending_payout(&session_id);
+    assert_eq!(Some(payout.winner), game.winner);
+    assert_eq!(payout.amount_stroops, 2 * stake + stake / 2);
+    assert_eq!(client.get_house_bankroll(), 10 * stake - stake / 2);
+}
+
+// ============================================================================
+// Tie Policy Tests
+// ============================================================================
+
+#[test]
+fn test_tie_redeals_then_pushes_after_limit() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 47u32;
+    assert_eq!(client.get_tie_policy(), TiePolicy::Redeal);
+    client.set_tie_policy(&TiePolicy::RedealLimit(1));
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+
+    // First tie is redealt.
+    set_hands(&env, &client, session_id, [&[10, 9], &[], &[10, 9], &[]]);
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+    assert_eq!(client.reveal_winner(&session_id, &player1), None);
+
+    let game = stored_game(&client, session_id);
+    assert_eq!(game.round, 2);
+    assert_eq!(game.tied_rounds, 1);
+    assert!(!game.is_push);
+    assert_eq!(game.player1_hand.len(), 2);
+
+    // Second tie ends the game as a push.
+    set_hands(&env, &client, session_id, [&[10, 8], &[], &[9, 9], &[]]);
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+    assert_eq!(client.reveal_winner(&session_id, &player1), None);
+
+    let game = stored_game(&client, session_id);
+    assert!(game.is_push);
+    assert_eq!(game.winner, None);
+    assert_eq!(client.reveal_winner(&session_id, &player1), None);
+    assert_twenty_one_error(&client.try_hit(&session_id, &player1), Error::GameAlreadyEnded);
+}
+
+#[test]
+fn test_round_cap_pushes_repeated_ties() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 67u32;
+    assert_eq!(client.get_round_cap(), 20);
+    client.set_round_cap(&2);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+
+    // The default Redeal policy redeals until the cap is reached.
+    set_hands(&env, &client, session_id, [&[10, 9], &[], &[10, 9], &[]]);
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+    assert_eq!(client.reveal_winner(&session_id, &player1), None);
+    assert_eq!(stored_game(&client, session_id).round, 2);
+
+    set_hands(&env, &client, session_id, [&[10, 8], &[], &[9, 9], &[]]);
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+    assert_eq!(client.reveal_winner(&session_id, &player1), None);
+
+    let game = stored_game(&client, session_id);
+    assert!(game.is_push);
+    assert_eq!(game.round, 2);
+    assert_eq!(game.winner, None);
+}
+
+#[test]
+fn test_staked_push_refunds_stakes() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let (xlm, _treasury) = setup_wagering(&env, &client, &player1, &player2);
+
+    let session_id = 48u32;
+    client.set_tie_policy(&TiePolicy::Push);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    client.set_game_stake(&session_id, &10_000_000);
+    client.deposit_stake(&session_id, &player1);
+    client.deposit_stake(&session_id, &player2);
+    let balance_before = xlm.balance(&player1);
+
+    set_hands(&env, &client, session_id, [&[1, 10], &[], &[10, 1], &[]]);
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+    assert_eq!(client.reveal_winner(&session_id, &player1), None);
+
+    assert!(stored_game(&client, session_id).is_push);
+    assert_eq!(xlm.balance(&player1), balance_before + 10_000_000);
+    assert_eq!(client.get_outstanding_liabilities(), 0);
+    assert_eq!(client.get_fee_accrued(), 2 * 10_000);
+    assert_twenty_one_error(&client.try_claim_winnings(&session_id), Error::NothingToClaim);
+}
+
+// ============================================================================
+// Timeout Tests
+// ============================================================================
+
+#[test]
+fn test_outsider_reveals_only_after_timeout() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let outsider = Address::generate(&env);
+
+    let session_id = 73u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    set_hands(&env, &client, session_id, [&[10, 9], &[], &[10, 7], &[]]);
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+
+    assert_twenty_one_error(
+        &client.try_reveal_winner(&session_id, &outsider),
+        Error::NotPlayer,
+    );
+
+    let timeout = client.get_action_timeout();
+    env.ledger().set_timestamp(env.ledger().timestamp() + timeout);
+    assert_eq!(client.reveal_winner(&session_id, &outsider), Some(player1.clone()));
+    assert_eq!(env.auths()[0].0, outsider);
+}
+
+#[test]
+fn test_claim_timeout_forfeits_idle_player() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 45u32;
+    client.set_action_timeout(&120);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    assert_eq!(client.get_action_timeout(), 120);
+    assert_twenty_one_error(&client.try_claim_timeout(&session_id), Error::NoIdlePlayer);
+
+    let start = env.ledger().timestamp();
+    env.ledger().set_timestamp(start + 30);
+    client.stick(&session_id, &player2);
+    env.ledger().set_timestamp(start + 149);
+    assert_twenty_one_error(&client.try_claim_timeout(&session_id), Error::TimeoutNotReached);
+
+    env.ledger().set_timestamp(start + 150);
+    assert_eq!(client.claim_timeout(&session_id), player2);
+    assert_eq!(stored_game(&client, session_id).winner, Some(player2));
+    assert_twenty_one_error(&client.try_claim_timeout(&session_id), Error::GameAlreadyEnded);
 }
 
 // ============================================================================
@@ -698,9 +2004,9 @@ fn test_face_cards_worth_10() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 25u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
-    let game = client.get_game(&session_id);
+    let game = stored_game(&client, session_id);
 
     // Manually verify card values
     for i in 0..game.player1_hand.len() {
@@ -717,18 +2023,62 @@ fn test_hand_value_calculation() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 26u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
     // Get hand value from contract
     let contract_value = client.get_hand_value(&session_id, &player1);
 
     // Calculate expected value manually
-    let game = client.get_game(&session_id);
+    let game = stored_game(&client, session_id);
     let expected_value = calculate_hand_value_helper(&game.player1_hand);
 
     assert_eq!(contract_value, expected_value);
 }
 
+#[test]
+fn test_ace_counts_as_11_unless_it_busts() {
+    let env = Env::default();
+    let value =
+        |cards: &[u8]| crate::calculate_hand_value(&Bytes::from_slice(&env, cards), AceMode::Soft).unwrap();
+
+    // Soft hands: Ace + King is 21, two Aces are 12.
+    assert_eq!(value(&[1, 13]), 21);
+    assert_eq!(value(&[1, 6]), 17);
+    assert_eq!(value(&[1, 1]), 12);
+    assert_eq!(value(&[1, 1, 9]), 21);
+
+    // Hard hands: the Ace falls back to 1 rather than busting.
+    assert_eq!(value(&[1, 6, 10]), 17);
+    assert_eq!(value(&[1, 1, 10, 10]), 22);
+}
+
+#[test]
+fn test_cards_dealt_from_deck_without_replacement() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 42u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    let game = stored_game(&client, session_id);
+    assert_eq!(game.deck.count_ones(), 4);
+
+    // Every card but the last King is gone, so the next hit must deal it.
+    let mut game = stored_game(&client, session_id);
+    game.player1_hand = Bytes::from_slice(&env, &[2, 2]);
+    game.deck = (1u64 << 52) - 1 - (1 << 51);
+    env.as_contract(&client.address, || {
+        env.storage().temporary().set(&DataKey::Game(session_id), &game);
+    });
+
+    client.hit(&session_id, &player1);
+    let game = stored_game(&client, session_id);
+    assert_eq!(game.player1_hand.get(2), Some(13));
+    assert_eq!(game.deck, (1u64 << 52) - 1);
+
+    // The exhausted deck is reshuffled before the next card.
+    client.hit(&session_id, &player1);
+    assert_eq!(stored_game(&client, session_id).deck.count_ones(), 1);
+}
+
 // ============================================================================
 // Admin Function Tests
 // ============================================================================
@@ -832,13 +2182,13 @@ fn test_deterministic_card_dealing() {
     let session_id = 27u32;
 
     // Start first game
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
-    let game1 = client.get_game(&session_id);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+    let game1 = stored_game(&client, session_id);
 
     // Start second game with same session_id in new environment (should be identical)
     let (_env2, client2, _hub2, player1_2, player2_2) = setup_test();
-    client2.start_game(&session_id, &player1_2, &player2_2, &100_0000000, &100_0000000);
-    let game2 = client2.get_game(&session_id);
+    client2.start_game(&session_id, &player1_2, &player2_2, &100_0000000, &100_0000000, &None);
+    let game2 = stored_game(&client2, session_id);
 
     // Note: Since we generate new addresses each time, the cards will be different
     // But we can verify that within the same session, cards are consistent
@@ -851,9 +2201,9 @@ fn test_round_counter() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 28u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
 
-    let game = client.get_game(&session_id);
+    let game = stored_game(&client, session_id);
     assert_eq!(game.round, 1); // First round
 }
 
@@ -867,6 +2217,171 @@ fn test_cannot_play_against_self() {
 
     let session_id = 29u32;
     // Try to start game where player1 plays against themselves
-    let result = client.try_start_game(&session_id, &player1, &player1, &100_0000000, &100_0000000);
+    let result = client.try_start_game(&session_id, &player1, &player1, &100_0000000, &100_0000000, &None);
     assert_twenty_one_error(&result, Error::SelfPlay);
 }
+
+#[test]
+fn test_cannot_reuse_live_session() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 30u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None);
+
+    // Neither a new pair nor a house game can take over a live session
+    let other = Address::generate(&env);
+    assert_twenty_one_error(
+        &client.try_start_game(&session_id, &player2, &other, &100_0000000, &100_0000000, &None),
+        Error::GameInProgress,
+    );
+    assert_twenty_one_error(
+        &client.try_start_house_game(&session_id, &other, &100_0000000, &None),
+        Error::GameInProgress,
+    );
+    assert_eq!(stored_game(&client, session_id).player1, player1);
+}
+
+// ============================================================================
+// Tournament Tests
+// ============================================================================
+
+/// Finish a leg game in player 1's favour, unless a natural already decided it
+fn finish_leg(env: &Env, client: &TwentyOneContractClient, session_id: u32) -> Address {
+    let game = stored_game(client, session_id);
+    if game.winner.is_none() {
+        set_hands(env, client, session_id, [&[10, 9], &[], &[10, 8], &[]]);
+        client.stick(&session_id, &game.player1);
+        client.stick(&session_id, &game.player2);
+        client.reveal_winner(&session_id, &game.player1);
+    }
+    stored_game(client, session_id).winner.unwrap()
+}
+
+#[test]
+fn test_tournament_runs_bracket_and_pays_champion() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let (xlm, _treasury) = setup_wagering(&env, &client, &player1, &player2);
+    let player3 = Address::generate(&env);
+    let player4 = Address::generate(&env);
+    let xlm_admin = soroban_sdk::token::StellarAssetClient::new(&env, &xlm.address);
+    xlm_admin.mint(&player3, &10_000_000_000);
+    xlm_admin.mint(&player4, &10_000_000_000);
+
+    let three = vec![&env, player1.clone(), player2.clone(), player3.clone()];
+    assert_twenty_one_error(
+        &client.try_create_tournament(&1, &three, &100_000_000, &300),
+        Error::InvalidPlayerCount,
+    );
+    let players = vec![&env, player1.clone(), player2.clone(), player3.clone(), player4.clone()];
+    client.create_tournament(&1, &players, &100_000_000, &300);
+
+    for player in players.iter().take(3) {
+        client.join_tournament(&1, &player);
+    }
+    assert_twenty_one_error(&client.try_join_tournament(&1, &player1), Error::AlreadyJoined);
+    assert_twenty_one_error(&client.try_advance_tournament(&1), Error::TournamentNotReady);
+    client.join_tournament(&1, &player4);
+    assert_eq!(xlm.balance(&client.address), 400_000_000);
+
+    // Leg 1 pairs the seeds in order
+    assert_eq!(client.advance_tournament(&1), None);
+    let tournament: Tournament = client.get_tournament(&1);
+    assert_eq!(tournament.leg, 1);
+    assert_eq!(tournament.sessions, vec![&env, 300, 301]);
+    assert_eq!(stored_game(&client, 301).player1, player3);
+    assert_twenty_one_error(&client.try_advance_tournament(&1), Error::TournamentNotReady);
+
+    let semi_winners = [finish_leg(&env, &client, 300), finish_leg(&env, &client, 301)];
+
+    // Leg results are archived, so the leg advances after its games expire
+    client.env.as_contract(&client.address, || {
+        for session_id in [300u32, 301] {
+            client.env.storage().temporary().remove(&DataKey::Game(session_id));
+        }
+    });
+
+    // The final is played between the two leg winners
+    assert_eq!(client.advance_tournament(&1), None);
+    let tournament = client.get_tournament(&1);
+    assert_eq!(tournament.leg, 2);
+    assert_eq!(tournament.sessions, vec![&env, 302]);
+    let final_game = stored_game(&client, 302);
+    assert_eq!(final_game.player1, semi_winners[0]);
+    assert_eq!(final_game.player2, semi_winners[1]);
+
+    let champion = finish_leg(&env, &client, 302);
+    let before = xlm.balance(&champion);
+    assert_eq!(client.advance_tournament(&1), Some(champion.clone()));
+    assert_eq!(xlm.balance(&champion), before + 400_000_000);
+    assert_eq!(client.get_tournament(&1).champion, Some(champion));
+    assert_eq!(client.get_outstanding_liabilities(), 0);
+    assert_twenty_one_error(&client.try_advance_tournament(&1), Error::GameAlreadyEnded);
+}
+
+#[test]
+fn test_tournament_refunds_entries_when_not_filled() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let (xlm, _treasury) = setup_wagering(&env, &client, &player1, &player2);
+
+    let players = vec![&env, player1.clone(), player2.clone()];
+    client.create_tournament(&2, &players, &100_000_000, &400);
+    client.join_tournament(&2, &player1);
+    let balance_before = xlm.balance(&player1);
+
+    // The join window stays open for a day
+    assert_twenty_one_error(&client.try_cancel_tournament(&2), Error::DeadlineNotReached);
+    let deadline = client.get_tournament(&2).join_deadline_ts;
+    env.ledger().set_timestamp(deadline + 1);
+    assert_twenty_one_error(
+        &client.try_join_tournament(&2, &player2),
+        Error::StakeDepositExpired,
+    );
+
+    client.cancel_tournament(&2);
+    assert_eq!(xlm.balance(&player1), balance_before + 100_000_000);
+    assert_eq!(client.get_outstanding_liabilities(), 0);
+    assert!(client.get_tournament(&2).is_cancelled);
+    assert_twenty_one_error(&client.try_advance_tournament(&2), Error::GameCancelled);
+    assert_twenty_one_error(&client.try_cancel_tournament(&2), Error::GameCancelled);
+}
+
+#[test]
+fn test_tournament_skips_used_sessions_and_admin_refunds() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let (xlm, _treasury) = setup_wagering(&env, &client, &player1, &player2);
+    let other1 = Address::generate(&env);
+    let other2 = Address::generate(&env);
+
+    // Session 500 holds a live game and 501 an archived result
+    client.start_game(&500, &other1, &other2, &0, &0, &None);
+    client.start_game(&501, &other1, &other2, &0, &0, &None);
+    finish_leg(&env, &client, 501);
+    env.as_contract(&client.address, || {
+        env.storage().temporary().remove(&DataKey::Game(501));
+    });
+
+    let players = vec![&env, player1.clone(), player2.clone()];
+    client.create_tournament(&3, &players, &100_000_000, &500);
+    client.join_tournament(&3, &player1);
+    client.join_tournament(&3, &player2);
+    let balance_before = xlm.balance(&player1);
+    assert_eq!(client.advance_tournament(&3), None);
+    assert_eq!(client.get_tournament(&3).sessions, vec![&env, 502]);
+
+    // A result recorded for other players does not decide the leg
+    let result: GameResult = env.as_contract(&client.address, || {
+        env.storage().persistent().get(&DataKey::GameResult(501)).unwrap()
+    });
+    env.as_contract(&client.address, || {
+        env.storage().persistent().set(&DataKey::GameResult(502), &result);
+    });
+    assert_twenty_one_error(&client.try_advance_tournament(&3), Error::TournamentNotReady);
+
+    // The admin can refund a tournament that cannot finish
+    client.refund_tournament(&3);
+    assert_eq!(xlm.balance(&player1), balance_before + 100_000_000);
+    assert_eq!(client.get_outstanding_liabilities(), 0);
+    assert!(client.get_tournament(&3).is_cancelled);
+    assert_twenty_one_error(&client.try_advance_tournament(&3), Error::GameCancelled);
+    assert_twenty_one_error(&client.try_refund_tournament(&3), Error::GameCancelled);
+}
@@ -4,24 +4,44 @@
 //!
 //! A two-player fighting game contract purpose-built for the Veilstar Brawl game.
 //! It supports:
-//! - on-chain combat move recording,
+//! - an open-challenge queue that escrows stakes and auto-creates sessions,
+//! - best-of-three series that escrow one stake across several sessions,
+//! - on-chain character/loadout selection before the first move, validated
+//!   against an admin-managed character roster,
+//! - on-chain combat move recording (capped per match and per turn), including
+//!   relayer-submitted moves signed by a player-registered ed25519 key,
+//! - power surge picks validated against an admin-managed card catalog,
 //! - optional per-match XLM staking (winner takes 2x stake),
 //! - protocol fee accounting (0.1% per player stake deposit),
-//! - periodic fee sweep to treasury.
+//! - configurable split of collected move/surge fees between winner and protocol,
+//! - per-season player stats, ELO ratings, and leaderboards,
+//! - on-chain achievements (first win, 10-win streak, flawless victory),
+//! - optional trophy NFT mint for high-stake winners,
+//! - optional betting pool per match, created, settled, and refunded on the
+//!   zk-betting contract as the match progresses,
+//! - periodic fee sweep to treasury,
+//! - per-player action nonces so client retries never double-charge,
+//! - optional contract-assigned session ids that are never reused,
+//! - contract-wide totals (matches, stakes, fees, moves) via `get_stats`.
+//!
+//! Results are finalized by the admin via `end_game` (subject to the ZK gate),
+//! or by anyone once both players attest to the same result.
 //!
 //! **Game Hub Integration:**
 //! Calls `start_game()` and `end_game()` on the Game Hub contract to satisfy
 //! hackathon requirements and register every match lifecycle event.
 //!
 //! **XLM Flow:**
-//! - `set_match_stake` sets the base stake for a session.
+//! - `set_match_stake` sets the base stake for a session once the contract can cover the 2x payout.
 //! - `deposit_stake` charges each player: `stake + 0.1% fee`.
-//! - `end_game` pays winner `2 * stake` and accrues fees on contract storage.
+//! - `end_game` records a `2 * stake` payout for the winner and accrues fees on contract storage.
+//! - `end_game` also splits collected move fees: the winner's share joins the payout, the rest accrues as fees.
+//! - `claim_winnings` lets the winner withdraw the recorded payout.
 //! - `sweep_treasury` can transfer accrued fees to treasury once every 24 hours.
 
 use soroban_sdk::{
-    contract, contractclient, contracterror, contractimpl, contracttype,
-    symbol_short, token, Address, Bytes, BytesN, Env, IntoVal, Vec, vec,
+    contract, contractclient, contracterror, contractevent, contractimpl, contracttype,
+    token, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, Vec, vec,
 };
 
 // ==========================================================================
@@ -43,6 +63,20 @@ pub trait GameHub {
     fn end_game(env: Env, session_id: u32, player1_won: bool);
 }
 
+#[contractclient(name = "TrophyNftClient")]
+pub trait TrophyNft {
+    fn mint_trophy(env: Env, to: Address, session_id: u32, metadata_hash: BytesN<32>);
+}
+
+/// Subset of the zk-betting contract used to manage one pool per match.
+/// The betting contract's admin must be this contract for these calls to authorize.
+#[contractclient(name = "BettingPoolClient")]
+pub trait BettingPool {
+    fn open_brawl_pool(env: Env, session_id: u32, deadline_ts: u64) -> u32;
+    fn notify_match_ended(env: Env, session_id: u32, winner: BetSide) -> u32;
+    fn notify_match_cancelled(env: Env, session_id: u32) -> u32;
+}
+
 #[contractclient(name = "ZkVerifierContractClient")]
 pub trait ZkVerifierContract {
     fn verify_round_proof(
@@ -85,6 +119,49 @@ pub enum Error {
     ZkVerifierNotConfigured = 22,
     ZkMatchOutcomeRequired = 23,
     InvalidWinnerClaim = 24,
+    MoveLimitExceeded = 25,
+    TurnMoveLimitExceeded = 26,
+    SurgeLimitExceeded = 27,
+    InvalidMoveLimits = 28,
+    SessionIdReused = 29,
+    InvalidSessionId = 30,
+    NothingToClaim = 31,
+    UnknownSurgeCard = 32,
+    SurgeCardDisabled = 33,
+    InvalidSurgeCard = 34,
+    SurgeAlreadySubmitted = 35,
+    CharacterAlreadySelected = 36,
+    CharacterSelectionClosed = 37,
+    UnknownCharacter = 38,
+    CharacterDisabled = 39,
+    InvalidSeason = 40,
+    InvalidRound = 41,
+    ChallengeNotFound = 42,
+    RatingTooLow = 43,
+    InvalidChallenge = 44,
+    InvalidFeePolicy = 45,
+    AttestationMissing = 46,
+    AttestationMismatch = 47,
+    UnsupportedMatchVersion = 48,
+    SeriesNotFound = 49,
+    SeriesAlreadyDecided = 50,
+    SeriesLegInProgress = 51,
+    MoveSignerNotSet = 52,
+    InvalidNonce = 53,
+    SeriesRefunded = 54,
+    InvalidTurn = 55,
+}
+
+// ==========================================================================
+// Betting pool types — mirrors zk-betting's `BetSide`
+// ==========================================================================
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum BetSide {
+    Player1 = 0,
+    Player2 = 1,
 }
 
 // ==========================================================================
@@ -101,6 +178,15 @@ pub enum MoveType {
     Special = 3,
 }
 
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Achievement {
+    FirstWin = 0,
+    WinStreak10 = 1,
+    FlawlessVictory = 2,
+}
+
 // ==========================================================================
 // Data types
 // ==========================================================================
@@ -108,6 +194,42 @@ pub enum MoveType {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Match {
+    pub player1: Address,
+    pub player2: Address,
+    pub player1_points: i128,
+    pub player2_points: i128,
+    pub player1_moves: u32,
+    pub player2_moves: u32,
+    pub player1_surges: u32,
+    pub player2_surges: u32,
+    pub player1_rounds_won: u32,
+    pub player2_rounds_won: u32,
+    pub total_xlm_collected: i128,
+    pub stake_amount_stroops: i128,
+    pub stake_fee_bps: u32,
+    pub surge_price_bps: u32,
+    pub stake_deadline_ts: u64,
+    pub player1_stake_paid: bool,
+    pub player2_stake_paid: bool,
+    pub fee_accrued_stroops: i128,
+    pub player1_zk_commits: u32,
+    pub player2_zk_commits: u32,
+    pub player1_zk_verified: u32,
+    pub player2_zk_verified: u32,
+    pub season_id: u32,
+    pub player1_character: Option<u32>,
+    pub player2_character: Option<u32>,
+    pub player1_loadout_hash: Option<BytesN<32>>,
+    pub player2_loadout_hash: Option<BytesN<32>>,
+    pub is_cancelled: bool,
+    pub winner: Option<Address>,
+}
+
+/// `Match` layout written before schema versioning (version 1). Kept only so
+/// `migrate_match` can decode sessions stored by older contract builds.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchV1 {
     pub player1: Address,
     pub player2: Address,
     pub player1_points: i128,
@@ -129,6 +251,123 @@ pub struct Match {
     pub winner: Option<Address>,
 }
 
+/// Anti-spam caps applied per player. Keeps a malicious client from bloating
+/// temporary storage and the event stream with thousands of paid junk moves.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MoveLimits {
+    pub max_moves_per_match: u32,
+    pub max_surges_per_match: u32,
+    pub max_moves_per_turn: u32,
+}
+
+/// Admin-managed power surge card entry. `cost_multiplier` scales the base
+/// per-action price, so a multiplier of 1 costs the same as a move.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SurgeCard {
+    pub card_code: u32,
+    pub cost_multiplier: u32,
+    pub enabled: bool,
+}
+
+/// Public challenge waiting in the matchmaking queue. The challenger's stake
+/// plus fee is escrowed until someone accepts or the challenge is cancelled.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OpenChallenge {
+    pub challenge_id: u32,
+    pub challenger: Address,
+    pub stake_amount_stroops: i128,
+    pub min_rating: i128,
+    pub created_ts: u64,
+}
+
+/// Per-season player record. `rating` is an ELO-style rating that starts at
+/// `INITIAL_RATING` every season.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlayerStats {
+    pub matches_played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub rating: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeaderboardEntry {
+    pub player: Address,
+    pub rating: i128,
+}
+
+/// Admin-managed roster entry. `patch_version` tracks the balance patch the
+/// character's stats were last changed in.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RosterCharacter {
+    pub character_id: u32,
+    pub name_hash: BytesN<32>,
+    pub enabled: bool,
+    pub patch_version: u32,
+}
+
+/// Stake configuration stored by `set_match_stake` until `start_game` applies it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeConfig {
+    pub stake_amount_stroops: i128,
+    pub surge_price_bps: u32,
+}
+
+/// Best-of-three series. One stake is escrowed up front and paid to the first
+/// player to win `SERIES_WINS_REQUIRED` legs, or refunded once the series sits
+/// idle for `SERIES_TIMEOUT_SECONDS`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Series {
+    pub series_id: u32,
+    pub player1: Address,
+    pub player2: Address,
+    pub stake_amount_stroops: i128,
+    pub player1_wins: u32,
+    pub player2_wins: u32,
+    pub session_ids: Vec<u32>,
+    pub winner: Option<Address>,
+    pub last_activity_ts: u64,
+    pub is_refunded: bool,
+}
+
+/// Contract-wide running totals for dashboards.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ContractStats {
+    pub matches_started: u64,
+    pub matches_settled: u64,
+    pub total_staked_stroops: i128,
+    pub total_fees_stroops: i128,
+    pub total_moves: u64,
+}
+
+/// Exact transfer a player must make in `deposit_stake`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositQuote {
+    pub token: Address,
+    pub stake_amount_stroops: i128,
+    pub fee_stroops: i128,
+    pub amount_stroops: i128,
+    pub deadline_ts: u64,
+}
+
+/// Winnings recorded at settlement, withdrawn by the winner via `claim_winnings`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingPayout {
+    pub winner: Address,
+    pub amount_stroops: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ZkVerificationRecord {
@@ -149,8 +388,24 @@ pub struct ZkMatchOutcomeRecord {
 #[derive(Clone)]
 pub enum DataKey {
     Match(u32),
+    MatchVersion(u32),
     MatchSalt(u32),
     PendingStake(u32),
+    TurnMoves(u32, u32, u32, bool),
+    RoundSurge(u32, u32, bool),
+    SessionClosed(u32),
+    PendingPayout(u32),
+    ResultAttestation(u32, bool),
+    MatchPool(u32),
+    SurgeCard(u32),
+    RosterCharacter(u32),
+    RosterIds,
+    PlayerStats(u32, Address),
+    Leaderboard(u32),
+    WinStreak(Address),
+    OpenChallenge(u32),
+    OpenChallengeIds,
+    Achievements(Address),
     ZkCommit(u32, BytesN<32>, u32, u32, bool),
     ZkVerified(u32, BytesN<32>, u32, u32, bool),
     ZkMatchOutcome(u32),
@@ -163,6 +418,24 @@ pub enum DataKey {
     FeeAccrued,
     LastSweepTs,
     ZkGateRequired,
+    MoveLimits,
+    AutoSessionIds,
+    NextSessionId,
+    OutstandingLiabilities,
+    CurrentSeason,
+    TrophyNftAddress,
+    TrophyMinStake,
+    NextChallengeId,
+    BettingPoolAddress,
+    BettingStakedOnly,
+    BettingWindowSeconds,
+    MoveFeeWinnerBps,
+    Stats,
+    Series(u32),
+    SessionSeries(u32),
+    NextSeriesId,
+    MoveSigner(Address),
+    ActionNonce(u32, Address),
 }
 
 // ==========================================================================
@@ -172,6 +445,9 @@ pub enum DataKey {
 /// 30-day TTL in ledgers (~5 s per ledger)
 const MATCH_TTL_LEDGERS: u32 = 518_400;
 
+/// Schema version of `Match` written by this build. Untagged entries are version 1.
+const MATCH_SCHEMA_VERSION: u32 = 2;
+
 /// 0.0001 XLM in stroops (7 decimals): 0.0001 * 10^7 = 1_000
 const MOVE_COST_STROOPS: i128 = 1_000;
 
@@ -187,6 +463,245 @@ const FEE_SWEEP_INTERVAL_SECONDS: u64 = 86_400;
 /// 60s stake deposit window after stake is configured.
 const STAKE_DEPOSIT_WINDOW_SECONDS: u64 = 60;
 
+/// Consecutive wins needed for the `WinStreak10` achievement.
+const WIN_STREAK_ACHIEVEMENT: u32 = 10;
+
+/// Rating every player starts a season with.
+const INITIAL_RATING: i128 = 1_200;
+
+/// Maximum rating swing per match.
+const RATING_K_FACTOR: i128 = 32;
+
+/// Number of players kept on each season's leaderboard.
+const LEADERBOARD_SIZE: u32 = 10;
+
+/// Stake-proportional power surges may cost at most 10% of the stake.
+const MAX_SURGE_PRICE_BPS: u32 = 1_000;
+
+/// Best-of-5 at 10 turns per round is the longest real match: 50 moves per player.
+const DEFAULT_MAX_MOVES_PER_MATCH: u32 = 50;
+
+/// One power surge pick per round, at most 5 rounds.
+const DEFAULT_MAX_SURGES_PER_MATCH: u32 = 5;
+
+/// Each player acts once per turn.
+const DEFAULT_MAX_MOVES_PER_TURN: u32 = 1;
+
+/// A round ends on the HP tiebreak after 10 turns.
+const MAX_TURNS_PER_ROUND: u32 = 10;
+
+/// Legs a player must win to take a best-of-three series.
+const SERIES_WINS_REQUIRED: u32 = 2;
+
+/// An undecided series with no activity for 7 days can be refunded.
+const SERIES_TIMEOUT_SECONDS: u64 = 604_800;
+
+// ==========================================================================
+// Events
+// ==========================================================================
+//
+// Each event is published as (name, key...) topics, where the keys identify
+// the match, challenge, series or account involved, with the remaining
+// fields as data.
+
+/// ("chall", challenge_id) -> (challenger, stake, min_rating): a challenge was posted
+#[contractevent(topics = ["chall"], data_format = "vec")]
+pub struct ChallengeCreated {
+    #[topic]
+    pub challenge_id: u32,
+    pub challenger: Address,
+    pub stake_amount_stroops: i128,
+    pub min_rating: i128,
+}
+
+/// ("chacc", challenge_id) -> (acceptor, session_id): a challenge was accepted
+#[contractevent(topics = ["chacc"], data_format = "vec")]
+pub struct ChallengeAccepted {
+    #[topic]
+    pub challenge_id: u32,
+    pub acceptor: Address,
+    pub session_id: u32,
+}
+
+/// ("chcnl", challenge_id) -> challenger: a challenge was withdrawn
+#[contractevent(topics = ["chcnl"], data_format = "single-value")]
+pub struct ChallengeCancelled {
+    #[topic]
+    pub challenge_id: u32,
+    pub challenger: Address,
+}
+
+/// ("series", series_id) -> (player1, player2, stake): a series was created
+#[contractevent(topics = ["series"], data_format = "vec")]
+pub struct SeriesCreated {
+    #[topic]
+    pub series_id: u32,
+    pub player1: Address,
+    pub player2: Address,
+    pub stake_amount_stroops: i128,
+}
+
+/// ("seriesrfd", series_id) -> stake: an idle series refunded both stakes
+#[contractevent(topics = ["seriesrfd"], data_format = "single-value")]
+pub struct SeriesRefunded {
+    #[topic]
+    pub series_id: u32,
+    pub stake_amount_stroops: i128,
+}
+
+/// ("serieswin", series_id) -> (winner, session_id): a series was decided
+#[contractevent(topics = ["serieswin"], data_format = "vec")]
+pub struct SeriesWon {
+    #[topic]
+    pub series_id: u32,
+    pub winner: Address,
+    pub session_id: u32,
+}
+
+/// ("charsel", session_id) -> (player, character_id, loadout_hash): a fighter was picked
+#[contractevent(topics = ["charsel"], data_format = "vec")]
+pub struct CharacterSelected {
+    #[topic]
+    pub session_id: u32,
+    pub player: Address,
+    pub character_id: u32,
+    pub loadout_hash: BytesN<32>,
+}
+
+/// ("signer", player) -> public_key: a move-signing key was registered
+#[contractevent(topics = ["signer"], data_format = "single-value")]
+pub struct MoveSignerSet {
+    #[topic]
+    pub player: Address,
+    pub public_key: BytesN<32>,
+}
+
+/// ("move", session_id, round, turn) -> (player, move_type): a move was recorded
+#[contractevent(topics = ["move"], data_format = "vec")]
+pub struct MoveSubmitted {
+    #[topic]
+    pub session_id: u32,
+    #[topic]
+    pub round: u32,
+    #[topic]
+    pub turn: u32,
+    pub player: Address,
+    pub move_type: MoveType,
+}
+
+/// ("surge", session_id, round) -> (player, card_code): a power surge was played
+#[contractevent(topics = ["surge"], data_format = "vec")]
+pub struct PowerSurgePlayed {
+    #[topic]
+    pub session_id: u32,
+    #[topic]
+    pub round: u32,
+    pub player: Address,
+    pub card_code: u32,
+}
+
+/// ("attest", session_id) -> (player, player1_won): a player attested the result
+#[contractevent(topics = ["attest"], data_format = "vec")]
+pub struct ResultAttested {
+    #[topic]
+    pub session_id: u32,
+    pub player: Address,
+    pub player1_won: bool,
+}
+
+/// ("zkout", session_id) -> verifier: the match outcome was proven
+#[contractevent(topics = ["zkout"], data_format = "single-value")]
+pub struct OutcomeProven {
+    #[topic]
+    pub session_id: u32,
+    pub verifier_contract: Address,
+}
+
+/// ("zkcmt", session_id, round, turn) -> (player, commitment): a move was committed
+#[contractevent(topics = ["zkcmt"], data_format = "vec")]
+pub struct MoveCommitted {
+    #[topic]
+    pub session_id: u32,
+    #[topic]
+    pub round: u32,
+    #[topic]
+    pub turn: u32,
+    pub player: Address,
+    pub commitment: BytesN<32>,
+}
+
+/// ("zkver", session_id, round, turn) -> (player, verifier): a move proof was verified
+#[contractevent(topics = ["zkver"], data_format = "vec")]
+pub struct MoveProofVerified {
+    #[topic]
+    pub session_id: u32,
+    #[topic]
+    pub round: u32,
+    #[topic]
+    pub turn: u32,
+    pub player: Address,
+    pub verifier_contract: Address,
+}
+
+/// ("claim", session_id) -> (winner, amount): winnings were claimed
+#[contractevent(topics = ["claim"], data_format = "vec")]
+pub struct WinningsClaimed {
+    #[topic]
+    pub session_id: u32,
+    pub winner: Address,
+    pub amount_stroops: i128,
+}
+
+/// ("season") -> season_id: a new season started
+#[contractevent(topics = ["season"], data_format = "single-value")]
+pub struct SeasonStarted {
+    pub season_id: u32,
+}
+
+/// ("migrate", session_id) -> (from_version, to_version): a stored match was migrated
+#[contractevent(topics = ["migrate"], data_format = "vec")]
+pub struct MatchMigrated {
+    #[topic]
+    pub session_id: u32,
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
+/// ("settle", session_id) -> (winner, payout, winner_share, protocol_share): a match was settled
+#[contractevent(topics = ["settle"], data_format = "vec")]
+pub struct MatchSettled {
+    #[topic]
+    pub session_id: u32,
+    pub winner: Address,
+    pub payout_amount: i128,
+    pub winner_share: i128,
+    pub protocol_share: i128,
+}
+
+/// ("pool", session_id) -> pool_id: a betting pool was opened for the match
+#[contractevent(topics = ["pool"], data_format = "single-value")]
+pub struct MatchPoolOpened {
+    #[topic]
+    pub session_id: u32,
+    pub pool_id: u32,
+}
+
+/// ("trophy", session_id) -> winner: a trophy was minted for the winner
+#[contractevent(topics = ["trophy"], data_format = "single-value")]
+pub struct TrophyMinted {
+    #[topic]
+    pub session_id: u32,
+    pub winner: Address,
+}
+
+/// ("achieve", player) -> achievement: an achievement was unlocked
+#[contractevent(topics = ["achieve"], data_format = "single-value")]
+pub struct AchievementUnlocked {
+    #[topic]
+    pub player: Address,
+    pub achievement: Achievement,
+}
+
 // ==========================================================================
 // Contract
 // ==========================================================================
@@ -233,6 +748,9 @@ impl VeilstarBrawlContract {
     // ======================================================================
 
     /// Start a new game – calls Game Hub `start_game`.
+    ///
+    /// When auto session ids are enabled the caller must pass `session_id = 0`
+    /// and the contract assigns the next free id. Returns the session id used.
     pub fn start_game(
         env: Env,
         session_id: u32,
@@ -240,7 +758,7 @@ impl VeilstarBrawlContract {
         player2: Address,
         player1_points: i128,
         player2_points: i128,
-    ) -> Result<(), Error> {
+    ) -> Result<u32, Error> {
         if player1 == player2 {
             panic!("Cannot play against yourself");
         }
@@ -253,51 +771,26 @@ impl VeilstarBrawlContract {
             vec![&env, session_id.into_val(&env), player2_points.into_val(&env)],
         );
 
-        // Register with Game Hub
-        let hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub not set");
-        let hub = GameHubClient::new(&env, &hub_addr);
-        hub.start_game(
-            &env.current_contract_address(),
-            &session_id,
+        let session_id = Self::resolve_session_id(&env, session_id)?;
+
+        let mut m = Self::open_match(
+            &env,
+            session_id,
             &player1,
             &player2,
-            &player1_points,
-            &player2_points,
-        );
-
-        let mut m = Match {
-            player1: player1.clone(),
-            player2: player2.clone(),
             player1_points,
             player2_points,
-            player1_moves: 0,
-            player2_moves: 0,
-            total_xlm_collected: 0,
-            stake_amount_stroops: 0,
-            stake_fee_bps: STAKE_FEE_BPS,
-            stake_deadline_ts: 0,
-            player1_stake_paid: false,
-            player2_stake_paid: false,
-            fee_accrued_stroops: 0,
-            player1_zk_commits: 0,
-            player2_zk_commits: 0,
-            player1_zk_verified: 0,
-            player2_zk_verified: 0,
-            is_cancelled: false,
-            winner: None,
-        };
+        );
 
         // Allow stake to be configured either before or after `start_game`.
         // This prevents tx ordering races where `set_match_stake` lands before the match exists.
         let pending_stake_key = DataKey::PendingStake(session_id);
-        if let Some(pending_stake_amount_stroops) = env.storage().temporary().get::<_, i128>(&pending_stake_key) {
-            if pending_stake_amount_stroops > 0 {
-                m.stake_amount_stroops = pending_stake_amount_stroops;
+        if let Some(pending) = env.storage().temporary().get::<_, StakeConfig>(&pending_stake_key) {
+            if pending.stake_amount_stroops > 0 {
+                Self::reserve_liability(&env, pending.stake_amount_stroops * 2)?;
+                m.stake_amount_stroops = pending.stake_amount_stroops;
                 m.stake_fee_bps = STAKE_FEE_BPS;
+                m.surge_price_bps = pending.surge_price_bps;
                 m.stake_deadline_ts = env
                     .ledger()
                     .timestamp()
@@ -307,225 +800,219 @@ impl VeilstarBrawlContract {
             env.storage().temporary().remove(&pending_stake_key);
         }
 
-        let key = DataKey::Match(session_id);
-        let mut salt_bytes = [0u8; 8];
-        salt_bytes[..4].copy_from_slice(&session_id.to_be_bytes());
-        salt_bytes[4..].copy_from_slice(&env.ledger().sequence().to_be_bytes());
-        let match_salt = env.crypto().sha256(&Bytes::from_array(&env, &salt_bytes));
-        let salt_key = DataKey::MatchSalt(session_id);
-
-        env.storage().temporary().set(&key, &m);
-        env.storage().temporary().set(&salt_key, &match_salt);
-        env.storage()
-            .temporary()
-            .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
-        env.storage()
-            .temporary()
-            .extend_ttl(&salt_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+        Self::store_new_match(&env, session_id, &m);
+        Self::create_pool_if_enabled(&env, session_id, &m);
 
-        Ok(())
+        Ok(session_id)
     }
 
-    /// Record a combat move on-chain and collect 0.0001 XLM from the player.
-    pub fn submit_move(
-        env: Env,
-        session_id: u32,
-        player: Address,
-        move_type: MoveType,
-        turn: u32,
-    ) -> Result<(), Error> {
-        player.require_auth();
-
-        let key = DataKey::Match(session_id);
-        let mut m: Match = env
-            .storage()
-            .temporary()
-            .get(&key)
-            .ok_or(Error::MatchNotFound)?;
+    // ======================================================================
+    // Open challenges
+    // ======================================================================
 
-        if m.winner.is_some() {
-            return Err(Error::MatchAlreadyEnded);
-        }
+    /// Escrow `stake + fee` into the public queue. The first player whose
+    /// current-season rating is at least `min_rating` can accept it.
+    pub fn post_open_challenge(
+        env: Env,
+        challenger: Address,
+        stake_amount_stroops: i128,
+        min_rating: i128,
+    ) -> Result<u32, Error> {
+        challenger.require_auth();
 
-        if m.is_cancelled {
-            return Err(Error::MatchCancelled);
+        if stake_amount_stroops <= 0 {
+            return Err(Error::InvalidStake);
         }
 
-        // Verify caller is a participant
-        let is_p1 = player == m.player1;
-        let is_p2 = player == m.player2;
-        if !is_p1 && !is_p2 {
-            return Err(Error::NotPlayer);
-        }
+        let escrow = stake_amount_stroops + Self::calc_fee(stake_amount_stroops, STAKE_FEE_BPS);
 
-        // Transfer 0.0001 XLM from player → this contract via SAC
         let xlm_addr: Address = env
             .storage()
             .instance()
             .get(&DataKey::XlmToken)
             .expect("XLM token not set");
         let xlm = token::Client::new(&env, &xlm_addr);
-        xlm.transfer(&player, &env.current_contract_address(), &MOVE_COST_STROOPS);
+        xlm.transfer(&challenger, env.current_contract_address(), &escrow);
 
-        // Update move counters
-        if is_p1 {
-            m.player1_moves += 1;
-        } else {
-            m.player2_moves += 1;
-        }
-        m.total_xlm_collected += MOVE_COST_STROOPS;
+        // Escrowed funds are owed back to the challenger until accepted.
+        Self::reserve_liability(&env, escrow)?;
 
-        env.storage().temporary().set(&key, &m);
+        let challenge_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextChallengeId)
+            .unwrap_or(1_u32);
         env.storage()
-            .temporary()
-            .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
-
-        // Emit event for indexers / explorers
-        env.events().publish(
-            (symbol_short!("move"), session_id, turn),
-            (player, move_type),
-        );
+            .instance()
+            .set(&DataKey::NextChallengeId, &(challenge_id + 1));
+
+        let challenge = OpenChallenge {
+            challenge_id,
+            challenger: challenger.clone(),
+            stake_amount_stroops,
+            min_rating,
+            created_ts: env.ledger().timestamp(),
+        };
+        let challenge_key = DataKey::OpenChallenge(challenge_id);
+        env.storage().persistent().set(&challenge_key, &challenge);
+        env.storage()
+            .persistent()
+            .extend_ttl(&challenge_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
 
-        Ok(())
+        let mut ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OpenChallengeIds)
+            .unwrap_or(Vec::new(&env));
+        ids.push_back(challenge_id);
+        Self::save_open_challenge_ids(&env, &ids);
+
+        ChallengeCreated {
+            challenge_id,
+            challenger,
+            stake_amount_stroops,
+            min_rating,
+        }
+        .publish(&env);
+
+        Ok(challenge_id)
     }
 
-    /// Record a power surge pick on-chain and collect 0.0001 XLM from the player.
-    pub fn submit_power_surge(
+    /// Match against an open challenge: escrow the same stake and start a
+    /// stake-funded session with a contract-assigned id. Returns the session id.
+    pub fn accept_open_challenge(
         env: Env,
-        session_id: u32,
-        player: Address,
-        round: u32,
-        card_code: u32,
-    ) -> Result<(), Error> {
-        player.require_auth();
+        challenge_id: u32,
+        acceptor: Address,
+    ) -> Result<u32, Error> {
+        acceptor.require_auth();
 
-        let key = DataKey::Match(session_id);
-        let mut m: Match = env
+        let challenge_key = DataKey::OpenChallenge(challenge_id);
+        let challenge: OpenChallenge = env
             .storage()
-            .temporary()
-            .get(&key)
-            .ok_or(Error::MatchNotFound)?;
+            .persistent()
+            .get(&challenge_key)
+            .ok_or(Error::ChallengeNotFound)?;
 
-        if m.winner.is_some() {
-            return Err(Error::MatchAlreadyEnded);
+        if acceptor == challenge.challenger {
+            return Err(Error::InvalidChallenge);
         }
 
-        if m.is_cancelled {
-            return Err(Error::MatchCancelled);
+        let season_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::CurrentSeason)
+            .unwrap_or(0_u32);
+        let acceptor_stats = Self::load_player_stats(&env, season_id, &acceptor);
+        if acceptor_stats.rating < challenge.min_rating {
+            return Err(Error::RatingTooLow);
         }
 
-        // Verify caller is a participant
-        let is_p1 = player == m.player1;
-        let is_p2 = player == m.player2;
-        if !is_p1 && !is_p2 {
-            return Err(Error::NotPlayer);
-        }
+        let stake = challenge.stake_amount_stroops;
+        let escrow = stake + Self::calc_fee(stake, STAKE_FEE_BPS);
 
-        // Transfer 0.0001 XLM from player → this contract via SAC
         let xlm_addr: Address = env
             .storage()
             .instance()
             .get(&DataKey::XlmToken)
             .expect("XLM token not set");
         let xlm = token::Client::new(&env, &xlm_addr);
-        xlm.transfer(&player, &env.current_contract_address(), &MOVE_COST_STROOPS);
-
-        // Track payment collected by contract
-        m.total_xlm_collected += MOVE_COST_STROOPS;
-
-        env.storage().temporary().set(&key, &m);
-        env.storage()
-            .temporary()
-            .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
-
-        // Emit event for indexers / explorers
-        env.events().publish(
-            (symbol_short!("surge"), session_id, round),
-            (player, card_code),
-        );
+        xlm.transfer(&acceptor, env.current_contract_address(), &escrow);
+
+        Self::remove_open_challenge(&env, challenge_id);
+        Self::release_liability(&env, escrow);
+        Self::reserve_liability(&env, stake * 2)?;
+
+        let session_id = Self::assign_session_id(&env);
+        let mut m = Self::open_match(&env, session_id, &challenge.challenger, &acceptor, 0, 0);
+        m.stake_amount_stroops = stake;
+        m.player1_stake_paid = true;
+        m.player2_stake_paid = true;
+        Self::store_new_match(&env, session_id, &m);
+        Self::update_stats(&env, |stats| stats.total_staked_stroops += stake * 2);
+        Self::create_pool_if_enabled(&env, session_id, &m);
+
+        ChallengeAccepted {
+            challenge_id,
+            acceptor,
+            session_id,
+        }
+        .publish(&env);
 
-        Ok(())
+        Ok(session_id)
     }
 
-    /// End a game and report to Game Hub.
-    /// Only admin can finalise a game result.
-    pub fn end_game(
-        env: Env,
-        session_id: u32,
-        player1_won: bool,
-    ) -> Result<(), Error> {
-        let admin: Address = env
+    /// Withdraw an unaccepted challenge and refund the escrowed stake + fee.
+    pub fn cancel_open_challenge(env: Env, challenge_id: u32) -> Result<(), Error> {
+        let challenge: OpenChallenge = env
             .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
+            .persistent()
+            .get(&DataKey::OpenChallenge(challenge_id))
+            .ok_or(Error::ChallengeNotFound)?;
 
-        let key = DataKey::Match(session_id);
-        let mut m: Match = env
-            .storage()
-            .temporary()
-            .get(&key)
-            .ok_or(Error::MatchNotFound)?;
+        challenge.challenger.require_auth();
 
-        if m.winner.is_some() {
-            return Err(Error::MatchAlreadyEnded);
-        }
+        let stake = challenge.stake_amount_stroops;
+        let escrow = stake + Self::calc_fee(stake, STAKE_FEE_BPS);
 
-        if m.is_cancelled {
-            return Err(Error::MatchCancelled);
-        }
+        Self::remove_open_challenge(&env, challenge_id);
+        Self::release_liability(&env, escrow);
 
-        let zk_gate_required: bool = env
+        let xlm_addr: Address = env
             .storage()
             .instance()
-            .get(&DataKey::ZkGateRequired)
-            .unwrap_or(true);
+            .get(&DataKey::XlmToken)
+            .expect("XLM token not set");
+        let xlm = token::Client::new(&env, &xlm_addr);
+        xlm.transfer(&env.current_contract_address(), &challenge.challenger, &escrow);
 
-        if zk_gate_required && (m.player1_zk_verified == 0 || m.player2_zk_verified == 0) {
-            return Err(Error::ZkCommitRequired);
+        ChallengeCancelled {
+            challenge_id,
+            challenger: challenge.challenger,
         }
+        .publish(&env);
 
-        let winner = if zk_gate_required {
-            let outcome_key = DataKey::ZkMatchOutcome(session_id);
-            let outcome: ZkMatchOutcomeRecord = env
-                .storage()
-                .temporary()
-                .get(&outcome_key)
-                .ok_or(Error::ZkMatchOutcomeRequired)?;
+        Ok(())
+    }
 
-            let expected_player1_won = outcome.winner == m.player1;
-            if expected_player1_won != player1_won {
-                return Err(Error::InvalidWinnerClaim);
-            }
+    // ======================================================================
+    // Best-of-three series
+    // ======================================================================
 
-            outcome.winner
-        } else if player1_won {
-            m.player1.clone()
-        } else {
-            m.player2.clone()
-        };
+    /// Open a best-of-three series. A non-zero stake is escrowed from both
+    /// players now (`stake + 0.1% fee` each) and paid out once a player wins
+    /// two legs. The fee is accrued on deposit.
+    pub fn create_series(
+        env: Env,
+        player1: Address,
+        player2: Address,
+        stake_amount_stroops: i128,
+    ) -> Result<u32, Error> {
+        if player1 == player2 {
+            panic!("Cannot play against yourself");
+        }
 
-        // If stake is configured, require both players to have deposited before finalizing.
-        if m.stake_amount_stroops > 0 {
-            if !m.player1_stake_paid || !m.player2_stake_paid {
-                return Err(Error::StakeNotPaid);
-            }
+        player1.require_auth();
+        player2.require_auth();
+
+        if stake_amount_stroops < 0 {
+            return Err(Error::InvalidStake);
+        }
 
-            // Winner gets exactly 2 * stake amount. Fee is retained in contract accounting.
+        if stake_amount_stroops > 0 {
+            let escrow = stake_amount_stroops + Self::calc_fee(stake_amount_stroops, STAKE_FEE_BPS);
             let xlm_addr: Address = env
                 .storage()
                 .instance()
                 .get(&DataKey::XlmToken)
                 .expect("XLM token not set");
             let xlm = token::Client::new(&env, &xlm_addr);
+            xlm.transfer(&player1, env.current_contract_address(), &escrow);
+            xlm.transfer(&player2, env.current_contract_address(), &escrow);
 
-            let winner_payout = m.stake_amount_stroops * 2;
-            xlm.transfer(&env.current_contract_address(), &winner, &winner_payout);
+            Self::reserve_liability(&env, stake_amount_stroops * 2)?;
 
-            // Retain total fee from both sides in contract-level accrued fee bucket.
-            let per_player_fee = Self::calc_fee(m.stake_amount_stroops, m.stake_fee_bps);
-            let total_fee = per_player_fee * 2;
+            let total_fee = (escrow - stake_amount_stroops) * 2;
             let mut accrued: i128 = env
                 .storage()
                 .instance()
@@ -533,44 +1020,194 @@ impl VeilstarBrawlContract {
                 .unwrap_or(0_i128);
             accrued += total_fee;
             env.storage().instance().set(&DataKey::FeeAccrued, &accrued);
-            m.fee_accrued_stroops += total_fee;
+            Self::update_stats(&env, |stats| {
+                stats.total_staked_stroops += stake_amount_stroops * 2;
+                stats.total_fees_stroops += total_fee;
+            });
         }
 
-        m.winner = Some(winner);
+        let series_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextSeriesId)
+            .unwrap_or(1_u32);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextSeriesId, &(series_id + 1));
 
-        env.storage().temporary().set(&key, &m);
+        let series = Series {
+            series_id,
+            player1: player1.clone(),
+            player2: player2.clone(),
+            stake_amount_stroops,
+            player1_wins: 0,
+            player2_wins: 0,
+            session_ids: Vec::new(&env),
+            winner: None,
+            last_activity_ts: env.ledger().timestamp(),
+            is_refunded: false,
+        };
+        let series_key = DataKey::Series(series_id);
+        env.storage().persistent().set(&series_key, &series);
+        env.storage()
+            .persistent()
+            .extend_ttl(&series_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+
+        SeriesCreated {
+            series_id,
+            player1,
+            player2,
+            stake_amount_stroops,
+        }
+        .publish(&env);
+
+        Ok(series_id)
+    }
+
+    /// Start the next leg of a series as a regular session (reported to the
+    /// Game Hub). Follows the same session id rules as `start_game`.
+    pub fn start_series_game(env: Env, series_id: u32, session_id: u32) -> Result<u32, Error> {
+        let series_key = DataKey::Series(series_id);
+        let mut series: Series = env
+            .storage()
+            .persistent()
+            .get(&series_key)
+            .ok_or(Error::SeriesNotFound)?;
+
+        series.player1.require_auth();
+        series.player2.require_auth();
+
+        if series.winner.is_some() {
+            return Err(Error::SeriesAlreadyDecided);
+        }
+
+        if series.is_refunded {
+            return Err(Error::SeriesRefunded);
+        }
+
+        if let Some(last_session) = series.session_ids.last() {
+            let last: Option<Match> = env.storage().temporary().get(&DataKey::Match(last_session));
+            if let Some(last) = last {
+                if last.winner.is_none() && !last.is_cancelled {
+                    return Err(Error::SeriesLegInProgress);
+                }
+            }
+        }
+
+        let session_id = Self::resolve_session_id(&env, session_id)?;
+        let m = Self::open_match(&env, session_id, &series.player1, &series.player2, 0, 0);
+        Self::store_new_match(&env, session_id, &m);
+
+        let link_key = DataKey::SessionSeries(session_id);
+        env.storage().temporary().set(&link_key, &series_id);
         env.storage()
             .temporary()
-            .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+            .extend_ttl(&link_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
 
-        // Report to Game Hub
-        let hub_addr: Address = env
+        series.session_ids.push_back(session_id);
+        series.last_activity_ts = env.ledger().timestamp();
+        env.storage().persistent().set(&series_key, &series);
+        env.storage()
+            .persistent()
+            .extend_ttl(&series_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+
+        Ok(session_id)
+    }
+
+    /// Refund an undecided series that has been idle for
+    /// `SERIES_TIMEOUT_SECONDS`. Both players get their stake back; the
+    /// escrow fee was accrued on deposit and is kept. Permissionless: funds
+    /// only go back to the players.
+    pub fn refund_series(env: Env, series_id: u32) -> Result<(), Error> {
+        let series_key = DataKey::Series(series_id);
+        let mut series: Series = env
             .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub not set");
-        let hub = GameHubClient::new(&env, &hub_addr);
-        hub.end_game(&session_id, &player1_won);
+            .persistent()
+            .get(&series_key)
+            .ok_or(Error::SeriesNotFound)?;
+
+        if series.winner.is_some() {
+            return Err(Error::SeriesAlreadyDecided);
+        }
+
+        if series.is_refunded {
+            return Err(Error::SeriesRefunded);
+        }
+
+        if env.ledger().timestamp() < series.last_activity_ts + SERIES_TIMEOUT_SECONDS {
+            return Err(Error::DeadlineNotReached);
+        }
+
+        if series.stake_amount_stroops > 0 {
+            let xlm_addr: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::XlmToken)
+                .expect("XLM token not set");
+            let xlm = token::Client::new(&env, &xlm_addr);
+            xlm.transfer(
+                &env.current_contract_address(),
+                &series.player1,
+                &series.stake_amount_stroops,
+            );
+            xlm.transfer(
+                &env.current_contract_address(),
+                &series.player2,
+                &series.stake_amount_stroops,
+            );
+            Self::release_liability(&env, series.stake_amount_stroops * 2);
+        }
+
+        series.is_refunded = true;
+        env.storage().persistent().set(&series_key, &series);
+        env.storage()
+            .persistent()
+            .extend_ttl(&series_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+
+        SeriesRefunded {
+            series_id,
+            stake_amount_stroops: series.stake_amount_stroops,
+        }
+        .publish(&env);
 
         Ok(())
     }
 
-    pub fn submit_zk_match_outcome(
+    pub fn get_series(env: Env, series_id: u32) -> Result<Series, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Series(series_id))
+            .ok_or(Error::SeriesNotFound)
+    }
+
+    /// Series a session belongs to, if it was started as a series leg.
+    pub fn get_session_series(env: Env, session_id: u32) -> Option<u32> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::SessionSeries(session_id))
+    }
+
+    // ======================================================================
+    // Round results
+    // ======================================================================
+
+    /// Record the winner of a finished round. Rounds must be reported in order.
+    /// Round tallies feed achievement checks in `end_game`.
+    pub fn record_round_result(
         env: Env,
         session_id: u32,
-        winner: Address,
-        vk_id: BytesN<32>,
-        proof: Bytes,
-        public_inputs: Vec<BytesN<32>>,
+        round: u32,
+        player1_won: bool,
     ) -> Result<(), Error> {
-        // Current Groth16 round-plan circuit exposes exactly one public input: `commitment`.
-        // Keep this strict so a caller cannot satisfy the verifier with a different statement.
-        if proof.len() != 256 || public_inputs.len() != 1 {
-            return Err(Error::ZkProofInvalid);
-        }
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
 
         let key = DataKey::Match(session_id);
-        let m: Match = env
+        let mut m: Match = env
             .storage()
             .temporary()
             .get(&key)
@@ -584,68 +1221,36 @@ impl VeilstarBrawlContract {
             return Err(Error::MatchCancelled);
         }
 
-        let zero = BytesN::from_array(&env, &[0u8; 32]);
-        let configured_vk_id: BytesN<32> = env
-            .storage()
-            .instance()
-            .get(&DataKey::ZkVerifierVkId)
-            .unwrap_or(zero.clone());
-
-        if configured_vk_id == zero || vk_id != configured_vk_id {
-            return Err(Error::ZkProofInvalid);
-        }
-
-        if winner != m.player1 && winner != m.player2 {
-            return Err(Error::InvalidWinnerClaim);
-        }
-
-        let verifier_contract: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::ZkVerifierContractAddress)
-            .ok_or(Error::ZkVerifierNotConfigured)?;
-
-        let verifier = ZkVerifierContractClient::new(&env, &verifier_contract);
-        let verified = verifier.verify_round_proof(&vk_id, &proof, &public_inputs);
-        if !verified {
-            return Err(Error::ZkProofInvalid);
+        if round != m.player1_rounds_won + m.player2_rounds_won + 1 {
+            return Err(Error::InvalidRound);
         }
 
-        let outcome_key = DataKey::ZkMatchOutcome(session_id);
-        if env.storage().temporary().has(&outcome_key) {
-            return Err(Error::ZkVerificationAlreadySubmitted);
+        if player1_won {
+            m.player1_rounds_won += 1;
+        } else {
+            m.player2_rounds_won += 1;
         }
 
-        let record = ZkMatchOutcomeRecord {
-            verifier_contract: verifier_contract.clone(),
-            winner,
-            vk_id,
-        };
-
-        env.storage().temporary().set(&outcome_key, &record);
+        env.storage().temporary().set(&key, &m);
         env.storage()
             .temporary()
-            .extend_ttl(&outcome_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
-
-        env.events().publish((symbol_short!("zkout"), session_id), verifier_contract);
+            .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
 
         Ok(())
     }
 
-    pub fn submit_zk_commit(
+    /// Record the character and loadout a player is using. Must happen before the
+    /// first move of the match so results and replays map to the exact fighters.
+    /// The character must be enabled in the roster.
+    pub fn select_character(
         env: Env,
         session_id: u32,
         player: Address,
-        round: u32,
-        turn: u32,
-        commitment: BytesN<32>,
+        character_id: u32,
+        loadout_hash: BytesN<32>,
     ) -> Result<(), Error> {
         player.require_auth();
 
-        if round == 0 || turn == 0 {
-            return Err(Error::InvalidZkCommitment);
-        }
-
         let key = DataKey::Match(session_id);
         let mut m: Match = env
             .storage()
@@ -667,25 +1272,38 @@ impl VeilstarBrawlContract {
             return Err(Error::NotPlayer);
         }
 
-        let match_salt: BytesN<32> = env
-            .storage()
-            .temporary()
-            .get(&DataKey::MatchSalt(session_id))
-            .ok_or(Error::MatchNotFound)?;
+        if m.player1_moves > 0 || m.player2_moves > 0 {
+            return Err(Error::CharacterSelectionClosed);
+        }
 
-        let zk_key = DataKey::ZkCommit(session_id, match_salt, round, turn, is_p1);
-        let had_existing_commit = env.storage().temporary().has(&zk_key);
-        env.storage().temporary().set(&zk_key, &commitment);
+        let existing = if is_p1 {
+            m.player1_character
+        } else {
+            m.player2_character
+        };
+        if existing.is_some() {
+            return Err(Error::CharacterAlreadySelected);
+        }
+
+        let roster_key = DataKey::RosterCharacter(character_id);
+        let character: RosterCharacter = env
+            .storage()
+            .persistent()
+            .get(&roster_key)
+            .ok_or(Error::UnknownCharacter)?;
+        if !character.enabled {
+            return Err(Error::CharacterDisabled);
+        }
         env.storage()
-            .temporary()
-            .extend_ttl(&zk_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+            .persistent()
+            .extend_ttl(&roster_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
 
-        if !had_existing_commit {
-            if is_p1 {
-                m.player1_zk_commits += 1;
-            } else if is_p2 {
-                m.player2_zk_commits += 1;
-            }
+        if is_p1 {
+            m.player1_character = Some(character_id);
+            m.player1_loadout_hash = Some(loadout_hash.clone());
+        } else {
+            m.player2_character = Some(character_id);
+            m.player2_loadout_hash = Some(loadout_hash.clone());
         }
 
         env.storage().temporary().set(&key, &m);
@@ -693,33 +1311,152 @@ impl VeilstarBrawlContract {
             .temporary()
             .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
 
-        env.events().publish(
-            (symbol_short!("zkcmt"), session_id, round, turn),
-            (player, commitment),
-        );
+        CharacterSelected {
+            session_id,
+            player,
+            character_id,
+            loadout_hash,
+        }
+        .publish(&env);
 
         Ok(())
     }
 
-    pub fn submit_zk_verification(
+    /// Record a combat move on-chain and collect 0.0001 XLM from the player.
+    /// `turn` counts from 1 within `round`; `nonce` must equal the player's
+    /// next action nonce for the match.
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit_move(
         env: Env,
         session_id: u32,
         player: Address,
+        move_type: MoveType,
         round: u32,
         turn: u32,
-        commitment: BytesN<32>,
-        vk_id: BytesN<32>,
-        proof: Bytes,
-        public_inputs: Vec<BytesN<32>>,
+        nonce: u32,
     ) -> Result<(), Error> {
-        if round == 0 || turn == 0 {
-            return Err(Error::InvalidZkCommitment);
-        }
+        player.require_auth();
 
-        if proof.len() == 0 {
-            return Err(Error::ZkProofInvalid);
+        Self::record_move(&env, session_id, &player, move_type, round, turn, nonce, &player)
+    }
+
+    /// Register the ed25519 key whose signatures authorize relayed moves
+    /// (`submit_move_for`) on the player's behalf.
+    pub fn set_move_signer(env: Env, player: Address, public_key: BytesN<32>) {
+        player.require_auth();
+
+        let signer_key = DataKey::MoveSigner(player.clone());
+        env.storage().persistent().set(&signer_key, &public_key);
+        env.storage()
+            .persistent()
+            .extend_ttl(&signer_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+
+        MoveSignerSet { player, public_key }.publish(&env);
+    }
+
+    /// Record a move on behalf of `player`. The relayer authorizes the call and
+    /// pays the 0.0001 XLM move price; the player's intent is proven by an
+    /// ed25519 signature over `move_intent_payload`. The payload commits to the
+    /// player's action nonce, so each signature is usable once. An invalid
+    /// signature aborts the call.
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit_move_for(
+        env: Env,
+        relayer: Address,
+        session_id: u32,
+        player: Address,
+        move_type: MoveType,
+        round: u32,
+        turn: u32,
+        nonce: u32,
+        signature: BytesN<64>,
+    ) -> Result<(), Error> {
+        relayer.require_auth();
+
+        let public_key: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MoveSigner(player.clone()))
+            .ok_or(Error::MoveSignerNotSet)?;
+
+        let payload = Self::move_intent_payload(
+            env.clone(),
+            session_id,
+            player.clone(),
+            move_type,
+            round,
+            turn,
+            nonce,
+        );
+        env.crypto().ed25519_verify(&public_key, &payload, &signature);
+
+        Self::record_move(&env, session_id, &player, move_type, round, turn, nonce, &relayer)
+    }
+
+    /// Bytes a player signs to authorize a relayed move: this contract's
+    /// address, session id, player, move type, round, turn, and action nonce
+    /// (XDR addresses, big-endian integers).
+    pub fn move_intent_payload(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        move_type: MoveType,
+        round: u32,
+        turn: u32,
+        nonce: u32,
+    ) -> Bytes {
+        let mut payload = env.current_contract_address().to_xdr(&env);
+        payload.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
+        payload.append(&player.to_xdr(&env));
+        payload.append(&Bytes::from_array(&env, &(move_type as u32).to_be_bytes()));
+        payload.append(&Bytes::from_array(&env, &round.to_be_bytes()));
+        payload.append(&Bytes::from_array(&env, &turn.to_be_bytes()));
+        payload.append(&Bytes::from_array(&env, &nonce.to_be_bytes()));
+        payload
+    }
+
+    /// Next action nonce `player` must pass to `submit_move`,
+    /// `submit_power_surge`, or `submit_zk_commit` in this match.
+    pub fn get_action_nonce(env: Env, session_id: u32, player: Address) -> u32 {
+        env.storage()
+            .temporary()
+            .get(&DataKey::ActionNonce(session_id, player))
+            .unwrap_or(0)
+    }
+
+    /// Require `nonce` to be the player's next action nonce, then advance it.
+    /// A retried submission reuses a consumed nonce and is rejected.
+    fn consume_action_nonce(
+        env: &Env,
+        session_id: u32,
+        player: &Address,
+        nonce: u32,
+    ) -> Result<(), Error> {
+        let nonce_key = DataKey::ActionNonce(session_id, player.clone());
+        let expected: u32 = env.storage().temporary().get(&nonce_key).unwrap_or(0);
+        if nonce != expected {
+            return Err(Error::InvalidNonce);
         }
 
+        env.storage().temporary().set(&nonce_key, &(nonce + 1));
+        env.storage()
+            .temporary()
+            .extend_ttl(&nonce_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+        Ok(())
+    }
+
+    /// Shared move path: validate limits, charge `payer`, and record the move.
+    #[allow(clippy::too_many_arguments)]
+    fn record_move(
+        env: &Env,
+        session_id: u32,
+        player: &Address,
+        move_type: MoveType,
+        round: u32,
+        turn: u32,
+        nonce: u32,
+        payer: &Address,
+    ) -> Result<(), Error> {
         let key = DataKey::Match(session_id);
         let mut m: Match = env
             .storage()
@@ -735,175 +1472,87 @@ impl VeilstarBrawlContract {
             return Err(Error::MatchCancelled);
         }
 
-        let is_p1 = player == m.player1;
-        let is_p2 = player == m.player2;
+        // Verify caller is a participant
+        let is_p1 = *player == m.player1;
+        let is_p2 = *player == m.player2;
         if !is_p1 && !is_p2 {
             return Err(Error::NotPlayer);
         }
 
-        let match_salt: BytesN<32> = env
-            .storage()
-            .temporary()
-            .get(&DataKey::MatchSalt(session_id))
-            .ok_or(Error::MatchNotFound)?;
-
-        let commit_key = DataKey::ZkCommit(session_id, match_salt.clone(), round, turn, is_p1);
-        let stored_commitment: BytesN<32> = env
-            .storage()
-            .temporary()
-            .get(&commit_key)
-            .ok_or(Error::ZkCommitNotFound)?;
+        Self::consume_action_nonce(env, session_id, player, nonce)?;
 
-        if stored_commitment != commitment {
-            return Err(Error::InvalidZkCommitment);
+        let limits = Self::get_move_limits(env.clone());
+        let player_moves = if is_p1 { m.player1_moves } else { m.player2_moves };
+        if player_moves >= limits.max_moves_per_match {
+            return Err(Error::MoveLimitExceeded);
         }
 
-        let verify_key = DataKey::ZkVerified(session_id, match_salt, round, turn, is_p1);
-        let had_existing_verification = env.storage().temporary().has(&verify_key);
-
-        let configured_vk_id: BytesN<32> = env
-            .storage()
-            .instance()
-            .get(&DataKey::ZkVerifierVkId)
-            .unwrap_or(BytesN::from_array(&env, &[0u8; 32]));
-
-        if configured_vk_id != BytesN::from_array(&env, &[0u8; 32]) && vk_id != configured_vk_id {
-            return Err(Error::ZkProofInvalid);
+        // Moves go to the round in play; turns restart at 1 every round, so
+        // the per-turn cap is per round
+        if round != m.player1_rounds_won + m.player2_rounds_won + 1 {
+            return Err(Error::InvalidRound);
         }
-
-        // Current Groth16 round-plan circuit exposes exactly one public input: `commitment`.
-        // Enforce that it matches the submitted commitment, otherwise a proof for some other
-        // commitment could be replayed to satisfy the ZK gate.
-        if proof.len() != 256 || public_inputs.len() != 1 {
-            return Err(Error::ZkProofInvalid);
+        if turn == 0 || turn > MAX_TURNS_PER_ROUND {
+            return Err(Error::InvalidTurn);
         }
-
-        let public_commitment = public_inputs.get(0).unwrap();
-        if public_commitment != commitment {
-            return Err(Error::ZkProofInvalid);
+        let turn_key = DataKey::TurnMoves(session_id, round, turn, is_p1);
+        let turn_moves: u32 = env.storage().temporary().get(&turn_key).unwrap_or(0);
+        if turn_moves >= limits.max_moves_per_turn {
+            return Err(Error::TurnMoveLimitExceeded);
         }
 
-        let verifier_contract: Address = env
+        // Transfer 0.0001 XLM from payer → this contract via SAC
+        let xlm_addr: Address = env
             .storage()
             .instance()
-            .get(&DataKey::ZkVerifierContractAddress)
-            .ok_or(Error::ZkVerifierNotConfigured)?;
-
-        let verifier = ZkVerifierContractClient::new(&env, &verifier_contract);
-        let verified = verifier.verify_round_proof(&vk_id, &proof, &public_inputs);
-        if !verified {
-            return Err(Error::ZkProofInvalid);
-        }
-
-        let record = ZkVerificationRecord {
-            verifier_contract: verifier_contract.clone(),
-            commitment,
-            vk_id,
-        };
+            .get(&DataKey::XlmToken)
+            .expect("XLM token not set");
+        let xlm = token::Client::new(env, &xlm_addr);
+        xlm.transfer(payer, &env.current_contract_address(), &MOVE_COST_STROOPS);
 
-        env.storage().temporary().set(&verify_key, &record);
+        env.storage().temporary().set(&turn_key, &(turn_moves + 1));
         env.storage()
             .temporary()
-            .extend_ttl(&verify_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+            .extend_ttl(&turn_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
 
-        if !had_existing_verification {
-            if is_p1 {
-                m.player1_zk_verified += 1;
-            } else if is_p2 {
-                m.player2_zk_verified += 1;
-            }
+        // Update move counters
+        if is_p1 {
+            m.player1_moves += 1;
+        } else {
+            m.player2_moves += 1;
         }
+        m.total_xlm_collected += MOVE_COST_STROOPS;
+        Self::update_stats(env, |stats| stats.total_moves += 1);
 
         env.storage().temporary().set(&key, &m);
         env.storage()
             .temporary()
             .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
 
-        env.events().publish(
-            (symbol_short!("zkver"), session_id, round, turn),
-            (player, verifier_contract),
-        );
-
-        Ok(())
-    }
-
-    /// Configure stake for a session before deposits begin.
-    /// Stake amount is the base wager (e.g. 1 XLM). Each player deposits stake + 0.1% fee.
-    pub fn set_match_stake(env: Env, session_id: u32, stake_amount_stroops: i128) -> Result<(), Error> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
-
-        if stake_amount_stroops <= 0 {
-            return Err(Error::InvalidStake);
-        }
-
-        // Fast-path: match already exists.
-        let key = DataKey::Match(session_id);
-        if let Some(mut m) = env.storage().temporary().get::<_, Match>(&key) {
-            if m.stake_amount_stroops > 0 {
-                if m.stake_amount_stroops != stake_amount_stroops {
-                    return Err(Error::InvalidStake);
-                }
-
-                env.storage().temporary().set(&key, &m);
-                env.storage()
-                    .temporary()
-                    .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
-                return Ok(());
-            }
-
-            m.stake_amount_stroops = stake_amount_stroops;
-            m.stake_fee_bps = STAKE_FEE_BPS;
-            m.stake_deadline_ts = env
-                .ledger()
-                .timestamp()
-                .saturating_add(STAKE_DEPOSIT_WINDOW_SECONDS);
-
-            env.storage().temporary().set(&key, &m);
-            env.storage()
-                .temporary()
-                .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
-
-            // Clear any pending config for this session to avoid stale state.
-            let pending_key = DataKey::PendingStake(session_id);
-            if env.storage().temporary().has(&pending_key) {
-                env.storage().temporary().remove(&pending_key);
-            }
-
-            return Ok(());
-        }
-
-        // Match not created yet — store a pending stake config so `start_game` can apply it.
-        let pending_key = DataKey::PendingStake(session_id);
-        if let Some(existing) = env.storage().temporary().get::<_, i128>(&pending_key) {
-            if existing != stake_amount_stroops {
-                return Err(Error::InvalidStake);
-            }
-
-            env.storage().temporary().set(&pending_key, &existing);
-            env.storage()
-                .temporary()
-                .extend_ttl(&pending_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
-            return Ok(());
+        // Emit event for indexers / explorers
+        MoveSubmitted {
+            session_id,
+            round,
+            turn,
+            player: player.clone(),
+            move_type,
         }
-
-        env.storage()
-            .temporary()
-            .set(&pending_key, &stake_amount_stroops);
-        env.storage()
-            .temporary()
-            .extend_ttl(&pending_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+        .publish(env);
 
         Ok(())
     }
 
-    /// Player deposit for stake-enabled matches.
-    /// Required amount is stake + 0.1% fee, transferred to this contract.
-    pub fn deposit_stake(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+    /// Record a power surge pick on-chain and collect the surge price (0.0001 XLM, or
+    /// `surge_price_bps` of the stake when configured) times the card's cost multiplier. Only enabled catalog cards are accepted,
+    /// and each player may pick at most once per round.
+    pub fn submit_power_surge(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        round: u32,
+        card_code: u32,
+        nonce: u32,
+    ) -> Result<(), Error> {
         player.require_auth();
 
         let key = DataKey::Match(session_id);
@@ -913,47 +1562,89 @@ impl VeilstarBrawlContract {
             .get(&key)
             .ok_or(Error::MatchNotFound)?;
 
-        if m.stake_amount_stroops <= 0 {
-            return Err(Error::StakeNotConfigured);
+        if m.winner.is_some() {
+            return Err(Error::MatchAlreadyEnded);
         }
 
         if m.is_cancelled {
             return Err(Error::MatchCancelled);
         }
 
-        if m.stake_deadline_ts > 0 && env.ledger().timestamp() > m.stake_deadline_ts {
-            return Err(Error::StakeDepositExpired);
-        }
-
+        // Verify caller is a participant
         let is_p1 = player == m.player1;
         let is_p2 = player == m.player2;
         if !is_p1 && !is_p2 {
             return Err(Error::NotPlayer);
         }
 
-        if (is_p1 && m.player1_stake_paid) || (is_p2 && m.player2_stake_paid) {
-            env.storage().temporary().set(&key, &m);
-            env.storage()
-                .temporary()
-                .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
-            return Ok(());
+        Self::consume_action_nonce(&env, session_id, &player, nonce)?;
+
+        let limits = Self::get_move_limits(env.clone());
+        let player_surges = if is_p1 { m.player1_surges } else { m.player2_surges };
+        if player_surges >= limits.max_surges_per_match {
+            return Err(Error::SurgeLimitExceeded);
         }
 
-        let fee = Self::calc_fee(m.stake_amount_stroops, m.stake_fee_bps);
-        let required = m.stake_amount_stroops + fee;
+        let round_key = DataKey::RoundSurge(session_id, round, is_p1);
+        if env.storage().temporary().has(&round_key) {
+            return Err(Error::SurgeAlreadySubmitted);
+        }
+
+        let card_key = DataKey::SurgeCard(card_code);
+        let card: SurgeCard = env
+            .storage()
+            .persistent()
+            .get(&card_key)
+            .ok_or(Error::UnknownSurgeCard)?;
+        if !card.enabled {
+            return Err(Error::SurgeCardDisabled);
+        }
+        env.storage()
+            .persistent()
+            .extend_ttl(&card_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+
+        // Stake-priced surges are protocol revenue; flat-priced ones stay in the
+        // collected move pool like regular moves.
+        let stake_priced = m.surge_price_bps > 0 && m.stake_amount_stroops > 0;
+        let base_cost = if stake_priced {
+            Self::calc_fee(m.stake_amount_stroops, m.surge_price_bps)
+        } else {
+            MOVE_COST_STROOPS
+        };
+        let surge_cost = base_cost * card.cost_multiplier as i128;
 
+        // Transfer the card price from player → this contract via SAC
         let xlm_addr: Address = env
             .storage()
             .instance()
             .get(&DataKey::XlmToken)
             .expect("XLM token not set");
         let xlm = token::Client::new(&env, &xlm_addr);
-        xlm.transfer(&player, &env.current_contract_address(), &required);
+        xlm.transfer(&player, &env.current_contract_address(), &surge_cost);
 
-        if is_p1 {
-            m.player1_stake_paid = true;
+        env.storage().temporary().set(&round_key, &card_code);
+        env.storage()
+            .temporary()
+            .extend_ttl(&round_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+
+        if is_p1 {
+            m.player1_surges += 1;
         } else {
-            m.player2_stake_paid = true;
+            m.player2_surges += 1;
+        }
+
+        // Track payment collected by contract
+        m.total_xlm_collected += surge_cost;
+        if stake_priced {
+            let mut accrued: i128 = env
+                .storage()
+                .instance()
+                .get(&DataKey::FeeAccrued)
+                .unwrap_or(0_i128);
+            accrued += surge_cost;
+            env.storage().instance().set(&DataKey::FeeAccrued, &accrued);
+            Self::update_stats(&env, |stats| stats.total_fees_stroops += surge_cost);
+            m.fee_accrued_stroops += surge_cost;
         }
 
         env.storage().temporary().set(&key, &m);
@@ -961,13 +1652,25 @@ impl VeilstarBrawlContract {
             .temporary()
             .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
 
+        // Emit event for indexers / explorers
+        PowerSurgePlayed {
+            session_id,
+            round,
+            player,
+            card_code,
+        }
+        .publish(&env);
+
         Ok(())
     }
 
-    /// Expire stake deposit window and cancel the match.
-    /// - If both deposits are missing: cancel without transfers.
-    /// - If exactly one player deposited: refund full deposited amount (stake + fee) to that player.
-    pub fn expire_stake(env: Env, session_id: u32) -> Result<(), Error> {
+    /// End a game and report to Game Hub.
+    /// Only admin can finalise a game result.
+    pub fn end_game(
+        env: Env,
+        session_id: u32,
+        player1_won: bool,
+    ) -> Result<(), Error> {
         let admin: Address = env
             .storage()
             .instance()
@@ -975,8 +1678,94 @@ impl VeilstarBrawlContract {
             .expect("Admin not set");
         admin.require_auth();
 
+        Self::finalize_match(&env, session_id, player1_won, true)
+    }
+
+    /// Record a player's view of the match result. Each player authorizes
+    /// their own attestation and may replace it until the match is finalized.
+    pub fn submit_result_attestation(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        player1_won: bool,
+    ) -> Result<(), Error> {
+        player.require_auth();
+
+        let m: Match = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Match(session_id))
+            .ok_or(Error::MatchNotFound)?;
+
+        if m.winner.is_some() {
+            return Err(Error::MatchAlreadyEnded);
+        }
+
+        if m.is_cancelled {
+            return Err(Error::MatchCancelled);
+        }
+
+        let is_p1 = player == m.player1;
+        if !is_p1 && player != m.player2 {
+            return Err(Error::NotPlayer);
+        }
+
+        let attestation_key = DataKey::ResultAttestation(session_id, is_p1);
+        env.storage().temporary().set(&attestation_key, &player1_won);
+        env.storage()
+            .temporary()
+            .extend_ttl(&attestation_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+
+        ResultAttested {
+            session_id,
+            player,
+            player1_won,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Finalize a match both players attested to the same result.
+    /// Callable by anyone; bypasses the admin and ZK outcome requirements.
+    pub fn finalize_attested_result(env: Env, session_id: u32) -> Result<(), Error> {
+        let player1_attestation: Option<bool> = env
+            .storage()
+            .temporary()
+            .get(&DataKey::ResultAttestation(session_id, true));
+        let player2_attestation: Option<bool> = env
+            .storage()
+            .temporary()
+            .get(&DataKey::ResultAttestation(session_id, false));
+
+        let (Some(player1_says), Some(player2_says)) = (player1_attestation, player2_attestation)
+        else {
+            return Err(Error::AttestationMissing);
+        };
+
+        if player1_says != player2_says {
+            return Err(Error::AttestationMismatch);
+        }
+
+        Self::finalize_match(&env, session_id, player1_says, false)
+    }
+
+    pub fn submit_zk_match_outcome(
+        env: Env,
+        session_id: u32,
+        winner: Address,
+        vk_id: BytesN<32>,
+        proof: Bytes,
+        public_inputs: Vec<BytesN<32>>,
+    ) -> Result<(), Error> {
+        // Current Groth16 round-plan circuit exposes exactly one public input: `commitment`.
+        // Keep this strict so a caller cannot satisfy the verifier with a different statement.
+        if proof.len() != 256 || public_inputs.len() != 1 {
+            return Err(Error::ZkProofInvalid);
+        }
+
         let key = DataKey::Match(session_id);
-        let mut m: Match = env
+        let m: Match = env
             .storage()
             .temporary()
             .get(&key)
@@ -990,62 +1779,72 @@ impl VeilstarBrawlContract {
             return Err(Error::MatchCancelled);
         }
 
-        if m.stake_amount_stroops <= 0 {
-            return Err(Error::StakeNotConfigured);
+        let zero = BytesN::from_array(&env, &[0u8; 32]);
+        let configured_vk_id: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ZkVerifierVkId)
+            .unwrap_or(zero.clone());
+
+        if configured_vk_id == zero || vk_id != configured_vk_id {
+            return Err(Error::ZkProofInvalid);
         }
 
-        if m.stake_deadline_ts == 0 || env.ledger().timestamp() < m.stake_deadline_ts {
-            return Err(Error::DeadlineNotReached);
+        if winner != m.player1 && winner != m.player2 {
+            return Err(Error::InvalidWinnerClaim);
         }
 
-        if m.player1_stake_paid ^ m.player2_stake_paid {
-            let xlm_addr: Address = env
-                .storage()
-                .instance()
-                .get(&DataKey::XlmToken)
-                .expect("XLM token not set");
-            let xlm = token::Client::new(&env, &xlm_addr);
+        let verifier_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::ZkVerifierContractAddress)
+            .ok_or(Error::ZkVerifierNotConfigured)?;
 
-            let refund_fee = Self::calc_fee(m.stake_amount_stroops, m.stake_fee_bps);
-            let refund_amount = m.stake_amount_stroops + refund_fee;
-            let refund_to = if m.player1_stake_paid {
-                m.player1.clone()
-            } else {
-                m.player2.clone()
-            };
+        let verifier = ZkVerifierContractClient::new(&env, &verifier_contract);
+        let verified = verifier.verify_round_proof(&vk_id, &proof, &public_inputs);
+        if !verified {
+            return Err(Error::ZkProofInvalid);
+        }
 
-            xlm.transfer(&env.current_contract_address(), &refund_to, &refund_amount);
+        let outcome_key = DataKey::ZkMatchOutcome(session_id);
+        if env.storage().temporary().has(&outcome_key) {
+            return Err(Error::ZkVerificationAlreadySubmitted);
         }
 
-        m.player1_stake_paid = false;
-        m.player2_stake_paid = false;
-        m.is_cancelled = true;
+        let record = ZkMatchOutcomeRecord {
+            verifier_contract: verifier_contract.clone(),
+            winner,
+            vk_id,
+        };
 
-        env.storage().temporary().set(&key, &m);
+        env.storage().temporary().set(&outcome_key, &record);
         env.storage()
             .temporary()
-            .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+            .extend_ttl(&outcome_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
 
-        let hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub not set");
-        let hub = GameHubClient::new(&env, &hub_addr);
-        hub.end_game(&session_id, &false);
+        OutcomeProven {
+            session_id,
+            verifier_contract,
+        }
+        .publish(&env);
 
         Ok(())
     }
 
-    /// Cancel an active match and refund any paid stakes.
-    /// Intended for abandonment/disconnect cancellation.
-    pub fn cancel_match(env: Env, session_id: u32) -> Result<(), Error> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
+    pub fn submit_zk_commit(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        round: u32,
+        turn: u32,
+        commitment: BytesN<32>,
+        nonce: u32,
+    ) -> Result<(), Error> {
+        player.require_auth();
+
+        if round == 0 || turn == 0 {
+            return Err(Error::InvalidZkCommitment);
+        }
 
         let key = DataKey::Match(session_id);
         let mut m: Match = env
@@ -1062,270 +1861,2039 @@ impl VeilstarBrawlContract {
             return Err(Error::MatchCancelled);
         }
 
-        if m.stake_amount_stroops > 0 {
-            let xlm_addr: Address = env
-                .storage()
-                .instance()
-                .get(&DataKey::XlmToken)
-                .expect("XLM token not set");
-            let xlm = token::Client::new(&env, &xlm_addr);
+        let is_p1 = player == m.player1;
+        let is_p2 = player == m.player2;
+        if !is_p1 && !is_p2 {
+            return Err(Error::NotPlayer);
+        }
 
-            let refund_fee = Self::calc_fee(m.stake_amount_stroops, m.stake_fee_bps);
-            let refund_amount = m.stake_amount_stroops + refund_fee;
+        Self::consume_action_nonce(&env, session_id, &player, nonce)?;
 
-            if m.player1_stake_paid {
-                xlm.transfer(&env.current_contract_address(), &m.player1, &refund_amount);
-            }
-            if m.player2_stake_paid {
-                xlm.transfer(&env.current_contract_address(), &m.player2, &refund_amount);
+        let match_salt: BytesN<32> = env
+            .storage()
+            .temporary()
+            .get(&DataKey::MatchSalt(session_id))
+            .ok_or(Error::MatchNotFound)?;
+
+        let zk_key = DataKey::ZkCommit(session_id, match_salt, round, turn, is_p1);
+        let had_existing_commit = env.storage().temporary().has(&zk_key);
+        env.storage().temporary().set(&zk_key, &commitment);
+        env.storage()
+            .temporary()
+            .extend_ttl(&zk_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+
+        if !had_existing_commit {
+            if is_p1 {
+                m.player1_zk_commits += 1;
+            } else if is_p2 {
+                m.player2_zk_commits += 1;
             }
         }
 
-        m.player1_stake_paid = false;
-        m.player2_stake_paid = false;
-        m.is_cancelled = true;
-
         env.storage().temporary().set(&key, &m);
         env.storage()
             .temporary()
             .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
 
-        let hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub not set");
-        let hub = GameHubClient::new(&env, &hub_addr);
-        hub.end_game(&session_id, &false);
+        MoveCommitted {
+            session_id,
+            round,
+            turn,
+            player,
+            commitment,
+        }
+        .publish(&env);
 
         Ok(())
     }
 
-    // ======================================================================
-    // Treasury sweep
-    // ======================================================================
+    pub fn submit_zk_verification(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        round: u32,
+        turn: u32,
+        commitment: BytesN<32>,
+        vk_id: BytesN<32>,
+        proof: Bytes,
+        public_inputs: Vec<BytesN<32>>,
+    ) -> Result<(), Error> {
+        if round == 0 || turn == 0 {
+            return Err(Error::InvalidZkCommitment);
+        }
 
-    /// Transfer accrued protocol fees to treasury wallet at most once every 24 hours.
-    pub fn sweep_treasury(env: Env) -> Result<i128, Error> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
+        if proof.len() == 0 {
+            return Err(Error::ZkProofInvalid);
+        }
 
-        let now_ts = env.ledger().timestamp();
-        let last_sweep: u64 = env
-            .storage()
+        let key = DataKey::Match(session_id);
+        let mut m: Match = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::MatchNotFound)?;
+
+        if m.winner.is_some() {
+            return Err(Error::MatchAlreadyEnded);
+        }
+
+        if m.is_cancelled {
+            return Err(Error::MatchCancelled);
+        }
+
+        let is_p1 = player == m.player1;
+        let is_p2 = player == m.player2;
+        if !is_p1 && !is_p2 {
+            return Err(Error::NotPlayer);
+        }
+
+        let match_salt: BytesN<32> = env
+            .storage()
+            .temporary()
+            .get(&DataKey::MatchSalt(session_id))
+            .ok_or(Error::MatchNotFound)?;
+
+        let commit_key = DataKey::ZkCommit(session_id, match_salt.clone(), round, turn, is_p1);
+        let stored_commitment: BytesN<32> = env
+            .storage()
+            .temporary()
+            .get(&commit_key)
+            .ok_or(Error::ZkCommitNotFound)?;
+
+        if stored_commitment != commitment {
+            return Err(Error::InvalidZkCommitment);
+        }
+
+        let verify_key = DataKey::ZkVerified(session_id, match_salt, round, turn, is_p1);
+        let had_existing_verification = env.storage().temporary().has(&verify_key);
+
+        let configured_vk_id: BytesN<32> = env
+            .storage()
             .instance()
-            .get(&DataKey::LastSweepTs)
-            .unwrap_or(0_u64);
+            .get(&DataKey::ZkVerifierVkId)
+            .unwrap_or(BytesN::from_array(&env, &[0u8; 32]));
 
-        if last_sweep > 0 && now_ts.saturating_sub(last_sweep) < FEE_SWEEP_INTERVAL_SECONDS {
-            return Err(Error::SweepTooEarly);
+        if configured_vk_id != BytesN::from_array(&env, &[0u8; 32]) && vk_id != configured_vk_id {
+            return Err(Error::ZkProofInvalid);
+        }
+
+        // Current Groth16 round-plan circuit exposes exactly one public input: `commitment`.
+        // Enforce that it matches the submitted commitment, otherwise a proof for some other
+        // commitment could be replayed to satisfy the ZK gate.
+        if proof.len() != 256 || public_inputs.len() != 1 {
+            return Err(Error::ZkProofInvalid);
+        }
+
+        let public_commitment = public_inputs.get(0).unwrap();
+        if public_commitment != commitment {
+            return Err(Error::ZkProofInvalid);
+        }
+
+        let verifier_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::ZkVerifierContractAddress)
+            .ok_or(Error::ZkVerifierNotConfigured)?;
+
+        let verifier = ZkVerifierContractClient::new(&env, &verifier_contract);
+        let verified = verifier.verify_round_proof(&vk_id, &proof, &public_inputs);
+        if !verified {
+            return Err(Error::ZkProofInvalid);
+        }
+
+        let record = ZkVerificationRecord {
+            verifier_contract: verifier_contract.clone(),
+            commitment,
+            vk_id,
+        };
+
+        env.storage().temporary().set(&verify_key, &record);
+        env.storage()
+            .temporary()
+            .extend_ttl(&verify_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+
+        if !had_existing_verification {
+            if is_p1 {
+                m.player1_zk_verified += 1;
+            } else if is_p2 {
+                m.player2_zk_verified += 1;
+            }
+        }
+
+        env.storage().temporary().set(&key, &m);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+
+        MoveProofVerified {
+            session_id,
+            round,
+            turn,
+            player,
+            verifier_contract,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Withdraw the payout recorded for the winner of a settled stake match.
+    pub fn claim_winnings(env: Env, session_id: u32) -> Result<i128, Error> {
+        let payout_key = DataKey::PendingPayout(session_id);
+        let payout: PendingPayout = env
+            .storage()
+            .persistent()
+            .get(&payout_key)
+            .ok_or(Error::NothingToClaim)?;
+
+        payout.winner.require_auth();
+
+        // Remove before transferring so a re-entrant claim finds nothing.
+        env.storage().persistent().remove(&payout_key);
+        Self::release_liability(&env, payout.amount_stroops);
+
+        let xlm_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::XlmToken)
+            .expect("XLM token not set");
+        let xlm = token::Client::new(&env, &xlm_addr);
+        xlm.transfer(&env.current_contract_address(), &payout.winner, &payout.amount_stroops);
+
+        WinningsClaimed {
+            session_id,
+            winner: payout.winner,
+            amount_stroops: payout.amount_stroops,
+        }
+        .publish(&env);
+
+        Ok(payout.amount_stroops)
+    }
+
+    /// Configure stake for a session before deposits begin.
+    /// Stake amount is the base wager (e.g. 1 XLM). Each player deposits stake + 0.1% fee.
+    /// A non-zero `surge_price_bps` prices power surges as that share of the stake
+    /// instead of the flat 0.0001 XLM, with the proceeds going to protocol fees.
+    pub fn set_match_stake(
+        env: Env,
+        session_id: u32,
+        stake_amount_stroops: i128,
+        surge_price_bps: u32,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        if stake_amount_stroops <= 0 || surge_price_bps > MAX_SURGE_PRICE_BPS {
+            return Err(Error::InvalidStake);
+        }
+
+        // Fast-path: match already exists.
+        let key = DataKey::Match(session_id);
+        if let Some(mut m) = env.storage().temporary().get::<_, Match>(&key) {
+            if m.stake_amount_stroops > 0 {
+                if m.stake_amount_stroops != stake_amount_stroops
+                    || m.surge_price_bps != surge_price_bps
+                {
+                    return Err(Error::InvalidStake);
+                }
+
+                env.storage().temporary().set(&key, &m);
+                env.storage()
+                    .temporary()
+                    .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+                return Ok(());
+            }
+
+            Self::reserve_liability(&env, stake_amount_stroops * 2)?;
+            m.stake_amount_stroops = stake_amount_stroops;
+            m.stake_fee_bps = STAKE_FEE_BPS;
+            m.surge_price_bps = surge_price_bps;
+            m.stake_deadline_ts = env
+                .ledger()
+                .timestamp()
+                .saturating_add(STAKE_DEPOSIT_WINDOW_SECONDS);
+
+            env.storage().temporary().set(&key, &m);
+            env.storage()
+                .temporary()
+                .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+
+            // Clear any pending config for this session to avoid stale state.
+            let pending_key = DataKey::PendingStake(session_id);
+            if env.storage().temporary().has(&pending_key) {
+                env.storage().temporary().remove(&pending_key);
+            }
+
+            Self::create_pool_if_enabled(&env, session_id, &m);
+
+            return Ok(());
+        }
+
+        // Match not created yet — store a pending stake config so `start_game` can apply it.
+        let pending_key = DataKey::PendingStake(session_id);
+        let config = StakeConfig {
+            stake_amount_stroops,
+            surge_price_bps,
+        };
+        if let Some(existing) = env.storage().temporary().get::<_, StakeConfig>(&pending_key) {
+            if existing != config {
+                return Err(Error::InvalidStake);
+            }
+
+            env.storage().temporary().set(&pending_key, &existing);
+            env.storage()
+                .temporary()
+                .extend_ttl(&pending_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+            return Ok(());
+        }
+
+        // Liability is only reserved once the stake is attached to a live match,
+        // but refuse configs the contract could not cover right now.
+        if Self::available_liquidity(&env) < stake_amount_stroops * 2 {
+            return Err(Error::InsufficientBalance);
+        }
+
+        env.storage().temporary().set(&pending_key, &config);
+        env.storage()
+            .temporary()
+            .extend_ttl(&pending_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+
+        Ok(())
+    }
+
+    /// Player deposit for stake-enabled matches.
+    /// Required amount is stake + 0.1% fee, transferred to this contract.
+    pub fn deposit_stake(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
+
+        let key = DataKey::Match(session_id);
+        let mut m: Match = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::MatchNotFound)?;
+
+        if m.stake_amount_stroops <= 0 {
+            return Err(Error::StakeNotConfigured);
+        }
+
+        if m.is_cancelled {
+            return Err(Error::MatchCancelled);
+        }
+
+        if m.stake_deadline_ts > 0 && env.ledger().timestamp() > m.stake_deadline_ts {
+            return Err(Error::StakeDepositExpired);
+        }
+
+        let is_p1 = player == m.player1;
+        let is_p2 = player == m.player2;
+        if !is_p1 && !is_p2 {
+            return Err(Error::NotPlayer);
+        }
+
+        if (is_p1 && m.player1_stake_paid) || (is_p2 && m.player2_stake_paid) {
+            env.storage().temporary().set(&key, &m);
+            env.storage()
+                .temporary()
+                .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+            return Ok(());
+        }
+
+        // This match's 2x payout is already part of outstanding liabilities;
+        // make sure a sweep or other settlement hasn't left it uncovered.
+        if Self::available_liquidity(&env) < 0 {
+            return Err(Error::InsufficientBalance);
         }
 
+        let fee = Self::calc_fee(m.stake_amount_stroops, m.stake_fee_bps);
+        let required = m.stake_amount_stroops + fee;
+
         let xlm_addr: Address = env
             .storage()
             .instance()
             .get(&DataKey::XlmToken)
             .expect("XLM token not set");
         let xlm = token::Client::new(&env, &xlm_addr);
+        xlm.transfer(&player, &env.current_contract_address(), &required);
+
+        if is_p1 {
+            m.player1_stake_paid = true;
+        } else {
+            m.player2_stake_paid = true;
+        }
+        Self::update_stats(&env, |stats| stats.total_staked_stroops += m.stake_amount_stroops);
+
+        env.storage().temporary().set(&key, &m);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+
+        Ok(())
+    }
+
+    /// Expire stake deposit window and cancel the match.
+    /// - If both deposits are missing: cancel without transfers.
+    /// - If exactly one player deposited: refund full deposited amount (stake + fee) to that player.
+    pub fn expire_stake(env: Env, session_id: u32) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        let key = DataKey::Match(session_id);
+        let mut m: Match = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::MatchNotFound)?;
+
+        if m.winner.is_some() {
+            return Err(Error::MatchAlreadyEnded);
+        }
+
+        if m.is_cancelled {
+            return Err(Error::MatchCancelled);
+        }
+
+        if m.stake_amount_stroops <= 0 {
+            return Err(Error::StakeNotConfigured);
+        }
+
+        if m.stake_deadline_ts == 0 || env.ledger().timestamp() < m.stake_deadline_ts {
+            return Err(Error::DeadlineNotReached);
+        }
+
+        if m.player1_stake_paid ^ m.player2_stake_paid {
+            let xlm_addr: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::XlmToken)
+                .expect("XLM token not set");
+            let xlm = token::Client::new(&env, &xlm_addr);
+
+            let refund_fee = Self::calc_fee(m.stake_amount_stroops, m.stake_fee_bps);
+            let refund_amount = m.stake_amount_stroops + refund_fee;
+            let refund_to = if m.player1_stake_paid {
+                m.player1.clone()
+            } else {
+                m.player2.clone()
+            };
+
+            xlm.transfer(&env.current_contract_address(), &refund_to, &refund_amount);
+        }
+
+        if m.stake_amount_stroops > 0 {
+            Self::release_liability(&env, m.stake_amount_stroops * 2);
+        }
+
+        m.player1_stake_paid = false;
+        m.player2_stake_paid = false;
+        m.is_cancelled = true;
+
+        env.storage().temporary().set(&key, &m);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+        Self::mark_session_closed(&env, session_id);
+
+        let hub_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GameHubAddress)
+            .expect("GameHub not set");
+        let hub = GameHubClient::new(&env, &hub_addr);
+        hub.end_game(&session_id, &false);
+
+        Self::settle_match_pool(&env, session_id, None);
+
+        Ok(())
+    }
+
+    /// Cancel an active match and refund any paid stakes.
+    /// Intended for abandonment/disconnect cancellation.
+    pub fn cancel_match(env: Env, session_id: u32) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        let key = DataKey::Match(session_id);
+        let mut m: Match = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::MatchNotFound)?;
+
+        if m.winner.is_some() {
+            return Err(Error::MatchAlreadyEnded);
+        }
+
+        if m.is_cancelled {
+            return Err(Error::MatchCancelled);
+        }
+
+        if m.stake_amount_stroops > 0 {
+            let xlm_addr: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::XlmToken)
+                .expect("XLM token not set");
+            let xlm = token::Client::new(&env, &xlm_addr);
+
+            let refund_fee = Self::calc_fee(m.stake_amount_stroops, m.stake_fee_bps);
+            let refund_amount = m.stake_amount_stroops + refund_fee;
+
+            if m.player1_stake_paid {
+                xlm.transfer(&env.current_contract_address(), &m.player1, &refund_amount);
+            }
+            if m.player2_stake_paid {
+                xlm.transfer(&env.current_contract_address(), &m.player2, &refund_amount);
+            }
+        }
+
+        if m.stake_amount_stroops > 0 {
+            Self::release_liability(&env, m.stake_amount_stroops * 2);
+        }
+
+        m.player1_stake_paid = false;
+        m.player2_stake_paid = false;
+        m.is_cancelled = true;
+
+        env.storage().temporary().set(&key, &m);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+        Self::mark_session_closed(&env, session_id);
+
+        let hub_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GameHubAddress)
+            .expect("GameHub not set");
+        let hub = GameHubClient::new(&env, &hub_addr);
+        hub.end_game(&session_id, &false);
+
+        Self::settle_match_pool(&env, session_id, None);
+
+        Ok(())
+    }
+
+    // ======================================================================
+    // Treasury sweep
+    // ======================================================================
+
+    /// Transfer accrued protocol fees to treasury wallet at most once every 24 hours.
+    pub fn sweep_treasury(env: Env) -> Result<i128, Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        let now_ts = env.ledger().timestamp();
+        let last_sweep: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::LastSweepTs)
+            .unwrap_or(0_u64);
+
+        if last_sweep > 0 && now_ts.saturating_sub(last_sweep) < FEE_SWEEP_INTERVAL_SECONDS {
+            return Err(Error::SweepTooEarly);
+        }
+
+        let xlm_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::XlmToken)
+            .expect("XLM token not set");
+        let xlm = token::Client::new(&env, &xlm_addr);
+
+        let accrued_fee: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::FeeAccrued)
+            .unwrap_or(0_i128);
+
+        if accrued_fee <= 0 {
+            return Err(Error::NothingToSweep);
+        }
+
+        // Never sweep funds owed to match winners.
+        let liabilities = Self::get_outstanding_liabilities(env.clone());
+        let balance = xlm.balance(&env.current_contract_address()) - liabilities;
+        let sweepable = if balance > RESERVE_STROOPS {
+            let above_reserve = balance - RESERVE_STROOPS;
+            if above_reserve < accrued_fee {
+                above_reserve
+            } else {
+                accrued_fee
+            }
+        } else {
+            0
+        };
+
+        if sweepable <= 0 {
+            return Err(Error::NothingToSweep);
+        }
+
+        let treasury: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TreasuryAddress)
+            .expect("Treasury not set");
+
+        xlm.transfer(&env.current_contract_address(), &treasury, &sweepable);
+
+        let remaining_fee = accrued_fee - sweepable;
+        env.storage().instance().set(&DataKey::FeeAccrued, &remaining_fee);
+        env.storage().instance().set(&DataKey::LastSweepTs, &now_ts);
+
+        Ok(sweepable)
+    }
+
+    // ======================================================================
+    // Read helpers
+    // ======================================================================
+
+    /// Get match state.
+    pub fn get_match(env: Env, session_id: u32) -> Result<Match, Error> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::Match(session_id))
+            .ok_or(Error::MatchNotFound)
+    }
+
+    pub fn get_pending_payout(env: Env, session_id: u32) -> Result<PendingPayout, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingPayout(session_id))
+            .ok_or(Error::NothingToClaim)
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set")
+    }
+
+    pub fn get_hub(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::GameHubAddress)
+            .expect("GameHub not set")
+    }
+
+    pub fn get_treasury(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::TreasuryAddress)
+            .expect("Treasury not set")
+    }
+
+    pub fn get_fee_accrued(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::FeeAccrued)
+            .unwrap_or(0_i128)
+    }
+
+    /// Total 2x payouts the contract has committed to but not yet paid or released.
+    pub fn get_outstanding_liabilities(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::OutstandingLiabilities)
+            .unwrap_or(0_i128)
+    }
+
+    pub fn get_last_sweep_ts(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::LastSweepTs)
+            .unwrap_or(0_u64)
+    }
+
+    pub fn get_trophy_nft(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::TrophyNftAddress)
+    }
+
+    /// Quote the amount (stake + fee), token, and deadline for a stake deposit.
+    pub fn quote_deposit(env: Env, session_id: u32) -> Result<DepositQuote, Error> {
+        let m: Match = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Match(session_id))
+            .ok_or(Error::MatchNotFound)?;
+
+        if m.stake_amount_stroops <= 0 {
+            return Err(Error::StakeNotConfigured);
+        }
+
+        if m.is_cancelled {
+            return Err(Error::MatchCancelled);
+        }
+
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::XlmToken)
+            .expect("XLM token not set");
+        let fee_stroops = Self::calc_fee(m.stake_amount_stroops, m.stake_fee_bps);
+
+        Ok(DepositQuote {
+            token,
+            stake_amount_stroops: m.stake_amount_stroops,
+            fee_stroops,
+            amount_stroops: m.stake_amount_stroops + fee_stroops,
+            deadline_ts: m.stake_deadline_ts,
+        })
+    }
+
+    /// Contract-wide totals: matches started/settled, XLM staked, fees, moves.
+    pub fn get_stats(env: Env) -> ContractStats {
+        env.storage()
+            .instance()
+            .get(&DataKey::Stats)
+            .unwrap_or_default()
+    }
+
+    /// Schema version of a stored match; untagged entries predate versioning.
+    pub fn get_match_version(env: Env, session_id: u32) -> Result<u32, Error> {
+        if !env.storage().temporary().has(&DataKey::Match(session_id)) {
+            return Err(Error::MatchNotFound);
+        }
+
+        Ok(env
+            .storage()
+            .temporary()
+            .get(&DataKey::MatchVersion(session_id))
+            .unwrap_or(1_u32))
+    }
+
+    pub fn get_move_fee_policy(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MoveFeeWinnerBps)
+            .unwrap_or(0_u32)
+    }
+
+    pub fn get_betting_pool(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::BettingPoolAddress)
+    }
+
+    /// Pool id opened on the betting contract for a session, if any.
+    pub fn get_match_pool(env: Env, session_id: u32) -> Option<u32> {
+        env.storage().temporary().get(&DataKey::MatchPool(session_id))
+    }
+
+    pub fn get_trophy_min_stake(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TrophyMinStake)
+            .unwrap_or(0_i128)
+    }
+
+    pub fn get_zk_gate_required(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::ZkGateRequired)
+            .unwrap_or(true)
+    }
+
+    pub fn get_zk_verifier_contract(env: Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::ZkVerifierContractAddress)
+            .ok_or(Error::ZkVerifierNotConfigured)
+    }
+
+    pub fn get_zk_verifier_vk_id(env: Env) -> BytesN<32> {
+        env.storage()
+            .instance()
+            .get(&DataKey::ZkVerifierVkId)
+            .unwrap_or(BytesN::from_array(&env, &[0u8; 32]))
+    }
+
+    pub fn get_auto_session_ids(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::AutoSessionIds)
+            .unwrap_or(false)
+    }
+
+    /// Next id the contract will try to assign in auto session id mode.
+    pub fn get_next_session_id(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::NextSessionId)
+            .unwrap_or(1_u32)
+    }
+
+    /// Whether a session id has been ended or cancelled and can never be reused.
+    pub fn is_session_closed(env: Env, session_id: u32) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::SessionClosed(session_id))
+    }
+
+    /// Card picked by a player in a given round, if any.
+    pub fn get_round_surge(
+        env: Env,
+        session_id: u32,
+        round: u32,
+        player: Address,
+    ) -> Result<u32, Error> {
+        let m: Match = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Match(session_id))
+            .ok_or(Error::MatchNotFound)?;
+
+        let is_p1 = player == m.player1;
+        if !is_p1 && player != m.player2 {
+            return Err(Error::NotPlayer);
+        }
+
+        env.storage()
+            .temporary()
+            .get(&DataKey::RoundSurge(session_id, round, is_p1))
+            .ok_or(Error::UnknownSurgeCard)
+    }
+
+    pub fn get_open_challenge(env: Env, challenge_id: u32) -> Result<OpenChallenge, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OpenChallenge(challenge_id))
+            .ok_or(Error::ChallengeNotFound)
+    }
+
+    /// Open challenges in posting order.
+    pub fn get_open_challenges(env: Env) -> Vec<OpenChallenge> {
+        let ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OpenChallengeIds)
+            .unwrap_or(Vec::new(&env));
+
+        let mut challenges = Vec::new(&env);
+        for challenge_id in ids.iter() {
+            if let Some(challenge) = env
+                .storage()
+                .persistent()
+                .get::<_, OpenChallenge>(&DataKey::OpenChallenge(challenge_id))
+            {
+                challenges.push_back(challenge);
+            }
+        }
+        challenges
+    }
+
+    /// Achievements a player has unlocked, in unlock order.
+    pub fn get_achievements(env: Env, player: Address) -> Vec<Achievement> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Achievements(player))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Current consecutive-win streak across all seasons.
+    pub fn get_win_streak(env: Env, player: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::WinStreak(player))
+            .unwrap_or(0_u32)
+    }
+
+    pub fn get_current_season(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CurrentSeason)
+            .unwrap_or(0_u32)
+    }
+
+    /// Player record for a season. Players without matches get a fresh record.
+    pub fn get_player_stats(env: Env, season_id: u32, player: Address) -> PlayerStats {
+        Self::load_player_stats(&env, season_id, &player)
+    }
+
+    /// Top players of a season by rating, highest first.
+    pub fn get_leaderboard(env: Env, season_id: u32) -> Vec<LeaderboardEntry> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Leaderboard(season_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_character(env: Env, character_id: u32) -> Result<RosterCharacter, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RosterCharacter(character_id))
+            .ok_or(Error::UnknownCharacter)
+    }
+
+    /// Every roster entry, including disabled characters.
+    pub fn get_roster(env: Env) -> Vec<RosterCharacter> {
+        let ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RosterIds)
+            .unwrap_or(Vec::new(&env));
+
+        let mut roster = Vec::new(&env);
+        for character_id in ids.iter() {
+            if let Some(character) = env
+                .storage()
+                .persistent()
+                .get::<_, RosterCharacter>(&DataKey::RosterCharacter(character_id))
+            {
+                roster.push_back(character);
+            }
+        }
+        roster
+    }
+
+    pub fn get_surge_card(env: Env, card_code: u32) -> Result<SurgeCard, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SurgeCard(card_code))
+            .ok_or(Error::UnknownSurgeCard)
+    }
+
+    pub fn get_move_limits(env: Env) -> MoveLimits {
+        env.storage()
+            .instance()
+            .get(&DataKey::MoveLimits)
+            .unwrap_or(MoveLimits {
+                max_moves_per_match: DEFAULT_MAX_MOVES_PER_MATCH,
+                max_surges_per_match: DEFAULT_MAX_SURGES_PER_MATCH,
+                max_moves_per_turn: DEFAULT_MAX_MOVES_PER_TURN,
+            })
+    }
+
+    pub fn get_zk_match_outcome(env: Env, session_id: u32) -> Result<ZkMatchOutcomeRecord, Error> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::ZkMatchOutcome(session_id))
+            .ok_or(Error::ZkMatchOutcomeRequired)
+    }
+
+    // ======================================================================
+    // Admin setters
+    // ======================================================================
+
+    pub fn set_admin(env: Env, new_admin: Address) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+    }
+
+    pub fn set_hub(env: Env, new_hub: Address) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::GameHubAddress, &new_hub);
+    }
+
+    pub fn set_treasury(env: Env, new_treasury: Address) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::TreasuryAddress, &new_treasury);
+    }
+
+    /// Configure the trophy NFT hook. Settled matches with a stake of at least
+    /// `min_stake_stroops` mint a trophy to the winner. Pass `None` to disable.
+    pub fn set_trophy_nft(
+        env: Env,
+        nft_contract: Option<Address>,
+        min_stake_stroops: i128,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        if min_stake_stroops <= 0 {
+            return Err(Error::InvalidStake);
+        }
+
+        match nft_contract {
+            Some(nft_contract) => env
+                .storage()
+                .instance()
+                .set(&DataKey::TrophyNftAddress, &nft_contract),
+            None => env.storage().instance().remove(&DataKey::TrophyNftAddress),
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::TrophyMinStake, &min_stake_stroops);
+
+        Ok(())
+    }
+
+    /// Configure automatic betting pools. Each new match (or, with `staked_only`,
+    /// each match once its stake is attached) opens a pool on the zk-betting
+    /// contract that accepts bets for `betting_window_seconds`. Pass `None` to disable.
+    pub fn set_betting_pool(
+        env: Env,
+        betting_contract: Option<Address>,
+        staked_only: bool,
+        betting_window_seconds: u64,
+    ) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        match betting_contract {
+            Some(betting_contract) => env
+                .storage()
+                .instance()
+                .set(&DataKey::BettingPoolAddress, &betting_contract),
+            None => env.storage().instance().remove(&DataKey::BettingPoolAddress),
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::BettingStakedOnly, &staked_only);
+        env.storage()
+            .instance()
+            .set(&DataKey::BettingWindowSeconds, &betting_window_seconds);
+    }
+
+    /// Set the share of collected move/surge fees (in bps) paid to the match
+    /// winner at `end_game`. The remainder accrues as protocol fees.
+    pub fn set_move_fee_policy(env: Env, winner_bps: u32) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        if winner_bps > 10_000 {
+            return Err(Error::InvalidFeePolicy);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::MoveFeeWinnerBps, &winner_bps);
+
+        Ok(())
+    }
+
+    pub fn set_zk_gate_required(env: Env, required: bool) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::ZkGateRequired, &required);
+    }
+
+    pub fn set_zk_verifier_contract(env: Env, verifier_contract: Address) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::ZkVerifierContractAddress, &verifier_contract);
+    }
+
+    pub fn set_zk_verifier_vk_id(env: Env, vk_id: BytesN<32>) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::ZkVerifierVkId, &vk_id);
+    }
+
+    /// Open a new season. Matches started from now on count towards it; stats of
+    /// earlier seasons stay queryable under their own season id.
+    pub fn start_season(env: Env, season_id: u32) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        let current: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::CurrentSeason)
+            .unwrap_or(0_u32);
+        if season_id <= current {
+            return Err(Error::InvalidSeason);
+        }
+
+        env.storage().instance().set(&DataKey::CurrentSeason, &season_id);
+        SeasonStarted { season_id }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Add or update a roster character. Balance patches bump `patch_version`
+    /// or disable the character outright.
+    pub fn set_character(
+        env: Env,
+        character_id: u32,
+        name_hash: BytesN<32>,
+        enabled: bool,
+        patch_version: u32,
+    ) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        let roster_key = DataKey::RosterCharacter(character_id);
+        if !env.storage().persistent().has(&roster_key) {
+            let mut ids: Vec<u32> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::RosterIds)
+                .unwrap_or(Vec::new(&env));
+            ids.push_back(character_id);
+            env.storage().persistent().set(&DataKey::RosterIds, &ids);
+        }
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKey::RosterIds, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+
+        let character = RosterCharacter {
+            character_id,
+            name_hash,
+            enabled,
+            patch_version,
+        };
+        env.storage().persistent().set(&roster_key, &character);
+        env.storage()
+            .persistent()
+            .extend_ttl(&roster_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+    }
+
+    /// Add or update a power surge card in the catalog. Disabling a card keeps
+    /// its entry so it can be re-enabled later.
+    pub fn set_surge_card(
+        env: Env,
+        card_code: u32,
+        cost_multiplier: u32,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        if cost_multiplier == 0 {
+            return Err(Error::InvalidSurgeCard);
+        }
+
+        let card_key = DataKey::SurgeCard(card_code);
+        let card = SurgeCard {
+            card_code,
+            cost_multiplier,
+            enabled,
+        };
+        env.storage().persistent().set(&card_key, &card);
+        env.storage()
+            .persistent()
+            .extend_ttl(&card_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+
+        Ok(())
+    }
+
+    /// Toggle contract-assigned session ids for `start_game`.
+    pub fn set_auto_session_ids(env: Env, enabled: bool) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::AutoSessionIds, &enabled);
+    }
+
+    /// Configure per-player anti-spam caps. All limits must be non-zero.
+    pub fn set_move_limits(
+        env: Env,
+        max_moves_per_match: u32,
+        max_surges_per_match: u32,
+        max_moves_per_turn: u32,
+    ) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+
+        if max_moves_per_match == 0 || max_surges_per_match == 0 || max_moves_per_turn == 0 {
+            return Err(Error::InvalidMoveLimits);
+        }
+
+        let limits = MoveLimits {
+            max_moves_per_match,
+            max_surges_per_match,
+            max_moves_per_turn,
+        };
+        env.storage().instance().set(&DataKey::MoveLimits, &limits);
+
+        Ok(())
+    }
+
+    /// Rewrite a stored match into the current `Match` layout. Run after an
+    /// upgrade that changes `Match` so live sessions keep decoding. Fields added
+    /// since the stored version take their defaults. Returns the schema version.
+    pub fn migrate_match(env: Env, session_id: u32) -> Result<u32, Error> {
+        let key = DataKey::Match(session_id);
+        if !env.storage().temporary().has(&key) {
+            return Err(Error::MatchNotFound);
+        }
+
+        let version_key = DataKey::MatchVersion(session_id);
+        let version: u32 = env
+            .storage()
+            .temporary()
+            .get(&version_key)
+            .unwrap_or(1_u32);
+        if version == MATCH_SCHEMA_VERSION {
+            return Ok(version);
+        }
+
+        let m = match version {
+            1 => {
+                let old: MatchV1 = env.storage().temporary().get(&key).unwrap();
+                let season_id: u32 = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::CurrentSeason)
+                    .unwrap_or(0_u32);
+                Match {
+                    player1: old.player1,
+                    player2: old.player2,
+                    player1_points: old.player1_points,
+                    player2_points: old.player2_points,
+                    player1_moves: old.player1_moves,
+                    player2_moves: old.player2_moves,
+                    player1_surges: 0,
+                    player2_surges: 0,
+                    player1_rounds_won: 0,
+                    player2_rounds_won: 0,
+                    total_xlm_collected: old.total_xlm_collected,
+                    stake_amount_stroops: old.stake_amount_stroops,
+                    stake_fee_bps: old.stake_fee_bps,
+                    surge_price_bps: 0,
+                    stake_deadline_ts: old.stake_deadline_ts,
+                    player1_stake_paid: old.player1_stake_paid,
+                    player2_stake_paid: old.player2_stake_paid,
+                    fee_accrued_stroops: old.fee_accrued_stroops,
+                    player1_zk_commits: old.player1_zk_commits,
+                    player2_zk_commits: old.player2_zk_commits,
+                    player1_zk_verified: old.player1_zk_verified,
+                    player2_zk_verified: old.player2_zk_verified,
+                    season_id,
+                    player1_character: None,
+                    player2_character: None,
+                    player1_loadout_hash: None,
+                    player2_loadout_hash: None,
+                    is_cancelled: old.is_cancelled,
+                    winner: old.winner,
+                }
+            }
+            _ => return Err(Error::UnsupportedMatchVersion),
+        };
+
+        env.storage().temporary().set(&key, &m);
+        env.storage()
+            .temporary()
+            .set(&version_key, &MATCH_SCHEMA_VERSION);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+        env.storage()
+            .temporary()
+            .extend_ttl(&version_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+
+        MatchMigrated {
+            session_id,
+            from_version: version,
+            to_version: MATCH_SCHEMA_VERSION,
+        }
+        .publish(&env);
+
+        Ok(MATCH_SCHEMA_VERSION)
+    }
+
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set");
+        admin.require_auth();
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    fn load_player_stats(env: &Env, season_id: u32, player: &Address) -> PlayerStats {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PlayerStats(season_id, player.clone()))
+            .unwrap_or(PlayerStats {
+                matches_played: 0,
+                wins: 0,
+                losses: 0,
+                rating: INITIAL_RATING,
+            })
+    }
+
+    fn save_player_stats(env: &Env, season_id: u32, player: &Address, stats: &PlayerStats) {
+        let stats_key = DataKey::PlayerStats(season_id, player.clone());
+        env.storage().persistent().set(&stats_key, stats);
+        env.storage()
+            .persistent()
+            .extend_ttl(&stats_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+    }
+
+    /// Update both players' season stats and the season leaderboard.
+    fn record_result(env: &Env, season_id: u32, winner: &Address, loser: &Address) {
+        let mut winner_stats = Self::load_player_stats(env, season_id, winner);
+        let mut loser_stats = Self::load_player_stats(env, season_id, loser);
+
+        // Linear approximation of the ELO expected score: an even match moves
+        // K/2 points, a 400+ point upset moves nearly K.
+        let diff = (loser_stats.rating - winner_stats.rating).clamp(-400, 400);
+        let delta = (RATING_K_FACTOR * (400 + diff) / 800).clamp(1, RATING_K_FACTOR - 1);
+
+        winner_stats.matches_played += 1;
+        winner_stats.wins += 1;
+        winner_stats.rating += delta;
+        loser_stats.matches_played += 1;
+        loser_stats.losses += 1;
+        loser_stats.rating -= delta;
+
+        Self::save_player_stats(env, season_id, winner, &winner_stats);
+        Self::save_player_stats(env, season_id, loser, &loser_stats);
+
+        let board_key = DataKey::Leaderboard(season_id);
+        let mut board: Vec<LeaderboardEntry> = env
+            .storage()
+            .persistent()
+            .get(&board_key)
+            .unwrap_or(Vec::new(env));
+        Self::upsert_leaderboard(&mut board, winner, winner_stats.rating);
+        Self::upsert_leaderboard(&mut board, loser, loser_stats.rating);
+        env.storage().persistent().set(&board_key, &board);
+        env.storage()
+            .persistent()
+            .extend_ttl(&board_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+    }
+
+    /// Settle a match for `player1_won`: record payouts and fees, update stats,
+    /// and report to the Game Hub. `enforce_zk_gate` is false for results both
+    /// players attested to.
+    fn finalize_match(
+        env: &Env,
+        session_id: u32,
+        player1_won: bool,
+        enforce_zk_gate: bool,
+    ) -> Result<(), Error> {
+        let key = DataKey::Match(session_id);
+        let mut m: Match = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::MatchNotFound)?;
+
+        if m.winner.is_some() {
+            return Err(Error::MatchAlreadyEnded);
+        }
+
+        if m.is_cancelled {
+            return Err(Error::MatchCancelled);
+        }
+
+        let zk_gate_required: bool = enforce_zk_gate
+            && env
+                .storage()
+                .instance()
+                .get(&DataKey::ZkGateRequired)
+                .unwrap_or(true);
+
+        if zk_gate_required && (m.player1_zk_verified == 0 || m.player2_zk_verified == 0) {
+            return Err(Error::ZkCommitRequired);
+        }
+
+        let winner = if zk_gate_required {
+            let outcome_key = DataKey::ZkMatchOutcome(session_id);
+            let outcome: ZkMatchOutcomeRecord = env
+                .storage()
+                .temporary()
+                .get(&outcome_key)
+                .ok_or(Error::ZkMatchOutcomeRequired)?;
+
+            let expected_player1_won = outcome.winner == m.player1;
+            if expected_player1_won != player1_won {
+                return Err(Error::InvalidWinnerClaim);
+            }
+
+            outcome.winner
+        } else if player1_won {
+            m.player1.clone()
+        } else {
+            m.player2.clone()
+        };
+
+        if m.stake_amount_stroops > 0 && (!m.player1_stake_paid || !m.player2_stake_paid) {
+            return Err(Error::StakeNotPaid);
+        }
+
+        // Move fees collected during the match, excluding stake-priced surges,
+        // which were already accrued as protocol fees when paid.
+        let move_pool = m.total_xlm_collected - m.fee_accrued_stroops;
+        let winner_bps: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MoveFeeWinnerBps)
+            .unwrap_or(0_u32);
+        // Cap the winner's share at free liquidity so settlement never fails
+        // on it; anything the contract cannot cover stays with the protocol.
+        let winner_share = (move_pool * winner_bps as i128 / 10_000)
+            .min(Self::available_liquidity(env).max(0));
+        let protocol_share = move_pool - winner_share;
+
+        // Winner can claim 2 * stake plus their share of move fees.
+        // Recording instead of pushing keeps settlement from failing on a bad transfer.
+        let mut payout_amount = winner_share;
+        let mut total_fee = protocol_share;
+
+        // If stake is configured, both players have deposited by now.
+        if m.stake_amount_stroops > 0 {
+            payout_amount += m.stake_amount_stroops * 2;
+
+            // Retain total fee from both sides in contract-level accrued fee bucket.
+            let per_player_fee = Self::calc_fee(m.stake_amount_stroops, m.stake_fee_bps);
+            total_fee += per_player_fee * 2;
+        }
+
+        if winner_share > 0 {
+            // The 2x stake liability was reserved when the stake was attached.
+            Self::reserve_liability(env, winner_share)?;
+        }
+
+        if payout_amount > 0 {
+            let payout = PendingPayout {
+                winner: winner.clone(),
+                amount_stroops: payout_amount,
+            };
+            let payout_key = DataKey::PendingPayout(session_id);
+            env.storage().persistent().set(&payout_key, &payout);
+            env.storage()
+                .persistent()
+                .extend_ttl(&payout_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+        }
+
+        if total_fee > 0 {
+            let mut accrued: i128 = env
+                .storage()
+                .instance()
+                .get(&DataKey::FeeAccrued)
+                .unwrap_or(0_i128);
+            accrued += total_fee;
+            env.storage().instance().set(&DataKey::FeeAccrued, &accrued);
+            m.fee_accrued_stroops += total_fee;
+        }
+
+        Self::update_stats(env, |stats| {
+            stats.matches_settled += 1;
+            stats.total_fees_stroops += total_fee;
+        });
+
+        let loser = if winner == m.player1 {
+            m.player2.clone()
+        } else {
+            m.player1.clone()
+        };
+        Self::record_result(env, m.season_id, &winner, &loser);
+
+        let loser_rounds_won = if winner == m.player1 {
+            m.player2_rounds_won
+        } else {
+            m.player1_rounds_won
+        };
+        let rounds_played = m.player1_rounds_won + m.player2_rounds_won;
+        let flawless = rounds_played > 0 && loser_rounds_won == 0;
+        Self::evaluate_achievements(env, &winner, &loser, flawless);
+
+        Self::mint_trophy_if_eligible(env, session_id, &m, &winner);
+
+        MatchSettled {
+            session_id,
+            winner: winner.clone(),
+            payout_amount,
+            winner_share,
+            protocol_share,
+        }
+        .publish(env);
+
+        Self::record_series_leg(env, session_id, &winner)?;
+
+        m.winner = Some(winner);
+
+        env.storage().temporary().set(&key, &m);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+        Self::mark_session_closed(env, session_id);
+
+        // Report to Game Hub
+        let hub_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GameHubAddress)
+            .expect("GameHub not set");
+        let hub = GameHubClient::new(env, &hub_addr);
+        hub.end_game(&session_id, &player1_won);
+
+        let side = if player1_won {
+            BetSide::Player1
+        } else {
+            BetSide::Player2
+        };
+        Self::settle_match_pool(env, session_id, Some(side));
+
+        Ok(())
+    }
+
+    /// Open a betting pool for the session on the configured betting contract.
+    /// The pool is linked to the session; its match id is the session id,
+    /// big-endian in the last 4 bytes. Best effort: a betting contract that
+    /// rejects the pool (paused, market taken) never blocks the match.
+    fn create_pool_if_enabled(env: &Env, session_id: u32, m: &Match) {
+        let betting_contract: Option<Address> =
+            env.storage().instance().get(&DataKey::BettingPoolAddress);
+        let Some(betting_contract) = betting_contract else {
+            return;
+        };
+
+        let pool_key = DataKey::MatchPool(session_id);
+        if env.storage().temporary().has(&pool_key) {
+            return;
+        }
+
+        let staked_only: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::BettingStakedOnly)
+            .unwrap_or(false);
+        if staked_only && m.stake_amount_stroops <= 0 {
+            return;
+        }
+
+        let window: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::BettingWindowSeconds)
+            .unwrap_or(0_u64);
+        let deadline_ts = env.ledger().timestamp().saturating_add(window);
+
+        let betting = BettingPoolClient::new(env, &betting_contract);
+        let Ok(Ok(pool_id)) = betting.try_open_brawl_pool(&session_id, &deadline_ts) else {
+            return;
+        };
+
+        env.storage().temporary().set(&pool_key, &pool_id);
+        env.storage()
+            .temporary()
+            .extend_ttl(&pool_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+
+        MatchPoolOpened { session_id, pool_id }.publish(env);
+    }
+
+    /// Report the session's result to its betting pool: settled for `winner`
+    /// once bets are revealed, or refunded when `None`. Best effort, so a
+    /// failing betting contract never blocks settlement of the match.
+    fn settle_match_pool(env: &Env, session_id: u32, winner: Option<BetSide>) {
+        if !env.storage().temporary().has(&DataKey::MatchPool(session_id)) {
+            return;
+        }
+
+        let betting_contract: Option<Address> =
+            env.storage().instance().get(&DataKey::BettingPoolAddress);
+        let Some(betting_contract) = betting_contract else {
+            return;
+        };
+
+        let betting = BettingPoolClient::new(env, &betting_contract);
+        match winner {
+            Some(side) => {
+                let _ = betting.try_notify_match_ended(&session_id, &side);
+            }
+            None => {
+                let _ = betting.try_notify_match_cancelled(&session_id);
+            }
+        }
+    }
 
-        let accrued_fee: i128 = env
+    /// Cross-call the trophy NFT contract for high-stake wins, if configured.
+    /// Minting is best effort. The metadata hash commits to the session, winner, stake, and season.
+    fn mint_trophy_if_eligible(env: &Env, session_id: u32, m: &Match, winner: &Address) {
+        let nft_contract: Option<Address> = env.storage().instance().get(&DataKey::TrophyNftAddress);
+        let Some(nft_contract) = nft_contract else {
+            return;
+        };
+
+        let min_stake: i128 = env
             .storage()
             .instance()
-            .get(&DataKey::FeeAccrued)
+            .get(&DataKey::TrophyMinStake)
             .unwrap_or(0_i128);
-
-        if accrued_fee <= 0 {
-            return Err(Error::NothingToSweep);
+        if m.stake_amount_stroops <= 0 || m.stake_amount_stroops < min_stake {
+            return;
         }
 
-        let balance = xlm.balance(&env.current_contract_address());
-        let sweepable = if balance > RESERVE_STROOPS {
-            let above_reserve = balance - RESERVE_STROOPS;
-            if above_reserve < accrued_fee {
-                above_reserve
-            } else {
-                accrued_fee
+        let mut preimage = Bytes::from_array(env, &session_id.to_be_bytes());
+        preimage.append(&winner.clone().to_xdr(env));
+        preimage.append(&Bytes::from_array(env, &m.stake_amount_stroops.to_be_bytes()));
+        preimage.append(&Bytes::from_array(env, &m.season_id.to_be_bytes()));
+        let metadata_hash: BytesN<32> = env.crypto().sha256(&preimage).into();
+
+        // A failing or paused NFT contract must not block match settlement;
+        // the trophy is simply skipped.
+        let nft = TrophyNftClient::new(env, &nft_contract);
+        if let Ok(Ok(())) = nft.try_mint_trophy(winner, &session_id, &metadata_hash) {
+            TrophyMinted {
+                session_id,
+                winner: winner.clone(),
             }
-        } else {
-            0
-        };
-
-        if sweepable <= 0 {
-            return Err(Error::NothingToSweep);
+            .publish(env);
         }
+    }
 
-        let treasury: Address = env
+    /// Update win streaks and unlock any newly earned achievements for the winner.
+    fn evaluate_achievements(env: &Env, winner: &Address, loser: &Address, flawless: bool) {
+        let winner_streak_key = DataKey::WinStreak(winner.clone());
+        let streak: u32 = env
             .storage()
-            .instance()
-            .get(&DataKey::TreasuryAddress)
-            .expect("Treasury not set");
+            .persistent()
+            .get(&winner_streak_key)
+            .unwrap_or(0_u32)
+            + 1;
+        env.storage().persistent().set(&winner_streak_key, &streak);
+        env.storage()
+            .persistent()
+            .extend_ttl(&winner_streak_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::WinStreak(loser.clone()));
 
-        xlm.transfer(&env.current_contract_address(), &treasury, &sweepable);
+        let achievements_key = DataKey::Achievements(winner.clone());
+        let mut unlocked: Vec<Achievement> = env
+            .storage()
+            .persistent()
+            .get(&achievements_key)
+            .unwrap_or(Vec::new(env));
+        let before = unlocked.len();
+
+        let mut candidates = Vec::new(env);
+        candidates.push_back(Achievement::FirstWin);
+        if streak >= WIN_STREAK_ACHIEVEMENT {
+            candidates.push_back(Achievement::WinStreak10);
+        }
+        if flawless {
+            candidates.push_back(Achievement::FlawlessVictory);
+        }
 
-        let remaining_fee = accrued_fee - sweepable;
-        env.storage().instance().set(&DataKey::FeeAccrued, &remaining_fee);
-        env.storage().instance().set(&DataKey::LastSweepTs, &now_ts);
+        for achievement in candidates.iter() {
+            if !unlocked.contains(achievement) {
+                unlocked.push_back(achievement);
+                AchievementUnlocked {
+                    player: winner.clone(),
+                    achievement,
+                }
+                .publish(env);
+            }
+        }
 
-        Ok(sweepable)
+        if unlocked.len() != before {
+            env.storage().persistent().set(&achievements_key, &unlocked);
+        }
+        if env.storage().persistent().has(&achievements_key) {
+            env.storage()
+                .persistent()
+                .extend_ttl(&achievements_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+        }
     }
 
-    // ======================================================================
-    // Read helpers
-    // ======================================================================
+    /// Keep the board sorted by rating (highest first) and capped at `LEADERBOARD_SIZE`.
+    fn upsert_leaderboard(board: &mut Vec<LeaderboardEntry>, player: &Address, rating: i128) {
+        if let Some(idx) = board.iter().position(|entry| entry.player == *player) {
+            board.remove(idx as u32);
+        }
 
-    /// Get match state.
-    pub fn get_match(env: Env, session_id: u32) -> Result<Match, Error> {
-        env.storage()
-            .temporary()
-            .get(&DataKey::Match(session_id))
-            .ok_or(Error::MatchNotFound)
-    }
+        let mut insert_at = board.len();
+        for (idx, entry) in board.iter().enumerate() {
+            if rating > entry.rating {
+                insert_at = idx as u32;
+                break;
+            }
+        }
 
-    pub fn get_admin(env: Env) -> Address {
-        env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set")
+        if insert_at < LEADERBOARD_SIZE {
+            board.insert(
+                insert_at,
+                LeaderboardEntry {
+                    player: player.clone(),
+                    rating,
+                },
+            );
+        }
+        while board.len() > LEADERBOARD_SIZE {
+            board.pop_back();
+        }
     }
 
-    pub fn get_hub(env: Env) -> Address {
+    fn remove_open_challenge(env: &Env, challenge_id: u32) {
         env.storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub not set")
+            .persistent()
+            .remove(&DataKey::OpenChallenge(challenge_id));
+
+        let ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OpenChallengeIds)
+            .unwrap_or(Vec::new(env));
+        let mut remaining = Vec::new(env);
+        for id in ids.iter() {
+            if id != challenge_id {
+                remaining.push_back(id);
+            }
+        }
+        Self::save_open_challenge_ids(env, &remaining);
     }
 
-    pub fn get_treasury(env: Env) -> Address {
+    fn save_open_challenge_ids(env: &Env, ids: &Vec<u32>) {
+        env.storage().persistent().set(&DataKey::OpenChallengeIds, ids);
         env.storage()
-            .instance()
-            .get(&DataKey::TreasuryAddress)
-            .expect("Treasury not set")
+            .persistent()
+            .extend_ttl(&DataKey::OpenChallengeIds, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
     }
 
-    pub fn get_fee_accrued(env: Env) -> i128 {
-        env.storage()
+    /// Register a session with the Game Hub and build its initial match state.
+    fn open_match(
+        env: &Env,
+        session_id: u32,
+        player1: &Address,
+        player2: &Address,
+        player1_points: i128,
+        player2_points: i128,
+    ) -> Match {
+        // Register with Game Hub
+        let hub_addr: Address = env
+            .storage()
             .instance()
-            .get(&DataKey::FeeAccrued)
-            .unwrap_or(0_i128)
-    }
+            .get(&DataKey::GameHubAddress)
+            .expect("GameHub not set");
+        let hub = GameHubClient::new(env, &hub_addr);
+        hub.start_game(
+            &env.current_contract_address(),
+            &session_id,
+            player1,
+            player2,
+            &player1_points,
+            &player2_points,
+        );
 
-    pub fn get_last_sweep_ts(env: Env) -> u64 {
-        env.storage()
+        let season_id: u32 = env
+            .storage()
             .instance()
-            .get(&DataKey::LastSweepTs)
-            .unwrap_or(0_u64)
+            .get(&DataKey::CurrentSeason)
+            .unwrap_or(0_u32);
+
+        Match {
+            player1: player1.clone(),
+            player2: player2.clone(),
+            player1_points,
+            player2_points,
+            player1_moves: 0,
+            player2_moves: 0,
+            player1_surges: 0,
+            player2_surges: 0,
+            player1_rounds_won: 0,
+            player2_rounds_won: 0,
+            total_xlm_collected: 0,
+            stake_amount_stroops: 0,
+            stake_fee_bps: STAKE_FEE_BPS,
+            surge_price_bps: 0,
+            stake_deadline_ts: 0,
+            player1_stake_paid: false,
+            player2_stake_paid: false,
+            fee_accrued_stroops: 0,
+            player1_zk_commits: 0,
+            player2_zk_commits: 0,
+            player1_zk_verified: 0,
+            player2_zk_verified: 0,
+            season_id,
+            player1_character: None,
+            player2_character: None,
+            player1_loadout_hash: None,
+            player2_loadout_hash: None,
+            is_cancelled: false,
+            winner: None,
+        }
     }
 
-    pub fn get_zk_gate_required(env: Env) -> bool {
+    /// Persist a freshly opened match together with its commitment salt.
+    fn store_new_match(env: &Env, session_id: u32, m: &Match) {
+        let key = DataKey::Match(session_id);
+        let mut salt_bytes = [0u8; 8];
+        salt_bytes[..4].copy_from_slice(&session_id.to_be_bytes());
+        salt_bytes[4..].copy_from_slice(&env.ledger().sequence().to_be_bytes());
+        let match_salt = env.crypto().sha256(&Bytes::from_array(env, &salt_bytes));
+        let salt_key = DataKey::MatchSalt(session_id);
+        let version_key = DataKey::MatchVersion(session_id);
+
+        env.storage().temporary().set(&key, m);
+        env.storage().temporary().set(&salt_key, &match_salt);
         env.storage()
-            .instance()
-            .get(&DataKey::ZkGateRequired)
-            .unwrap_or(true)
+            .temporary()
+            .set(&version_key, &MATCH_SCHEMA_VERSION);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+        env.storage()
+            .temporary()
+            .extend_ttl(&salt_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+        env.storage()
+            .temporary()
+            .extend_ttl(&version_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+
+        Self::update_stats(env, |stats| stats.matches_started += 1);
     }
 
-    pub fn get_zk_verifier_contract(env: Env) -> Result<Address, Error> {
-        env.storage()
+    fn update_stats(env: &Env, update: impl FnOnce(&mut ContractStats)) {
+        let mut stats: ContractStats = env
+            .storage()
             .instance()
-            .get(&DataKey::ZkVerifierContractAddress)
-            .ok_or(Error::ZkVerifierNotConfigured)
+            .get(&DataKey::Stats)
+            .unwrap_or_default();
+        update(&mut stats);
+        env.storage().instance().set(&DataKey::Stats, &stats);
     }
 
-    pub fn get_zk_verifier_vk_id(env: Env) -> BytesN<32> {
-        env.storage()
+    /// Apply the session id policy: in auto mode the caller passes 0 and gets
+    /// the next free id; otherwise the caller's id must never have been used.
+    fn resolve_session_id(env: &Env, session_id: u32) -> Result<u32, Error> {
+        let auto_session_ids: bool = env
+            .storage()
             .instance()
-            .get(&DataKey::ZkVerifierVkId)
-            .unwrap_or(BytesN::from_array(&env, &[0u8; 32]))
-    }
+            .get(&DataKey::AutoSessionIds)
+            .unwrap_or(false);
 
-    pub fn get_zk_match_outcome(env: Env, session_id: u32) -> Result<ZkMatchOutcomeRecord, Error> {
-        env.storage()
-            .temporary()
-            .get(&DataKey::ZkMatchOutcome(session_id))
-            .ok_or(Error::ZkMatchOutcomeRequired)
+        if auto_session_ids {
+            if session_id != 0 {
+                return Err(Error::InvalidSessionId);
+            }
+            Ok(Self::assign_session_id(env))
+        } else {
+            if Self::is_session_taken(env, session_id) {
+                return Err(Error::SessionIdReused);
+            }
+            Ok(session_id)
+        }
     }
 
-    // ======================================================================
-    // Admin setters
-    // ======================================================================
+    /// Count a finished leg toward its series. The leg that decides the series
+    /// adds the escrowed `2 * stake` to that session's pending payout. Legs
+    /// of a refunded series no longer count.
+    fn record_series_leg(env: &Env, session_id: u32, winner: &Address) -> Result<(), Error> {
+        let series_id: Option<u32> = env
+            .storage()
+            .temporary()
+            .get(&DataKey::SessionSeries(session_id));
+        let Some(series_id) = series_id else {
+            return Ok(());
+        };
 
-    pub fn set_admin(env: Env, new_admin: Address) {
-        let admin: Address = env
+        let series_key = DataKey::Series(series_id);
+        let mut series: Series = env
             .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
-        env.storage().instance().set(&DataKey::Admin, &new_admin);
+            .persistent()
+            .get(&series_key)
+            .ok_or(Error::SeriesNotFound)?;
+        if series.is_refunded || series.winner.is_some() {
+            return Ok(());
+        }
+
+        series.last_activity_ts = env.ledger().timestamp();
+        if *winner == series.player1 {
+            series.player1_wins += 1;
+        } else {
+            series.player2_wins += 1;
+        }
+
+        if series.player1_wins >= SERIES_WINS_REQUIRED || series.player2_wins >= SERIES_WINS_REQUIRED {
+            series.winner = Some(winner.clone());
+
+            if series.stake_amount_stroops > 0 {
+                let payout_key = DataKey::PendingPayout(session_id);
+                let mut payout: PendingPayout = env
+                    .storage()
+                    .persistent()
+                    .get(&payout_key)
+                    .unwrap_or(PendingPayout {
+                        winner: winner.clone(),
+                        amount_stroops: 0,
+                    });
+                payout.amount_stroops += series.stake_amount_stroops * 2;
+                env.storage().persistent().set(&payout_key, &payout);
+                env.storage()
+                    .persistent()
+                    .extend_ttl(&payout_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+            }
+
+            SeriesWon {
+                series_id,
+                winner: winner.clone(),
+                session_id,
+            }
+            .publish(env);
+        }
+
+        env.storage().persistent().set(&series_key, &series);
+        env.storage()
+            .persistent()
+            .extend_ttl(&series_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
+        Ok(())
     }
 
-    pub fn set_hub(env: Env, new_hub: Address) {
-        let admin: Address = env
+    /// Hand out the next monotonically increasing session id, skipping any id
+    /// already taken by a manually started or closed session.
+    fn assign_session_id(env: &Env) -> u32 {
+        let mut next: u32 = env
             .storage()
             .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
+            .get(&DataKey::NextSessionId)
+            .unwrap_or(1_u32);
+
+        while Self::is_session_taken(env, next) {
+            next = next.checked_add(1).expect("Session ids exhausted");
+        }
+
         env.storage()
             .instance()
-            .set(&DataKey::GameHubAddress, &new_hub);
+            .set(&DataKey::NextSessionId, &(next.checked_add(1).expect("Session ids exhausted")));
+        next
     }
 
-    pub fn set_treasury(env: Env, new_treasury: Address) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
+    fn is_session_taken(env: &Env, session_id: u32) -> bool {
+        env.storage().temporary().has(&DataKey::Match(session_id))
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::SessionClosed(session_id))
+    }
+
+    fn mark_session_closed(env: &Env, session_id: u32) {
+        let closed_key = DataKey::SessionClosed(session_id);
+        env.storage().persistent().set(&closed_key, &true);
         env.storage()
-            .instance()
-            .set(&DataKey::TreasuryAddress, &new_treasury);
+            .persistent()
+            .extend_ttl(&closed_key, MATCH_TTL_LEDGERS, MATCH_TTL_LEDGERS);
     }
 
-    pub fn set_zk_gate_required(env: Env, required: bool) {
-        let admin: Address = env
+    /// XLM balance not already spoken for by accrued fees, the reserve, or
+    /// outstanding match liabilities.
+    fn available_liquidity(env: &Env) -> i128 {
+        let xlm_addr: Address = env
             .storage()
             .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
-        env.storage().instance().set(&DataKey::ZkGateRequired, &required);
-    }
+            .get(&DataKey::XlmToken)
+            .expect("XLM token not set");
+        let xlm = token::Client::new(env, &xlm_addr);
+        let balance = xlm.balance(&env.current_contract_address());
 
-    pub fn set_zk_verifier_contract(env: Env, verifier_contract: Address) {
-        let admin: Address = env
+        let accrued_fee: i128 = env
             .storage()
             .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
-        env.storage()
+            .get(&DataKey::FeeAccrued)
+            .unwrap_or(0_i128);
+        let liabilities: i128 = env
+            .storage()
             .instance()
-            .set(&DataKey::ZkVerifierContractAddress, &verifier_contract);
+            .get(&DataKey::OutstandingLiabilities)
+            .unwrap_or(0_i128);
+
+        balance - accrued_fee - RESERVE_STROOPS - liabilities
     }
 
-    pub fn set_zk_verifier_vk_id(env: Env, vk_id: BytesN<32>) {
-        let admin: Address = env
+    fn reserve_liability(env: &Env, amount_stroops: i128) -> Result<(), Error> {
+        if Self::available_liquidity(env) < amount_stroops {
+            return Err(Error::InsufficientBalance);
+        }
+
+        let liabilities: i128 = env
             .storage()
             .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
-        env.storage().instance().set(&DataKey::ZkVerifierVkId, &vk_id);
+            .get(&DataKey::OutstandingLiabilities)
+            .unwrap_or(0_i128);
+        env.storage()
+            .instance()
+            .set(&DataKey::OutstandingLiabilities, &(liabilities + amount_stroops));
+        Ok(())
     }
 
-    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
-        let admin: Address = env
+    fn release_liability(env: &Env, amount_stroops: i128) {
+        let liabilities: i128 = env
             .storage()
             .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
+            .get(&DataKey::OutstandingLiabilities)
+            .unwrap_or(0_i128);
+        let remaining = if liabilities > amount_stroops {
+            liabilities - amount_stroops
+        } else {
+            0
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::OutstandingLiabilities, &remaining);
     }
 
     fn calc_fee(stake_amount_stroops: i128, fee_bps: u32) -> i128 {
@@ -3,9 +3,12 @@
 //! Unit tests for the Veilstar Brawl fighting game contract.
 //! Uses a mock GameHub and a mock XLM token (SAC) for isolation.
 
-use crate::{Error, MoveType, VeilstarBrawlContract, VeilstarBrawlContractClient};
+use crate::{
+    Achievement, BetSide, DataKey, Error, MatchV1, MoveType, VeilstarBrawlContract,
+    VeilstarBrawlContractClient,
+};
 use soroban_sdk::testutils::{Address as _, Ledger as _};
-use soroban_sdk::{contract, contractimpl, vec, Address, Bytes, BytesN, Env, Vec};
+use soroban_sdk::{contract, contractimpl, symbol_short, vec, Address, Bytes, BytesN, Env, Vec};
 
 // ============================================================================
 // Mock GameHub
@@ -47,6 +50,87 @@ impl MockZkVerifier {
     }
 }
 
+#[contract]
+pub struct MockTrophyNft;
+
+#[contractimpl]
+impl MockTrophyNft {
+    pub fn mint_trophy(env: Env, to: Address, session_id: u32, metadata_hash: BytesN<32>) {
+        env.storage()
+            .instance()
+            .set(&session_id, &(to, metadata_hash));
+    }
+
+    pub fn minted(env: Env, session_id: u32) -> Option<(Address, BytesN<32>)> {
+        env.storage().instance().get(&session_id)
+    }
+}
+
+#[contract]
+pub struct MockBettingPool;
+
+#[contractimpl]
+impl MockBettingPool {
+    /// Pool ids count up from 1; keys of `u32::MAX - session_id` map
+    /// sessions to pools.
+    pub fn open_brawl_pool(env: Env, session_id: u32, deadline_ts: u64) -> u32 {
+        Self::require_live(&env);
+        let pool_id: u32 = env.storage().instance().get(&0u32).unwrap_or(0) + 1;
+        env.storage().instance().set(&0u32, &pool_id);
+        env.storage()
+            .instance()
+            .set(&pool_id, &(session_id, deadline_ts, 0u32));
+        env.storage().instance().set(&(u32::MAX - session_id), &pool_id);
+        pool_id
+    }
+
+    pub fn notify_match_ended(env: Env, session_id: u32, winner: BetSide) -> u32 {
+        Self::require_live(&env);
+        let pool_id: u32 = env.storage().instance().get(&(u32::MAX - session_id)).unwrap();
+        let status = match winner {
+            BetSide::Player1 => 1u32,
+            BetSide::Player2 => 2u32,
+        };
+        Self::set_status(&env, pool_id, status);
+        1
+    }
+
+    pub fn notify_match_cancelled(env: Env, session_id: u32) -> u32 {
+        Self::require_live(&env);
+        let pool_id: u32 = env.storage().instance().get(&(u32::MAX - session_id)).unwrap();
+        Self::set_status(&env, pool_id, 3);
+        1
+    }
+
+    /// Make every hook fail, as a paused betting contract would.
+    pub fn set_paused(env: Env, paused: bool) {
+        env.storage().instance().set(&symbol_short!("paused"), &paused);
+    }
+
+    /// (session_id, deadline_ts, status) where status is 0 open, 1/2 settled
+    /// for player 1/2, 3 refunded.
+    pub fn pool(env: Env, pool_id: u32) -> Option<(u32, u64, u32)> {
+        env.storage().instance().get(&pool_id)
+    }
+
+    fn require_live(env: &Env) {
+        let paused: bool = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("paused"))
+            .unwrap_or(false);
+        assert!(!paused, "betting paused");
+    }
+
+    fn set_status(env: &Env, pool_id: u32, status: u32) {
+        let (session_id, deadline_ts, _): (u32, u64, u32) =
+            env.storage().instance().get(&pool_id).unwrap();
+        env.storage()
+            .instance()
+            .set(&pool_id, &(session_id, deadline_ts, status));
+    }
+}
+
 // ============================================================================
 // Helpers
 // ============================================================================
@@ -108,6 +192,17 @@ fn setup_test() -> (
     (env, client, admin, player1, player2, treasury, xlm_addr, verifier_addr)
 }
 
+fn register_surge_cards(client: &VeilstarBrawlContractClient<'static>) {
+    for card_code in 0u32..15 {
+        client.set_surge_card(&card_code, &1u32, &true);
+    }
+}
+
+fn register_character(env: &Env, client: &VeilstarBrawlContractClient<'static>, character_id: u32) {
+    let name_hash = BytesN::from_array(env, &[character_id as u8; 32]);
+    client.set_character(&character_id, &name_hash, &true, &1u32);
+}
+
 fn assert_contract_error<T: core::fmt::Debug, E: core::fmt::Debug>(
     result: &Result<Result<T, E>, Result<Error, soroban_sdk::InvokeError>>,
     expected: Error,
@@ -143,9 +238,9 @@ fn test_submit_move_increments_counters() {
 
     client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
 
-    client.submit_move(&1u32, &p1, &MoveType::Punch, &1u32);
-    client.submit_move(&1u32, &p2, &MoveType::Block, &1u32);
-    client.submit_move(&1u32, &p1, &MoveType::Kick, &2u32);
+    client.submit_move(&1u32, &p1, &MoveType::Punch, &1u32, &1u32, &0u32);
+    client.submit_move(&1u32, &p2, &MoveType::Block, &1u32, &1u32, &0u32);
+    client.submit_move(&1u32, &p1, &MoveType::Kick, &1u32, &2u32, &1u32);
 
     let m = client.get_match(&1u32);
     assert_eq!(m.player1_moves, 2);
@@ -157,10 +252,11 @@ fn test_submit_move_increments_counters() {
 fn test_submit_power_surge_collects_fee() {
     let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
 
+    register_surge_cards(&client);
     client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
 
-    client.submit_power_surge(&1u32, &p1, &1u32, &7u32);
-    client.submit_power_surge(&1u32, &p2, &1u32, &3u32);
+    client.submit_power_surge(&1u32, &p1, &1u32, &7u32, &0u32);
+    client.submit_power_surge(&1u32, &p2, &1u32, &3u32, &0u32);
 
     let m = client.get_match(&1u32);
     assert_eq!(m.total_xlm_collected, 2_000); // 2 * 1_000 stroops
@@ -171,7 +267,7 @@ fn test_end_match_sets_winner() {
     let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
 
     client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
-    client.submit_move(&1u32, &p1, &MoveType::Special, &1u32);
+    client.submit_move(&1u32, &p1, &MoveType::Special, &1u32, &1u32, &0u32);
 
     // Player 1 wins
     client.end_game(&1u32, &true);
@@ -202,7 +298,7 @@ fn test_cannot_submit_move_after_match_ended() {
     client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
     client.end_game(&1u32, &true);
 
-    let result = client.try_submit_move(&1u32, &p1, &MoveType::Punch, &1u32);
+    let result = client.try_submit_move(&1u32, &p1, &MoveType::Punch, &1u32, &1u32, &0u32);
     assert_contract_error(&result, Error::MatchAlreadyEnded);
 }
 
@@ -224,7 +320,7 @@ fn test_non_player_cannot_submit_move() {
 
     client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
 
-    let result = client.try_submit_move(&1u32, &outsider, &MoveType::Punch, &1u32);
+    let result = client.try_submit_move(&1u32, &outsider, &MoveType::Punch, &1u32, &1u32, &0u32);
     assert_contract_error(&result, Error::NotPlayer);
 }
 
@@ -247,7 +343,7 @@ fn test_sweep_treasury() {
     client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
 
     // Fees accrue from stake settlement, not move submissions.
-    client.set_match_stake(&1u32, &10_000_000i128); // 1 XLM stake per player
+    client.set_match_stake(&1u32, &10_000_000i128, &0u32); // 1 XLM stake per player
     client.deposit_stake(&1u32, &p1);
     client.deposit_stake(&1u32, &p2);
     client.end_game(&1u32, &true);
@@ -281,8 +377,8 @@ fn test_multiple_independent_matches() {
     client.start_game(&10u32, &p1, &p2, &100_000, &100_000);
     client.start_game(&20u32, &p2, &p1, &200_000, &200_000);
 
-    client.submit_move(&10u32, &p1, &MoveType::Punch, &1u32);
-    client.submit_move(&20u32, &p2, &MoveType::Special, &1u32);
+    client.submit_move(&10u32, &p1, &MoveType::Punch, &1u32, &1u32, &0u32);
+    client.submit_move(&20u32, &p2, &MoveType::Special, &1u32, &1u32, &0u32);
 
     let m1 = client.get_match(&10u32);
     let m2 = client.get_match(&20u32);
@@ -303,10 +399,10 @@ fn test_all_move_types() {
 
     client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
 
-    client.submit_move(&1u32, &p1, &MoveType::Punch, &1u32);
-    client.submit_move(&1u32, &p1, &MoveType::Kick, &2u32);
-    client.submit_move(&1u32, &p1, &MoveType::Block, &3u32);
-    client.submit_move(&1u32, &p1, &MoveType::Special, &4u32);
+    client.submit_move(&1u32, &p1, &MoveType::Punch, &1u32, &1u32, &0u32);
+    client.submit_move(&1u32, &p1, &MoveType::Kick, &1u32, &2u32, &1u32);
+    client.submit_move(&1u32, &p1, &MoveType::Block, &1u32, &3u32, &2u32);
+    client.submit_move(&1u32, &p1, &MoveType::Special, &1u32, &4u32, &3u32);
 
     let m = client.get_match(&1u32);
     assert_eq!(m.player1_moves, 4);
@@ -318,8 +414,8 @@ fn test_set_match_stake_is_idempotent_for_same_amount() {
 
     client.start_game(&55u32, &p1, &p2, &100_000, &100_000);
 
-    client.set_match_stake(&55u32, &10_000_000i128);
-    client.set_match_stake(&55u32, &10_000_000i128);
+    client.set_match_stake(&55u32, &10_000_000i128, &0u32);
+    client.set_match_stake(&55u32, &10_000_000i128, &0u32);
 
     let m = client.get_match(&55u32);
     assert_eq!(m.stake_amount_stroops, 10_000_000i128);
@@ -330,7 +426,7 @@ fn test_set_match_stake_before_start_game_applies_on_start() {
     let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
 
     // Configure stake before the match exists (simulates tx ordering race).
-    client.set_match_stake(&999u32, &10_000_000i128);
+    client.set_match_stake(&999u32, &10_000_000i128, &0u32);
 
     client.start_game(&999u32, &p1, &p2, &100_000, &100_000);
 
@@ -343,9 +439,9 @@ fn test_set_match_stake_before_start_game_applies_on_start() {
 fn test_set_match_stake_before_start_game_rejects_mismatch() {
     let (_env, client, _admin, _p1, _p2, _treasury, _xlm, _verifier) = setup_test();
 
-    client.set_match_stake(&1000u32, &10_000_000i128);
+    client.set_match_stake(&1000u32, &10_000_000i128, &0u32);
 
-    let result = client.try_set_match_stake(&1000u32, &20_000_000i128);
+    let result = client.try_set_match_stake(&1000u32, &20_000_000i128, &0u32);
     assert_contract_error(&result, Error::InvalidStake);
 }
 
@@ -354,7 +450,7 @@ fn test_deposit_stake_is_idempotent_per_player() {
     let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
 
     client.start_game(&77u32, &p1, &p2, &100_000, &100_000);
-    client.set_match_stake(&77u32, &10_000_000i128);
+    client.set_match_stake(&77u32, &10_000_000i128, &0u32);
 
     client.deposit_stake(&77u32, &p1);
     client.deposit_stake(&77u32, &p1);
@@ -374,8 +470,8 @@ fn test_end_game_requires_zk_commit_when_gate_enabled() {
 
     let c1 = BytesN::from_array(&env, &[1u8; 32]);
     let c2 = BytesN::from_array(&env, &[2u8; 32]);
-    client.submit_zk_commit(&101u32, &p1, &1u32, &1u32, &c1);
-    client.submit_zk_commit(&101u32, &p2, &1u32, &1u32, &c2);
+    client.submit_zk_commit(&101u32, &p1, &1u32, &1u32, &c1, &0u32);
+    client.submit_zk_commit(&101u32, &p2, &1u32, &1u32, &c2, &0u32);
 
     let result = client.try_end_game(&101u32, &true);
     assert_contract_error(&result, Error::ZkCommitRequired);
@@ -391,8 +487,8 @@ fn test_submit_zk_commit_allows_end_game_under_gate() {
     let c1 = BytesN::from_array(&env, &[1u8; 32]);
     let c2 = BytesN::from_array(&env, &[2u8; 32]);
 
-    client.submit_zk_commit(&102u32, &p1, &1u32, &1u32, &c1);
-    client.submit_zk_commit(&102u32, &p2, &1u32, &1u32, &c2);
+    client.submit_zk_commit(&102u32, &p1, &1u32, &1u32, &c1, &0u32);
+    client.submit_zk_commit(&102u32, &p2, &1u32, &1u32, &c2, &0u32);
 
     client.set_zk_verifier_contract(&verifier);
 
@@ -450,8 +546,8 @@ fn test_end_game_requires_match_outcome_when_gate_enabled() {
 
     client.set_zk_verifier_contract(&verifier);
     client.set_zk_verifier_vk_id(&vk_id);
-    client.submit_zk_commit(&110u32, &p1, &1u32, &1u32, &c1);
-    client.submit_zk_commit(&110u32, &p2, &1u32, &1u32, &c2);
+    client.submit_zk_commit(&110u32, &p1, &1u32, &1u32, &c1, &0u32);
+    client.submit_zk_commit(&110u32, &p2, &1u32, &1u32, &c2, &0u32);
     client.submit_zk_verification(&110u32, &p1, &1u32, &1u32, &c1, &vk_id, &proof, &public_inputs_p1);
     client.submit_zk_verification(&110u32, &p2, &1u32, &1u32, &c2, &vk_id, &proof, &public_inputs_p2);
 
@@ -475,8 +571,8 @@ fn test_end_game_rejects_winner_mismatch_with_match_outcome() {
 
     client.set_zk_verifier_contract(&verifier);
     client.set_zk_verifier_vk_id(&vk_id);
-    client.submit_zk_commit(&111u32, &p1, &1u32, &1u32, &c1);
-    client.submit_zk_commit(&111u32, &p2, &1u32, &1u32, &c2);
+    client.submit_zk_commit(&111u32, &p1, &1u32, &1u32, &c1, &0u32);
+    client.submit_zk_commit(&111u32, &p2, &1u32, &1u32, &c2, &0u32);
     client.submit_zk_verification(&111u32, &p1, &1u32, &1u32, &c1, &vk_id, &proof, &public_inputs_p1);
     client.submit_zk_verification(&111u32, &p2, &1u32, &1u32, &c2, &vk_id, &proof, &public_inputs_p2);
     client.submit_zk_match_outcome(&111u32, &p2, &vk_id, &proof, &public_inputs_p2);
@@ -496,10 +592,10 @@ fn test_duplicate_zk_commit_rejected() {
     client.start_game(&103u32, &p1, &p2, &100_000, &100_000);
 
     let c1 = BytesN::from_array(&env, &[9u8; 32]);
-    client.submit_zk_commit(&103u32, &p1, &2u32, &3u32, &c1);
+    client.submit_zk_commit(&103u32, &p1, &2u32, &3u32, &c1, &0u32);
 
     // Idempotent duplicate commit should succeed and not inflate counters.
-    client.submit_zk_commit(&103u32, &p1, &2u32, &3u32, &c1);
+    client.submit_zk_commit(&103u32, &p1, &2u32, &3u32, &c1, &1u32);
     let m = client.get_match(&103u32);
     assert_eq!(m.player1_zk_commits, 1);
 }
@@ -518,7 +614,7 @@ fn test_duplicate_zk_verification_rejected() {
     let proof = Bytes::from_array(&env, &[9u8; 256]);
     let public_inputs = vec![&env, c1.clone()];
 
-    client.submit_zk_commit(&104u32, &p1, &1u32, &2u32, &c1);
+    client.submit_zk_commit(&104u32, &p1, &1u32, &2u32, &c1, &0u32);
     client.submit_zk_verification(
         &104u32,
         &p1,
@@ -545,3 +641,1186 @@ fn test_duplicate_zk_verification_rejected() {
     let m = client.get_match(&104u32);
     assert_eq!(m.player1_zk_verified, 1);
 }
+
+// ============================================================================
+// Move limits
+// ============================================================================
+
+#[test]
+fn test_second_move_in_same_turn_rejected() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.submit_move(&1u32, &p1, &MoveType::Punch, &1u32, &1u32, &0u32);
+
+    let result = client.try_submit_move(&1u32, &p1, &MoveType::Kick, &1u32, &1u32, &1u32);
+    assert_contract_error(&result, Error::TurnMoveLimitExceeded);
+
+    // The opponent still has their own allowance for the turn.
+    client.submit_move(&1u32, &p2, &MoveType::Block, &1u32, &1u32, &0u32);
+    let m = client.get_match(&1u32);
+    assert_eq!(m.player1_moves, 1);
+    assert_eq!(m.player2_moves, 1);
+}
+
+#[test]
+fn test_turn_cap_resets_each_round() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.submit_move(&1u32, &p1, &MoveType::Punch, &1u32, &1u32, &0u32);
+    client.record_round_result(&1u32, &1u32, &true);
+
+    // Turn numbering restarts in round 2 with a fresh per-turn allowance.
+    client.submit_move(&1u32, &p1, &MoveType::Kick, &2u32, &1u32, &1u32);
+    client.submit_move(&1u32, &p2, &MoveType::Block, &2u32, &1u32, &0u32);
+    let result = client.try_submit_move(&1u32, &p1, &MoveType::Punch, &2u32, &1u32, &2u32);
+    assert_contract_error(&result, Error::TurnMoveLimitExceeded);
+
+    // Moves cannot be filed against a round that has already been recorded.
+    let result = client.try_submit_move(&1u32, &p2, &MoveType::Punch, &1u32, &2u32, &1u32);
+    assert_contract_error(&result, Error::InvalidRound);
+    assert_eq!(client.get_match(&1u32).player1_moves, 2);
+}
+
+#[test]
+fn test_moves_bounded_to_current_round_and_turn() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+
+    // Moves cannot skip ahead to a round not yet in play.
+    let result = client.try_submit_move(&1u32, &p1, &MoveType::Punch, &2u32, &1u32, &0u32);
+    assert_contract_error(&result, Error::InvalidRound);
+
+    // Turns count from 1 up to the round's last turn.
+    let result = client.try_submit_move(&1u32, &p1, &MoveType::Punch, &1u32, &0u32, &0u32);
+    assert_contract_error(&result, Error::InvalidTurn);
+    let result = client.try_submit_move(&1u32, &p1, &MoveType::Punch, &1u32, &11u32, &0u32);
+    assert_contract_error(&result, Error::InvalidTurn);
+    client.submit_move(&1u32, &p1, &MoveType::Punch, &1u32, &10u32, &0u32);
+    assert_eq!(client.get_match(&1u32).player1_moves, 1);
+}
+
+#[test]
+fn test_move_cap_per_match_enforced() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.set_move_limits(&2u32, &1u32, &1u32);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+
+    client.submit_move(&1u32, &p1, &MoveType::Punch, &1u32, &1u32, &0u32);
+    client.submit_move(&1u32, &p1, &MoveType::Kick, &1u32, &2u32, &1u32);
+
+    let result = client.try_submit_move(&1u32, &p1, &MoveType::Special, &1u32, &3u32, &2u32);
+    assert_contract_error(&result, Error::MoveLimitExceeded);
+    assert_eq!(client.get_match(&1u32).total_xlm_collected, 2_000);
+}
+
+#[test]
+fn test_surge_cap_per_match_enforced() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.set_move_limits(&50u32, &1u32, &1u32);
+    register_surge_cards(&client);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+
+    client.submit_power_surge(&1u32, &p1, &1u32, &7u32, &0u32);
+
+    let result = client.try_submit_power_surge(&1u32, &p1, &2u32, &3u32, &1u32);
+    assert_contract_error(&result, Error::SurgeLimitExceeded);
+
+    let m = client.get_match(&1u32);
+    assert_eq!(m.player1_surges, 1);
+    assert_eq!(m.player2_surges, 0);
+}
+
+#[test]
+fn test_set_move_limits_rejects_zero() {
+    let (_env, client, _admin, _p1, _p2, _treasury, _xlm, _verifier) = setup_test();
+
+    let result = client.try_set_move_limits(&0u32, &5u32, &1u32);
+    assert_contract_error(&result, Error::InvalidMoveLimits);
+
+    let limits = client.get_move_limits();
+    assert_eq!(limits.max_moves_per_match, 50);
+    assert_eq!(limits.max_surges_per_match, 5);
+    assert_eq!(limits.max_moves_per_turn, 1);
+}
+
+// ============================================================================
+// Session ids
+// ============================================================================
+
+#[test]
+fn test_auto_session_ids_are_monotonic() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.set_auto_session_ids(&true);
+
+    let first = client.start_game(&0u32, &p1, &p2, &100_000, &100_000);
+    let second = client.start_game(&0u32, &p2, &p1, &100_000, &100_000);
+
+    assert_eq!(first, 1);
+    assert_eq!(second, 2);
+    assert_eq!(client.get_match(&second).player1, p2);
+    assert_eq!(client.get_next_session_id(), 3);
+}
+
+#[test]
+fn test_auto_session_ids_reject_caller_chosen_id() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.set_auto_session_ids(&true);
+
+    let result = client.try_start_game(&42u32, &p1, &p2, &100_000, &100_000);
+    assert_contract_error(&result, Error::InvalidSessionId);
+}
+
+#[test]
+fn test_auto_session_ids_skip_manually_used_ids() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.set_auto_session_ids(&true);
+
+    let assigned = client.start_game(&0u32, &p1, &p2, &100_000, &100_000);
+    assert_eq!(assigned, 2);
+}
+
+#[test]
+fn test_start_game_rejects_active_session_id() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.start_game(&5u32, &p1, &p2, &100_000, &100_000);
+
+    let result = client.try_start_game(&5u32, &p1, &p2, &100_000, &100_000);
+    assert_contract_error(&result, Error::SessionIdReused);
+}
+
+#[test]
+fn test_start_game_rejects_cancelled_session_id() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.start_game(&6u32, &p1, &p2, &100_000, &100_000);
+    client.cancel_match(&6u32);
+    assert!(client.is_session_closed(&6u32));
+
+    let result = client.try_start_game(&6u32, &p1, &p2, &100_000, &100_000);
+    assert_contract_error(&result, Error::SessionIdReused);
+}
+
+// ============================================================================
+// Winner claims
+// ============================================================================
+
+#[test]
+fn test_end_game_records_payout_and_winner_claims() {
+    let (env, client, _admin, p1, p2, _treasury, xlm_addr, _verifier) = setup_test();
+    let xlm = soroban_sdk::token::Client::new(&env, &xlm_addr);
+
+    client.set_zk_gate_required(&false);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.set_match_stake(&1u32, &10_000_000i128, &0u32);
+    client.deposit_stake(&1u32, &p1);
+    client.deposit_stake(&1u32, &p2);
+
+    let before_settle = xlm.balance(&p1);
+    client.end_game(&1u32, &true);
+    assert_eq!(xlm.balance(&p1), before_settle);
+
+    let payout = client.get_pending_payout(&1u32);
+    assert_eq!(payout.winner, p1);
+    assert_eq!(payout.amount_stroops, 20_000_000);
+
+    let claimed = client.claim_winnings(&1u32);
+    assert_eq!(claimed, 20_000_000);
+    assert_eq!(xlm.balance(&p1), before_settle + 20_000_000);
+}
+
+#[test]
+fn test_claim_winnings_only_once() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.set_zk_gate_required(&false);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.set_match_stake(&1u32, &10_000_000i128, &0u32);
+    client.deposit_stake(&1u32, &p1);
+    client.deposit_stake(&1u32, &p2);
+    client.end_game(&1u32, &false);
+
+    client.claim_winnings(&1u32);
+
+    let result = client.try_claim_winnings(&1u32);
+    assert_contract_error(&result, Error::NothingToClaim);
+}
+
+#[test]
+fn test_claim_winnings_without_stake_fails() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.set_zk_gate_required(&false);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.end_game(&1u32, &true);
+
+    let result = client.try_claim_winnings(&1u32);
+    assert_contract_error(&result, Error::NothingToClaim);
+}
+
+// ============================================================================
+// Solvency
+// ============================================================================
+
+#[test]
+fn test_set_match_stake_tracks_liability() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.set_match_stake(&1u32, &10_000_000i128, &0u32);
+
+    assert_eq!(client.get_outstanding_liabilities(), 20_000_000);
+}
+
+#[test]
+fn test_set_match_stake_rejects_uncovered_payout() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    // Contract holds 20 XLM with a 10 XLM reserve, so a 6 XLM stake (12 XLM payout) is uncovered.
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    let result = client.try_set_match_stake(&1u32, &60_000_000i128, &0u32);
+    assert_contract_error(&result, Error::InsufficientBalance);
+
+    let result = client.try_set_match_stake(&2u32, &60_000_000i128, &0u32);
+    assert_contract_error(&result, Error::InsufficientBalance);
+    assert_eq!(client.get_outstanding_liabilities(), 0);
+}
+
+#[test]
+fn test_liability_released_on_cancel_and_claim() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.set_zk_gate_required(&false);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.start_game(&2u32, &p1, &p2, &100_000, &100_000);
+    client.set_match_stake(&1u32, &10_000_000i128, &0u32);
+    client.set_match_stake(&2u32, &10_000_000i128, &0u32);
+    assert_eq!(client.get_outstanding_liabilities(), 40_000_000);
+
+    client.cancel_match(&2u32);
+    assert_eq!(client.get_outstanding_liabilities(), 20_000_000);
+
+    client.deposit_stake(&1u32, &p1);
+    client.deposit_stake(&1u32, &p2);
+    client.end_game(&1u32, &true);
+    assert_eq!(client.get_outstanding_liabilities(), 20_000_000);
+
+    client.claim_winnings(&1u32);
+    assert_eq!(client.get_outstanding_liabilities(), 0);
+}
+
+// ============================================================================
+// Power surge catalog
+// ============================================================================
+
+#[test]
+fn test_unknown_surge_card_rejected() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+
+    let result = client.try_submit_power_surge(&1u32, &p1, &1u32, &99u32, &0u32);
+    assert_contract_error(&result, Error::UnknownSurgeCard);
+}
+
+#[test]
+fn test_disabled_surge_card_rejected() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.set_surge_card(&4u32, &1u32, &false);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+
+    let result = client.try_submit_power_surge(&1u32, &p1, &1u32, &4u32, &0u32);
+    assert_contract_error(&result, Error::SurgeCardDisabled);
+
+    client.set_surge_card(&4u32, &1u32, &true);
+    client.submit_power_surge(&1u32, &p1, &1u32, &4u32, &0u32);
+}
+
+#[test]
+fn test_surge_card_cost_multiplier_applied() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.set_surge_card(&13u32, &5u32, &true);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+
+    client.submit_power_surge(&1u32, &p1, &1u32, &13u32, &0u32);
+
+    let m = client.get_match(&1u32);
+    assert_eq!(m.total_xlm_collected, 5_000);
+    assert_eq!(client.get_surge_card(&13u32).cost_multiplier, 5);
+}
+
+#[test]
+fn test_set_surge_card_rejects_zero_multiplier() {
+    let (_env, client, _admin, _p1, _p2, _treasury, _xlm, _verifier) = setup_test();
+
+    let result = client.try_set_surge_card(&1u32, &0u32, &true);
+    assert_contract_error(&result, Error::InvalidSurgeCard);
+}
+
+#[test]
+fn test_one_power_surge_per_player_per_round() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    register_surge_cards(&client);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+
+    client.submit_power_surge(&1u32, &p1, &1u32, &7u32, &0u32);
+    let result = client.try_submit_power_surge(&1u32, &p1, &1u32, &8u32, &1u32);
+    assert_contract_error(&result, Error::SurgeAlreadySubmitted);
+
+    client.submit_power_surge(&1u32, &p2, &1u32, &8u32, &0u32);
+    client.submit_power_surge(&1u32, &p1, &2u32, &8u32, &1u32);
+
+    assert_eq!(client.get_round_surge(&1u32, &1u32, &p1), 7);
+    assert_eq!(client.get_round_surge(&1u32, &1u32, &p2), 8);
+
+    let m = client.get_match(&1u32);
+    assert_eq!(m.player1_surges, 2);
+    assert_eq!(m.total_xlm_collected, 3_000);
+}
+
+#[test]
+fn test_stake_priced_power_surge_accrues_fee() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    register_surge_cards(&client);
+    client.set_surge_card(&13u32, &2u32, &true);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    // 50 bps of a 1 XLM stake = 50_000 stroops per surge.
+    client.set_match_stake(&1u32, &10_000_000i128, &50u32);
+
+    client.submit_power_surge(&1u32, &p1, &1u32, &7u32, &0u32);
+    client.submit_power_surge(&1u32, &p2, &1u32, &13u32, &0u32);
+
+    let m = client.get_match(&1u32);
+    assert_eq!(m.surge_price_bps, 50);
+    assert_eq!(m.total_xlm_collected, 150_000);
+    assert_eq!(m.fee_accrued_stroops, 150_000);
+    assert_eq!(client.get_fee_accrued(), 150_000);
+}
+
+#[test]
+fn test_set_match_stake_rejects_excessive_surge_price() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+
+    let result = client.try_set_match_stake(&1u32, &10_000_000i128, &1_001u32);
+    assert_contract_error(&result, Error::InvalidStake);
+}
+
+#[test]
+fn test_pending_stake_carries_surge_price() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.set_match_stake(&1u32, &10_000_000i128, &25u32);
+    let result = client.try_set_match_stake(&1u32, &10_000_000i128, &30u32);
+    assert_contract_error(&result, Error::InvalidStake);
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    assert_eq!(client.get_match(&1u32).surge_price_bps, 25);
+}
+
+// ============================================================================
+// Character selection
+// ============================================================================
+
+#[test]
+fn test_select_character_records_selection() {
+    let (env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+    let loadout = BytesN::from_array(&env, &[5u8; 32]);
+
+    register_character(&env, &client, 3);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.select_character(&1u32, &p1, &3u32, &loadout);
+
+    let m = client.get_match(&1u32);
+    assert_eq!(m.player1_character, Some(3));
+    assert_eq!(m.player1_loadout_hash, Some(loadout));
+    assert!(m.player2_character.is_none());
+}
+
+#[test]
+fn test_select_character_only_once() {
+    let (env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+    let loadout = BytesN::from_array(&env, &[5u8; 32]);
+
+    register_character(&env, &client, 3);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.select_character(&1u32, &p1, &3u32, &loadout);
+
+    let result = client.try_select_character(&1u32, &p1, &4u32, &loadout);
+    assert_contract_error(&result, Error::CharacterAlreadySelected);
+}
+
+#[test]
+fn test_select_character_closed_after_first_move() {
+    let (env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+    let loadout = BytesN::from_array(&env, &[5u8; 32]);
+
+    register_character(&env, &client, 4);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.submit_move(&1u32, &p1, &MoveType::Punch, &1u32, &1u32, &0u32);
+
+    let result = client.try_select_character(&1u32, &p2, &4u32, &loadout);
+    assert_contract_error(&result, Error::CharacterSelectionClosed);
+}
+
+#[test]
+fn test_select_character_rejects_unknown_and_disabled() {
+    let (env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+    let loadout = BytesN::from_array(&env, &[5u8; 32]);
+    let name_hash = BytesN::from_array(&env, &[8u8; 32]);
+
+    client.set_character(&8u32, &name_hash, &false, &2u32);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+
+    let result = client.try_select_character(&1u32, &p1, &99u32, &loadout);
+    assert_contract_error(&result, Error::UnknownCharacter);
+
+    let result = client.try_select_character(&1u32, &p1, &8u32, &loadout);
+    assert_contract_error(&result, Error::CharacterDisabled);
+}
+
+#[test]
+fn test_roster_lists_characters_and_tracks_patches() {
+    let (env, client, _admin, _p1, _p2, _treasury, _xlm, _verifier) = setup_test();
+
+    register_character(&env, &client, 1);
+    register_character(&env, &client, 2);
+    let name_hash = BytesN::from_array(&env, &[1u8; 32]);
+    client.set_character(&1u32, &name_hash, &false, &3u32);
+
+    let roster = client.get_roster();
+    assert_eq!(roster.len(), 2);
+    let patched = client.get_character(&1u32);
+    assert!(!patched.enabled);
+    assert_eq!(patched.patch_version, 3);
+}
+
+// ============================================================================
+// Seasons
+// ============================================================================
+
+#[test]
+fn test_end_game_updates_season_stats_and_leaderboard() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.set_zk_gate_required(&false);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.end_game(&1u32, &true);
+
+    let winner = client.get_player_stats(&0u32, &p1);
+    let loser = client.get_player_stats(&0u32, &p2);
+    assert_eq!(winner.wins, 1);
+    assert_eq!(winner.rating, 1_216);
+    assert_eq!(loser.losses, 1);
+    assert_eq!(loser.rating, 1_184);
+
+    let board = client.get_leaderboard(&0u32);
+    assert_eq!(board.len(), 2);
+    assert_eq!(board.get(0).unwrap().player, p1);
+    assert_eq!(board.get(1).unwrap().player, p2);
+}
+
+#[test]
+fn test_new_season_resets_stats_and_keeps_history() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.set_zk_gate_required(&false);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.end_game(&1u32, &true);
+
+    client.start_season(&1u32);
+    assert_eq!(client.get_current_season(), 1);
+
+    client.start_game(&2u32, &p1, &p2, &100_000, &100_000);
+    assert_eq!(client.get_match(&2u32).season_id, 1);
+    client.end_game(&2u32, &false);
+
+    let season1 = client.get_player_stats(&1u32, &p2);
+    assert_eq!(season1.wins, 1);
+    assert_eq!(season1.rating, 1_216);
+    assert_eq!(client.get_leaderboard(&1u32).get(0).unwrap().player, p2);
+
+    let season0 = client.get_player_stats(&0u32, &p2);
+    assert_eq!(season0.losses, 1);
+    assert_eq!(season0.wins, 0);
+}
+
+#[test]
+fn test_start_season_must_increase() {
+    let (_env, client, _admin, _p1, _p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.start_season(&3u32);
+
+    let result = client.try_start_season(&3u32);
+    assert_contract_error(&result, Error::InvalidSeason);
+    let result = client.try_start_season(&2u32);
+    assert_contract_error(&result, Error::InvalidSeason);
+}
+
+// ============================================================================
+// Achievements
+// ============================================================================
+
+#[test]
+fn test_first_win_and_flawless_achievements() {
+    let (env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.set_zk_gate_required(&false);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.record_round_result(&1u32, &1u32, &true);
+    client.record_round_result(&1u32, &2u32, &true);
+    client.end_game(&1u32, &true);
+
+    let unlocked = client.get_achievements(&p1);
+    assert_eq!(
+        unlocked,
+        vec![&env, Achievement::FirstWin, Achievement::FlawlessVictory]
+    );
+    assert_eq!(client.get_achievements(&p2).len(), 0);
+}
+
+#[test]
+fn test_round_lost_is_not_flawless() {
+    let (env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.set_zk_gate_required(&false);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.record_round_result(&1u32, &1u32, &false);
+    client.record_round_result(&1u32, &2u32, &true);
+    client.record_round_result(&1u32, &3u32, &true);
+    client.end_game(&1u32, &true);
+
+    assert_eq!(client.get_achievements(&p1), vec![&env, Achievement::FirstWin]);
+}
+
+#[test]
+fn test_round_results_must_be_sequential() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.record_round_result(&1u32, &1u32, &true);
+
+    let result = client.try_record_round_result(&1u32, &1u32, &true);
+    assert_contract_error(&result, Error::InvalidRound);
+}
+
+#[test]
+fn test_win_streak_achievement_and_reset() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.set_zk_gate_required(&false);
+    for session_id in 1u32..=10 {
+        client.start_game(&session_id, &p1, &p2, &100_000, &100_000);
+        client.end_game(&session_id, &true);
+    }
+
+    assert_eq!(client.get_win_streak(&p1), 10);
+    assert!(client.get_achievements(&p1).contains(Achievement::WinStreak10));
+
+    client.start_game(&11u32, &p1, &p2, &100_000, &100_000);
+    client.end_game(&11u32, &false);
+    assert_eq!(client.get_win_streak(&p1), 0);
+    assert_eq!(client.get_win_streak(&p2), 1);
+}
+
+// ============================================================================
+// Trophy NFT hook
+// ============================================================================
+
+#[test]
+fn test_high_stake_win_mints_trophy() {
+    let (env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+    let nft_addr = env.register(MockTrophyNft, ());
+    let nft = MockTrophyNftClient::new(&env, &nft_addr);
+
+    client.set_zk_gate_required(&false);
+    client.set_trophy_nft(&Some(nft_addr.clone()), &10_000_000i128);
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.set_match_stake(&1u32, &10_000_000i128, &0u32);
+    client.deposit_stake(&1u32, &p1);
+    client.deposit_stake(&1u32, &p2);
+    client.end_game(&1u32, &false);
+
+    let (minted_to, _metadata_hash) = nft.minted(&1u32).unwrap();
+    assert_eq!(minted_to, p2);
+}
+
+#[test]
+fn test_failing_trophy_mint_does_not_block_settlement() {
+    let (env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+    // A contract without `mint_trophy` makes every mint fail.
+    let broken_nft = env.register(MockZkVerifier, ());
+
+    client.set_zk_gate_required(&false);
+    client.set_trophy_nft(&Some(broken_nft), &10_000_000i128);
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.set_match_stake(&1u32, &10_000_000i128, &0u32);
+    client.deposit_stake(&1u32, &p1);
+    client.deposit_stake(&1u32, &p2);
+    client.end_game(&1u32, &false);
+
+    assert_eq!(client.get_match(&1u32).winner, Some(p2));
+}
+
+#[test]
+fn test_low_stake_win_skips_trophy() {
+    let (env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+    let nft_addr = env.register(MockTrophyNft, ());
+    let nft = MockTrophyNftClient::new(&env, &nft_addr);
+
+    client.set_zk_gate_required(&false);
+    client.set_trophy_nft(&Some(nft_addr.clone()), &20_000_000i128);
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.set_match_stake(&1u32, &10_000_000i128, &0u32);
+    client.deposit_stake(&1u32, &p1);
+    client.deposit_stake(&1u32, &p2);
+    client.end_game(&1u32, &true);
+
+    client.start_game(&2u32, &p1, &p2, &100_000, &100_000);
+    client.end_game(&2u32, &true);
+
+    assert!(nft.minted(&1u32).is_none());
+    assert!(nft.minted(&2u32).is_none());
+}
+
+// ============================================================================
+// Open challenges
+// ============================================================================
+
+#[test]
+fn test_open_challenge_accept_creates_staked_session() {
+    let (env, client, _admin, p1, p2, _treasury, xlm_addr, _verifier) = setup_test();
+    let xlm = soroban_sdk::token::Client::new(&env, &xlm_addr);
+    let p1_before = xlm.balance(&p1);
+
+    let challenge_id = client.post_open_challenge(&p1, &10_000_000i128, &0i128);
+    assert_eq!(client.get_open_challenges().len(), 1);
+    assert_eq!(xlm.balance(&p1), p1_before - 10_010_000);
+
+    let session_id = client.accept_open_challenge(&challenge_id, &p2);
+
+    let m = client.get_match(&session_id);
+    assert_eq!(m.player1, p1);
+    assert_eq!(m.player2, p2);
+    assert_eq!(m.stake_amount_stroops, 10_000_000);
+    assert!(m.player1_stake_paid);
+    assert!(m.player2_stake_paid);
+    assert_eq!(client.get_open_challenges().len(), 0);
+    assert_eq!(client.get_outstanding_liabilities(), 20_000_000);
+
+    let result = client.try_accept_open_challenge(&challenge_id, &p2);
+    assert_contract_error(&result, Error::ChallengeNotFound);
+}
+
+#[test]
+fn test_open_challenge_enforces_min_rating() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    let challenge_id = client.post_open_challenge(&p1, &10_000_000i128, &1_300i128);
+
+    let result = client.try_accept_open_challenge(&challenge_id, &p2);
+    assert_contract_error(&result, Error::RatingTooLow);
+
+    let result = client.try_accept_open_challenge(&challenge_id, &p1);
+    assert_contract_error(&result, Error::InvalidChallenge);
+}
+
+#[test]
+fn test_cancel_open_challenge_refunds_escrow() {
+    let (env, client, _admin, p1, _p2, _treasury, xlm_addr, _verifier) = setup_test();
+    let xlm = soroban_sdk::token::Client::new(&env, &xlm_addr);
+    let p1_before = xlm.balance(&p1);
+
+    let challenge_id = client.post_open_challenge(&p1, &10_000_000i128, &0i128);
+    client.cancel_open_challenge(&challenge_id);
+
+    assert_eq!(xlm.balance(&p1), p1_before);
+    assert_eq!(client.get_outstanding_liabilities(), 0);
+    assert_eq!(client.get_open_challenges().len(), 0);
+}
+
+// ============================================================================
+// Betting pool hook
+// ============================================================================
+
+#[test]
+fn test_start_game_opens_pool_and_end_game_settles_it() {
+    let (env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+    let betting_addr = env.register(MockBettingPool, ());
+    let betting = MockBettingPoolClient::new(&env, &betting_addr);
+
+    client.set_zk_gate_required(&false);
+    client.set_betting_pool(&Some(betting_addr.clone()), &false, &600u64);
+
+    client.start_game(&7u32, &p1, &p2, &100_000, &100_000);
+    let pool_id = client.get_match_pool(&7u32).unwrap();
+
+    let (session_id, deadline_ts, status) = betting.pool(&pool_id).unwrap();
+    assert_eq!(session_id, 7);
+    assert_eq!(deadline_ts, env.ledger().timestamp() + 600);
+    assert_eq!(status, 0);
+
+    client.end_game(&7u32, &false);
+    assert_eq!(betting.pool(&pool_id).unwrap().2, 2);
+}
+
+#[test]
+fn test_failing_betting_contract_does_not_block_matches() {
+    let (env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+    let betting_addr = env.register(MockBettingPool, ());
+    let betting = MockBettingPoolClient::new(&env, &betting_addr);
+
+    client.set_zk_gate_required(&false);
+    client.set_betting_pool(&Some(betting_addr.clone()), &false, &600u64);
+
+    // A paused betting contract opens no pool, but the match still starts.
+    betting.set_paused(&true);
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    assert!(client.get_match_pool(&1u32).is_none());
+    client.end_game(&1u32, &true);
+
+    // Pausing after the pool opened cannot block the result either.
+    betting.set_paused(&false);
+    client.start_game(&2u32, &p1, &p2, &100_000, &100_000);
+    let pool_id = client.get_match_pool(&2u32).unwrap();
+    betting.set_paused(&true);
+    client.end_game(&2u32, &false);
+    assert_eq!(client.get_match(&2u32).winner, Some(p2));
+    assert_eq!(betting.pool(&pool_id).unwrap().2, 0);
+}
+
+#[test]
+fn test_staked_only_pool_opens_on_stake_and_refunds_on_cancel() {
+    let (env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+    let betting_addr = env.register(MockBettingPool, ());
+    let betting = MockBettingPoolClient::new(&env, &betting_addr);
+
+    client.set_betting_pool(&Some(betting_addr.clone()), &true, &600u64);
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    assert!(client.get_match_pool(&1u32).is_none());
+
+    client.set_match_stake(&1u32, &10_000_000i128, &0u32);
+    let pool_id = client.get_match_pool(&1u32).unwrap();
+
+    client.cancel_match(&1u32);
+    assert_eq!(betting.pool(&pool_id).unwrap().2, 3);
+}
+
+// ============================================================================
+// Move fee redistribution
+// ============================================================================
+
+#[test]
+fn test_move_fees_split_between_winner_and_protocol() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+    client.set_zk_gate_required(&false);
+    client.set_move_fee_policy(&8_000u32);
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.submit_move(&1u32, &p1, &MoveType::Punch, &1u32, &1u32, &0u32);
+    client.submit_move(&1u32, &p2, &MoveType::Kick, &1u32, &1u32, &0u32);
+    client.submit_move(&1u32, &p1, &MoveType::Block, &1u32, &2u32, &1u32);
+    client.submit_move(&1u32, &p2, &MoveType::Special, &1u32, &2u32, &1u32);
+    client.submit_move(&1u32, &p1, &MoveType::Punch, &1u32, &3u32, &2u32);
+
+    let fees_before = client.get_fee_accrued();
+    client.end_game(&1u32, &true);
+
+    // 5 moves * 1_000 stroops: 80% to the winner, 20% to protocol fees.
+    let payout = client.get_pending_payout(&1u32);
+    assert_eq!(payout.winner, p1);
+    assert_eq!(payout.amount_stroops, 4_000);
+    assert_eq!(client.get_fee_accrued(), fees_before + 1_000);
+    assert_eq!(client.claim_winnings(&1u32), 4_000);
+}
+
+#[test]
+fn test_move_fee_share_adds_to_stake_payout() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+    client.set_zk_gate_required(&false);
+    client.set_move_fee_policy(&10_000u32);
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.set_match_stake(&1u32, &10_000_000i128, &0u32);
+    client.deposit_stake(&1u32, &p1);
+    client.deposit_stake(&1u32, &p2);
+    client.submit_move(&1u32, &p2, &MoveType::Kick, &1u32, &1u32, &0u32);
+    client.end_game(&1u32, &false);
+
+    let payout = client.get_pending_payout(&1u32);
+    assert_eq!(payout.amount_stroops, 20_001_000);
+    assert_eq!(client.get_outstanding_liabilities(), 20_001_000);
+}
+
+#[test]
+fn test_move_fee_share_capped_at_free_liquidity() {
+    let (env, client, _admin, p1, p2, _treasury, xlm, _verifier) = setup_test();
+    client.set_zk_gate_required(&false);
+    client.set_move_fee_policy(&8_000u32);
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    for turn in 1u32..=5 {
+        client.submit_move(&1u32, &p1, &MoveType::Punch, &1u32, &turn, &(turn - 1));
+    }
+
+    // Leave only 1_000 stroops above the reserve.
+    let token = soroban_sdk::token::Client::new(&env, &xlm);
+    token.burn(&client.address, &100_004_000);
+
+    let fees_before = client.get_fee_accrued();
+    client.end_game(&1u32, &true);
+
+    assert_eq!(client.get_pending_payout(&1u32).amount_stroops, 1_000);
+    assert_eq!(client.get_fee_accrued(), fees_before + 4_000);
+    assert_eq!(client.get_outstanding_liabilities(), 1_000);
+}
+
+#[test]
+fn test_move_fee_policy_rejects_over_100_percent() {
+    let (_env, client, _admin, _p1, _p2, _treasury, _xlm, _verifier) = setup_test();
+    let result = client.try_set_move_fee_policy(&10_001u32);
+    assert_contract_error(&result, Error::InvalidFeePolicy);
+    assert_eq!(client.get_move_fee_policy(), 0);
+}
+
+// ============================================================================
+// Deposit quote
+// ============================================================================
+
+#[test]
+fn test_quote_deposit_matches_deposit_transfer() {
+    let (env, client, _admin, p1, p2, _treasury, xlm_addr, _verifier) = setup_test();
+    let xlm = soroban_sdk::token::Client::new(&env, &xlm_addr);
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    let result = client.try_quote_deposit(&1u32);
+    assert_contract_error(&result, Error::StakeNotConfigured);
+
+    client.set_match_stake(&1u32, &10_000_000i128, &0u32);
+    let quote = client.quote_deposit(&1u32);
+    assert_eq!(quote.token, xlm_addr);
+    assert_eq!(quote.fee_stroops, 10_000);
+    assert_eq!(quote.amount_stroops, 10_010_000);
+    assert_eq!(quote.deadline_ts, env.ledger().timestamp() + 60);
+
+    let before = xlm.balance(&p1);
+    client.deposit_stake(&1u32, &p1);
+    assert_eq!(before - xlm.balance(&p1), quote.amount_stroops);
+}
+
+// ============================================================================
+// Result attestation
+// ============================================================================
+
+#[test]
+fn test_matching_attestations_finalize_without_zk_gate() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.submit_result_attestation(&1u32, &p1, &false);
+
+    let result = client.try_finalize_attested_result(&1u32);
+    assert_contract_error(&result, Error::AttestationMissing);
+
+    client.submit_result_attestation(&1u32, &p2, &false);
+    client.finalize_attested_result(&1u32);
+
+    let m = client.get_match(&1u32);
+    assert_eq!(m.winner, Some(p2));
+}
+
+#[test]
+fn test_conflicting_attestations_cannot_finalize() {
+    let (env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.submit_result_attestation(&1u32, &p1, &true);
+    client.submit_result_attestation(&1u32, &p2, &false);
+
+    let result = client.try_finalize_attested_result(&1u32);
+    assert_contract_error(&result, Error::AttestationMismatch);
+
+    let outsider = Address::generate(&env);
+    let result = client.try_submit_result_attestation(&1u32, &outsider, &true);
+    assert_contract_error(&result, Error::NotPlayer);
+}
+
+// ============================================================================
+// Match schema migration
+// ============================================================================
+
+#[test]
+fn test_migrate_match_upgrades_legacy_layout() {
+    let (env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    let legacy = MatchV1 {
+        player1: p1.clone(),
+        player2: p2.clone(),
+        player1_points: 100_000,
+        player2_points: 100_000,
+        player1_moves: 3,
+        player2_moves: 2,
+        total_xlm_collected: 5_000,
+        stake_amount_stroops: 0,
+        stake_fee_bps: 0,
+        stake_deadline_ts: 0,
+        player1_stake_paid: false,
+        player2_stake_paid: false,
+        fee_accrued_stroops: 0,
+        player1_zk_commits: 0,
+        player2_zk_commits: 0,
+        player1_zk_verified: 0,
+        player2_zk_verified: 0,
+        is_cancelled: false,
+        winner: None,
+    };
+    env.as_contract(&client.address, || {
+        env.storage().temporary().set(&DataKey::Match(5), &legacy);
+    });
+
+    assert_eq!(client.get_match_version(&5u32), 1);
+    assert_eq!(client.migrate_match(&5u32), 2);
+    assert_eq!(client.get_match_version(&5u32), 2);
+
+    let m = client.get_match(&5u32);
+    assert_eq!(m.player1, p1);
+    assert_eq!(m.player1_moves, 3);
+    assert_eq!(m.total_xlm_collected, 5_000);
+    assert_eq!(m.player1_character, None);
+
+    // Already current: migration is a no-op.
+    assert_eq!(client.migrate_match(&5u32), 2);
+}
+
+#[test]
+fn test_new_matches_are_tagged_with_current_version() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    assert_eq!(client.get_match_version(&1u32), 2);
+
+    let result = client.try_migrate_match(&2u32);
+    assert_contract_error(&result, Error::MatchNotFound);
+}
+
+// ============================================================================
+// Global stats
+// ============================================================================
+
+#[test]
+fn test_get_stats_tracks_lifecycle_totals() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+    client.set_zk_gate_required(&false);
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.set_match_stake(&1u32, &10_000_000i128, &0u32);
+    client.deposit_stake(&1u32, &p1);
+    client.deposit_stake(&1u32, &p2);
+    client.submit_move(&1u32, &p1, &MoveType::Punch, &1u32, &1u32, &0u32);
+    client.submit_move(&1u32, &p2, &MoveType::Kick, &1u32, &1u32, &0u32);
+    client.end_game(&1u32, &true);
+
+    client.start_game(&2u32, &p1, &p2, &100_000, &100_000);
+
+    let stats = client.get_stats();
+    assert_eq!(stats.matches_started, 2);
+    assert_eq!(stats.matches_settled, 1);
+    assert_eq!(stats.total_staked_stroops, 20_000_000);
+    assert_eq!(stats.total_moves, 2);
+    // 2 * 0.1% stake fee + both moves under the default all-protocol policy.
+    assert_eq!(stats.total_fees_stroops, 22_000);
+}
+
+// ============================================================================
+// Best-of-three series
+// ============================================================================
+
+#[test]
+fn test_series_pays_stake_once_a_player_wins_two_legs() {
+    let (env, client, _admin, p1, p2, _treasury, xlm_addr, _verifier) = setup_test();
+    let xlm = soroban_sdk::token::Client::new(&env, &xlm_addr);
+    client.set_zk_gate_required(&false);
+
+    let p2_before = xlm.balance(&p2);
+    let series_id = client.create_series(&p1, &p2, &10_000_000i128);
+    assert_eq!(xlm.balance(&p2), p2_before - 10_010_000);
+    assert_eq!(client.get_outstanding_liabilities(), 20_000_000);
+
+    client.start_series_game(&series_id, &1u32);
+    let result = client.try_start_series_game(&series_id, &2u32);
+    assert_contract_error(&result, Error::SeriesLegInProgress);
+    client.end_game(&1u32, &false);
+
+    client.start_series_game(&series_id, &2u32);
+    client.end_game(&2u32, &true);
+    assert!(client.try_get_pending_payout(&2u32).is_err());
+
+    client.start_series_game(&series_id, &3u32);
+    client.end_game(&3u32, &false);
+
+    let series = client.get_series(&series_id);
+    assert_eq!(series.winner, Some(p2.clone()));
+    assert_eq!(series.player1_wins, 1);
+    assert_eq!(series.player2_wins, 2);
+    assert_eq!(series.session_ids, vec![&env, 1u32, 2u32, 3u32]);
+    assert_eq!(client.get_session_series(&3u32), Some(series_id));
+
+    assert_eq!(client.claim_winnings(&3u32), 20_000_000);
+    assert_eq!(client.get_outstanding_liabilities(), 0);
+
+    let result = client.try_start_series_game(&series_id, &4u32);
+    assert_contract_error(&result, Error::SeriesAlreadyDecided);
+}
+
+#[test]
+fn test_series_accrues_fee_on_deposit() {
+    let (_env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+    client.set_zk_gate_required(&false);
+
+    let fees_before = client.get_fee_accrued();
+    client.create_series(&p1, &p2, &10_000_000i128);
+    assert_eq!(client.get_fee_accrued(), fees_before + 20_000);
+}
+
+#[test]
+fn test_idle_series_refunds_stakes_after_timeout() {
+    let (env, client, _admin, p1, p2, _treasury, xlm_addr, _verifier) = setup_test();
+    let xlm = soroban_sdk::token::Client::new(&env, &xlm_addr);
+    client.set_zk_gate_required(&false);
+
+    let series_id = client.create_series(&p1, &p2, &10_000_000i128);
+    client.start_series_game(&series_id, &1u32);
+    client.end_game(&1u32, &true);
+    client.start_series_game(&series_id, &2u32);
+
+    let result = client.try_refund_series(&series_id);
+    assert_contract_error(&result, Error::DeadlineNotReached);
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 604_800);
+    let p1_before = xlm.balance(&p1);
+    let p2_before = xlm.balance(&p2);
+    client.refund_series(&series_id);
+    assert_eq!(xlm.balance(&p1), p1_before + 10_000_000);
+    assert_eq!(xlm.balance(&p2), p2_before + 10_000_000);
+    assert_eq!(client.get_outstanding_liabilities(), 0);
+
+    // The abandoned leg can still end, but no longer decides the series.
+    client.end_game(&2u32, &true);
+    let series = client.get_series(&series_id);
+    assert!(series.is_refunded);
+    assert_eq!(series.winner, None);
+    assert!(client.try_get_pending_payout(&2u32).is_err());
+
+    assert_contract_error(&client.try_refund_series(&series_id), Error::SeriesRefunded);
+    let result = client.try_start_series_game(&series_id, &3u32);
+    assert_contract_error(&result, Error::SeriesRefunded);
+}
+
+// ============================================================================
+// Relayed moves
+// ============================================================================
+
+#[test]
+fn test_submit_move_for_charges_relayer_and_consumes_nonce() {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    let (env, client, _admin, p1, p2, _treasury, xlm_addr, _verifier) = setup_test();
+    let xlm = soroban_sdk::token::Client::new(&env, &xlm_addr);
+    let relayer = Address::generate(&env);
+    soroban_sdk::token::StellarAssetClient::new(&env, &xlm_addr).mint(&relayer, &10_000_000);
+
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+    client.set_move_signer(&p1, &public_key);
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+
+    let payload = client.move_intent_payload(&1u32, &p1, &MoveType::Kick, &1u32, &1u32, &0u32);
+    let mut payload_bytes = [0u8; 256];
+    let len = payload.len() as usize;
+    payload.copy_into_slice(&mut payload_bytes[..len]);
+    let signature = BytesN::from_array(&env, &signing_key.sign(&payload_bytes[..len]).to_bytes());
+
+    let p1_before = xlm.balance(&p1);
+    client.submit_move_for(&relayer, &1u32, &p1, &MoveType::Kick, &1u32, &1u32, &0u32, &signature);
+
+    assert_eq!(xlm.balance(&p1), p1_before);
+    assert_eq!(xlm.balance(&relayer), 10_000_000 - 1_000);
+    assert_eq!(client.get_match(&1u32).player1_moves, 1);
+    assert_eq!(client.get_action_nonce(&1u32, &p1), 1);
+
+    // Replaying the signature reuses the consumed nonce; bumping the nonce
+    // changes the payload so the signature no longer verifies.
+    let replay =
+        client.try_submit_move_for(&relayer, &1u32, &p1, &MoveType::Kick, &1u32, &1u32, &0u32, &signature);
+    assert_contract_error(&replay, Error::InvalidNonce);
+    let replay =
+        client.try_submit_move_for(&relayer, &1u32, &p1, &MoveType::Kick, &1u32, &2u32, &1u32, &signature);
+    assert!(replay.is_err());
+}
+
+#[test]
+fn test_submit_move_for_requires_registered_signer() {
+    let (env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+    let relayer = Address::generate(&env);
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    let signature = BytesN::from_array(&env, &[0u8; 64]);
+    let result = client.try_submit_move_for(&relayer, &1u32, &p1, &MoveType::Punch, &1u32, &1u32, &0u32, &signature);
+    assert_contract_error(&result, Error::MoveSignerNotSet);
+}
+
+// ============================================================================
+// Action nonces
+// ============================================================================
+
+#[test]
+fn test_retried_move_with_same_nonce_is_not_charged_twice() {
+    let (env, client, _admin, p1, p2, _treasury, xlm_addr, _verifier) = setup_test();
+    let xlm = soroban_sdk::token::Client::new(&env, &xlm_addr);
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    let before = xlm.balance(&p1);
+    client.submit_move(&1u32, &p1, &MoveType::Punch, &1u32, &1u32, &0u32);
+
+    let result = client.try_submit_move(&1u32, &p1, &MoveType::Punch, &1u32, &1u32, &0u32);
+    assert_contract_error(&result, Error::InvalidNonce);
+    assert_eq!(xlm.balance(&p1), before - 1_000);
+    assert_eq!(client.get_action_nonce(&1u32, &p1), 1);
+}
+
+#[test]
+fn test_action_nonce_is_shared_across_actions_and_per_player() {
+    let (env, client, _admin, p1, p2, _treasury, _xlm, _verifier) = setup_test();
+    register_surge_cards(&client);
+
+    client.start_game(&1u32, &p1, &p2, &100_000, &100_000);
+    client.submit_power_surge(&1u32, &p1, &1u32, &7u32, &0u32);
+    let commitment = BytesN::from_array(&env, &[3u8; 32]);
+    client.submit_zk_commit(&1u32, &p1, &1u32, &1u32, &commitment, &1u32);
+
+    let result = client.try_submit_move(&1u32, &p1, &MoveType::Kick, &1u32, &1u32, &3u32);
+    assert_contract_error(&result, Error::InvalidNonce);
+    client.submit_move(&1u32, &p1, &MoveType::Kick, &1u32, &1u32, &2u32);
+
+    assert_eq!(client.get_action_nonce(&1u32, &p1), 3);
+    assert_eq!(client.get_action_nonce(&1u32, &p2), 0);
+}
@@ -14,11 +14,21 @@
 //! 5. Admin settles with winner (`settle_pool` / `settle_pool_zk`)
 //! 6. Winners claim payouts (`claim_payout`)
 //!
-//! **Fee:** 1% protocol fee on each bet deposit.
+//! **Fee:** protocol fee on each bet deposit, 1% by default. The admin can
+//! change the default and pools can override it at creation (up to 10%).
+//!
+//! **Bankroll:** Winners are paid a fixed multiple of their stake (2x unless
+//! the pool sets its own), so the house covers everything above the stake.
+//! That share of open stakes counts against the house bankroll and
+//! `commit_bet` is rejected once it would exceed it.
+//!
+//! **Solvency:** every pool tracks its worst-case payout liability, and
+//! `commit_bet` is rejected if liabilities across all pools would exceed the
+//! contract balance minus accrued fees.
 
 use soroban_sdk::{
-    contract, contractclient, contracterror, contractimpl, contracttype,
-    symbol_short, token, Address, Bytes, BytesN, Env, Vec,
+    contract, contractclient, contracterror, contractevent, contractimpl, contracttype,
+    token, xdr::ToXdr, Address, Bytes, BytesN, Env, Map, Vec,
 };
 
 // ==========================================================================
@@ -35,6 +45,63 @@ pub trait ZkVerifier {
     ) -> bool;
 }
 
+// ==========================================================================
+// Veilstar Brawl interface (cross-contract call)
+// ==========================================================================
+
+/// Mirror of `veilstar_brawl::Match`. Field names and types must match the
+/// brawl contract's layout exactly for the cross-contract decode to succeed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BrawlMatch {
+    pub player1: Address,
+    pub player2: Address,
+    pub player1_points: i128,
+    pub player2_points: i128,
+    pub player1_moves: u32,
+    pub player2_moves: u32,
+    pub player1_surges: u32,
+    pub player2_surges: u32,
+    pub player1_rounds_won: u32,
+    pub player2_rounds_won: u32,
+    pub total_xlm_collected: i128,
+    pub stake_amount_stroops: i128,
+    pub stake_fee_bps: u32,
+    pub surge_price_bps: u32,
+    pub stake_deadline_ts: u64,
+    pub player1_stake_paid: bool,
+    pub player2_stake_paid: bool,
+    pub fee_accrued_stroops: i128,
+    pub player1_zk_commits: u32,
+    pub player2_zk_commits: u32,
+    pub player1_zk_verified: u32,
+    pub player2_zk_verified: u32,
+    pub season_id: u32,
+    pub player1_character: Option<u32>,
+    pub player2_character: Option<u32>,
+    pub player1_loadout_hash: Option<BytesN<32>>,
+    pub player2_loadout_hash: Option<BytesN<32>>,
+    pub is_cancelled: bool,
+    pub winner: Option<Address>,
+}
+
+#[contractclient(name = "VeilstarBrawlClient")]
+pub trait VeilstarBrawl {
+    fn get_match(env: Env, session_id: u32) -> BrawlMatch;
+}
+
+// ==========================================================================
+// Outcome oracle interface (cross-contract call)
+// ==========================================================================
+
+/// Result source for matches not run by the brawl contract (e.g. exhibition
+/// matches). Returns the raw outcome for a match and market (`Market as u32`),
+/// in the same encoding as `settle_market`, or None while undecided.
+#[contractclient(name = "OutcomeOracleClient")]
+pub trait OutcomeOracle {
+    fn get_outcome(env: Env, match_id: BytesN<32>, market: u32) -> Option<u32>;
+}
+
 // ==========================================================================
 // Errors
 // ==========================================================================
@@ -63,6 +130,40 @@ pub enum Error {
     BettingDeadlinePassed = 18,
     NothingToSweep = 19,
     SweepTooEarly = 20,
+    InsufficientBankroll = 21,
+    BetLimitExceeded = 22,
+    PoolLimitExceeded = 23,
+    DrawNotAllowed = 24,
+    InvalidOutcome = 25,
+    RevealDeadlinePassed = 26,
+    ReclaimNotAvailable = 27,
+    PublicInputMismatch = 28,
+    BrawlNotConfigured = 29,
+    SessionNotLinked = 30,
+    MatchNotFinished = 31,
+    MatchCancelled = 32,
+    DeadlineNotReached = 33,
+    InsufficientLiquidity = 34,
+    NothingToClaim = 35,
+    NotAllowlisted = 36,
+    Blacklisted = 37,
+    OracleNotConfigured = 38,
+    MatchNotFound = 39,
+    MatchAlreadyEnded = 40,
+    NotZkBet = 41,
+    ZkClaimWindowClosed = 42,
+    NullifierUsed = 43,
+    Paused = 44,
+    NoPendingAdmin = 45,
+    ClaimExpired = 46,
+    RateLimited = 47,
+    MarketExists = 48,
+    AuditorNotConfigured = 49,
+    InvalidDeadline = 50,
+    RevealWindowOpen = 51,
+    CardGameNotConfigured = 52,
+    NotBlacklisted = 53,
+    EncryptedSideTooLong = 54,
 }
 
 // ==========================================================================
@@ -85,6 +186,66 @@ pub enum PoolStatus {
 pub enum BetSide {
     Player1 = 0,
     Player2 = 1,
+    Draw = 2,
+}
+
+/// What a pool is betting on. Player1/Player2 sides map to the market:
+/// - `MatchWinner`: Player1 / Player2 wins the match.
+/// - `TotalMoves`, `RoundCount`: Player1 = over `line`, Player2 = under.
+/// - `FirstBlood`: Player1 / Player2 lands the first hit.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Market {
+    MatchWinner = 0,
+    TotalMoves = 1,
+    FirstBlood = 2,
+    RoundCount = 3,
+}
+
+/// Proof statements checked by `verify_zk_proof`. Each has its own
+/// verification key, and its value is the domain tag every such proof
+/// carries as public input [0], so a proof for one statement never passes
+/// as another.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ProofKind {
+    CommitBet = 1,
+    ClaimBet = 2,
+    CommitNote = 3,
+    ClaimNote = 4,
+}
+
+/// Why an admin cancelled a pool, for downstream systems.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum CancelReason {
+    MatchCancelled = 0,
+    IntegrityIssue = 1,
+    NoContest = 2,
+}
+
+/// Per-pool overrides accepted by `create_pool_with_config`.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PoolConfig {
+    /// Min single bet; 0 uses the 0.1 XLM default.
+    pub min_bet: i128,
+    /// Max single bet; 0 uses the global limit.
+    pub max_bet_per_bettor: i128,
+    /// Max total staked; 0 uses the global limit.
+    pub max_pool_size: i128,
+    /// Fee in bps; None uses the global default.
+    pub fee_bps: Option<u32>,
+    /// Wallet this pool's fees are swept to; None uses the global treasury.
+    pub treasury: Option<Address>,
+    /// Payout on a winning stake in bps (1x-5x); None uses 2x.
+    pub payout_multiplier_bps: Option<u32>,
+    /// Minimum reveal time after lock before settlement; None uses the
+    /// global default.
+    pub reveal_window_secs: Option<u64>,
 }
 
 #[contracttype]
@@ -92,22 +253,136 @@ pub enum BetSide {
 pub struct BetPool {
     pub pool_id: u32,
     pub match_id: BytesN<32>,
+    pub market: Market,
+    /// Over/under threshold for `TotalMoves` and `RoundCount`, else 0.
+    pub line: u32,
     pub status: PoolStatus,
     pub player1_total: i128,
     pub player2_total: i128,
+    pub draw_total: i128,
     pub total_pool: i128,
     pub total_fees: i128,
     pub bet_count: u32,
     pub reveal_count: u32,
     pub deadline_ts: u64,
-    /// Winner side: 0=Player1, 1=Player2, 255=None
+    /// Winner side: 0=Player1, 1=Player2, 2=Draw, 255=None
+    pub winner_side: u32,
+    /// Whether Draw is a valid side for this pool.
+    pub allow_draw: bool,
+    /// Unix timestamp after which reveals are rejected; 0 = no deadline.
+    pub reveal_deadline_ts: u64,
+    /// Share of an unrevealed stake kept as penalty, in bps. 10_000 means
+    /// unrevealed bets are forfeited entirely.
+    pub forfeit_bps: u32,
+    /// Ledger timestamp of settlement, 0 until settled.
+    pub settled_ts: u64,
+    /// Brawl session this pool bets on, used by `settle_from_match`.
+    pub session_id: Option<u32>,
+    /// Only allowlisted addresses may bet when set.
+    pub allowlist_only: bool,
+    /// Fighters of the underlying match, credited the rake share of fees.
+    pub fighter1: Option<Address>,
+    pub fighter2: Option<Address>,
+    /// Smallest accepted bet for this pool.
+    pub min_bet: i128,
+    /// Per-pool override of the max single bet; 0 uses the global limit.
+    pub max_bet_per_bettor: i128,
+    /// Per-pool override of the max total staked; 0 uses the global limit.
+    pub max_pool_size: i128,
+    /// Fee charged on deposits, fixed for the pool's lifetime.
+    pub fee_bps: u32,
+    /// Worst-case amount the pool can still pay out: every stake winning while
+    /// open, then the unclaimed winnings (and reclaimable stakes) once settled.
+    pub liability: i128,
+    /// Stakes of proof-verified bets that have not been revealed or claimed.
+    pub zk_total: i128,
+    /// Hash chain over the pool's private note commitments, in deposit order.
+    pub notes_root: BytesN<32>,
+    /// Treasury override for sponsored or partner pools.
+    pub treasury: Option<Address>,
+    /// Payout on a winning stake in bps (20_000 = 2x).
+    pub payout_multiplier_bps: u32,
+    /// Share of each winning payout actually paid, in bps. Below 10_000 when
+    /// the contract could not cover every winner at settlement.
+    pub haircut_bps: u32,
+    /// Winnings and reclaimed stakes paid out since settlement.
+    pub claimed_total: i128,
+    /// Ledger timestamp of lock, 0 while open.
+    pub locked_ts: u64,
+    /// Seconds after lock during which settlement is rejected so bettors
+    /// can reveal; skipped once every bet is revealed.
+    pub reveal_window_secs: u64,
+}
+
+/// Display metadata for betting UIs. Hashes keep long strings off-chain
+/// while letting clients verify what they show.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolMetadata {
+    pub player1_name_hash: BytesN<32>,
+    pub player2_name_hash: BytesN<32>,
+    pub scheduled_start_ts: u64,
+    pub stream_url_hash: BytesN<32>,
+}
+
+/// Live odds of a pool from its revealed stakes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolOdds {
+    pub player1_total: i128,
+    pub player2_total: i128,
+    pub draw_total: i128,
+    /// Implied probabilities in bps; all zero before any reveal.
+    pub player1_bps: u32,
+    pub player2_bps: u32,
+    pub draw_bps: u32,
+    /// Payout on a winning stake in bps (20_000 = 2x).
+    pub payout_multiplier_bps: u32,
+}
+
+/// Final state of a closed pool, archived in persistent storage.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolArchive {
+    pub pool_id: u32,
+    pub match_id: BytesN<32>,
+    pub market: Market,
+    pub status: PoolStatus,
     pub winner_side: u32,
+    pub total_pool: i128,
+    pub total_fees: i128,
+    pub bet_count: u32,
+    pub closed_ts: u64,
+}
+
+/// Lifetime betting record of a bettor.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BettorStats {
+    pub total_bets: u32,
+    /// Total staked, net of cancelled bets.
+    pub volume: i128,
+    pub wins: u32,
+    /// Consecutive pools (in betting order) won, counted at claim.
+    pub current_streak: u32,
+    pub best_streak: u32,
+    pub last_win_pool: u32,
+}
+
+/// A leaderboard row, ranked by best streak then total wins.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PredictorEntry {
+    pub bettor: Address,
+    pub best_streak: u32,
+    pub wins: u32,
 }
 
 /// Sentinel value for "no side set"
 const SIDE_NONE: u32 = 255;
 const SIDE_P1: u32 = 0;
 const SIDE_P2: u32 = 1;
+const SIDE_DRAW: u32 = 2;
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -117,9 +392,37 @@ pub struct BetCommit {
     pub amount: i128,
     pub fee_paid: i128,
     pub revealed: bool,
-    /// Revealed side: 0=Player1, 1=Player2, 255=None
+    /// Revealed side: 0=Player1, 1=Player2, 2=Draw, 255=None
     pub side: u32,
     pub claimed: bool,
+    /// Referrer credited a share of this bet's fee at settlement.
+    pub referrer: Option<Address>,
+    pub referral_fee: i128,
+    /// Commitment was proven well-formed at commit time (`commit_bet_zk`).
+    pub zk_verified: bool,
+    /// Side encrypted off-chain to the auditor key (`commit_bet_audited`).
+    pub encrypted_side: Option<Bytes>,
+}
+
+/// One pool status transition. `actor` is the admin for admin calls and
+/// None for permissionless ones (expiry, proofs, oracles).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatusChange {
+    pub status: PoolStatus,
+    pub ts: u64,
+    pub actor: Option<Address>,
+}
+
+/// A private-mode deposit. The depositor is kept only for refunds; payouts
+/// go to whoever proves ownership of the note (`claim_note`).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NoteDeposit {
+    pub depositor: Address,
+    pub commitment: BytesN<32>,
+    pub amount: i128,
+    pub fee_paid: i128,
 }
 
 #[contracttype]
@@ -136,6 +439,53 @@ pub enum DataKey {
     Pool(u32),
     Bet(u32, Address),      // (pool_id, bettor)
     PoolBettors(u32),       // pool_id -> Vec<Address>
+    MatchPools(BytesN<32>), // match_id -> Vec<u32>
+    BrawlContract,
+    Bankroll,
+    OpenExposure,
+    MaxBetPerBettor,
+    MaxPoolSize,
+    FeeBps,
+    TotalLiability,
+    HouseBalance,
+    RakeBps,
+    RakeBalance(Address),   // fighter -> claimable rake
+    ReferralBps,
+    PoolReferrals(u32),     // pool_id -> Map<referrer, pending fee share>
+    ReferralBalance(Address), // referrer -> claimable referral rewards
+    Allowlisted(Address),
+    Blacklisted(Address),
+    OutcomeOracle,
+    PoolMetadata(u32),
+    DistributeCursor(u32),  // pool_id -> next index into PoolBettors
+    OpenPools,              // Vec<u32> of pools still accepting bets
+    BettorPools(Address),   // bettor -> Vec<u32> of pools bet in
+    PoolNotes(u32),         // pool_id -> Vec<NoteDeposit>
+    Nullifier(BytesN<32>),  // spent note nullifiers
+    PoolHistory(u32),       // pool_id -> Vec<StatusChange>
+    TreasuryFees(Address),  // override treasury -> accrued fees
+    TreasuryFeesTotal,      // sum of all TreasuryFees buckets
+    BettorStats(Address),
+    Paused,
+    PendingAdmin,
+    ArchivedPool(u32),      // pool_id -> PoolArchive
+    AutoSweepThreshold,
+    Leaderboard,            // Vec<PredictorEntry>, best first
+    RateLimit,              // (max commits, window seconds)
+    CommitWindow(Address),  // bettor -> (window start, count, previous window count)
+    MarketPool(BytesN<32>, Market), // (match_id, market) -> canonical pool id
+    CancelReason(u32),      // pool_id -> CancelReason
+    PoolFees(u32),          // pool_id -> fees accrued to the protocol
+    DailyFees(u64),         // day (ts / 86_400) -> fees accrued that day
+    AuditorKey,
+    RevealWindow,
+    SessionPools(u32),      // brawl session_id -> Vec<u32> of linked pools
+    CardGameContract,
+    CardPool(u32),          // card game session_id -> pool_id
+    CardResult(u32),        // pool_id -> winning side reported by the card game
+    ProofVk(ProofKind),     // proof statement -> verification key id
+    RakeBalanceTotal,       // sum of all RakeBalance entries
+    ReferralBalanceTotal,   // sum of all ReferralBalance entries
 }
 
 // ==========================================================================
@@ -145,15 +495,319 @@ pub enum DataKey {
 /// 30-day TTL in ledgers (~5s per ledger)
 const POOL_TTL_LEDGERS: u32 = 518_400;
 
-/// 1% protocol fee in basis points
+/// ~1-year TTL in ledgers for archived pool records
+const ARCHIVE_TTL_LEDGERS: u32 = 6_307_200;
+
+/// Age after which an archived pool record may be pruned
+const ARCHIVE_RETENTION_SECONDS: u64 = 365 * 86_400;
+
+/// Default 1% protocol fee in basis points
 const FEE_BPS: u32 = 100;
 
+/// Upper bound for any configured fee: 10%
+const MAX_FEE_BPS: u32 = 1_000;
+
+/// Upper bound for the referrer's share of a bet fee: 50%
+const MAX_REFERRAL_BPS: u32 = 5_000;
+
+/// Upper bound for the fighters' share of pool fees: 50%
+const MAX_RAKE_BPS: u32 = 5_000;
+
 /// 24h sweep interval
 const SWEEP_INTERVAL_SECONDS: u64 = 86_400;
 
 /// Minimum bet amount: 0.1 XLM = 1_000_000 stroops
 const MIN_BET_STROOPS: i128 = 1_000_000;
 
+/// Maximum pools returned by `get_pools`
+const MAX_POOL_PAGE: u32 = 50;
+
+/// Pool ids per page returned by `get_bettor_pools`
+const BETTOR_PAGE_SIZE: u32 = 20;
+
+/// Bettors processed per `admin_distribute` call when walking the pool
+const DISTRIBUTE_CHUNK: u32 = 25;
+
+/// Payouts made per `admin_distribute` walk. Each payout touches several
+/// ledger entries, so a chunk of mostly winners stops early to stay inside
+/// the transaction footprint limits.
+const DISTRIBUTE_MAX_PAYOUTS: u32 = 8;
+
+/// Minimum reveal window for pools closed by a game contract hook, so
+/// bettors can reveal after the game ends even when no window is configured
+const GAME_REVEAL_WINDOW_SECONDS: u64 = 3_600;
+
+/// Unsettled time past the deadline after which anyone may refund a pool
+const ABANDONED_POOL_SECONDS: u64 = 7 * 86_400;
+
+/// Entries kept on the predictor leaderboard
+const LEADERBOARD_SIZE: u32 = 20;
+
+/// Delay after settlement before unrevealed bettors may reclaim
+const RECLAIM_GRACE_SECONDS: u64 = 86_400;
+
+/// Time after settlement after which unclaimed winnings expire to fees
+const CLAIM_EXPIRY_SECONDS: u64 = 14 * 86_400;
+
+const BPS_DENOMINATOR: u32 = 10_000;
+
+/// House model pays a fixed 2x on winning stakes unless the pool overrides it
+const PAYOUT_MULTIPLIER_BPS: u32 = 20_000;
+
+/// Upper bound for a pool's payout multiplier: 5x
+const MAX_PAYOUT_MULTIPLIER_BPS: u32 = 50_000;
+
+/// Longest audited side ciphertext; one sealed box of the side fits easily
+const MAX_ENCRYPTED_SIDE_BYTES: u32 = 128;
+
+// ==========================================================================
+// Events
+// ==========================================================================
+//
+// Each event is published as (name, key) topics, where the key is the pool
+// ID or the account involved, with the remaining fields as data.
+
+/// ("pool", pool_id) -> (match_id, market): a pool was created
+#[contractevent(topics = ["pool"], data_format = "vec")]
+pub struct PoolCreated {
+    #[topic]
+    pub pool_id: u32,
+    pub match_id: BytesN<32>,
+    pub market: u32,
+}
+
+/// ("bet", pool_id) -> (bettor, amount, total_pool, bet_count): a bet was committed
+#[contractevent(topics = ["bet"], data_format = "vec")]
+pub struct BetCommitted {
+    #[topic]
+    pub pool_id: u32,
+    pub bettor: Address,
+    pub amount: i128,
+    pub total_pool: i128,
+    pub bet_count: u32,
+}
+
+/// ("note", pool_id) -> (commitment, amount, total_pool): a shielded note was committed
+#[contractevent(topics = ["note"], data_format = "vec")]
+pub struct NoteCommitted {
+    #[topic]
+    pub pool_id: u32,
+    pub commitment: BytesN<32>,
+    pub amount: i128,
+    pub total_pool: i128,
+}
+
+/// ("bet_up", pool_id) -> (bettor, amount, total_pool): a bet was topped up
+#[contractevent(topics = ["bet_up"], data_format = "vec")]
+pub struct BetIncreased {
+    #[topic]
+    pub pool_id: u32,
+    pub bettor: Address,
+    pub amount: i128,
+    pub total_pool: i128,
+}
+
+/// ("cancel", pool_id) -> (bettor, refund): a bet was cancelled before the deadline
+#[contractevent(topics = ["cancel"], data_format = "vec")]
+pub struct BetCancelled {
+    #[topic]
+    pub pool_id: u32,
+    pub bettor: Address,
+    pub refund: i128,
+}
+
+/// ("deadline", pool_id) -> (old_deadline_ts, new_deadline_ts): a deadline was extended
+#[contractevent(topics = ["deadline"], data_format = "vec")]
+pub struct DeadlineExtended {
+    #[topic]
+    pub pool_id: u32,
+    pub old_deadline_ts: u64,
+    pub new_deadline_ts: u64,
+}
+
+/// ("lock", pool_id) -> bet_count: betting closed
+#[contractevent(topics = ["lock"], data_format = "single-value")]
+pub struct PoolLocked {
+    #[topic]
+    pub pool_id: u32,
+    pub bet_count: u32,
+}
+
+/// ("reveal", pool_id) -> (bettor, side, side totals, implied odds): a bet was revealed
+#[contractevent(topics = ["reveal"], data_format = "vec")]
+pub struct BetRevealed {
+    #[topic]
+    pub pool_id: u32,
+    pub bettor: Address,
+    pub side: u32,
+    pub totals: (i128, i128, i128),
+    pub odds_bps: (u32, u32, u32),
+}
+
+/// ("haircut", pool_id) -> haircut_bps: winning payouts were scaled down to the funds held
+#[contractevent(topics = ["haircut"], data_format = "single-value")]
+pub struct PayoutHaircut {
+    #[topic]
+    pub pool_id: u32,
+    pub haircut_bps: u32,
+}
+
+/// ("settle", pool_id) -> (winner, total_pool, side totals, implied odds): a pool was settled
+#[contractevent(topics = ["settle"], data_format = "vec")]
+pub struct PoolSettled {
+    #[topic]
+    pub pool_id: u32,
+    pub winner: u32,
+    pub total_pool: i128,
+    pub totals: (i128, i128, i128),
+    pub odds_bps: (u32, u32, u32),
+}
+
+/// ("distrib", pool_id) -> (processed, total_paid): a chunk of payouts was distributed
+#[contractevent(topics = ["distrib"], data_format = "vec")]
+pub struct PayoutsDistributed {
+    #[topic]
+    pub pool_id: u32,
+    pub processed: u32,
+    pub total_paid: i128,
+}
+
+/// ("claim", pool_id) -> (bettor, payout): a payout was claimed
+#[contractevent(topics = ["claim"], data_format = "vec")]
+pub struct PayoutClaimed {
+    #[topic]
+    pub pool_id: u32,
+    pub bettor: Address,
+    pub payout: i128,
+}
+
+/// ("note_clm", pool_id) -> (nullifier, payout): a shielded note was claimed
+#[contractevent(topics = ["note_clm"], data_format = "vec")]
+pub struct NoteClaimed {
+    #[topic]
+    pub pool_id: u32,
+    pub nullifier: BytesN<32>,
+    pub payout: i128,
+}
+
+/// ("zk_rel", pool_id) -> released: unclaimed ZK-verified stakes went back to the house
+#[contractevent(topics = ["zk_rel"], data_format = "single-value")]
+pub struct ZkStakesReleased {
+    #[topic]
+    pub pool_id: u32,
+    pub released: i128,
+}
+
+/// ("expired", pool_id) -> liability: unclaimed payouts expired
+#[contractevent(topics = ["expired"], data_format = "single-value")]
+pub struct PayoutsExpired {
+    #[topic]
+    pub pool_id: u32,
+    pub liability: i128,
+}
+
+/// ("forfeit", pool_id) -> (bettor, payout): a blacklisted winner's payout was voided
+#[contractevent(topics = ["forfeit"], data_format = "vec")]
+pub struct PayoutForfeited {
+    #[topic]
+    pub pool_id: u32,
+    pub bettor: Address,
+    pub payout: i128,
+}
+
+/// ("reclaim", pool_id) -> (bettor, refund, penalty): an unrevealed stake was reclaimed
+#[contractevent(topics = ["reclaim"], data_format = "vec")]
+pub struct StakeReclaimed {
+    #[topic]
+    pub pool_id: u32,
+    pub bettor: Address,
+    pub refund: i128,
+    pub penalty: i128,
+}
+
+/// ("referral", referrer) -> amount: referral rewards were claimed
+#[contractevent(topics = ["referral"], data_format = "single-value")]
+pub struct ReferralClaimed {
+    #[topic]
+    pub referrer: Address,
+    pub amount: i128,
+}
+
+/// ("rake", fighter) -> amount: fighter rake was claimed
+#[contractevent(topics = ["rake"], data_format = "single-value")]
+pub struct RakeClaimed {
+    #[topic]
+    pub fighter: Address,
+    pub amount: i128,
+}
+
+/// ("pool_cxl", pool_id) -> reason: a pool was cancelled and every bettor refunded
+#[contractevent(topics = ["pool_cxl"], data_format = "single-value")]
+pub struct PoolCancelled {
+    #[topic]
+    pub pool_id: u32,
+    pub reason: u32,
+}
+
+/// ("refund", pool_id) -> bet_count: a pool was refunded
+#[contractevent(topics = ["refund"], data_format = "single-value")]
+pub struct PoolRefunded {
+    #[topic]
+    pub pool_id: u32,
+    pub bet_count: u32,
+}
+
+/// ("bankdep", from) -> amount: the house bankroll was funded
+#[contractevent(topics = ["bankdep"], data_format = "single-value")]
+pub struct BankrollDeposited {
+    #[topic]
+    pub from: Address,
+    pub amount: i128,
+}
+
+/// ("bankwd", to) -> amount: house bankroll was withdrawn
+#[contractevent(topics = ["bankwd"], data_format = "single-value")]
+pub struct BankrollWithdrawn {
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+}
+
+/// ("profitwd", to) -> amount: settled house profit was withdrawn
+#[contractevent(topics = ["profitwd"], data_format = "single-value")]
+pub struct ProfitWithdrawn {
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+}
+
+/// ("auto_swp") -> accrued: fees over the threshold were swept to the treasury
+#[contractevent(topics = ["auto_swp"], data_format = "single-value")]
+pub struct FeesAutoSwept {
+    pub accrued: i128,
+}
+
+/// ("sweep_tr", treasury) -> accrued: accrued fees were swept to the treasury
+#[contractevent(topics = ["sweep_tr"], data_format = "single-value")]
+pub struct TreasurySwept {
+    #[topic]
+    pub treasury: Address,
+    pub accrued: i128,
+}
+
+/// ("admin") -> (previous, new): a pending admin accepted the role
+#[contractevent(topics = ["admin"], data_format = "vec")]
+pub struct AdminChanged {
+    pub previous: Address,
+    pub new_admin: Address,
+}
+
+/// ("paused") -> paused: betting was paused or resumed
+#[contractevent(topics = ["paused"], data_format = "single-value")]
+pub struct PausedSet {
+    pub paused: bool,
+}
+
 // ==========================================================================
 // Contract
 // ==========================================================================
@@ -178,7 +832,7 @@ impl ZkBettingContract {
         env.storage().instance().set(&DataKey::XlmToken, &xlm_token);
         env.storage().instance().set(&DataKey::FeeAccrued, &0_i128);
         env.storage().instance().set(&DataKey::LastSweepTs, &0_u64);
-        env.storage().instance().set(&DataKey::PoolCounter, &0_u32);
+        env.storage().persistent().set(&DataKey::PoolCounter, &0_u32);
     }
 
     // ======================================================================
@@ -195,27 +849,163 @@ impl ZkBettingContract {
         match_id: BytesN<32>,
         deadline_ts: u64,
     ) -> Result<u32, Error> {
-        Self::require_admin(&env)?;
+        Self::create_pool_with_config(env, match_id, deadline_ts, PoolConfig::default())
+    }
 
-        let mut counter: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::PoolCounter)
-            .unwrap_or(0);
-        counter += 1;
+    /// Create a pool that overrides the global limits and fee.
+    pub fn create_pool_with_config(
+        env: Env,
+        match_id: BytesN<32>,
+        deadline_ts: u64,
+        config: PoolConfig,
+    ) -> Result<u32, Error> {
+        let admin = Self::require_admin(&env)?;
+        Self::create_pool_internal(
+            &env,
+            admin,
+            match_id,
+            Market::MatchWinner,
+            0,
+            deadline_ts,
+            config,
+            None,
+        )
+    }
+
+    /// Create a match-winner pool linked to a live brawl session. The session
+    /// must exist on the brawl contract and must not have ended.
+    pub fn create_session_pool(
+        env: Env,
+        match_id: BytesN<32>,
+        session_id: u32,
+        deadline_ts: u64,
+    ) -> Result<u32, Error> {
+        let admin = Self::require_admin(&env)?;
+        Self::require_live_session(&env, session_id)?;
+        Self::create_pool_internal(
+            &env,
+            admin,
+            match_id,
+            Market::MatchWinner,
+            0,
+            deadline_ts,
+            PoolConfig::default(),
+            Some(session_id),
+        )
+    }
+
+    /// Create a pool on a secondary market of a match. A match can have
+    /// several pools, one per market, each settled with `settle_market`.
+    pub fn create_market_pool(
+        env: Env,
+        match_id: BytesN<32>,
+        market: Market,
+        line: u32,
+        deadline_ts: u64,
+    ) -> Result<u32, Error> {
+        let admin = Self::require_admin(&env)?;
+        Self::create_pool_internal(
+            &env,
+            admin,
+            match_id,
+            market,
+            line,
+            deadline_ts,
+            PoolConfig::default(),
+            None,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_pool_internal(
+        env: &Env,
+        creator: Address,
+        match_id: BytesN<32>,
+        market: Market,
+        line: u32,
+        deadline_ts: u64,
+        config: PoolConfig,
+        session_id: Option<u32>,
+    ) -> Result<u32, Error> {
+        Self::require_not_paused(env)?;
+
+        // One canonical pool per (match, market); a refunded pool frees the slot.
+        let market_key = DataKey::MarketPool(match_id.clone(), market);
+        if let Some(existing) = env.storage().temporary().get::<_, u32>(&market_key) {
+            if Self::get_pool(env.clone(), existing)
+                .is_ok_and(|pool| pool.status != PoolStatus::Refunded)
+            {
+                return Err(Error::MarketExists);
+            }
+        }
+
+        if config.min_bet < 0 || config.max_bet_per_bettor < 0 || config.max_pool_size < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let min_bet = if config.min_bet > 0 {
+            config.min_bet
+        } else {
+            MIN_BET_STROOPS
+        };
+        if config.max_bet_per_bettor > 0 && min_bet > config.max_bet_per_bettor {
+            return Err(Error::InvalidAmount);
+        }
+
+        let fee_bps = match config.fee_bps {
+            Some(bps) if bps > MAX_FEE_BPS => return Err(Error::InvalidAmount),
+            Some(bps) => bps,
+            None => Self::get_fee_bps(env.clone()),
+        };
+
+        let payout_multiplier_bps = match config.payout_multiplier_bps {
+            Some(bps) if !(BPS_DENOMINATOR..=MAX_PAYOUT_MULTIPLIER_BPS).contains(&bps) => {
+                return Err(Error::InvalidAmount)
+            }
+            Some(bps) => bps,
+            None => PAYOUT_MULTIPLIER_BPS,
+        };
+
+        let counter = Self::get_pool_counter(env.clone()) + 1;
 
         let pool = BetPool {
             pool_id: counter,
-            match_id,
+            match_id: match_id.clone(),
+            market,
+            line,
             status: PoolStatus::Open,
             player1_total: 0,
             player2_total: 0,
+            draw_total: 0,
             total_pool: 0,
             total_fees: 0,
             bet_count: 0,
             reveal_count: 0,
             deadline_ts,
             winner_side: SIDE_NONE,
+            allow_draw: false,
+            reveal_deadline_ts: 0,
+            forfeit_bps: BPS_DENOMINATOR,
+            settled_ts: 0,
+            session_id,
+            allowlist_only: false,
+            fighter1: None,
+            fighter2: None,
+            min_bet,
+            max_bet_per_bettor: config.max_bet_per_bettor,
+            max_pool_size: config.max_pool_size,
+            fee_bps,
+            liability: 0,
+            zk_total: 0,
+            notes_root: BytesN::from_array(env, &[0u8; 32]),
+            treasury: config.treasury,
+            payout_multiplier_bps,
+            haircut_bps: BPS_DENOMINATOR,
+            claimed_total: 0,
+            locked_ts: 0,
+            reveal_window_secs: config
+                .reveal_window_secs
+                .unwrap_or_else(|| Self::get_reveal_window(env.clone())),
         };
 
         let key = DataKey::Pool(counter);
@@ -226,29 +1016,60 @@ impl ZkBettingContract {
 
         // Empty bettors list
         let bettors_key = DataKey::PoolBettors(counter);
-        let empty_bettors: Vec<Address> = Vec::new(&env);
+        let empty_bettors: Vec<Address> = Vec::new(env);
         env.storage().temporary().set(&bettors_key, &empty_bettors);
         env.storage()
             .temporary()
             .extend_ttl(&bettors_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
 
-        env.storage().instance().set(&DataKey::PoolCounter, &counter);
+        if let Some(session_id) = session_id {
+            Self::index_session_pool(env, session_id, counter);
+        }
 
-        env.events().publish(
-            (symbol_short!("pool"), counter),
-            pool.match_id.clone(),
-        );
+        env.storage().temporary().set(&market_key, &counter);
+        env.storage()
+            .temporary()
+            .extend_ttl(&market_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
 
-        Ok(counter)
-    }
+        let match_pools_key = DataKey::MatchPools(match_id);
+        let mut match_pools: Vec<u32> = env
+            .storage()
+            .temporary()
+            .get(&match_pools_key)
+            .unwrap_or(Vec::new(env));
+        match_pools.push_back(counter);
+        env.storage().temporary().set(&match_pools_key, &match_pools);
+        env.storage()
+            .temporary()
+            .extend_ttl(&match_pools_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
 
-    /// Commit a bet with a hidden side.
+        env.storage().persistent().set(&DataKey::PoolCounter, &counter);
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKey::PoolCounter, ARCHIVE_TTL_LEDGERS, ARCHIVE_TTL_LEDGERS);
+        Self::record_status(env, counter, PoolStatus::Open, Some(creator));
+
+        let mut open_pools = Self::get_open_pools(env.clone());
+        open_pools.push_back(counter);
+        Self::save_open_pools(env, open_pools);
+
+        PoolCreated {
+            pool_id: counter,
+            match_id: pool.match_id.clone(),
+            market: market as u32,
+        }
+        .publish(env);
+
+        Ok(counter)
+    }
+
+    /// Commit a bet with a hidden side.
     ///
     /// The commitment is SHA256(side_byte || salt_bytes).
-    /// - side_byte: 0 = Player1, 1 = Player2
+    /// - side_byte: 0 = Player1, 1 = Player2, 2 = Draw
     /// - salt_bytes: 32 random bytes chosen by bettor
     ///
-    /// Bettor deposits `amount + 1% fee` in XLM.
+    /// Bettor deposits `amount` plus the pool's fee in XLM.
     pub fn commit_bet(
         env: Env,
         pool_id: u32,
@@ -258,24 +1079,130 @@ impl ZkBettingContract {
     ) -> Result<(), Error> {
         bettor.require_auth();
 
-        if amount < MIN_BET_STROOPS {
-            return Err(Error::InvalidAmount);
+        Self::commit_bet_internal(&env, pool_id, bettor, commitment, amount, None)
+    }
+
+    /// Commit a bet and credit `referrer` with a share of its fee once the
+    /// pool settles.
+    pub fn commit_bet_with_referrer(
+        env: Env,
+        pool_id: u32,
+        bettor: Address,
+        commitment: BytesN<32>,
+        amount: i128,
+        referrer: Address,
+    ) -> Result<(), Error> {
+        bettor.require_auth();
+
+        if referrer == bettor {
+            return Err(Error::Unauthorized);
+        }
+
+        Self::commit_bet_internal(&env, pool_id, bettor, commitment, amount, Some(referrer))
+    }
+
+    /// Commit a bet and also store its side encrypted to the auditor's public
+    /// key, so unrevealed bets can be audited. Only the auditor can read it;
+    /// other participants learn nothing before the reveal.
+    pub fn commit_bet_audited(
+        env: Env,
+        pool_id: u32,
+        bettor: Address,
+        commitment: BytesN<32>,
+        amount: i128,
+        encrypted_side: Bytes,
+    ) -> Result<(), Error> {
+        bettor.require_auth();
+
+        if Self::get_auditor_key(env.clone()).is_none() {
+            return Err(Error::AuditorNotConfigured);
+        }
+        if encrypted_side.len() > MAX_ENCRYPTED_SIDE_BYTES {
+            return Err(Error::EncryptedSideTooLong);
         }
 
+        Self::commit_bet_internal(&env, pool_id, bettor.clone(), commitment, amount, None)?;
+
+        let bet_key = DataKey::Bet(pool_id, bettor);
+        let mut bet: BetCommit = env
+            .storage()
+            .temporary()
+            .get(&bet_key)
+            .ok_or(Error::BetNotFound)?;
+        bet.encrypted_side = Some(encrypted_side);
+        env.storage().temporary().set(&bet_key, &bet);
+
+        Ok(())
+    }
+
+    /// Commit a bet whose hidden side is proven well-formed by a Groth16
+    /// proof: the commitment opens to Player1 or Player2 and the staked
+    /// amount matches the deposit. Such a bet needs no reveal; after
+    /// settlement the bettor claims with a second proof (`claim_payout_zk`).
+    ///
+    /// Public inputs: [0] = `ProofKind::CommitBet` tag, [1] = commitment,
+    /// [2] = pool_id, [3] = amount.
+    pub fn commit_bet_zk(
+        env: Env,
+        pool_id: u32,
+        bettor: Address,
+        commitment: BytesN<32>,
+        amount: i128,
+        proof: Bytes,
+        public_inputs: Vec<BytesN<32>>,
+    ) -> Result<(), Error> {
+        bettor.require_auth();
+
+        let expected = Vec::from_array(
+            &env,
+            [
+                commitment.clone(),
+                Self::u32_to_bytes32(&env, pool_id),
+                Self::i128_to_bytes32(&env, amount)?,
+            ],
+        );
+        Self::verify_zk_proof(&env, ProofKind::CommitBet, &proof, &public_inputs, &expected)?;
+
+        Self::commit_bet_internal(&env, pool_id, bettor.clone(), commitment, amount, None)?;
+
+        let bet_key = DataKey::Bet(pool_id, bettor);
+        let mut bet: BetCommit = env
+            .storage()
+            .temporary()
+            .get(&bet_key)
+            .ok_or(Error::BetNotFound)?;
+        bet.zk_verified = true;
+        env.storage().temporary().set(&bet_key, &bet);
+
         let pool_key = DataKey::Pool(pool_id);
         let mut pool: BetPool = env
             .storage()
             .temporary()
             .get(&pool_key)
             .ok_or(Error::PoolNotFound)?;
+        pool.zk_total += amount;
+        env.storage().temporary().set(&pool_key, &pool);
 
-        if pool.status != PoolStatus::Open {
-            return Err(Error::PoolNotOpen);
-        }
+        Ok(())
+    }
 
-        if pool.deadline_ts > 0 && env.ledger().timestamp() > pool.deadline_ts {
-            return Err(Error::BettingDeadlinePassed);
-        }
+    fn commit_bet_internal(
+        env: &Env,
+        pool_id: u32,
+        bettor: Address,
+        commitment: BytesN<32>,
+        amount: i128,
+        referrer: Option<Address>,
+    ) -> Result<(), Error> {
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: BetPool = env
+            .storage()
+            .temporary()
+            .get(&pool_key)
+            .ok_or(Error::PoolNotFound)?;
+
+        Self::require_bettable(env, &pool, &bettor, amount)?;
+        Self::consume_commit_quota(env, &bettor)?;
 
         // Check for duplicate
         let bet_key = DataKey::Bet(pool_id, bettor.clone());
@@ -283,9 +1210,15 @@ impl ZkBettingContract {
             return Err(Error::AlreadyCommitted);
         }
 
+        Self::reserve_bet_capacity(env, &mut pool, amount, amount)?;
+
         // Calculate fee
-        let fee = Self::calc_fee(amount);
+        let fee = Self::calc_fee(amount, pool.fee_bps);
         let required = amount + fee;
+        let referral_fee = match referrer {
+            Some(_) => Self::referral_share(env, fee),
+            None => 0,
+        };
 
         // Transfer XLM from bettor → contract
         let xlm_addr: Address = env
@@ -293,7 +1226,7 @@ impl ZkBettingContract {
             .instance()
             .get(&DataKey::XlmToken)
             .expect("XLM not set");
-        let xlm = token::Client::new(&env, &xlm_addr);
+        let xlm = token::Client::new(env, &xlm_addr);
         xlm.transfer(&bettor, &env.current_contract_address(), &required);
 
         // Store bet
@@ -305,6 +1238,10 @@ impl ZkBettingContract {
             revealed: false,
             side: SIDE_NONE,
             claimed: false,
+            referrer: referrer.clone(),
+            referral_fee,
+            zk_verified: false,
+            encrypted_side: None,
         };
 
         env.storage().temporary().set(&bet_key, &bet);
@@ -312,19 +1249,43 @@ impl ZkBettingContract {
             .temporary()
             .extend_ttl(&bet_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
 
+        if let Some(referrer) = referrer {
+            Self::add_pending_referral(env, pool_id, referrer, referral_fee);
+        }
+
         // Add to bettors list
         let bettors_key = DataKey::PoolBettors(pool_id);
         let mut bettors: Vec<Address> = env
             .storage()
             .temporary()
             .get(&bettors_key)
-            .unwrap_or(Vec::new(&env));
+            .unwrap_or(Vec::new(env));
         bettors.push_back(bettor.clone());
         env.storage().temporary().set(&bettors_key, &bettors);
         env.storage()
             .temporary()
             .extend_ttl(&bettors_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
 
+        // Record the pool in the bettor's history (once, even after a cancel).
+        let history_key = DataKey::BettorPools(bettor.clone());
+        let mut history: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&history_key)
+            .unwrap_or(Vec::new(env));
+        if !history.contains(pool_id) {
+            history.push_back(pool_id);
+            env.storage().persistent().set(&history_key, &history);
+        }
+        env.storage()
+            .persistent()
+            .extend_ttl(&history_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+
+        let mut stats = Self::get_bettor_stats(env.clone(), bettor.clone());
+        stats.total_bets += 1;
+        stats.volume += amount;
+        Self::set_bettor_stats(env, &bettor, &stats);
+
         // Update pool
         pool.total_pool += amount;
         pool.total_fees += fee;
@@ -335,17 +1296,110 @@ impl ZkBettingContract {
             .temporary()
             .extend_ttl(&pool_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
 
-        env.events().publish(
-            (symbol_short!("bet"), pool_id),
-            (bettor, amount),
-        );
+        BetCommitted {
+            pool_id,
+            bettor,
+            amount,
+            total_pool: pool.total_pool,
+            bet_count: pool.bet_count,
+        }
+        .publish(env);
 
         Ok(())
     }
 
-    /// Lock the pool — no more bets accepted.
-    pub fn lock_pool(env: Env, pool_id: u32) -> Result<(), Error> {
-        Self::require_admin(&env)?;
+    /// Checks shared by every deposit path.
+    fn require_bettable(
+        env: &Env,
+        pool: &BetPool,
+        bettor: &Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        Self::require_not_paused(env)?;
+
+        if amount < pool.min_bet {
+            return Err(Error::InvalidAmount);
+        }
+
+        if pool.allowlist_only && !Self::is_allowlisted(env.clone(), bettor.clone()) {
+            return Err(Error::NotAllowlisted);
+        }
+
+        if Self::is_blacklisted(env.clone(), bettor.clone()) {
+            return Err(Error::Blacklisted);
+        }
+
+        if pool.status != PoolStatus::Open {
+            return Err(Error::PoolNotOpen);
+        }
+
+        if pool.deadline_ts > 0 && env.ledger().timestamp() > pool.deadline_ts {
+            return Err(Error::BettingDeadlinePassed);
+        }
+
+        Ok(())
+    }
+
+    /// Count a commit against the bettor's rate limit. Uses a sliding window
+    /// approximated from the current and previous fixed windows.
+    fn consume_commit_quota(env: &Env, bettor: &Address) -> Result<(), Error> {
+        let (max_commits, window) = Self::get_rate_limit(env.clone());
+        if max_commits == 0 || window == 0 {
+            return Ok(());
+        }
+
+        let now = env.ledger().timestamp();
+        let key = DataKey::CommitWindow(bettor.clone());
+        let (mut start, mut count, mut previous): (u64, u32, u32) =
+            env.storage().temporary().get(&key).unwrap_or((now, 0, 0));
+        if now >= start + 2 * window {
+            (start, count, previous) = (now - now % window, 0, 0);
+        } else if now >= start + window {
+            (start, count, previous) = (start + window, 0, count);
+        }
+
+        // Weight the previous window by how much of it still overlaps.
+        let overlap = window - (now - start);
+        let estimate = previous as u64 * overlap / window + count as u64;
+        if estimate >= max_commits as u64 {
+            return Err(Error::RateLimited);
+        }
+
+        env.storage()
+            .temporary()
+            .set(&key, &(start, count + 1, previous));
+        let ttl = (2 * window / 5).clamp(1, POOL_TTL_LEDGERS as u64) as u32;
+        env.storage().temporary().extend_ttl(&key, ttl, ttl);
+        Ok(())
+    }
+
+    /// Deposit into a pool's private note set. `commitment` is a note whose
+    /// side and amount are proven well-formed as in `commit_bet_zk`, under
+    /// the note circuit's own key. The note is later claimed to any address
+    /// with `claim_note`, without linking the payout to the depositor.
+    ///
+    /// Public inputs: [0] = `ProofKind::CommitNote` tag, [1] = commitment,
+    /// [2] = pool_id, [3] = amount.
+    pub fn commit_note(
+        env: Env,
+        pool_id: u32,
+        depositor: Address,
+        commitment: BytesN<32>,
+        amount: i128,
+        proof: Bytes,
+        public_inputs: Vec<BytesN<32>>,
+    ) -> Result<(), Error> {
+        depositor.require_auth();
+
+        let expected = Vec::from_array(
+            &env,
+            [
+                commitment.clone(),
+                Self::u32_to_bytes32(&env, pool_id),
+                Self::i128_to_bytes32(&env, amount)?,
+            ],
+        );
+        Self::verify_zk_proof(&env, ProofKind::CommitNote, &proof, &public_inputs, &expected)?;
 
         let pool_key = DataKey::Pool(pool_id);
         let mut pool: BetPool = env
@@ -354,60 +1408,76 @@ impl ZkBettingContract {
             .get(&pool_key)
             .ok_or(Error::PoolNotFound)?;
 
-        if pool.status != PoolStatus::Open {
-            return Err(Error::PoolAlreadyLocked);
-        }
+        Self::require_bettable(&env, &pool, &depositor, amount)?;
+        Self::consume_commit_quota(&env, &depositor)?;
+        Self::reserve_bet_capacity(&env, &mut pool, amount, amount)?;
 
-        pool.status = PoolStatus::Locked;
+        let fee = Self::calc_fee(amount, pool.fee_bps);
+        let xlm_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::XlmToken)
+            .expect("XLM not set");
+        let xlm = token::Client::new(&env, &xlm_addr);
+        xlm.transfer(&depositor, env.current_contract_address(), &(amount + fee));
+
+        let notes_key = DataKey::PoolNotes(pool_id);
+        let mut notes: Vec<NoteDeposit> = env
+            .storage()
+            .temporary()
+            .get(&notes_key)
+            .unwrap_or(Vec::new(&env));
+        notes.push_back(NoteDeposit {
+            depositor,
+            commitment: commitment.clone(),
+            amount,
+            fee_paid: fee,
+        });
+        env.storage().temporary().set(&notes_key, &notes);
+        env.storage()
+            .temporary()
+            .extend_ttl(&notes_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+
+        // notes_root = SHA256(notes_root || commitment)
+        let mut preimage: Bytes = pool.notes_root.clone().into();
+        let commitment_bytes: Bytes = commitment.clone().into();
+        preimage.append(&commitment_bytes);
+        pool.notes_root = env.crypto().sha256(&preimage).into();
 
+        pool.total_pool += amount;
+        pool.total_fees += fee;
+        pool.bet_count += 1;
+        pool.zk_total += amount;
         env.storage().temporary().set(&pool_key, &pool);
         env.storage()
             .temporary()
             .extend_ttl(&pool_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
 
-        env.events().publish(
-            (symbol_short!("lock"), pool_id),
-            pool.bet_count,
-        );
+        NoteCommitted {
+            pool_id,
+            commitment,
+            amount,
+            total_pool: pool.total_pool,
+        }
+        .publish(&env);
 
         Ok(())
     }
 
-    /// Reveal the bet — bettor provides the original `side` + `salt`.
-    /// Contract verifies SHA256(side_byte || salt) == stored commitment.
-    pub fn reveal_bet(
+    /// Add to an existing bet while the pool is open. The hidden side is
+    /// unchanged; the extra stake pays the pool's fee. The bettor must still
+    /// be allowed to bet on the pool, as for `commit_bet`.
+    pub fn increase_bet(
         env: Env,
         pool_id: u32,
         bettor: Address,
-        side: BetSide,
-        salt: BytesN<32>,
+        extra_amount: i128,
     ) -> Result<(), Error> {
         bettor.require_auth();
 
-        Self::reveal_bet_internal(env, pool_id, bettor, side, salt)
-    }
-
-    /// Admin reveal path for house-managed bot betting flow.
-    /// Uses bettor commitment + provided side/salt but does not require bettor auth.
-    pub fn admin_reveal_bet(
-        env: Env,
-        pool_id: u32,
-        bettor: Address,
-        side: BetSide,
-        salt: BytesN<32>,
-    ) -> Result<(), Error> {
-        Self::require_admin(&env)?;
-
-        Self::reveal_bet_internal(env, pool_id, bettor, side, salt)
-    }
-
-    fn reveal_bet_internal(
-        env: Env,
-        pool_id: u32,
-        bettor: Address,
-        side: BetSide,
-        salt: BytesN<32>,
-    ) -> Result<(), Error> {
+        if extra_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
 
         let pool_key = DataKey::Pool(pool_id);
         let mut pool: BetPool = env
@@ -416,10 +1486,6 @@ impl ZkBettingContract {
             .get(&pool_key)
             .ok_or(Error::PoolNotFound)?;
 
-        if pool.status != PoolStatus::Locked {
-            return Err(Error::PoolNotLocked);
-        }
-
         let bet_key = DataKey::Bet(pool_id, bettor.clone());
         let mut bet: BetCommit = env
             .storage()
@@ -427,78 +1493,62 @@ impl ZkBettingContract {
             .get(&bet_key)
             .ok_or(Error::BetNotFound)?;
 
-        if bet.revealed {
-            return Err(Error::AlreadyRevealed);
-        }
-
-        // Recompute commitment: SHA256(side_byte || salt)
-        let side_byte: u8 = match side {
-            BetSide::Player1 => 0,
-            BetSide::Player2 => 1,
-        };
-
-        let mut preimage = Bytes::new(&env);
-        preimage.push_back(side_byte);
-        let salt_bytes: Bytes = salt.into();
-        preimage.append(&salt_bytes);
-
-        let computed_hash = env.crypto().sha256(&preimage);
-        let computed: BytesN<32> = computed_hash.into();
+        // The raised bet must still be one the bettor could place
+        Self::require_bettable(&env, &pool, &bettor, bet.amount + extra_amount)?;
 
-        if computed != bet.commitment {
-            return Err(Error::InvalidReveal);
+        // The commit proof is bound to the original amount.
+        if bet.zk_verified {
+            return Err(Error::InvalidAmount);
         }
 
-        // Valid reveal
-        bet.revealed = true;
-        let side_u32 = match side {
-            BetSide::Player1 => SIDE_P1,
-            BetSide::Player2 => SIDE_P2,
-        };
-        bet.side = side_u32;
+        Self::reserve_bet_capacity(&env, &mut pool, bet.amount + extra_amount, extra_amount)?;
 
+        let fee = Self::calc_fee(extra_amount, pool.fee_bps);
+        let xlm_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::XlmToken)
+            .expect("XLM not set");
+        let xlm = token::Client::new(&env, &xlm_addr);
+        xlm.transfer(&bettor, env.current_contract_address(), &(extra_amount + fee));
+
+        bet.amount += extra_amount;
+        bet.fee_paid += fee;
+        if let Some(referrer) = bet.referrer.clone() {
+            let referral_fee = Self::referral_share(&env, fee);
+            bet.referral_fee += referral_fee;
+            Self::add_pending_referral(&env, pool_id, referrer, referral_fee);
+        }
         env.storage().temporary().set(&bet_key, &bet);
         env.storage()
             .temporary()
             .extend_ttl(&bet_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
 
-        // Update pool totals by side
-        match side {
-            BetSide::Player1 => pool.player1_total += bet.amount,
-            BetSide::Player2 => pool.player2_total += bet.amount,
-        }
-        pool.reveal_count += 1;
+        let mut stats = Self::get_bettor_stats(env.clone(), bettor.clone());
+        stats.volume += extra_amount;
+        Self::set_bettor_stats(&env, &bettor, &stats);
 
+        pool.total_pool += extra_amount;
+        pool.total_fees += fee;
         env.storage().temporary().set(&pool_key, &pool);
         env.storage()
             .temporary()
             .extend_ttl(&pool_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
 
-        env.events().publish(
-            (symbol_short!("reveal"), pool_id),
-            (bettor, side_byte as u32),
-        );
+        BetIncreased {
+            pool_id,
+            bettor,
+            amount: bet.amount,
+            total_pool: pool.total_pool,
+        }
+        .publish(&env);
 
         Ok(())
     }
 
-    /// Settle the pool — admin declares the winner.
-    /// Unrevealed bets are treated as losses (forfeited).
-    pub fn settle_pool(
-        env: Env,
-        pool_id: u32,
-        winner: BetSide,
-    ) -> Result<(), Error> {
-        Self::require_admin(&env)?;
-
-        Self::settle_pool_internal(&env, pool_id, winner)
-    }
-
-    fn settle_pool_internal(
-        env: &Env,
-        pool_id: u32,
-        winner: BetSide,
-    ) -> Result<(), Error> {
+    /// Cancel a bet while the pool is still open, refunding amount + fee.
+    pub fn cancel_bet(env: Env, pool_id: u32, bettor: Address) -> Result<i128, Error> {
+        bettor.require_auth();
 
         let pool_key = DataKey::Pool(pool_id);
         let mut pool: BetPool = env
@@ -507,208 +1557,153 @@ impl ZkBettingContract {
             .get(&pool_key)
             .ok_or(Error::PoolNotFound)?;
 
-        if pool.status == PoolStatus::Settled || pool.status == PoolStatus::Refunded {
-            return Err(Error::PoolAlreadySettled);
+        if pool.status != PoolStatus::Open {
+            return Err(Error::PoolNotOpen);
         }
 
-        pool.status = PoolStatus::Settled;
-        let winner_u32 = match winner {
-            BetSide::Player1 => SIDE_P1,
-            BetSide::Player2 => SIDE_P2,
-        };
-        pool.winner_side = winner_u32;
-
-        env.storage().temporary().set(&pool_key, &pool);
-        env.storage()
-            .temporary()
-            .extend_ttl(&pool_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
-
-        // Accrue fees
-        let mut accrued: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::FeeAccrued)
-            .unwrap_or(0);
-        accrued += pool.total_fees;
-        env.storage().instance().set(&DataKey::FeeAccrued, &accrued);
-
-        let winner_u32 = match winner {
-            BetSide::Player1 => 0u32,
-            BetSide::Player2 => 1u32,
-        };
-
-        env.events().publish(
-            (symbol_short!("settle"), pool_id),
-            winner_u32,
-        );
-
-        Ok(())
-    }
-
-    /// Settle using a ZK proof of the match outcome.
-    /// The proof is verified against the configured zk-groth16-verifier contract.
-    pub fn settle_pool_zk(
-        env: Env,
-        pool_id: u32,
-        winner: BetSide,
-        vk_id: BytesN<32>,
-        proof: Bytes,
-        public_inputs: Vec<BytesN<32>>,
-    ) -> Result<(), Error> {
-        Self::require_admin(&env)?;
+        if pool.deadline_ts > 0 && env.ledger().timestamp() > pool.deadline_ts {
+            return Err(Error::BettingDeadlinePassed);
+        }
 
-        let pool: BetPool = env
+        let bet_key = DataKey::Bet(pool_id, bettor.clone());
+        let bet: BetCommit = env
             .storage()
             .temporary()
-            .get(&DataKey::Pool(pool_id))
-            .ok_or(Error::PoolNotFound)?;
-
-        let winner_side = match winner {
-            BetSide::Player1 => SIDE_P1,
-            BetSide::Player2 => SIDE_P2,
-        };
+            .get(&bet_key)
+            .ok_or(Error::BetNotFound)?;
 
-        // Verify ZK proof
-        let verifier_addr: Address = env
+        let refund = bet.amount + bet.fee_paid;
+        let xlm_addr: Address = env
             .storage()
             .instance()
-            .get(&DataKey::ZkVerifier)
-            .ok_or(Error::ZkVerifierNotConfigured)?;
+            .get(&DataKey::XlmToken)
+            .expect("XLM not set");
+        let xlm = token::Client::new(&env, &xlm_addr);
+        xlm.transfer(&env.current_contract_address(), &bettor, &refund);
 
-        let configured_vk_id: BytesN<32> = env
-            .storage()
-            .instance()
-            .get(&DataKey::ZkVkId)
-            .ok_or(Error::ZkVerifierNotConfigured)?;
+        env.storage().temporary().remove(&bet_key);
 
-        if vk_id != configured_vk_id {
-            return Err(Error::ZkProofInvalid);
+        if let Some(referrer) = bet.referrer.clone() {
+            Self::add_pending_referral(&env, pool_id, referrer, -bet.referral_fee);
         }
 
-        if proof.len() != 256 || public_inputs.len() < 3 {
-            return Err(Error::ZkProofInvalid);
+        let bettors_key = DataKey::PoolBettors(pool_id);
+        let mut bettors: Vec<Address> = env
+            .storage()
+            .temporary()
+            .get(&bettors_key)
+            .unwrap_or(Vec::new(&env));
+        if let Some(index) = bettors.first_index_of(&bettor) {
+            bettors.remove(index);
         }
+        env.storage().temporary().set(&bettors_key, &bettors);
 
-        let input_match_id = public_inputs.get(0).ok_or(Error::ZkProofInvalid)?;
-        let input_pool_id = public_inputs.get(1).ok_or(Error::ZkProofInvalid)?;
-        let input_winner_side = public_inputs.get(2).ok_or(Error::ZkProofInvalid)?;
-
-        let expected_pool_id = Self::u32_to_bytes32(&env, pool_id);
-        let expected_winner_side = Self::u32_to_bytes32(&env, winner_side);
+        Self::release_exposure(&env, Self::house_cover(&pool, bet.amount));
+        let released = Self::payout_for(&pool, bet.amount);
+        Self::reduce_liability(&env, &mut pool, released);
 
-        if input_match_id != pool.match_id
-            || input_pool_id != expected_pool_id
-            || input_winner_side != expected_winner_side
-        {
-            return Err(Error::ZkProofInvalid);
-        }
+        let mut stats = Self::get_bettor_stats(env.clone(), bettor.clone());
+        stats.total_bets -= 1;
+        stats.volume -= bet.amount;
+        Self::set_bettor_stats(&env, &bettor, &stats);
 
-        let verifier = ZkVerifierClient::new(&env, &verifier_addr);
-        let verified = verifier.verify_round_proof(&vk_id, &proof, &public_inputs);
-        if !verified {
-            return Err(Error::ZkProofInvalid);
+        pool.total_pool -= bet.amount;
+        pool.total_fees -= bet.fee_paid;
+        pool.bet_count -= 1;
+        if bet.zk_verified {
+            pool.zk_total -= bet.amount;
         }
 
-        // Proof is valid — proceed with settlement
-        Self::settle_pool_internal(&env, pool_id, winner)
-    }
+        env.storage().temporary().set(&pool_key, &pool);
+        env.storage()
+            .temporary()
+            .extend_ttl(&pool_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
 
-    /// Claim payout after settlement.
-    ///
-    /// House model payout:
-    /// - Winning revealed bet gets fixed `2x` of stake amount.
-    /// - Losing or unrevealed bet gets no payout.
-    pub fn claim_payout(env: Env, pool_id: u32, bettor: Address) -> Result<i128, Error> {
-        bettor.require_auth();
+        BetCancelled {
+            pool_id,
+            bettor,
+            refund,
+        }
+        .publish(&env);
 
-        Self::claim_payout_internal(env, pool_id, bettor)
+        Ok(refund)
     }
 
-    /// Admin claim path for house-managed bot betting flow.
-    /// Transfers payout directly to bettor without requiring bettor auth.
-    pub fn admin_claim_payout(env: Env, pool_id: u32, bettor: Address) -> Result<i128, Error> {
+    /// Name the two fighters of the pool's match so they share the rake.
+    pub fn set_pool_fighters(
+        env: Env,
+        pool_id: u32,
+        fighter1: Address,
+        fighter2: Address,
+    ) -> Result<(), Error> {
         Self::require_admin(&env)?;
 
-        Self::claim_payout_internal(env, pool_id, bettor)
-    }
-
-    fn claim_payout_internal(env: Env, pool_id: u32, bettor: Address) -> Result<i128, Error> {
-
         let pool_key = DataKey::Pool(pool_id);
-        let pool: BetPool = env
+        let mut pool: BetPool = env
             .storage()
             .temporary()
             .get(&pool_key)
             .ok_or(Error::PoolNotFound)?;
 
-        if pool.status != PoolStatus::Settled {
-            return Err(Error::PoolNotSettled);
+        if pool.status == PoolStatus::Settled || pool.status == PoolStatus::Refunded {
+            return Err(Error::PoolAlreadySettled);
         }
 
-        let bet_key = DataKey::Bet(pool_id, bettor.clone());
-        let mut bet: BetCommit = env
-            .storage()
+        pool.fighter1 = Some(fighter1);
+        pool.fighter2 = Some(fighter2);
+        env.storage().temporary().set(&pool_key, &pool);
+        env.storage()
             .temporary()
-            .get(&bet_key)
-            .ok_or(Error::BetNotFound)?;
+            .extend_ttl(&pool_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
 
-        if bet.claimed {
-            return Err(Error::AlreadyClaimed);
-        }
+        Ok(())
+    }
 
-        // Must have revealed and bet on winning side
-        if pool.winner_side == SIDE_NONE {
-            return Err(Error::InvalidWinner);
-        }
-        let pool_winner_side = pool.winner_side;
+    /// Restrict betting on a pool to allowlisted addresses.
+    pub fn set_pool_allowlist_only(env: Env, pool_id: u32, allowlist_only: bool) -> Result<(), Error> {
+        Self::require_admin(&env)?;
 
-        if !bet.revealed {
-            // Unrevealed = forfeited, no payout
-            bet.claimed = true;
-            env.storage().temporary().set(&bet_key, &bet);
-            return Err(Error::NoPayout);
-        }
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: BetPool = env
+            .storage()
+            .temporary()
+            .get(&pool_key)
+            .ok_or(Error::PoolNotFound)?;
 
-        if bet.side != pool_winner_side {
-            // Bet on losing side
-            bet.claimed = true;
-            env.storage().temporary().set(&bet_key, &bet);
-            return Err(Error::NoPayout);
+        if pool.status != PoolStatus::Open {
+            return Err(Error::PoolNotOpen);
         }
 
-        // House fixed payout = 2x stake
-        let payout = bet.amount * 2;
+        pool.allowlist_only = allowlist_only;
+        env.storage().temporary().set(&pool_key, &pool);
+        env.storage()
+            .temporary()
+            .extend_ttl(&pool_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
 
-        if payout <= 0 {
-            return Err(Error::NoPayout);
-        }
+        Ok(())
+    }
 
-        // Transfer payout
-        let xlm_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::XlmToken)
-            .expect("XLM not set");
-        let xlm = token::Client::new(&env, &xlm_addr);
-        xlm.transfer(&env.current_contract_address(), &bettor, &payout);
+    /// Attach display metadata to a pool.
+    pub fn set_pool_metadata(env: Env, pool_id: u32, metadata: PoolMetadata) -> Result<(), Error> {
+        Self::require_admin(&env)?;
 
-        bet.claimed = true;
-        env.storage().temporary().set(&bet_key, &bet);
+        // Pool must exist; metadata lives exactly as long as it.
+        Self::get_pool(env.clone(), pool_id)?;
+
+        let key = DataKey::PoolMetadata(pool_id);
+        env.storage().temporary().set(&key, &metadata);
         env.storage()
             .temporary()
-            .extend_ttl(&bet_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
-
-        env.events().publish(
-            (symbol_short!("claim"), pool_id),
-            (bettor, payout),
-        );
+            .extend_ttl(&key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
 
-        Ok(payout)
+        Ok(())
     }
 
-    /// Refund all bettors (match cancelled).
-    pub fn refund_pool(env: Env, pool_id: u32) -> Result<(), Error> {
+    /// Link a pool to the brawl session it bets on. The session must exist
+    /// and must not have ended.
+    pub fn link_pool_session(env: Env, pool_id: u32, session_id: u32) -> Result<(), Error> {
         Self::require_admin(&env)?;
+        Self::require_live_session(&env, session_id)?;
 
         let pool_key = DataKey::Pool(pool_id);
         let mut pool: BetPool = env
@@ -721,149 +1716,2397 @@ impl ZkBettingContract {
             return Err(Error::PoolAlreadySettled);
         }
 
-        let xlm_addr: Address = env
+        pool.session_id = Some(session_id);
+        env.storage().temporary().set(&pool_key, &pool);
+        env.storage()
+            .temporary()
+            .extend_ttl(&pool_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+        Self::index_session_pool(&env, session_id, pool_id);
+
+        Ok(())
+    }
+
+    fn index_session_pool(env: &Env, session_id: u32, pool_id: u32) {
+        let key = DataKey::SessionPools(session_id);
+        let mut pools: Vec<u32> = env
             .storage()
-            .instance()
-            .get(&DataKey::XlmToken)
-            .expect("XLM not set");
-        let xlm = token::Client::new(&env, &xlm_addr);
+            .temporary()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        if !pools.contains(pool_id) {
+            pools.push_back(pool_id);
+        }
+        env.storage().temporary().set(&key, &pools);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+    }
 
-        // Refund each bettor: amount + fee
-        let bettors_key = DataKey::PoolBettors(pool_id);
-        let bettors: Vec<Address> = env
+    /// Hook for the configured brawl contract: open a match-winner pool
+    /// linked to a new session. The match id is the session id, big-endian
+    /// in the last 4 bytes.
+    pub fn open_brawl_pool(env: Env, session_id: u32, deadline_ts: u64) -> Result<u32, Error> {
+        let brawl = Self::require_brawl(&env)?;
+
+        let mut match_id = [0u8; 32];
+        match_id[28..].copy_from_slice(&session_id.to_be_bytes());
+
+        Self::create_pool_internal(
+            &env,
+            brawl,
+            BytesN::from_array(&env, &match_id),
+            Market::MatchWinner,
+            0,
+            deadline_ts,
+            PoolConfig::default(),
+            Some(session_id),
+        )
+    }
+
+    /// Hook for the configured brawl contract: a session ended with
+    /// `winner`. Every unsettled pool linked to it is locked so bets can be
+    /// revealed; match-winner pools are settled once all bets are revealed
+    /// or the reveal window closes (see `settle_from_match`). Returns the
+    /// pools closed.
+    pub fn notify_match_ended(env: Env, session_id: u32, winner: BetSide) -> Result<u32, Error> {
+        let brawl = Self::require_brawl(&env)?;
+
+        let pools: Vec<u32> = env
             .storage()
             .temporary()
-            .get(&bettors_key)
+            .get(&DataKey::SessionPools(session_id))
             .unwrap_or(Vec::new(&env));
 
-        for i in 0..bettors.len() {
-            let bettor_addr = bettors.get(i).unwrap();
-            let bet_key = DataKey::Bet(pool_id, bettor_addr.clone());
-            if let Some(mut bet) = env.storage().temporary().get::<_, BetCommit>(&bet_key) {
-                if !bet.claimed {
-                    let refund = bet.amount + bet.fee_paid;
-                    xlm.transfer(&env.current_contract_address(), &bettor_addr, &refund);
-                    bet.claimed = true;
-                    env.storage().temporary().set(&bet_key, &bet);
-                }
+        let mut closed = 0;
+        for pool_id in pools.iter() {
+            let Ok(pool) = Self::get_pool(env.clone(), pool_id) else {
+                continue;
+            };
+            if pool.session_id != Some(session_id)
+                || pool.status == PoolStatus::Settled
+                || pool.status == PoolStatus::Refunded
+            {
+                continue;
             }
+            if pool.market == Market::MatchWinner {
+                Self::close_game_pool(&env, pool_id, winner, brawl.clone())?;
+            } else if pool.status == PoolStatus::Open {
+                // Other markets settle from the match record via `settle_from_match`
+                Self::lock_game_pool(&env, pool_id, brawl.clone())?;
+            }
+            closed += 1;
         }
 
-        pool.status = PoolStatus::Refunded;
-        env.storage().temporary().set(&pool_key, &pool);
-        env.storage()
+        Ok(closed)
+    }
+
+    /// Hook for the configured brawl contract: refund every unsettled pool
+    /// linked to a session that was cancelled. Returns the pools refunded.
+    pub fn notify_match_cancelled(env: Env, session_id: u32) -> Result<u32, Error> {
+        let brawl = Self::require_brawl(&env)?;
+
+        let pools: Vec<u32> = env
+            .storage()
             .temporary()
-            .extend_ttl(&pool_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+            .get(&DataKey::SessionPools(session_id))
+            .unwrap_or(Vec::new(&env));
 
-        env.events().publish(
-            (symbol_short!("refund"), pool_id),
-            pool.bet_count,
-        );
+        let mut refunded = 0;
+        for pool_id in pools.iter() {
+            let Ok(pool) = Self::get_pool(env.clone(), pool_id) else {
+                continue;
+            };
+            // Skip pools relinked to another session or already closed.
+            if pool.session_id != Some(session_id)
+                || pool.status == PoolStatus::Settled
+                || pool.status == PoolStatus::Refunded
+            {
+                continue;
+            }
+            Self::refund_pool_internal(&env, pool_id, Some(brawl.clone()))?;
+            refunded += 1;
+        }
 
-        Ok(())
+        Ok(refunded)
     }
 
-    // ======================================================================
-    // Treasury sweep
-    // ======================================================================
+    /// Hook for the configured card game contract: open a match-winner pool
+    /// for a new card game session. Player1/Player2 are the game's seats.
+    pub fn open_card_pool(env: Env, session_id: u32, deadline_ts: u64) -> Result<u32, Error> {
+        let card_game = Self::require_card_game(&env)?;
 
-    /// Transfer accrued protocol fees to treasury (max once per 24h).
+        let mut preimage = card_game.clone().to_xdr(&env);
+        preimage.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
+        let match_id: BytesN<32> = env.crypto().sha256(&preimage).into();
+
+        let pool_id = Self::create_pool_internal(
+            &env,
+            card_game,
+            match_id,
+            Market::MatchWinner,
+            0,
+            deadline_ts,
+            PoolConfig::default(),
+            None,
+        )?;
+
+        let key = DataKey::CardPool(session_id);
+        env.storage().temporary().set(&key, &pool_id);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+
+        Ok(pool_id)
+    }
+
+    /// Hook for the configured card game contract: report how a session
+    /// ended. A winner locks the pool and settles it, or is recorded for
+    /// `settle_from_card_game` while bets can still be revealed; `None`
+    /// (push or abandoned game) refunds it. Pools already closed by the
+    /// admin are left alone so the game can always finish.
+    pub fn settle_card_pool(
+        env: Env,
+        session_id: u32,
+        player1_won: Option<bool>,
+    ) -> Result<(), Error> {
+        let card_game = Self::require_card_game(&env)?;
+        let pool_id: u32 = env
+            .storage()
+            .temporary()
+            .get(&DataKey::CardPool(session_id))
+            .ok_or(Error::PoolNotFound)?;
+
+        let pool = Self::get_pool(env.clone(), pool_id)?;
+        if pool.status == PoolStatus::Settled || pool.status == PoolStatus::Refunded {
+            return Ok(());
+        }
+
+        let Some(player1_won) = player1_won else {
+            return Self::refund_pool_internal(&env, pool_id, Some(card_game));
+        };
+        let winner = if player1_won {
+            BetSide::Player1
+        } else {
+            BetSide::Player2
+        };
+        let key = DataKey::CardResult(pool_id);
+        env.storage().temporary().set(&key, &winner);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+
+        Self::close_game_pool(&env, pool_id, winner, card_game)
+    }
+
+    /// Settle a card game pool from the result its game reported, once the
+    /// reveal window has closed. Permissionless: the result comes from the
+    /// card game contract, not from the caller.
+    pub fn settle_from_card_game(env: Env, pool_id: u32) -> Result<(), Error> {
+        let winner: BetSide = env
+            .storage()
+            .temporary()
+            .get(&DataKey::CardResult(pool_id))
+            .ok_or(Error::MatchNotFinished)?;
+
+        Self::settle_pool_internal(&env, pool_id, winner, None)
+    }
+
+    fn require_brawl(env: &Env) -> Result<Address, Error> {
+        let brawl: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::BrawlContract)
+            .ok_or(Error::BrawlNotConfigured)?;
+        brawl.require_auth();
+        Ok(brawl)
+    }
+
+    /// Close betting on a pool whose game has ended: lock it if still open
+    /// and make sure bettors get a reveal window before it can settle.
+    fn lock_game_pool(env: &Env, pool_id: u32, game: Address) -> Result<(), Error> {
+        let pool = Self::get_pool(env.clone(), pool_id)?;
+        if pool.status == PoolStatus::Open {
+            Self::lock_pool_internal(env, pool_id, Some(game))?;
+        }
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: BetPool = env
+            .storage()
+            .temporary()
+            .get(&pool_key)
+            .ok_or(Error::PoolNotFound)?;
+        if pool.reveal_window_secs < GAME_REVEAL_WINDOW_SECONDS {
+            pool.reveal_window_secs = GAME_REVEAL_WINDOW_SECONDS;
+            env.storage().temporary().set(&pool_key, &pool);
+        }
+        Ok(())
+    }
+
+    /// Lock a game's pool and settle it for `winner` if every bet is
+    /// revealed; otherwise settlement waits for the reveal window to close.
+    fn close_game_pool(
+        env: &Env,
+        pool_id: u32,
+        winner: BetSide,
+        game: Address,
+    ) -> Result<(), Error> {
+        Self::lock_game_pool(env, pool_id, game.clone())?;
+
+        match Self::settle_pool_internal(env, pool_id, winner, Some(game)) {
+            Err(Error::RevealWindowOpen) => Ok(()),
+            result => result,
+        }
+    }
+
+    fn require_card_game(env: &Env) -> Result<Address, Error> {
+        let card_game: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::CardGameContract)
+            .ok_or(Error::CardGameNotConfigured)?;
+        card_game.require_auth();
+        Ok(card_game)
+    }
+
+    /// Enable or disable Draw as a bettable side. Only allowed while the pool
+    /// is open and has no bets, so bettors always know the terms.
+    pub fn set_pool_allow_draw(env: Env, pool_id: u32, allow_draw: bool) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: BetPool = env
+            .storage()
+            .temporary()
+            .get(&pool_key)
+            .ok_or(Error::PoolNotFound)?;
+
+        if pool.status != PoolStatus::Open || pool.bet_count > 0 {
+            return Err(Error::PoolNotOpen);
+        }
+
+        pool.allow_draw = allow_draw;
+        env.storage().temporary().set(&pool_key, &pool);
+        env.storage()
+            .temporary()
+            .extend_ttl(&pool_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+
+        Ok(())
+    }
+
+    /// Set the reveal deadline and the penalty kept from unrevealed stakes.
+    /// With `forfeit_bps` below 10_000, unrevealed bettors can reclaim the rest
+    /// of their stake once the settlement grace window has passed. Only
+    /// allowed before the first bet.
+    pub fn set_reveal_policy(
+        env: Env,
+        pool_id: u32,
+        reveal_deadline_ts: u64,
+        forfeit_bps: u32,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        if forfeit_bps > BPS_DENOMINATOR {
+            return Err(Error::InvalidAmount);
+        }
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: BetPool = env
+            .storage()
+            .temporary()
+            .get(&pool_key)
+            .ok_or(Error::PoolNotFound)?;
+
+        if pool.status != PoolStatus::Open || pool.bet_count > 0 {
+            return Err(Error::PoolNotOpen);
+        }
+
+        pool.reveal_deadline_ts = reveal_deadline_ts;
+        pool.forfeit_bps = forfeit_bps;
+        env.storage().temporary().set(&pool_key, &pool);
+        env.storage()
+            .temporary()
+            .extend_ttl(&pool_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+
+        Ok(())
+    }
+
+    /// Push back the betting deadline of an open pool, e.g. when its match
+    /// is delayed. The new deadline must be later than the current one and
+    /// in the future; pools without a deadline cannot be extended.
+    pub fn extend_deadline(env: Env, pool_id: u32, new_deadline_ts: u64) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: BetPool = env
+            .storage()
+            .temporary()
+            .get(&pool_key)
+            .ok_or(Error::PoolNotFound)?;
+
+        if pool.status != PoolStatus::Open {
+            return Err(Error::PoolNotOpen);
+        }
+        if pool.deadline_ts == 0
+            || new_deadline_ts <= pool.deadline_ts
+            || new_deadline_ts <= env.ledger().timestamp()
+        {
+            return Err(Error::InvalidDeadline);
+        }
+
+        let old_deadline_ts = pool.deadline_ts;
+        pool.deadline_ts = new_deadline_ts;
+        env.storage().temporary().set(&pool_key, &pool);
+        env.storage()
+            .temporary()
+            .extend_ttl(&pool_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+
+        DeadlineExtended {
+            pool_id,
+            old_deadline_ts,
+            new_deadline_ts,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Lock the pool — no more bets accepted.
+    pub fn lock_pool(env: Env, pool_id: u32) -> Result<(), Error> {
+        let admin = Self::require_admin(&env)?;
+
+        Self::lock_pool_internal(&env, pool_id, Some(admin))
+    }
+
+    /// Lock a pool whose betting deadline has passed. Callable by anyone so
+    /// reveals can start even if the admin is offline.
+    pub fn lock_pool_expired(env: Env, pool_id: u32) -> Result<(), Error> {
+        Self::lock_pool_internal(&env, pool_id, None)
+    }
+
+    /// Lock a pool. Without an admin `actor`, only once its deadline passed.
+    fn lock_pool_internal(env: &Env, pool_id: u32, actor: Option<Address>) -> Result<(), Error> {
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: BetPool = env
+            .storage()
+            .temporary()
+            .get(&pool_key)
+            .ok_or(Error::PoolNotFound)?;
+
+        if pool.status != PoolStatus::Open {
+            return Err(Error::PoolAlreadyLocked);
+        }
+
+        if actor.is_none()
+            && (pool.deadline_ts == 0 || env.ledger().timestamp() <= pool.deadline_ts)
+        {
+            return Err(Error::DeadlineNotReached);
+        }
+
+        pool.status = PoolStatus::Locked;
+        pool.locked_ts = env.ledger().timestamp();
+        Self::remove_open_pool(env, pool_id);
+        Self::record_status(env, pool_id, PoolStatus::Locked, actor);
+
+        env.storage().temporary().set(&pool_key, &pool);
+        env.storage()
+            .temporary()
+            .extend_ttl(&pool_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+
+        PoolLocked {
+            pool_id,
+            bet_count: pool.bet_count,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Reveal the bet — bettor provides the original `side` + `salt`.
+    /// Contract verifies SHA256(side_byte || salt) == stored commitment.
+    pub fn reveal_bet(
+        env: Env,
+        pool_id: u32,
+        bettor: Address,
+        side: BetSide,
+        salt: BytesN<32>,
+    ) -> Result<(), Error> {
+        bettor.require_auth();
+
+        Self::reveal_bet_internal(env, pool_id, bettor, side, salt)
+    }
+
+    /// Admin reveal path for house-managed bot betting flow.
+    /// Uses bettor commitment + provided side/salt but does not require bettor auth.
+    pub fn admin_reveal_bet(
+        env: Env,
+        pool_id: u32,
+        bettor: Address,
+        side: BetSide,
+        salt: BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        Self::reveal_bet_internal(env, pool_id, bettor, side, salt)
+    }
+
+    fn reveal_bet_internal(
+        env: Env,
+        pool_id: u32,
+        bettor: Address,
+        side: BetSide,
+        salt: BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::require_not_paused(&env)?;
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: BetPool = env
+            .storage()
+            .temporary()
+            .get(&pool_key)
+            .ok_or(Error::PoolNotFound)?;
+
+        if pool.status != PoolStatus::Locked {
+            return Err(Error::PoolNotLocked);
+        }
+
+        if pool.reveal_deadline_ts > 0 && env.ledger().timestamp() > pool.reveal_deadline_ts {
+            return Err(Error::RevealDeadlinePassed);
+        }
+
+        let bet_key = DataKey::Bet(pool_id, bettor.clone());
+        let mut bet: BetCommit = env
+            .storage()
+            .temporary()
+            .get(&bet_key)
+            .ok_or(Error::BetNotFound)?;
+
+        if bet.revealed {
+            return Err(Error::AlreadyRevealed);
+        }
+
+        if side == BetSide::Draw && !pool.allow_draw {
+            return Err(Error::DrawNotAllowed);
+        }
+
+        // Recompute commitment: SHA256(side_byte || salt)
+        let side_byte: u8 = match side {
+            BetSide::Player1 => 0,
+            BetSide::Player2 => 1,
+            BetSide::Draw => 2,
+        };
+
+        let mut preimage = Bytes::new(&env);
+        preimage.push_back(side_byte);
+        let salt_bytes: Bytes = salt.into();
+        preimage.append(&salt_bytes);
+
+        let computed_hash = env.crypto().sha256(&preimage);
+        let computed: BytesN<32> = computed_hash.into();
+
+        if computed != bet.commitment {
+            return Err(Error::InvalidReveal);
+        }
+
+        // Valid reveal
+        bet.revealed = true;
+        let side_u32 = match side {
+            BetSide::Player1 => SIDE_P1,
+            BetSide::Player2 => SIDE_P2,
+            BetSide::Draw => SIDE_DRAW,
+        };
+        bet.side = side_u32;
+
+        env.storage().temporary().set(&bet_key, &bet);
+        env.storage()
+            .temporary()
+            .extend_ttl(&bet_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+
+        // Update pool totals by side
+        match side {
+            BetSide::Player1 => pool.player1_total += bet.amount,
+            BetSide::Player2 => pool.player2_total += bet.amount,
+            BetSide::Draw => pool.draw_total += bet.amount,
+        }
+        pool.reveal_count += 1;
+        if bet.zk_verified {
+            pool.zk_total -= bet.amount;
+        }
+
+        env.storage().temporary().set(&pool_key, &pool);
+        env.storage()
+            .temporary()
+            .extend_ttl(&pool_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+
+        BetRevealed {
+            pool_id,
+            bettor,
+            side: side_byte as u32,
+            totals: (pool.player1_total, pool.player2_total, pool.draw_total),
+            odds_bps: Self::implied_odds_bps(&pool),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Settle the pool — admin declares the winner.
+    /// Unrevealed bets are treated as losses (forfeited).
+    pub fn settle_pool(
+        env: Env,
+        pool_id: u32,
+        winner: BetSide,
+    ) -> Result<(), Error> {
+        let admin = Self::require_admin(&env)?;
+
+        Self::settle_pool_internal(&env, pool_id, winner, Some(admin))
+    }
+
+    /// Settle a pool from the raw outcome of its market.
+    ///
+    /// `outcome` is the winning player index (0/1, or 2 for a draw) for
+    /// `MatchWinner` and `FirstBlood`, and the observed count for
+    /// `TotalMoves` / `RoundCount`. Counts above the line settle Player1
+    /// (over); counts at the line settle Draw when enabled, else Player2.
+    pub fn settle_market(env: Env, pool_id: u32, outcome: u32) -> Result<(), Error> {
+        let admin = Self::require_admin(&env)?;
+
+        let pool = Self::get_pool(env.clone(), pool_id)?;
+        let winner = Self::market_winner(&pool, outcome)?;
+
+        Self::settle_pool_internal(&env, pool_id, winner, Some(admin))
+    }
+
+    fn require_live_session(env: &Env, session_id: u32) -> Result<(), Error> {
+        let brawl_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::BrawlContract)
+            .ok_or(Error::BrawlNotConfigured)?;
+        let brawl = VeilstarBrawlClient::new(env, &brawl_addr);
+        let game = match brawl.try_get_match(&session_id) {
+            Ok(Ok(game)) => game,
+            _ => return Err(Error::MatchNotFound),
+        };
+
+        if game.is_cancelled {
+            return Err(Error::MatchCancelled);
+        }
+        if game.winner.is_some() {
+            return Err(Error::MatchAlreadyEnded);
+        }
+        Ok(())
+    }
+
+    /// Settle a pool from its linked brawl session, read straight from the
+    /// brawl contract. Permissionless: the result comes from chain state,
+    /// not from the betting admin.
+    pub fn settle_from_match(env: Env, pool_id: u32) -> Result<(), Error> {
+        let pool = Self::get_pool(env.clone(), pool_id)?;
+        let session_id = pool.session_id.ok_or(Error::SessionNotLinked)?;
+
+        let brawl_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::BrawlContract)
+            .ok_or(Error::BrawlNotConfigured)?;
+        let brawl = VeilstarBrawlClient::new(&env, &brawl_addr);
+        let game = match brawl.try_get_match(&session_id) {
+            Ok(Ok(game)) => game,
+            _ => return Err(Error::MatchNotFinished),
+        };
+
+        if game.is_cancelled {
+            return Err(Error::MatchCancelled);
+        }
+        let winner = game.winner.ok_or(Error::MatchNotFinished)?;
+
+        let outcome = match pool.market {
+            // A winner that is neither player records a draw: it settles Draw
+            // where the pool offers it and refunds the pool otherwise.
+            Market::MatchWinner => {
+                if winner == game.player1 {
+                    SIDE_P1
+                } else if winner == game.player2 {
+                    SIDE_P2
+                } else if pool.allow_draw {
+                    SIDE_DRAW
+                } else {
+                    return Self::refund_pool_internal(&env, pool_id, None);
+                }
+            }
+            Market::TotalMoves => game.player1_moves + game.player2_moves,
+            Market::RoundCount => game.player1_rounds_won + game.player2_rounds_won,
+            // The brawl contract does not record who landed the first hit.
+            Market::FirstBlood => return Err(Error::InvalidOutcome),
+        };
+        let winner_side = Self::market_winner(&pool, outcome)?;
+
+        Self::settle_pool_internal(&env, pool_id, winner_side, None)
+    }
+
+    /// Settle a pool from the configured outcome oracle. Permissionless:
+    /// the result comes from the oracle, not from the caller.
+    pub fn settle_from_oracle(env: Env, pool_id: u32) -> Result<(), Error> {
+        let pool = Self::get_pool(env.clone(), pool_id)?;
+
+        let oracle_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::OutcomeOracle)
+            .ok_or(Error::OracleNotConfigured)?;
+        let oracle = OutcomeOracleClient::new(&env, &oracle_addr);
+        let outcome = oracle
+            .get_outcome(&pool.match_id, &(pool.market as u32))
+            .ok_or(Error::MatchNotFinished)?;
+
+        let winner = Self::market_winner(&pool, outcome)?;
+        Self::settle_pool_internal(&env, pool_id, winner, None)
+    }
+
+    fn settle_pool_internal(
+        env: &Env,
+        pool_id: u32,
+        winner: BetSide,
+        actor: Option<Address>,
+    ) -> Result<(), Error> {
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: BetPool = env
+            .storage()
+            .temporary()
+            .get(&pool_key)
+            .ok_or(Error::PoolNotFound)?;
+
+        if pool.status == PoolStatus::Settled || pool.status == PoolStatus::Refunded {
+            return Err(Error::PoolAlreadySettled);
+        }
+
+        if winner == BetSide::Draw && !pool.allow_draw {
+            return Err(Error::DrawNotAllowed);
+        }
+
+        if pool.reveal_window_secs > 0
+            && pool.reveal_count < pool.bet_count
+            && (pool.status == PoolStatus::Open
+                || env.ledger().timestamp() < pool.locked_ts + pool.reveal_window_secs)
+        {
+            return Err(Error::RevealWindowOpen);
+        }
+
+        pool.status = PoolStatus::Settled;
+        Self::remove_open_pool(env, pool_id);
+        Self::record_status(env, pool_id, PoolStatus::Settled, actor);
+        pool.settled_ts = env.ledger().timestamp();
+        let winner_u32 = match winner {
+            BetSide::Player1 => SIDE_P1,
+            BetSide::Player2 => SIDE_P2,
+            BetSide::Draw => SIDE_DRAW,
+        };
+        pool.winner_side = winner_u32;
+        Self::archive_pool(env, &pool);
+
+        // Only the winners' payouts (and reclaimable unrevealed stakes) remain owed.
+        let winner_total = match winner {
+            BetSide::Player1 => pool.player1_total,
+            BetSide::Player2 => pool.player2_total,
+            BetSide::Draw => pool.draw_total,
+        };
+        let revealed_total = pool.player1_total + pool.player2_total + pool.draw_total;
+        let exposure = Self::house_cover(&pool, pool.total_pool);
+        let reclaimable = if pool.forfeit_bps < BPS_DENOMINATOR {
+            pool.total_pool - revealed_total - pool.zk_total
+        } else {
+            0
+        };
+
+        // If the contract cannot cover every winner, scale all payouts down
+        // pro rata instead of paying claimers first-come-first-served.
+        // Proof-verified stakes may still be claimed as winners.
+        let winners_owed = Self::payout_for(&pool, winner_total + pool.zk_total);
+        let other_liability = Self::get_total_liability(env.clone()) - pool.liability;
+        let funds = Self::free_balance(env) - pool.total_fees - other_liability - reclaimable;
+        if winners_owed > funds {
+            let haircut = funds.max(0) * BPS_DENOMINATOR as i128 / winners_owed;
+            pool.haircut_bps = haircut as u32;
+            PayoutHaircut {
+                pool_id,
+                haircut_bps: pool.haircut_bps,
+            }
+            .publish(env);
+        }
+
+        let owed = Self::payout_for(&pool, winner_total + pool.zk_total) + reclaimable;
+        let released = pool.liability - owed;
+        Self::reduce_liability(env, &mut pool, released);
+
+        env.storage().temporary().set(&pool_key, &pool);
+        env.storage()
+            .temporary()
+            .extend_ttl(&pool_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+
+        // Accrue fees, less the fighters' rake when the pool names them
+        let rake = Self::credit_rake(env, &pool);
+        let referrals = Self::credit_referrals(env, pool_id);
+        Self::accrue_fees(env, &pool, pool.total_fees - rake - referrals);
+        Self::auto_sweep(env);
+
+        // Stakes now belong to the house, minus the payouts owed to revealed winners.
+        // Unrevealed stakes stay out of the bankroll while they can be reclaimed
+        // or, for proof-verified bets, claimed.
+        let house_share = if pool.forfeit_bps < BPS_DENOMINATOR {
+            revealed_total
+        } else {
+            pool.total_pool - pool.zk_total
+        };
+        let house_result = house_share - Self::payout_for(&pool, winner_total);
+        let bankroll = Self::get_bankroll(env.clone()) + house_result;
+        env.storage().instance().set(&DataKey::Bankroll, &bankroll);
+
+        // Settlement P&L: losing and forfeited stakes in, winners' house half out.
+        let house_balance = Self::get_house_balance(env.clone()) + house_result;
+        env.storage()
+            .instance()
+            .set(&DataKey::HouseBalance, &house_balance);
+        Self::release_exposure(env, exposure);
+
+        PoolSettled {
+            pool_id,
+            winner: winner_u32,
+            total_pool: pool.total_pool,
+            totals: (pool.player1_total, pool.player2_total, pool.draw_total),
+            odds_bps: Self::implied_odds_bps(&pool),
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Settle using a ZK proof of the match outcome.
+    /// The proof is verified against the configured zk-groth16-verifier contract.
+    /// Permissionless: anyone holding a valid, correctly bound proof can settle,
+    /// so the operator cannot withhold settlement.
+    pub fn settle_pool_zk(
+        env: Env,
+        pool_id: u32,
+        winner: BetSide,
+        vk_id: BytesN<32>,
+        proof: Bytes,
+        public_inputs: Vec<BytesN<32>>,
+    ) -> Result<(), Error> {
+        let pool: BetPool = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(Error::PoolNotFound)?;
+
+        let winner_side = match winner {
+            BetSide::Player1 => SIDE_P1,
+            BetSide::Player2 => SIDE_P2,
+            BetSide::Draw => SIDE_DRAW,
+        };
+
+        // Verify ZK proof
+        let verifier_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::ZkVerifier)
+            .ok_or(Error::ZkVerifierNotConfigured)?;
+
+        let configured_vk_id: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ZkVkId)
+            .ok_or(Error::ZkVerifierNotConfigured)?;
+
+        if vk_id != configured_vk_id {
+            return Err(Error::ZkProofInvalid);
+        }
+
+        if proof.len() != 256 || public_inputs.len() < 3 {
+            return Err(Error::ZkProofInvalid);
+        }
+
+        // Bind the proof to this pool and claimed outcome before verifying it:
+        // [0] = match_id, [1] = pool_id, [2] = winner side, both as
+        // big-endian field elements.
+        let input_match_id = public_inputs.get(0).ok_or(Error::ZkProofInvalid)?;
+        let input_pool_id = public_inputs.get(1).ok_or(Error::ZkProofInvalid)?;
+        let input_winner_side = public_inputs.get(2).ok_or(Error::ZkProofInvalid)?;
+
+        let expected_pool_id = Self::u32_to_bytes32(&env, pool_id);
+        let expected_winner_side = Self::u32_to_bytes32(&env, winner_side);
+
+        if input_match_id != pool.match_id
+            || input_pool_id != expected_pool_id
+            || input_winner_side != expected_winner_side
+        {
+            return Err(Error::PublicInputMismatch);
+        }
+
+        let verifier = ZkVerifierClient::new(&env, &verifier_addr);
+        let verified = verifier.verify_round_proof(&vk_id, &proof, &public_inputs);
+        if !verified {
+            return Err(Error::ZkProofInvalid);
+        }
+
+        // Proof is valid — proceed with settlement
+        Self::settle_pool_internal(&env, pool_id, winner, None)
+    }
+
+    /// Claim payout after settlement.
+    ///
+    /// House model payout:
+    /// - Winning revealed bet gets the pool's fixed multiple (2x by default).
+    /// - Losing or unrevealed bet gets no payout.
+    pub fn claim_payout(env: Env, pool_id: u32, bettor: Address) -> Result<i128, Error> {
+        bettor.require_auth();
+
+        Self::claim_payout_internal(env, pool_id, bettor.clone(), bettor)
+    }
+
+    /// Claim payout to a different address, e.g. a cold wallet or custodial
+    /// account. Authorized by the bettor.
+    pub fn claim_payout_to(
+        env: Env,
+        pool_id: u32,
+        bettor: Address,
+        recipient: Address,
+    ) -> Result<i128, Error> {
+        bettor.require_auth();
+
+        Self::claim_payout_internal(env, pool_id, bettor, recipient)
+    }
+
+    /// Admin claim path for house-managed bot betting flow.
+    /// Transfers payout directly to bettor without requiring bettor auth.
+    pub fn admin_claim_payout(env: Env, pool_id: u32, bettor: Address) -> Result<i128, Error> {
+        Self::require_admin(&env)?;
+
+        Self::claim_payout_internal(env, pool_id, bettor.clone(), bettor)
+    }
+
+    /// Push payouts to winning bettors for the house-managed flow.
+    ///
+    /// With an explicit `bettors` list, pays exactly those addresses. With an
+    /// empty list, walks the pool's bettors from a stored cursor, up to
+    /// `DISTRIBUTE_CHUNK` bettors or `DISTRIBUTE_MAX_PAYOUTS` payouts per
+    /// call, so large pools can be processed across several transactions.
+    /// Bettors with nothing to claim are skipped.
+    /// Returns the total paid out by this call.
+    pub fn admin_distribute(
+        env: Env,
+        pool_id: u32,
+        bettors: Vec<Address>,
+    ) -> Result<i128, Error> {
+        Self::require_admin(&env)?;
+
+        let pool = Self::get_pool(env.clone(), pool_id)?;
+        if pool.status != PoolStatus::Settled {
+            return Err(Error::PoolNotSettled);
+        }
+
+        let cursor_key = DataKey::DistributeCursor(pool_id);
+        let walk_pool = bettors.is_empty();
+        let mut total_paid: i128 = 0;
+        let mut processed: u32 = 0;
+        if walk_pool {
+            let all: Vec<Address> = env
+                .storage()
+                .temporary()
+                .get(&DataKey::PoolBettors(pool_id))
+                .unwrap_or(Vec::new(&env));
+            let mut cursor: u32 = env.storage().temporary().get(&cursor_key).unwrap_or(0);
+            let mut payouts: u32 = 0;
+            while cursor < all.len() && processed < DISTRIBUTE_CHUNK && payouts < DISTRIBUTE_MAX_PAYOUTS {
+                let bettor = all.get_unchecked(cursor);
+                if let Ok(paid) = Self::claim_payout_internal(env.clone(), pool_id, bettor.clone(), bettor) {
+                    total_paid += paid;
+                    payouts += 1;
+                }
+                cursor += 1;
+                processed += 1;
+            }
+
+            env.storage().temporary().set(&cursor_key, &cursor);
+            env.storage()
+                .temporary()
+                .extend_ttl(&cursor_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+        } else {
+            for bettor in bettors.iter() {
+                if let Ok(paid) = Self::claim_payout_internal(env.clone(), pool_id, bettor.clone(), bettor) {
+                    total_paid += paid;
+                }
+                processed += 1;
+            }
+        }
+
+        PayoutsDistributed {
+            pool_id,
+            processed,
+            total_paid,
+        }
+        .publish(&env);
+
+        Ok(total_paid)
+    }
+
+    fn claim_payout_internal(
+        env: Env,
+        pool_id: u32,
+        bettor: Address,
+        recipient: Address,
+    ) -> Result<i128, Error> {
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: BetPool = env
+            .storage()
+            .temporary()
+            .get(&pool_key)
+            .ok_or(Error::PoolNotFound)?;
+
+        if pool.status != PoolStatus::Settled {
+            return Err(Error::PoolNotSettled);
+        }
+
+        if env.ledger().timestamp() >= pool.settled_ts + CLAIM_EXPIRY_SECONDS {
+            return Err(Error::ClaimExpired);
+        }
+
+        if Self::is_blacklisted(env.clone(), bettor.clone()) {
+            return Err(Error::Blacklisted);
+        }
+
+        let bet_key = DataKey::Bet(pool_id, bettor.clone());
+        let mut bet: BetCommit = env
+            .storage()
+            .temporary()
+            .get(&bet_key)
+            .ok_or(Error::BetNotFound)?;
+
+        if bet.claimed {
+            return Err(Error::AlreadyClaimed);
+        }
+
+        // Must have revealed and bet on winning side
+        if pool.winner_side == SIDE_NONE {
+            return Err(Error::InvalidWinner);
+        }
+        let pool_winner_side = pool.winner_side;
+
+        if !bet.revealed {
+            if pool.forfeit_bps < BPS_DENOMINATOR || bet.zk_verified {
+                // Left open for reclaim_unrevealed or claim_payout_zk.
+                return Err(Error::NoPayout);
+            }
+            // Unrevealed = forfeited, no payout
+            bet.claimed = true;
+            env.storage().temporary().set(&bet_key, &bet);
+            return Err(Error::NoPayout);
+        }
+
+        if bet.side != pool_winner_side {
+            // Bet on losing side
+            bet.claimed = true;
+            env.storage().temporary().set(&bet_key, &bet);
+            return Err(Error::NoPayout);
+        }
+
+        // House fixed payout at the pool's multiplier
+        let payout = Self::payout_for(&pool, bet.amount);
+
+        if payout <= 0 {
+            return Err(Error::NoPayout);
+        }
+
+        // Transfer payout
+        let xlm_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::XlmToken)
+            .expect("XLM not set");
+        let xlm = token::Client::new(&env, &xlm_addr);
+        xlm.transfer(&env.current_contract_address(), &recipient, &payout);
+
+        bet.claimed = true;
+        env.storage().temporary().set(&bet_key, &bet);
+        env.storage()
+            .temporary()
+            .extend_ttl(&bet_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+
+        Self::reduce_liability(&env, &mut pool, payout);
+        pool.claimed_total += payout;
+        env.storage().temporary().set(&pool_key, &pool);
+        Self::record_win(&env, &bettor, pool_id);
+
+        PayoutClaimed {
+            pool_id,
+            bettor,
+            payout,
+        }
+        .publish(&env);
+
+        Ok(payout)
+    }
+
+    /// Claim a winning proof-verified bet without revealing it. The proof
+    /// shows the bet's commitment opens to the pool's winning side. Open until
+    /// the reclaim grace window after settlement closes.
+    ///
+    /// Public inputs: [0] = `ProofKind::ClaimBet` tag, [1] = commitment,
+    /// [2] = pool_id, [3] = winner side.
+    pub fn claim_payout_zk(
+        env: Env,
+        pool_id: u32,
+        bettor: Address,
+        proof: Bytes,
+        public_inputs: Vec<BytesN<32>>,
+    ) -> Result<i128, Error> {
+        bettor.require_auth();
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: BetPool = env
+            .storage()
+            .temporary()
+            .get(&pool_key)
+            .ok_or(Error::PoolNotFound)?;
+
+        if pool.status != PoolStatus::Settled {
+            return Err(Error::PoolNotSettled);
+        }
+        if env.ledger().timestamp() >= pool.settled_ts + RECLAIM_GRACE_SECONDS {
+            return Err(Error::ZkClaimWindowClosed);
+        }
+        if Self::is_blacklisted(env.clone(), bettor.clone()) {
+            return Err(Error::Blacklisted);
+        }
+
+        let bet_key = DataKey::Bet(pool_id, bettor.clone());
+        let mut bet: BetCommit = env
+            .storage()
+            .temporary()
+            .get(&bet_key)
+            .ok_or(Error::BetNotFound)?;
+
+        if !bet.zk_verified {
+            return Err(Error::NotZkBet);
+        }
+        if bet.revealed {
+            return Err(Error::AlreadyRevealed);
+        }
+        if bet.claimed {
+            return Err(Error::AlreadyClaimed);
+        }
+
+        let expected = Vec::from_array(
+            &env,
+            [
+                bet.commitment.clone(),
+                Self::u32_to_bytes32(&env, pool_id),
+                Self::u32_to_bytes32(&env, pool.winner_side),
+            ],
+        );
+        Self::verify_zk_proof(&env, ProofKind::ClaimBet, &proof, &public_inputs, &expected)?;
+
+        let payout = Self::payout_for(&pool, bet.amount);
+
+        let xlm_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::XlmToken)
+            .expect("XLM not set");
+        let xlm = token::Client::new(&env, &xlm_addr);
+        xlm.transfer(&env.current_contract_address(), &bettor, &payout);
+
+        bet.claimed = true;
+        bet.side = pool.winner_side;
+        env.storage().temporary().set(&bet_key, &bet);
+        env.storage()
+            .temporary()
+            .extend_ttl(&bet_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+
+        Self::book_zk_payout(&env, &mut pool, bet.amount, payout);
+        env.storage().temporary().set(&pool_key, &pool);
+        Self::record_win(&env, &bettor, pool_id);
+
+        PayoutClaimed {
+            pool_id,
+            bettor,
+            payout,
+        }
+        .publish(&env);
+
+        Ok(payout)
+    }
+
+    /// Claim a winning private note to `recipient`. The proof shows that a
+    /// note in the pool's note set opens to the winning side and `amount`,
+    /// and that `nullifier` is derived from it; spent nullifiers are stored
+    /// so each note pays once. Anyone may relay the call, since the proof
+    /// binds the recipient.
+    ///
+    /// Public inputs: [0] = `ProofKind::ClaimNote` tag, [1] = notes_root,
+    /// [2] = pool_id, [3] = winner side, [4] = amount, [5] = nullifier,
+    /// [6] = SHA256 of the recipient's XDR.
+    pub fn claim_note(
+        env: Env,
+        pool_id: u32,
+        nullifier: BytesN<32>,
+        recipient: Address,
+        amount: i128,
+        proof: Bytes,
+        public_inputs: Vec<BytesN<32>>,
+    ) -> Result<i128, Error> {
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: BetPool = env
+            .storage()
+            .temporary()
+            .get(&pool_key)
+            .ok_or(Error::PoolNotFound)?;
+
+        if pool.status != PoolStatus::Settled {
+            return Err(Error::PoolNotSettled);
+        }
+        if env.ledger().timestamp() >= pool.settled_ts + RECLAIM_GRACE_SECONDS {
+            return Err(Error::ZkClaimWindowClosed);
+        }
+
+        let nullifier_key = DataKey::Nullifier(nullifier.clone());
+        if env.storage().persistent().has(&nullifier_key) {
+            return Err(Error::NullifierUsed);
+        }
+        if amount <= 0 || amount > pool.zk_total {
+            return Err(Error::InvalidAmount);
+        }
+
+        let recipient_hash: BytesN<32> = env
+            .crypto()
+            .sha256(&recipient.clone().to_xdr(&env))
+            .into();
+        let expected = Vec::from_array(
+            &env,
+            [
+                pool.notes_root.clone(),
+                Self::u32_to_bytes32(&env, pool_id),
+                Self::u32_to_bytes32(&env, pool.winner_side),
+                Self::i128_to_bytes32(&env, amount)?,
+                nullifier.clone(),
+                recipient_hash,
+            ],
+        );
+        Self::verify_zk_proof(&env, ProofKind::ClaimNote, &proof, &public_inputs, &expected)?;
+
+        env.storage().persistent().set(&nullifier_key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&nullifier_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+
+        let payout = Self::payout_for(&pool, amount);
+
+        let xlm_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::XlmToken)
+            .expect("XLM not set");
+        let xlm = token::Client::new(&env, &xlm_addr);
+        xlm.transfer(&env.current_contract_address(), &recipient, &payout);
+
+        Self::book_zk_payout(&env, &mut pool, amount, payout);
+        env.storage().temporary().set(&pool_key, &pool);
+
+        NoteClaimed {
+            pool_id,
+            nullifier,
+            payout,
+        }
+        .publish(&env);
+
+        Ok(payout)
+    }
+
+    pub fn is_nullifier_used(env: Env, nullifier: BytesN<32>) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Nullifier(nullifier))
+    }
+
+    /// Book a proof-verified winning payout. The stake was held out of the
+    /// bankroll at settlement; the house now pays the winning half on top.
+    fn book_zk_payout(env: &Env, pool: &mut BetPool, stake: i128, payout: i128) {
+        let house_result = stake - payout;
+        let bankroll = Self::get_bankroll(env.clone()) + house_result;
+        env.storage().instance().set(&DataKey::Bankroll, &bankroll);
+        let house_balance = Self::get_house_balance(env.clone()) + house_result;
+        env.storage()
+            .instance()
+            .set(&DataKey::HouseBalance, &house_balance);
+
+        pool.zk_total -= stake;
+        pool.claimed_total += payout;
+        Self::reduce_liability(env, pool, payout);
+    }
+
+    /// Move proof-verified stakes that were not claimed within the grace
+    /// window into the house bankroll. Permissionless.
+    pub fn release_zk_stakes(env: Env, pool_id: u32) -> Result<i128, Error> {
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: BetPool = env
+            .storage()
+            .temporary()
+            .get(&pool_key)
+            .ok_or(Error::PoolNotFound)?;
+
+        if pool.status != PoolStatus::Settled {
+            return Err(Error::PoolNotSettled);
+        }
+        if env.ledger().timestamp() < pool.settled_ts + RECLAIM_GRACE_SECONDS {
+            return Err(Error::ReclaimNotAvailable);
+        }
+
+        let released = Self::release_zk_internal(&env, &mut pool);
+        if released <= 0 {
+            return Err(Error::NothingToClaim);
+        }
+        env.storage().temporary().set(&pool_key, &pool);
+
+        Ok(released)
+    }
+
+    fn release_zk_internal(env: &Env, pool: &mut BetPool) -> i128 {
+        let released = pool.zk_total;
+        if released <= 0 {
+            return 0;
+        }
+
+        let bankroll = Self::get_bankroll(env.clone()) + released;
+        env.storage().instance().set(&DataKey::Bankroll, &bankroll);
+        let house_balance = Self::get_house_balance(env.clone()) + released;
+        env.storage()
+            .instance()
+            .set(&DataKey::HouseBalance, &house_balance);
+
+        pool.zk_total = 0;
+        let owed = Self::payout_for(pool, released);
+        Self::reduce_liability(env, pool, owed);
+
+        ZkStakesReleased {
+            pool_id: pool.pool_id,
+            released,
+        }
+        .publish(env);
+
+        released
+    }
+
+    /// Move winnings and reclaimable stakes still unclaimed 14 days after
+    /// settlement into the pool's fee bucket. Callable by anyone; claims on
+    /// the pool are closed from then on. Returns the amount moved to fees.
+    pub fn reclaim_expired(env: Env, pool_id: u32) -> Result<i128, Error> {
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: BetPool = env
+            .storage()
+            .temporary()
+            .get(&pool_key)
+            .ok_or(Error::PoolNotFound)?;
+
+        if pool.status != PoolStatus::Settled {
+            return Err(Error::PoolNotSettled);
+        }
+        if env.ledger().timestamp() < pool.settled_ts + CLAIM_EXPIRY_SECONDS {
+            return Err(Error::DeadlineNotReached);
+        }
+
+        // Unclaimed proof-verified stakes return to the house first; only
+        // the house-funded winnings left over become fees.
+        Self::release_zk_internal(&env, &mut pool);
+
+        let expired = pool.liability;
+        if expired <= 0 {
+            return Err(Error::NothingToClaim);
+        }
+        Self::reduce_liability(&env, &mut pool, expired);
+        Self::accrue_fees(&env, &pool, expired);
+        env.storage().temporary().set(&pool_key, &pool);
+
+        PayoutsExpired {
+            pool_id,
+            liability: expired,
+        }
+        .publish(&env);
+
+        Ok(expired)
+    }
+
+    /// Void the unclaimed winning payout of a blacklisted bettor, who can no
+    /// longer claim it, and release its booked liability back to the house.
+    /// Returns the amount released.
+    pub fn forfeit_blacklisted_payout(env: Env, pool_id: u32, bettor: Address) -> Result<i128, Error> {
+        Self::require_admin(&env)?;
+
+        if !Self::is_blacklisted(env.clone(), bettor.clone()) {
+            return Err(Error::NotBlacklisted);
+        }
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: BetPool = env
+            .storage()
+            .temporary()
+            .get(&pool_key)
+            .ok_or(Error::PoolNotFound)?;
+        if pool.status != PoolStatus::Settled {
+            return Err(Error::PoolNotSettled);
+        }
+
+        let bet_key = DataKey::Bet(pool_id, bettor.clone());
+        let mut bet: BetCommit = env
+            .storage()
+            .temporary()
+            .get(&bet_key)
+            .ok_or(Error::BetNotFound)?;
+        if bet.claimed {
+            return Err(Error::AlreadyClaimed);
+        }
+        if !bet.revealed || bet.side != pool.winner_side {
+            return Err(Error::NoPayout);
+        }
+
+        let payout = Self::payout_for(&pool, bet.amount);
+        bet.claimed = true;
+        env.storage().temporary().set(&bet_key, &bet);
+        env.storage()
+            .temporary()
+            .extend_ttl(&bet_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+
+        Self::reduce_liability(&env, &mut pool, payout);
+        env.storage().temporary().set(&pool_key, &pool);
+
+        PayoutForfeited {
+            pool_id,
+            bettor,
+            payout,
+        }
+        .publish(&env);
+
+        Ok(payout)
+    }
+
+    /// Unclaimed winnings and reclaimable stakes of a settled pool, the
+    /// amount already claimed, and when the remainder expires to fees.
+    pub fn get_unclaimed(env: Env, pool_id: u32) -> Result<(i128, i128, u64), Error> {
+        let pool = Self::get_pool(env, pool_id)?;
+        if pool.status != PoolStatus::Settled {
+            return Err(Error::PoolNotSettled);
+        }
+        Ok((
+            pool.liability,
+            pool.claimed_total,
+            pool.settled_ts + CLAIM_EXPIRY_SECONDS,
+        ))
+    }
+
+    /// Reclaim an unrevealed stake minus the pool's forfeit penalty, once the
+    /// grace window after settlement has passed. The penalty goes to fees.
+    pub fn reclaim_unrevealed(env: Env, pool_id: u32, bettor: Address) -> Result<i128, Error> {
+        bettor.require_auth();
+
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: BetPool = env
+            .storage()
+            .temporary()
+            .get(&pool_key)
+            .ok_or(Error::PoolNotFound)?;
+
+        if pool.status != PoolStatus::Settled {
+            return Err(Error::PoolNotSettled);
+        }
+
+        if pool.forfeit_bps >= BPS_DENOMINATOR
+            || env.ledger().timestamp() < pool.settled_ts + RECLAIM_GRACE_SECONDS
+        {
+            return Err(Error::ReclaimNotAvailable);
+        }
+        if env.ledger().timestamp() >= pool.settled_ts + CLAIM_EXPIRY_SECONDS {
+            return Err(Error::ClaimExpired);
+        }
+
+        let bet_key = DataKey::Bet(pool_id, bettor.clone());
+        let mut bet: BetCommit = env
+            .storage()
+            .temporary()
+            .get(&bet_key)
+            .ok_or(Error::BetNotFound)?;
+
+        if bet.revealed {
+            return Err(Error::AlreadyRevealed);
+        }
+        if bet.claimed {
+            return Err(Error::AlreadyClaimed);
+        }
+        if bet.zk_verified {
+            return Err(Error::ReclaimNotAvailable);
+        }
+
+        let penalty = bet.amount * pool.forfeit_bps as i128 / BPS_DENOMINATOR as i128;
+        let refund = bet.amount - penalty;
+
+        Self::accrue_fees(&env, &pool, penalty);
+
+        bet.claimed = true;
+        env.storage().temporary().set(&bet_key, &bet);
+        env.storage()
+            .temporary()
+            .extend_ttl(&bet_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+
+        Self::reduce_liability(&env, &mut pool, bet.amount);
+        pool.claimed_total += refund;
+        env.storage().temporary().set(&pool_key, &pool);
+
+        if refund > 0 {
+            let xlm_addr: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::XlmToken)
+                .expect("XLM not set");
+            let xlm = token::Client::new(&env, &xlm_addr);
+            xlm.transfer(&env.current_contract_address(), &bettor, &refund);
+        }
+
+        StakeReclaimed {
+            pool_id,
+            bettor,
+            refund,
+            penalty,
+        }
+        .publish(&env);
+
+        Ok(refund)
+    }
+
+    /// Withdraw a referrer's accumulated rewards.
+    pub fn claim_referral(env: Env, referrer: Address) -> Result<i128, Error> {
+        referrer.require_auth();
+
+        let key = DataKey::ReferralBalance(referrer.clone());
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount <= 0 {
+            return Err(Error::NothingToClaim);
+        }
+        env.storage().persistent().remove(&key);
+        Self::add_claimable_total(&env, DataKey::ReferralBalanceTotal, -amount);
+
+        let xlm_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::XlmToken)
+            .expect("XLM not set");
+        let xlm = token::Client::new(&env, &xlm_addr);
+        xlm.transfer(&env.current_contract_address(), &referrer, &amount);
+
+        ReferralClaimed { referrer, amount }.publish(&env);
+
+        Ok(amount)
+    }
+
+    /// Withdraw a fighter's accumulated rake share.
+    pub fn claim_rake(env: Env, fighter: Address) -> Result<i128, Error> {
+        fighter.require_auth();
+
+        let key = DataKey::RakeBalance(fighter.clone());
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount <= 0 {
+            return Err(Error::NothingToClaim);
+        }
+        env.storage().persistent().remove(&key);
+        Self::add_claimable_total(&env, DataKey::RakeBalanceTotal, -amount);
+
+        let xlm_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::XlmToken)
+            .expect("XLM not set");
+        let xlm = token::Client::new(&env, &xlm_addr);
+        xlm.transfer(&env.current_contract_address(), &fighter, &amount);
+
+        RakeClaimed { fighter, amount }.publish(&env);
+
+        Ok(amount)
+    }
+
+    /// Refund all bettors (match cancelled).
+    pub fn refund_pool(env: Env, pool_id: u32) -> Result<(), Error> {
+        let admin = Self::require_admin(&env)?;
+
+        Self::refund_pool_internal(&env, pool_id, Some(admin))
+    }
+
+    /// Cancel a pool with a reason code, then refund every bettor.
+    pub fn cancel_pool(env: Env, pool_id: u32, reason: CancelReason) -> Result<(), Error> {
+        let admin = Self::require_admin(&env)?;
+
+        let key = DataKey::CancelReason(pool_id);
+        env.storage().persistent().set(&key, &reason);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ARCHIVE_TTL_LEDGERS, ARCHIVE_TTL_LEDGERS);
+
+        PoolCancelled {
+            pool_id,
+            reason: reason as u32,
+        }
+        .publish(&env);
+
+        Self::refund_pool_internal(&env, pool_id, Some(admin))
+    }
+
+    /// Refund a pool left unsettled for 7 days past its betting deadline.
+    /// Callable by anyone; funds only ever go back to the bettors.
+    pub fn refund_pool_expired(env: Env, pool_id: u32) -> Result<(), Error> {
+        let pool = Self::get_pool(env.clone(), pool_id)?;
+
+        if pool.deadline_ts == 0
+            || env.ledger().timestamp() <= pool.deadline_ts + ABANDONED_POOL_SECONDS
+        {
+            return Err(Error::DeadlineNotReached);
+        }
+
+        Self::refund_pool_internal(&env, pool_id, None)
+    }
+
+    fn refund_pool_internal(
+        env: &Env,
+        pool_id: u32,
+        actor: Option<Address>,
+    ) -> Result<(), Error> {
+        let pool_key = DataKey::Pool(pool_id);
+        let mut pool: BetPool = env
+            .storage()
+            .temporary()
+            .get(&pool_key)
+            .ok_or(Error::PoolNotFound)?;
+
+        if pool.status == PoolStatus::Settled || pool.status == PoolStatus::Refunded {
+            return Err(Error::PoolAlreadySettled);
+        }
+
+        let xlm_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::XlmToken)
+            .expect("XLM not set");
+        let xlm = token::Client::new(env, &xlm_addr);
+
+        // Refund each bettor: amount + fee
+        let bettors_key = DataKey::PoolBettors(pool_id);
+        let bettors: Vec<Address> = env
+            .storage()
+            .temporary()
+            .get(&bettors_key)
+            .unwrap_or(Vec::new(env));
+
+        for i in 0..bettors.len() {
+            let bettor_addr = bettors.get(i).unwrap();
+            let bet_key = DataKey::Bet(pool_id, bettor_addr.clone());
+            if let Some(mut bet) = env.storage().temporary().get::<_, BetCommit>(&bet_key) {
+                if !bet.claimed {
+                    let refund = bet.amount + bet.fee_paid;
+                    xlm.transfer(&env.current_contract_address(), &bettor_addr, &refund);
+                    bet.claimed = true;
+                    env.storage().temporary().set(&bet_key, &bet);
+                }
+            }
+        }
+
+        let notes: Vec<NoteDeposit> = env
+            .storage()
+            .temporary()
+            .get(&DataKey::PoolNotes(pool_id))
+            .unwrap_or(Vec::new(env));
+        for note in notes.iter() {
+            xlm.transfer(
+                &env.current_contract_address(),
+                &note.depositor,
+                &(note.amount + note.fee_paid),
+            );
+        }
+        pool.zk_total = 0;
+
+        Self::release_exposure(env, Self::house_cover(&pool, pool.total_pool));
+        env.storage().temporary().remove(&DataKey::PoolReferrals(pool_id));
+        let released = pool.liability;
+        Self::reduce_liability(env, &mut pool, released);
+
+        pool.status = PoolStatus::Refunded;
+        Self::remove_open_pool(env, pool_id);
+        Self::record_status(env, pool_id, PoolStatus::Refunded, actor);
+        Self::archive_pool(env, &pool);
+        env.storage().temporary().set(&pool_key, &pool);
+        env.storage()
+            .temporary()
+            .extend_ttl(&pool_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+
+        PoolRefunded {
+            pool_id,
+            bet_count: pool.bet_count,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Delete archived records older than the one-year retention period.
+    /// Callable by anyone; records that are too recent are skipped.
+    /// Returns the number pruned.
+    pub fn prune_archive(env: Env, pool_ids: Vec<u32>) -> u32 {
+        let now = env.ledger().timestamp();
+        let mut pruned = 0;
+        for pool_id in pool_ids.iter() {
+            let key = DataKey::ArchivedPool(pool_id);
+            if let Some(record) = env.storage().persistent().get::<_, PoolArchive>(&key) {
+                if now >= record.closed_ts + ARCHIVE_RETENTION_SECONDS {
+                    env.storage().persistent().remove(&key);
+                    env.storage()
+                        .persistent()
+                        .remove(&DataKey::CancelReason(pool_id));
+                    env.storage().persistent().remove(&DataKey::PoolFees(pool_id));
+                    pruned += 1;
+                }
+            }
+        }
+        pruned
+    }
+
+    // ======================================================================
+    // House bankroll
+    // ======================================================================
+
+    /// Add XLM to the house bankroll that backs winning payouts.
+    pub fn deposit_bankroll(env: Env, from: Address, amount: i128) -> Result<(), Error> {
+        from.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let xlm_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::XlmToken)
+            .expect("XLM not set");
+        let xlm = token::Client::new(&env, &xlm_addr);
+        xlm.transfer(&from, env.current_contract_address(), &amount);
+
+        let bankroll = Self::get_bankroll(env.clone()) + amount;
+        env.storage().instance().set(&DataKey::Bankroll, &bankroll);
+
+        BankrollDeposited { from, amount }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Withdraw bankroll not currently backing open bets.
+    pub fn withdraw_bankroll(env: Env, to: Address, amount: i128) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let bankroll = Self::get_bankroll(env.clone());
+        if amount > bankroll - Self::get_open_exposure(env.clone()) {
+            return Err(Error::InsufficientBankroll);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Bankroll, &(bankroll - amount));
+
+        let xlm_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::XlmToken)
+            .expect("XLM not set");
+        let xlm = token::Client::new(&env, &xlm_addr);
+        xlm.transfer(&env.current_contract_address(), &to, &amount);
+
+        BankrollWithdrawn { to, amount }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Withdraw house profit accumulated from settlements. Limited to the
+    /// positive house balance and to bankroll not backing open bets.
+    pub fn withdraw_house_profit(env: Env, to: Address, amount: i128) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let house_balance = Self::get_house_balance(env.clone());
+        let bankroll = Self::get_bankroll(env.clone());
+        if amount > house_balance || amount > bankroll - Self::get_open_exposure(env.clone()) {
+            return Err(Error::InsufficientBankroll);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::HouseBalance, &(house_balance - amount));
+        env.storage()
+            .instance()
+            .set(&DataKey::Bankroll, &(bankroll - amount));
+
+        let xlm_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::XlmToken)
+            .expect("XLM not set");
+        let xlm = token::Client::new(&env, &xlm_addr);
+        xlm.transfer(&env.current_contract_address(), &to, &amount);
+
+        ProfitWithdrawn { to, amount }.publish(&env);
+
+        Ok(())
+    }
+
+    // ======================================================================
+    // Treasury sweep
+    // ======================================================================
+
+    /// Transfer accrued protocol fees to treasury (max once per 24h).
     pub fn sweep_treasury(env: Env) -> Result<i128, Error> {
         Self::require_admin(&env)?;
 
-        let now_ts = env.ledger().timestamp();
-        let last_sweep: u64 = env
+        let now_ts = env.ledger().timestamp();
+        let last_sweep: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::LastSweepTs)
+            .unwrap_or(0);
+
+        if last_sweep > 0 && now_ts.saturating_sub(last_sweep) < SWEEP_INTERVAL_SECONDS {
+            return Err(Error::SweepTooEarly);
+        }
+
+        let accrued = Self::get_fee_accrued(env.clone());
+        if accrued <= 0 {
+            return Err(Error::NothingToSweep);
+        }
+
+        Self::transfer_accrued_fees(&env, accrued);
+        env.storage().instance().set(&DataKey::LastSweepTs, &now_ts);
+
+        Ok(accrued)
+    }
+
+    /// Forward accrued fees to the treasury at settlement once they reach
+    /// `threshold`, independent of the daily sweep. 0 disables.
+    pub fn set_auto_sweep_threshold(env: Env, threshold: i128) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        if threshold < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::AutoSweepThreshold, &threshold);
+        Ok(())
+    }
+
+    pub fn get_auto_sweep_threshold(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::AutoSweepThreshold)
+            .unwrap_or(0)
+    }
+
+    fn auto_sweep(env: &Env) {
+        let threshold = Self::get_auto_sweep_threshold(env.clone());
+        let accrued = Self::get_fee_accrued(env.clone());
+        if threshold > 0 && accrued >= threshold {
+            Self::transfer_accrued_fees(env, accrued);
+            FeesAutoSwept { accrued }.publish(env);
+        }
+    }
+
+    fn transfer_accrued_fees(env: &Env, accrued: i128) {
+        let xlm_addr: Address = env
             .storage()
             .instance()
-            .get(&DataKey::LastSweepTs)
-            .unwrap_or(0);
+            .get(&DataKey::XlmToken)
+            .expect("XLM not set");
+        let xlm = token::Client::new(env, &xlm_addr);
+
+        let treasury: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Treasury)
+            .expect("Treasury not set");
+
+        xlm.transfer(&env.current_contract_address(), &treasury, &accrued);
+
+        env.storage().instance().set(&DataKey::FeeAccrued, &0_i128);
+    }
+
+    /// Transfer the fees accrued by pools that override the treasury to
+    /// that treasury.
+    pub fn sweep_treasury_fees(env: Env, treasury: Address) -> Result<i128, Error> {
+        Self::require_admin(&env)?;
+
+        let key = DataKey::TreasuryFees(treasury.clone());
+        let accrued: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if accrued <= 0 {
+            return Err(Error::NothingToSweep);
+        }
+
+        let xlm_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::XlmToken)
+            .expect("XLM not set");
+        let xlm = token::Client::new(&env, &xlm_addr);
+        xlm.transfer(&env.current_contract_address(), &treasury, &accrued);
+
+        env.storage().persistent().remove(&key);
+        let total = Self::get_treasury_fees_total(&env) - accrued;
+        env.storage()
+            .instance()
+            .set(&DataKey::TreasuryFeesTotal, &total);
+
+        TreasurySwept { treasury, accrued }.publish(&env);
+
+        Ok(accrued)
+    }
+
+    // ======================================================================
+    // Read helpers
+    // ======================================================================
+
+    pub fn get_pool(env: Env, pool_id: u32) -> Result<BetPool, Error> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::Pool(pool_id))
+            .ok_or(Error::PoolNotFound)
+    }
+
+    /// All pool ids created for a match, across markets.
+    pub fn get_match_pools(env: Env, match_id: BytesN<32>) -> Vec<u32> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::MatchPools(match_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// The canonical pool for a match and market.
+    pub fn get_pool_by_match(env: Env, match_id: BytesN<32>, market: Market) -> Result<u32, Error> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::MarketPool(match_id, market))
+            .ok_or(Error::PoolNotFound)
+    }
+
+    /// Up to `limit` pools (max 50) with ids from `start`, skipping expired ones.
+    pub fn get_pools(env: Env, start: u32, limit: u32) -> Vec<BetPool> {
+        let counter = Self::get_pool_counter(env.clone());
+        let end = start
+            .max(1)
+            .saturating_add(limit.min(MAX_POOL_PAGE))
+            .min(counter.saturating_add(1));
+
+        let mut pools = Vec::new(&env);
+        for pool_id in start.max(1)..end {
+            if let Some(pool) = env
+                .storage()
+                .temporary()
+                .get::<_, BetPool>(&DataKey::Pool(pool_id))
+            {
+                pools.push_back(pool);
+            }
+        }
+        pools
+    }
+
+    /// Ids of pools still open for bets.
+    pub fn get_open_pools(env: Env) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OpenPools)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Pools a bettor has bet in, oldest first, 20 per page.
+    pub fn get_bettor_pools(env: Env, bettor: Address, page: u32) -> Vec<u32> {
+        let history: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BettorPools(bettor))
+            .unwrap_or(Vec::new(&env));
+
+        let start = page.saturating_mul(BETTOR_PAGE_SIZE).min(history.len());
+        let end = start.saturating_add(BETTOR_PAGE_SIZE).min(history.len());
+        history.slice(start..end)
+    }
+
+    pub fn get_bet(env: Env, pool_id: u32, bettor: Address) -> Result<BetCommit, Error> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::Bet(pool_id, bettor))
+            .ok_or(Error::BetNotFound)
+    }
+
+    /// Next index into the pool's bettors for `admin_distribute`.
+    pub fn get_distribute_cursor(env: Env, pool_id: u32) -> u32 {
+        env.storage()
+            .temporary()
+            .get(&DataKey::DistributeCursor(pool_id))
+            .unwrap_or(0)
+    }
+
+    /// Pools created so far. Falls back to the instance entry used before
+    /// the counter moved to persistent storage.
+    pub fn get_pool_counter(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PoolCounter)
+            .or_else(|| env.storage().instance().get(&DataKey::PoolCounter))
+            .unwrap_or(0)
+    }
+
+    /// Compact record of a settled or refunded pool, kept after the pool
+    /// itself expires from temporary storage.
+    pub fn get_archived_pool(env: Env, pool_id: u32) -> Result<PoolArchive, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ArchivedPool(pool_id))
+            .ok_or(Error::PoolNotFound)
+    }
+
+    /// Reason recorded by `cancel_pool`, if the pool was cancelled.
+    pub fn get_cancel_reason(env: Env, pool_id: u32) -> Option<CancelReason> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CancelReason(pool_id))
+    }
+
+    /// Max commits per bettor per window, and the window in seconds.
+    pub fn get_rate_limit(env: Env) -> (u32, u64) {
+        env.storage()
+            .instance()
+            .get(&DataKey::RateLimit)
+            .unwrap_or((0, 0))
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::PendingAdmin)
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Admin not set")
+    }
+
+    pub fn get_outcome_oracle(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::OutcomeOracle)
+    }
+
+    pub fn get_proof_vk(env: Env, kind: ProofKind) -> Option<BytesN<32>> {
+        env.storage().instance().get(&DataKey::ProofVk(kind))
+    }
+
+    pub fn get_brawl_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::BrawlContract)
+    }
+
+    pub fn get_card_game_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::CardGameContract)
+    }
+
+    pub fn get_bankroll(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Bankroll)
+            .unwrap_or(0)
+    }
+
+    /// Sum of stakes in open or locked pools that the bankroll must cover.
+    pub fn get_open_exposure(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::OpenExposure)
+            .unwrap_or(0)
+    }
+
+    /// House exposure of a single pool: its stakes until it settles or refunds.
+    pub fn get_pool_exposure(env: Env, pool_id: u32) -> Result<i128, Error> {
+        let pool = Self::get_pool(env, pool_id)?;
+        match pool.status {
+            PoolStatus::Open | PoolStatus::Locked => Ok(pool.total_pool),
+            PoolStatus::Settled | PoolStatus::Refunded => Ok(0),
+        }
+    }
+
+    pub fn is_allowlisted(env: Env, bettor: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Allowlisted(bettor))
+            .unwrap_or(false)
+    }
+
+    pub fn is_blacklisted(env: Env, bettor: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Blacklisted(bettor))
+            .unwrap_or(false)
+    }
+
+    pub fn get_referral_bps(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::ReferralBps).unwrap_or(0)
+    }
+
+    pub fn get_referral_balance(env: Env, referrer: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReferralBalance(referrer))
+            .unwrap_or(0)
+    }
+
+    pub fn get_rake_bps(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::RakeBps).unwrap_or(0)
+    }
+
+    pub fn get_rake_balance(env: Env, fighter: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RakeBalance(fighter))
+            .unwrap_or(0)
+    }
+
+    /// Net house result of all settlements, minus profit withdrawn.
+    pub fn get_house_balance(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::HouseBalance)
+            .unwrap_or(0)
+    }
+
+    /// Pool state together with its display metadata, if any.
+    pub fn get_pool_info(
+        env: Env,
+        pool_id: u32,
+    ) -> Result<(BetPool, Option<PoolMetadata>), Error> {
+        let pool = Self::get_pool(env.clone(), pool_id)?;
+        let metadata = env
+            .storage()
+            .temporary()
+            .get(&DataKey::PoolMetadata(pool_id));
+        Ok((pool, metadata))
+    }
+
+    /// Timestamped status transitions of a pool and who made them.
+    pub fn get_pool_history(env: Env, pool_id: u32) -> Vec<StatusChange> {
+        env.storage()
+            .temporary()
+            .get(&DataKey::PoolHistory(pool_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Side totals, implied probabilities and payout multiplier of a pool.
+    pub fn get_odds(env: Env, pool_id: u32) -> Result<PoolOdds, Error> {
+        let pool = Self::get_pool(env, pool_id)?;
+        let (player1_bps, player2_bps, draw_bps) = Self::implied_odds_bps(&pool);
+        Ok(PoolOdds {
+            player1_total: pool.player1_total,
+            player2_total: pool.player2_total,
+            draw_total: pool.draw_total,
+            player1_bps,
+            player2_bps,
+            draw_bps,
+            payout_multiplier_bps: pool.payout_multiplier_bps,
+        })
+    }
+
+    /// Worst-case payouts still owed across all pools.
+    pub fn get_total_liability(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalLiability)
+            .unwrap_or(0)
+    }
+
+    /// Limits in force for a pool as `(max_bet_per_bettor, max_pool_size)`.
+    /// 0 means unlimited.
+    pub fn get_pool_limits(env: Env, pool_id: u32) -> Result<(i128, i128), Error> {
+        let pool = Self::get_pool(env.clone(), pool_id)?;
+        Ok(Self::effective_limits(&env, &pool))
+    }
+
+    /// Default fee applied to new pools, in bps.
+    pub fn get_fee_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::FeeBps)
+            .unwrap_or(FEE_BPS)
+    }
+
+    /// Default reveal window applied to new pools, in seconds.
+    pub fn get_reveal_window(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RevealWindow)
+            .unwrap_or(0)
+    }
+
+    pub fn get_fee_accrued(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::FeeAccrued)
+            .unwrap_or(0)
+    }
+
+    /// Fees a pool has accrued to the protocol (net of rake and referral
+    /// shares, plus forfeits and expired winnings), for treasury reconciliation.
+    pub fn get_pool_fees(env: Env, pool_id: u32) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PoolFees(pool_id))
+            .unwrap_or(0)
+    }
+
+    /// Fees accrued across all pools on a UTC day (`timestamp / 86_400`).
+    pub fn get_daily_fees(env: Env, day: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DailyFees(day))
+            .unwrap_or(0)
+    }
+
+    pub fn get_bettor_stats(env: Env, bettor: Address) -> BettorStats {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BettorStats(bettor))
+            .unwrap_or_default()
+    }
+
+    /// Top `n` bettors (up to 20) by best win streak, then total wins.
+    pub fn get_top_predictors(env: Env, n: u32) -> Vec<PredictorEntry> {
+        let board: Vec<PredictorEntry> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Leaderboard)
+            .unwrap_or(Vec::new(&env));
+        if n >= board.len() {
+            board
+        } else {
+            board.slice(0..n)
+        }
+    }
+
+    /// Fees accrued for an override treasury and not yet swept.
+    pub fn get_treasury_fees(env: Env, treasury: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TreasuryFees(treasury))
+            .unwrap_or(0)
+    }
+
+    // ======================================================================
+    // Admin setters
+    // ======================================================================
+
+    /// Propose a new admin. Control only moves once `new_admin` calls
+    /// `accept_admin`, so a mistyped address cannot brick the contract.
+    pub fn set_admin(env: Env, new_admin: Address) {
+        Self::require_admin(&env).expect("Unauthorized");
+        env.storage().instance().set(&DataKey::PendingAdmin, &new_admin);
+    }
+
+    /// Accept a pending admin proposal. Must be signed by the proposed admin.
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        let pending: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::PendingAdmin)
+            .ok_or(Error::NoPendingAdmin)?;
+        pending.require_auth();
+
+        let previous = Self::get_admin(env.clone());
+        env.storage().instance().set(&DataKey::Admin, &pending);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+
+        AdminChanged {
+            previous,
+            new_admin: pending,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn set_treasury(env: Env, new_treasury: Address) {
+        Self::require_admin(&env).expect("Unauthorized");
+        env.storage().instance().set(&DataKey::Treasury, &new_treasury);
+    }
+
+    /// Global caps on a single bet and on a pool's total stakes. 0 = no limit.
+    pub fn set_bet_limits(
+        env: Env,
+        max_bet_per_bettor: i128,
+        max_pool_size: i128,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env)?;
 
-        if last_sweep > 0 && now_ts.saturating_sub(last_sweep) < SWEEP_INTERVAL_SECONDS {
-            return Err(Error::SweepTooEarly);
+        if max_bet_per_bettor < 0 || max_pool_size < 0 {
+            return Err(Error::InvalidAmount);
         }
 
-        let accrued: i128 = env
-            .storage()
+        env.storage()
             .instance()
-            .get(&DataKey::FeeAccrued)
-            .unwrap_or(0);
+            .set(&DataKey::MaxBetPerBettor, &max_bet_per_bettor);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxPoolSize, &max_pool_size);
 
-        if accrued <= 0 {
-            return Err(Error::NothingToSweep);
+        Ok(())
+    }
+
+    /// Default fee for pools created from now on. Existing pools keep theirs.
+    pub fn set_fee_bps(env: Env, fee_bps: u32) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        if fee_bps > MAX_FEE_BPS {
+            return Err(Error::InvalidAmount);
         }
 
-        let xlm_addr: Address = env
-            .storage()
+        env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+        Ok(())
+    }
+
+    /// Default minimum reveal window after lock for pools created from now
+    /// on. Existing pools keep theirs.
+    pub fn set_reveal_window(env: Env, window_secs: u64) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        env.storage()
             .instance()
-            .get(&DataKey::XlmToken)
-            .expect("XLM not set");
-        let xlm = token::Client::new(&env, &xlm_addr);
+            .set(&DataKey::RevealWindow, &window_secs);
+        Ok(())
+    }
 
-        let treasury: Address = env
-            .storage()
+    /// Cap commits per bettor to `max_commits` per `window_secs` sliding
+    /// window, to blunt spam bots. 0 disables.
+    pub fn set_rate_limit(env: Env, max_commits: u32, window_secs: u64) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        env.storage()
             .instance()
-            .get(&DataKey::Treasury)
-            .expect("Treasury not set");
+            .set(&DataKey::RateLimit, &(max_commits, window_secs));
+        Ok(())
+    }
 
-        xlm.transfer(&env.current_contract_address(), &treasury, &accrued);
+    /// Pause or resume pool creation, betting and reveals for incident
+    /// response. Claims, refunds and settlement stay available.
+    pub fn set_paused(env: Env, paused: bool) -> Result<(), Error> {
+        Self::require_admin(&env)?;
 
-        env.storage().instance().set(&DataKey::FeeAccrued, &0_i128);
-        env.storage().instance().set(&DataKey::LastSweepTs, &now_ts);
+        env.storage().instance().set(&DataKey::Paused, &paused);
+        PausedSet { paused }.publish(&env);
+        Ok(())
+    }
 
-        Ok(accrued)
+    /// Register addresses (e.g. KYC'd or region-allowed) for allowlist-only pools.
+    pub fn add_allowlisted(env: Env, bettors: Vec<Address>) {
+        Self::require_admin(&env).expect("Unauthorized");
+        for bettor in bettors.iter() {
+            let key = DataKey::Allowlisted(bettor);
+            env.storage().persistent().set(&key, &true);
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+        }
     }
 
-    // ======================================================================
-    // Read helpers
-    // ======================================================================
+    pub fn remove_allowlisted(env: Env, bettors: Vec<Address>) {
+        Self::require_admin(&env).expect("Unauthorized");
+        for bettor in bettors.iter() {
+            env.storage().persistent().remove(&DataKey::Allowlisted(bettor));
+        }
+    }
 
-    pub fn get_pool(env: Env, pool_id: u32) -> Result<BetPool, Error> {
-        env.storage()
-            .temporary()
-            .get(&DataKey::Pool(pool_id))
-            .ok_or(Error::PoolNotFound)
+    /// Exclude abusive or self-betting accounts (e.g. a match's own fighters)
+    /// from betting and claiming.
+    pub fn add_blacklisted(env: Env, bettors: Vec<Address>) {
+        Self::require_admin(&env).expect("Unauthorized");
+        for bettor in bettors.iter() {
+            let key = DataKey::Blacklisted(bettor);
+            env.storage().persistent().set(&key, &true);
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+        }
     }
 
-    pub fn get_bet(env: Env, pool_id: u32, bettor: Address) -> Result<BetCommit, Error> {
-        env.storage()
-            .temporary()
-            .get(&DataKey::Bet(pool_id, bettor))
-            .ok_or(Error::BetNotFound)
+    pub fn remove_blacklisted(env: Env, bettors: Vec<Address>) {
+        Self::require_admin(&env).expect("Unauthorized");
+        for bettor in bettors.iter() {
+            env.storage().persistent().remove(&DataKey::Blacklisted(bettor));
+        }
     }
 
-    pub fn get_pool_counter(env: Env) -> u32 {
-        env.storage()
-            .instance()
-            .get(&DataKey::PoolCounter)
-            .unwrap_or(0)
+    /// Share of a referred bet's fee credited to the referrer (max 50%).
+    pub fn set_referral_bps(env: Env, referral_bps: u32) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        if referral_bps > MAX_REFERRAL_BPS {
+            return Err(Error::InvalidAmount);
+        }
+
+        env.storage().instance().set(&DataKey::ReferralBps, &referral_bps);
+        Ok(())
     }
 
-    pub fn get_admin(env: Env) -> Address {
-        env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set")
+    /// Share of each settled pool's fees credited to its fighters (max 50%).
+    pub fn set_rake_bps(env: Env, rake_bps: u32) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+
+        if rake_bps > MAX_RAKE_BPS {
+            return Err(Error::InvalidAmount);
+        }
+
+        env.storage().instance().set(&DataKey::RakeBps, &rake_bps);
+        Ok(())
     }
 
-    pub fn get_fee_accrued(env: Env) -> i128 {
-        env.storage()
-            .instance()
-            .get(&DataKey::FeeAccrued)
-            .unwrap_or(0)
+    pub fn set_outcome_oracle(env: Env, oracle: Address) {
+        Self::require_admin(&env).expect("Unauthorized");
+        env.storage().instance().set(&DataKey::OutcomeOracle, &oracle);
     }
 
-    // ======================================================================
-    // Admin setters
-    // ======================================================================
+    /// Public key bettors encrypt their side to in `commit_bet_audited`.
+    pub fn set_auditor_key(env: Env, key: BytesN<32>) {
+        Self::require_admin(&env).expect("Unauthorized");
+        env.storage().instance().set(&DataKey::AuditorKey, &key);
+    }
 
-    pub fn set_admin(env: Env, new_admin: Address) {
+    pub fn get_auditor_key(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&DataKey::AuditorKey)
+    }
+
+    pub fn set_brawl_contract(env: Env, brawl: Address) {
         Self::require_admin(&env).expect("Unauthorized");
-        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.storage().instance().set(&DataKey::BrawlContract, &brawl);
     }
 
-    pub fn set_treasury(env: Env, new_treasury: Address) {
+    /// Card game contract allowed to open and settle pools for its sessions.
+    pub fn set_card_game_contract(env: Env, card_game: Address) {
         Self::require_admin(&env).expect("Unauthorized");
-        env.storage().instance().set(&DataKey::Treasury, &new_treasury);
+        env.storage().instance().set(&DataKey::CardGameContract, &card_game);
     }
 
     pub fn set_zk_verifier(env: Env, verifier: Address, vk_id: BytesN<32>) {
@@ -872,6 +4115,13 @@ impl ZkBettingContract {
         env.storage().instance().set(&DataKey::ZkVkId, &vk_id);
     }
 
+    /// Verification key for one proof statement. Separate from the
+    /// settlement key set by `set_zk_verifier`.
+    pub fn set_proof_vk(env: Env, kind: ProofKind, vk_id: BytesN<32>) {
+        Self::require_admin(&env).expect("Unauthorized");
+        env.storage().instance().set(&DataKey::ProofVk(kind), &vk_id);
+    }
+
     pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
         Self::require_admin(&env).expect("Unauthorized");
         env.deployer().update_current_contract_wasm(new_wasm_hash);
@@ -881,19 +4131,460 @@ impl ZkBettingContract {
     // Internal
     // ======================================================================
 
-    fn require_admin(env: &Env) -> Result<(), Error> {
+    fn require_not_paused(env: &Env) -> Result<(), Error> {
+        if Self::is_paused(env.clone()) {
+            return Err(Error::Paused);
+        }
+        Ok(())
+    }
+
+    fn require_admin(env: &Env) -> Result<Address, Error> {
         let admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
             .ok_or(Error::Unauthorized)?;
         admin.require_auth();
+        Ok(admin)
+    }
+
+    /// Implied probability of each side from revealed stakes, in bps, as
+    /// `(player1, player2, draw)`. All zero before any reveal.
+    fn implied_odds_bps(pool: &BetPool) -> (u32, u32, u32) {
+        let revealed = pool.player1_total + pool.player2_total + pool.draw_total;
+        if revealed == 0 {
+            return (0, 0, 0);
+        }
+        let bps = |total: i128| (total * BPS_DENOMINATOR as i128 / revealed) as u32;
+        (bps(pool.player1_total), bps(pool.player2_total), bps(pool.draw_total))
+    }
+
+    /// Map a market's raw outcome to the winning side.
+    fn market_winner(pool: &BetPool, outcome: u32) -> Result<BetSide, Error> {
+        let winner = match pool.market {
+            Market::MatchWinner | Market::FirstBlood => match outcome {
+                SIDE_P1 => BetSide::Player1,
+                SIDE_P2 => BetSide::Player2,
+                SIDE_DRAW => BetSide::Draw,
+                _ => return Err(Error::InvalidOutcome),
+            },
+            Market::TotalMoves | Market::RoundCount => {
+                if outcome > pool.line {
+                    BetSide::Player1
+                } else if outcome == pool.line && pool.allow_draw {
+                    BetSide::Draw
+                } else {
+                    BetSide::Player2
+                }
+            }
+        };
+        Ok(winner)
+    }
+
+    /// Check bet and pool limits for a bet growing to `bet_amount` by `added`
+    /// stake, then reserve `added` against the house bankroll.
+    fn reserve_bet_capacity(
+        env: &Env,
+        pool: &mut BetPool,
+        bet_amount: i128,
+        added: i128,
+    ) -> Result<(), Error> {
+        let (max_bet, max_pool) = Self::effective_limits(env, pool);
+        if max_bet > 0 && bet_amount > max_bet {
+            return Err(Error::BetLimitExceeded);
+        }
+        if max_pool > 0 && pool.total_pool + added > max_pool {
+            return Err(Error::PoolLimitExceeded);
+        }
+
+        // The house must be able to cover this bet winning.
+        let exposure = Self::get_open_exposure(env.clone()) + Self::house_cover(pool, added);
+        if exposure > Self::get_bankroll(env.clone()) {
+            return Err(Error::InsufficientBankroll);
+        }
+        // Worst case the bet wins; all liabilities must stay covered by
+        // the balance (including this deposit) net of accrued fees.
+        let incoming = added + Self::calc_fee(added, pool.fee_bps);
+        let available = Self::free_balance(env) + incoming;
+        let payout = Self::payout_for(pool, added);
+        let liability = Self::get_total_liability(env.clone()) + payout;
+        if liability > available {
+            return Err(Error::InsufficientLiquidity);
+        }
+
+        env.storage().instance().set(&DataKey::OpenExposure, &exposure);
+        env.storage().instance().set(&DataKey::TotalLiability, &liability);
+        pool.liability += payout;
+
+        Ok(())
+    }
+
+    fn referral_share(env: &Env, fee: i128) -> i128 {
+        fee * Self::get_referral_bps(env.clone()) as i128 / BPS_DENOMINATOR as i128
+    }
+
+    /// Adjust a referrer's pending share in an unsettled pool. Pending shares
+    /// only become claimable at settlement, since fees are refunded otherwise.
+    fn add_pending_referral(env: &Env, pool_id: u32, referrer: Address, amount: i128) {
+        let key = DataKey::PoolReferrals(pool_id);
+        let mut pending: Map<Address, i128> = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .unwrap_or(Map::new(env));
+        let total = pending.get(referrer.clone()).unwrap_or(0) + amount;
+        if total > 0 {
+            pending.set(referrer, total);
+        } else {
+            pending.remove(referrer);
+        }
+        env.storage().temporary().set(&key, &pending);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+    }
+
+    /// Move a settled pool's pending referral shares to referrer balances.
+    /// Returns the total credited.
+    fn credit_referrals(env: &Env, pool_id: u32) -> i128 {
+        let key = DataKey::PoolReferrals(pool_id);
+        let pending: Map<Address, i128> = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .unwrap_or(Map::new(env));
+
+        let mut total = 0;
+        for (referrer, amount) in pending.iter() {
+            let balance_key = DataKey::ReferralBalance(referrer);
+            let balance: i128 = env.storage().persistent().get(&balance_key).unwrap_or(0);
+            env.storage().persistent().set(&balance_key, &(balance + amount));
+            env.storage()
+                .persistent()
+                .extend_ttl(&balance_key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+            total += amount;
+        }
+        env.storage().temporary().remove(&key);
+        Self::add_claimable_total(env, DataKey::ReferralBalanceTotal, total);
+
+        total
+    }
+
+    /// Split the rake share of a settled pool's fees between its fighters.
+    /// Returns the total credited, 0 if the pool names no fighters.
+    fn credit_rake(env: &Env, pool: &BetPool) -> i128 {
+        let (Some(fighter1), Some(fighter2)) = (pool.fighter1.clone(), pool.fighter2.clone()) else {
+            return 0;
+        };
+
+        let rake = pool.total_fees * Self::get_rake_bps(env.clone()) as i128
+            / BPS_DENOMINATOR as i128;
+        let half = rake / 2;
+        if half == 0 {
+            return 0;
+        }
+
+        for fighter in [fighter1, fighter2] {
+            let key = DataKey::RakeBalance(fighter);
+            let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            env.storage().persistent().set(&key, &(balance + half));
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+        }
+        Self::add_claimable_total(env, DataKey::RakeBalanceTotal, half * 2);
+
+        half * 2
+    }
+
+    fn get_claimable_total(env: &Env, total_key: &DataKey) -> i128 {
+        env.storage().instance().get(total_key).unwrap_or(0)
+    }
+
+    /// Adjust the running sum kept under `total_key` by `delta`.
+    fn add_claimable_total(env: &Env, total_key: DataKey, delta: i128) {
+        let total = Self::get_claimable_total(env, &total_key) + delta;
+        env.storage().instance().set(&total_key, &total);
+    }
+
+    /// Release `amount` of a pool's recorded liability.
+    fn reduce_liability(env: &Env, pool: &mut BetPool, amount: i128) {
+        pool.liability -= amount;
+        let total = Self::get_total_liability(env.clone()) - amount;
+        env.storage().instance().set(&DataKey::TotalLiability, &total);
+    }
+
+    fn effective_limits(env: &Env, pool: &BetPool) -> (i128, i128) {
+        let max_bet = if pool.max_bet_per_bettor > 0 {
+            pool.max_bet_per_bettor
+        } else {
+            env.storage()
+                .instance()
+                .get(&DataKey::MaxBetPerBettor)
+                .unwrap_or(0)
+        };
+        let max_pool = if pool.max_pool_size > 0 {
+            pool.max_pool_size
+        } else {
+            env.storage()
+                .instance()
+                .get(&DataKey::MaxPoolSize)
+                .unwrap_or(0)
+        };
+        (max_bet, max_pool)
+    }
+
+    /// Add fees to the pool's treasury bucket: its override treasury if set,
+    /// otherwise the global accrual swept by `sweep_treasury`.
+    fn accrue_fees(env: &Env, pool: &BetPool, amount: i128) {
+        let day = env.ledger().timestamp() / 86_400;
+        for key in [DataKey::PoolFees(pool.pool_id), DataKey::DailyFees(day)] {
+            let accrued: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            env.storage().persistent().set(&key, &(accrued + amount));
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, ARCHIVE_TTL_LEDGERS, ARCHIVE_TTL_LEDGERS);
+        }
+
+        match &pool.treasury {
+            Some(treasury) => {
+                let key = DataKey::TreasuryFees(treasury.clone());
+                let accrued: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+                env.storage().persistent().set(&key, &(accrued + amount));
+                env.storage()
+                    .persistent()
+                    .extend_ttl(&key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+                let total = Self::get_treasury_fees_total(env) + amount;
+                env.storage()
+                    .instance()
+                    .set(&DataKey::TreasuryFeesTotal, &total);
+            }
+            None => {
+                let accrued = Self::get_fee_accrued(env.clone()) + amount;
+                env.storage().instance().set(&DataKey::FeeAccrued, &accrued);
+            }
+        }
+    }
+
+    fn get_treasury_fees_total(env: &Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TreasuryFeesTotal)
+            .unwrap_or(0)
+    }
+
+    fn set_bettor_stats(env: &Env, bettor: &Address, stats: &BettorStats) {
+        let key = DataKey::BettorStats(bettor.clone());
+        env.storage().persistent().set(&key, stats);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+    }
+
+    /// Count a claimed win. The streak continues when the bettor's previous
+    /// pool, in betting order, was also a claimed win.
+    fn record_win(env: &Env, bettor: &Address, pool_id: u32) {
+        let mut stats = Self::get_bettor_stats(env.clone(), bettor.clone());
+        let history: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BettorPools(bettor.clone()))
+            .unwrap_or(Vec::new(env));
+        let previous = match history.first_index_of(pool_id) {
+            Some(index) if index > 0 => history.get(index - 1),
+            _ => None,
+        };
+
+        let continues = stats.current_streak > 0 && previous == Some(stats.last_win_pool);
+        stats.wins += 1;
+        stats.current_streak = if continues { stats.current_streak + 1 } else { 1 };
+        stats.best_streak = stats.best_streak.max(stats.current_streak);
+        stats.last_win_pool = pool_id;
+        Self::set_bettor_stats(env, bettor, &stats);
+        Self::update_leaderboard(env, bettor, &stats);
+    }
+
+    /// Re-rank a bettor on the leaderboard after a win.
+    fn update_leaderboard(env: &Env, bettor: &Address, stats: &BettorStats) {
+        let mut board = Self::get_top_predictors(env.clone(), LEADERBOARD_SIZE);
+        if let Some(index) = board.iter().position(|entry| entry.bettor == *bettor) {
+            board.remove(index as u32);
+        }
+
+        let rank = (stats.best_streak, stats.wins);
+        let index = board
+            .iter()
+            .position(|entry| (entry.best_streak, entry.wins) < rank)
+            .unwrap_or(board.len() as usize) as u32;
+        if index >= LEADERBOARD_SIZE {
+            return;
+        }
+        board.insert(
+            index,
+            PredictorEntry {
+                bettor: bettor.clone(),
+                best_streak: stats.best_streak,
+                wins: stats.wins,
+            },
+        );
+        if board.len() > LEADERBOARD_SIZE {
+            board.pop_back();
+        }
+
+        env.storage().persistent().set(&DataKey::Leaderboard, &board);
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKey::Leaderboard, ARCHIVE_TTL_LEDGERS, ARCHIVE_TTL_LEDGERS);
+    }
+
+    fn archive_pool(env: &Env, pool: &BetPool) {
+        let key = DataKey::ArchivedPool(pool.pool_id);
+        let record = PoolArchive {
+            pool_id: pool.pool_id,
+            match_id: pool.match_id.clone(),
+            market: pool.market,
+            status: pool.status,
+            winner_side: pool.winner_side,
+            total_pool: pool.total_pool,
+            total_fees: pool.total_fees,
+            bet_count: pool.bet_count,
+            closed_ts: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&key, &record);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ARCHIVE_TTL_LEDGERS, ARCHIVE_TTL_LEDGERS);
+    }
+
+    /// Append a status transition to the pool's audit log.
+    fn record_status(env: &Env, pool_id: u32, status: PoolStatus, actor: Option<Address>) {
+        let key = DataKey::PoolHistory(pool_id);
+        let mut history: Vec<StatusChange> = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        history.push_back(StatusChange {
+            status,
+            ts: env.ledger().timestamp(),
+            actor,
+        });
+        env.storage().temporary().set(&key, &history);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+    }
+
+    fn remove_open_pool(env: &Env, pool_id: u32) {
+        let mut open_pools = Self::get_open_pools(env.clone());
+        if let Some(index) = open_pools.first_index_of(pool_id) {
+            open_pools.remove(index);
+            Self::save_open_pools(env, open_pools);
+        }
+    }
+
+    /// Store the open pool index, dropping pools whose entry has expired or
+    /// whose betting deadline has passed, so the list stays bounded by the
+    /// pools actually taking bets.
+    fn save_open_pools(env: &Env, open_pools: Vec<u32>) {
+        let now = env.ledger().timestamp();
+        let mut live = Vec::new(env);
+        for pool_id in open_pools.iter() {
+            let pool: Option<BetPool> = env.storage().temporary().get(&DataKey::Pool(pool_id));
+            if let Some(pool) = pool {
+                if pool.deadline_ts == 0 || now <= pool.deadline_ts {
+                    live.push_back(pool_id);
+                }
+            }
+        }
+        env.storage().persistent().set(&DataKey::OpenPools, &live);
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKey::OpenPools, POOL_TTL_LEDGERS, POOL_TTL_LEDGERS);
+    }
+
+    fn release_exposure(env: &Env, amount: i128) {
+        let exposure = Self::get_open_exposure(env.clone()) - amount;
+        env.storage()
+            .instance()
+            .set(&DataKey::OpenExposure, &exposure.max(0));
+    }
+
+    /// Payout on a winning `stake` at the pool's multiplier, after any
+    /// settlement haircut.
+    fn payout_for(pool: &BetPool, stake: i128) -> i128 {
+        let full = stake * pool.payout_multiplier_bps as i128 / BPS_DENOMINATOR as i128;
+        full * pool.haircut_bps as i128 / BPS_DENOMINATOR as i128
+    }
+
+    /// Contract balance not set aside as accrued fees or claimable rake and
+    /// referral rewards.
+    fn free_balance(env: &Env) -> i128 {
+        let xlm_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::XlmToken)
+            .expect("XLM not set");
+        token::Client::new(env, &xlm_addr).balance(&env.current_contract_address())
+            - Self::get_fee_accrued(env.clone())
+            - Self::get_treasury_fees_total(env)
+            - Self::get_claimable_total(env, &DataKey::RakeBalanceTotal)
+            - Self::get_claimable_total(env, &DataKey::ReferralBalanceTotal)
+    }
+
+    /// Part of a winning payout the house covers beyond the stake itself.
+    fn house_cover(pool: &BetPool, stake: i128) -> i128 {
+        Self::payout_for(pool, stake) - stake
+    }
+
+    fn calc_fee(amount: i128, fee_bps: u32) -> i128 {
+        // Round up
+        ((amount * fee_bps as i128) + 9_999) / 10_000
+    }
+
+    /// Check a `kind` proof against the configured verifier and that
+    /// statement's key, bound to its domain tag and then `expected` as its
+    /// leading public inputs.
+    fn verify_zk_proof(
+        env: &Env,
+        kind: ProofKind,
+        proof: &Bytes,
+        public_inputs: &Vec<BytesN<32>>,
+        expected: &Vec<BytesN<32>>,
+    ) -> Result<(), Error> {
+        let verifier_addr: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::ZkVerifier)
+            .ok_or(Error::ZkVerifierNotConfigured)?;
+        let vk_id = Self::get_proof_vk(env.clone(), kind).ok_or(Error::ZkVerifierNotConfigured)?;
+
+        if proof.len() != 256 || public_inputs.len() < expected.len() + 1 {
+            return Err(Error::ZkProofInvalid);
+        }
+        if public_inputs.get(0) != Some(Self::u32_to_bytes32(env, kind as u32)) {
+            return Err(Error::PublicInputMismatch);
+        }
+        for (i, input) in expected.iter().enumerate() {
+            if public_inputs.get(i as u32 + 1) != Some(input) {
+                return Err(Error::PublicInputMismatch);
+            }
+        }
+
+        let verifier = ZkVerifierClient::new(env, &verifier_addr);
+        if !verifier.verify_round_proof(&vk_id, proof, public_inputs) {
+            return Err(Error::ZkProofInvalid);
+        }
         Ok(())
     }
 
-    fn calc_fee(amount: i128) -> i128 {
-        // 1% = 100 bps, round up
-        ((amount * FEE_BPS as i128) + 9_999) / 10_000
+    fn i128_to_bytes32(env: &Env, value: i128) -> Result<BytesN<32>, Error> {
+        if value < 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let mut raw = [0u8; 32];
+        raw[16..].copy_from_slice(&value.to_be_bytes());
+        Ok(BytesN::from_array(env, &raw))
     }
 
     fn u32_to_bytes32(env: &Env, value: u32) -> BytesN<32> {
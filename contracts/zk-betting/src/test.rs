@@ -2,9 +2,9 @@
 
 use super::*;
 use soroban_sdk::{
-    contract, contractimpl,
-    testutils::Address as _,
-    Bytes, BytesN, Env, Vec,
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Events as _, Ledger as _},
+    Bytes, BytesN, Env, IntoVal, Vec,
 };
 
 #[contract]
@@ -37,6 +37,71 @@ impl MockVerifierRejectContract {
     }
 }
 
+#[contract]
+struct MockBrawlContract;
+
+#[contractimpl]
+impl MockBrawlContract {
+    pub fn set_match(env: Env, session_id: u32, game: BrawlMatch) {
+        env.storage().temporary().set(&session_id, &game);
+    }
+
+    pub fn get_match(env: Env, session_id: u32) -> BrawlMatch {
+        env.storage().temporary().get(&session_id).unwrap()
+    }
+}
+
+#[contract]
+struct MockOracleContract;
+
+#[contractimpl]
+impl MockOracleContract {
+    pub fn set_outcome(env: Env, match_id: BytesN<32>, market: u32, outcome: u32) {
+        env.storage().temporary().set(&(match_id, market), &outcome);
+    }
+
+    pub fn get_outcome(env: Env, match_id: BytesN<32>, market: u32) -> Option<u32> {
+        env.storage().temporary().get(&(match_id, market))
+    }
+}
+
+fn brawl_match(env: &Env, winner_is_player1: Option<bool>) -> BrawlMatch {
+    let player1 = Address::generate(env);
+    let player2 = Address::generate(env);
+    let winner = winner_is_player1.map(|p1| if p1 { player1.clone() } else { player2.clone() });
+    BrawlMatch {
+        player1,
+        player2,
+        player1_points: 0,
+        player2_points: 0,
+        player1_moves: 12,
+        player2_moves: 10,
+        player1_surges: 0,
+        player2_surges: 0,
+        player1_rounds_won: 2,
+        player2_rounds_won: 1,
+        total_xlm_collected: 0,
+        stake_amount_stroops: 0,
+        stake_fee_bps: 0,
+        surge_price_bps: 0,
+        stake_deadline_ts: 0,
+        player1_stake_paid: false,
+        player2_stake_paid: false,
+        fee_accrued_stroops: 0,
+        player1_zk_commits: 0,
+        player2_zk_commits: 0,
+        player1_zk_verified: 0,
+        player2_zk_verified: 0,
+        season_id: 1,
+        player1_character: None,
+        player2_character: None,
+        player1_loadout_hash: None,
+        player2_loadout_hash: None,
+        is_cancelled: false,
+        winner,
+    }
+}
+
 fn setup_env() -> (Env, Address, Address, Address, Address) {
     let env = Env::default();
     env.mock_all_auths();
@@ -47,6 +112,10 @@ fn setup_env() -> (Env, Address, Address, Address, Address) {
 
     let contract_id = env.register(ZkBettingContract, (&admin, &treasury, &xlm_token));
 
+    // House bankroll backing the winning half of 2x payouts.
+    token::StellarAssetClient::new(&env, &xlm_token).mint(&admin, &100_000_000_000);
+    ZkBettingContractClient::new(&env, &contract_id).deposit_bankroll(&admin, &10_000_000_000);
+
     (env, contract_id, admin, treasury, xlm_token)
 }
 
@@ -62,6 +131,11 @@ fn match_id(env: &Env) -> BytesN<32> {
     BytesN::from_array(env, &[1u8; 32])
 }
 
+/// A match id no pool uses yet, for tests that open several pools.
+fn next_match_id(env: &Env, client: &ZkBettingContractClient) -> BytesN<32> {
+    u32_to_bytes32(env, client.get_pool_counter() + 1)
+}
+
 fn u32_to_bytes32(env: &Env, value: u32) -> BytesN<32> {
     let mut out = [0u8; 32];
     let be = value.to_be_bytes();
@@ -184,6 +258,31 @@ fn test_settle_and_claim() {
     assert_eq!(balance_after - balance_before, payout);
 }
 
+#[test]
+fn test_claim_payout_to_recipient() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let bettor = Address::generate(&env);
+    let cold_wallet = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &xlm_token).mint(&bettor, &1_000_000_000);
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt = BytesN::from_array(&env, &[10u8; 32]);
+    client.commit_bet(&pool_id, &bettor, &make_commitment(&env, 0, &salt), &100_000_000);
+    client.lock_pool(&pool_id);
+    client.reveal_bet(&pool_id, &bettor, &BetSide::Player1, &salt);
+    client.settle_pool(&pool_id, &BetSide::Player1);
+
+    assert_eq!(client.claim_payout_to(&pool_id, &bettor, &cold_wallet), 200_000_000);
+    assert_eq!(token::Client::new(&env, &xlm_token).balance(&cold_wallet), 200_000_000);
+    assert!(client.get_bet(&pool_id, &bettor).claimed);
+    assert_eq!(
+        client.try_claim_payout(&pool_id, &bettor),
+        Err(Ok(Error::AlreadyClaimed))
+    );
+}
+
 #[test]
 fn test_refund_pool() {
     let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
@@ -267,10 +366,9 @@ fn test_pool_counter_increments() {
     let (env, contract_id, _admin, _treasury, _xlm) = setup_env();
     let client = ZkBettingContractClient::new(&env, &contract_id);
 
-    let mid = match_id(&env);
-    let id1 = client.create_pool(&mid, &0);
-    let id2 = client.create_pool(&mid, &0);
-    let id3 = client.create_pool(&mid, &0);
+    let id1 = client.create_pool(&next_match_id(&env, &client), &0);
+    let id2 = client.create_pool(&next_match_id(&env, &client), &0);
+    let id3 = client.create_pool(&next_match_id(&env, &client), &0);
 
     assert_eq!(id1, 1);
     assert_eq!(id2, 2);
@@ -309,6 +407,33 @@ fn test_settle_pool_zk_success_with_bound_inputs() {
     assert_eq!(pool.winner_side, winner_side);
 }
 
+#[test]
+fn test_settle_pool_zk_is_permissionless() {
+    let (env, contract_id, _admin, _treasury, _xlm) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let verifier = env.register(MockVerifierAcceptContract, ());
+    let vk_id = BytesN::from_array(&env, &[7u8; 32]);
+    client.set_zk_verifier(&verifier, &vk_id);
+
+    let mid = match_id(&env);
+    let pool_id = client.create_pool(&mid, &0);
+    client.lock_pool(&pool_id);
+
+    // No authorizations at all: a third party relays the proof.
+    env.set_auths(&[]);
+
+    let proof = Bytes::from_array(&env, &[5u8; 256]);
+    let public_inputs = Vec::from_array(
+        &env,
+        [mid, u32_to_bytes32(&env, pool_id), u32_to_bytes32(&env, 1)],
+    );
+    client.settle_pool_zk(&pool_id, &BetSide::Player2, &vk_id, &proof, &public_inputs);
+
+    assert_eq!(client.get_pool(&pool_id).status, PoolStatus::Settled);
+    assert!(client.try_settle_pool(&pool_id, &BetSide::Player1).is_err());
+}
+
 #[test]
 fn test_settle_pool_zk_requires_verifier_config() {
     let (env, contract_id, _admin, _treasury, _xlm) = setup_env();
@@ -385,7 +510,7 @@ fn test_settle_pool_zk_rejects_public_input_binding_mismatch() {
         ],
     );
     let winner_result = client.try_settle_pool_zk(&pool_id, &BetSide::Player1, &vk_id, &proof, &bad_winner_inputs);
-    assert!(winner_result.is_err());
+    assert_eq!(winner_result, Err(Ok(Error::PublicInputMismatch)));
 
     let bad_pool_inputs = Vec::from_array(
         &env,
@@ -396,7 +521,7 @@ fn test_settle_pool_zk_rejects_public_input_binding_mismatch() {
         ],
     );
     let pool_result = client.try_settle_pool_zk(&pool_id, &BetSide::Player1, &vk_id, &proof, &bad_pool_inputs);
-    assert!(pool_result.is_err());
+    assert_eq!(pool_result, Err(Ok(Error::PublicInputMismatch)));
 
     let bad_match_inputs = Vec::from_array(
         &env,
@@ -407,7 +532,13 @@ fn test_settle_pool_zk_rejects_public_input_binding_mismatch() {
         ],
     );
     let match_result = client.try_settle_pool_zk(&pool_id, &BetSide::Player1, &vk_id, &proof, &bad_match_inputs);
-    assert!(match_result.is_err());
+    assert_eq!(match_result, Err(Ok(Error::PublicInputMismatch)));
+
+    let short_inputs = Vec::from_array(&env, [mid.clone(), u32_to_bytes32(&env, pool_id)]);
+    let short_result = client.try_settle_pool_zk(&pool_id, &BetSide::Player1, &vk_id, &proof, &short_inputs);
+    assert_eq!(short_result, Err(Ok(Error::ZkProofInvalid)));
+
+    assert_eq!(client.get_pool(&pool_id).status, PoolStatus::Locked);
 }
 
 #[test]
@@ -436,3 +567,2058 @@ fn test_settle_pool_zk_rejects_when_verifier_returns_false() {
     let result = client.try_settle_pool_zk(&pool_id, &BetSide::Player1, &vk_id, &proof, &public_inputs);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_commit_bet_rejected_beyond_bankroll() {
+    let (env, contract_id, admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let bettor = Address::generate(&env);
+    xlm.mint(&bettor, &100_000_000_000);
+
+    client.withdraw_bankroll(&admin, &9_900_000_000); // leave 10 XLM
+    let pool_id = client.create_pool(&match_id(&env), &0);
+
+    let salt = BytesN::from_array(&env, &[1u8; 32]);
+    let commit = make_commitment(&env, 0, &salt);
+    let result = client.try_commit_bet(&pool_id, &bettor, &commit, &100_000_001);
+    assert_eq!(result, Err(Ok(Error::InsufficientBankroll)));
+
+    client.commit_bet(&pool_id, &bettor, &commit, &100_000_000);
+    assert_eq!(client.get_open_exposure(), 100_000_000);
+    assert_eq!(client.get_pool_exposure(&pool_id), 100_000_000);
+
+    // Bankroll backing the open bet cannot be withdrawn.
+    let result = client.try_withdraw_bankroll(&admin, &1);
+    assert_eq!(result, Err(Ok(Error::InsufficientBankroll)));
+}
+
+#[test]
+fn test_settlement_moves_stakes_into_bankroll() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    xlm.mint(&winner, &100_000_000_000);
+    xlm.mint(&loser, &100_000_000_000);
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt_w = BytesN::from_array(&env, &[1u8; 32]);
+    let salt_l = BytesN::from_array(&env, &[2u8; 32]);
+    client.commit_bet(&pool_id, &winner, &make_commitment(&env, 0, &salt_w), &100_000_000);
+    client.commit_bet(&pool_id, &loser, &make_commitment(&env, 1, &salt_l), &300_000_000);
+    client.lock_pool(&pool_id);
+    client.reveal_bet(&pool_id, &winner, &BetSide::Player1, &salt_w);
+    client.reveal_bet(&pool_id, &loser, &BetSide::Player2, &salt_l);
+    client.settle_pool(&pool_id, &BetSide::Player1);
+
+    // 400 in stakes, 200 owed to the winner: the house nets 200.
+    assert_eq!(client.get_bankroll(), 10_000_000_000 + 200_000_000);
+    assert_eq!(client.get_open_exposure(), 0);
+    assert_eq!(client.get_pool_exposure(&pool_id), 0);
+}
+
+#[test]
+fn test_global_bet_limits_enforced() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let bettor1 = Address::generate(&env);
+    let bettor2 = Address::generate(&env);
+    xlm.mint(&bettor1, &100_000_000_000);
+    xlm.mint(&bettor2, &100_000_000_000);
+
+    client.set_bet_limits(&50_000_000, &80_000_000);
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt = BytesN::from_array(&env, &[1u8; 32]);
+    let commit = make_commitment(&env, 0, &salt);
+
+    let result = client.try_commit_bet(&pool_id, &bettor1, &commit, &50_000_001);
+    assert_eq!(result, Err(Ok(Error::BetLimitExceeded)));
+    client.commit_bet(&pool_id, &bettor1, &commit, &50_000_000);
+
+    let result = client.try_commit_bet(&pool_id, &bettor2, &commit, &40_000_000);
+    assert_eq!(result, Err(Ok(Error::PoolLimitExceeded)));
+    client.commit_bet(&pool_id, &bettor2, &commit, &30_000_000);
+}
+
+#[test]
+fn test_pool_limits_override_global() {
+    let (env, contract_id, _admin, _treasury, _xlm) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    client.set_bet_limits(&50_000_000, &80_000_000);
+    let default_pool = client.create_pool(&next_match_id(&env, &client), &0);
+    let custom_pool = client.create_pool_with_config(
+        &next_match_id(&env, &client),
+        &0,
+        &PoolConfig {
+            min_bet: 0,
+            max_bet_per_bettor: 200_000_000,
+            max_pool_size: 0,
+            fee_bps: None,
+            treasury: None,
+            payout_multiplier_bps: None,
+            reveal_window_secs: None,
+        },
+    );
+
+    assert_eq!(client.get_pool_limits(&default_pool), (50_000_000, 80_000_000));
+    assert_eq!(client.get_pool_limits(&custom_pool), (200_000_000, 80_000_000));
+}
+
+#[test]
+fn test_draw_side_settles_when_enabled() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let xlm_client = token::Client::new(&env, &xlm_token);
+    let bettor1 = Address::generate(&env);
+    let bettor2 = Address::generate(&env);
+    xlm.mint(&bettor1, &100_000_000_000);
+    xlm.mint(&bettor2, &100_000_000_000);
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    client.set_pool_allow_draw(&pool_id, &true);
+
+    let salt1 = BytesN::from_array(&env, &[1u8; 32]);
+    let salt2 = BytesN::from_array(&env, &[2u8; 32]);
+    client.commit_bet(&pool_id, &bettor1, &make_commitment(&env, 2, &salt1), &10_000_000);
+    client.commit_bet(&pool_id, &bettor2, &make_commitment(&env, 0, &salt2), &10_000_000);
+    client.lock_pool(&pool_id);
+    client.reveal_bet(&pool_id, &bettor1, &BetSide::Draw, &salt1);
+    client.reveal_bet(&pool_id, &bettor2, &BetSide::Player1, &salt2);
+
+    client.settle_pool(&pool_id, &BetSide::Draw);
+    let pool = client.get_pool(&pool_id);
+    assert_eq!(pool.winner_side, 2);
+    assert_eq!(pool.draw_total, 10_000_000);
+
+    let before = xlm_client.balance(&bettor1);
+    assert_eq!(client.claim_payout(&pool_id, &bettor1), 20_000_000);
+    assert_eq!(xlm_client.balance(&bettor1) - before, 20_000_000);
+    assert_eq!(
+        client.try_claim_payout(&pool_id, &bettor2),
+        Err(Ok(Error::NoPayout))
+    );
+}
+
+#[test]
+fn test_draw_rejected_when_pool_disallows_it() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let bettor = Address::generate(&env);
+    xlm.mint(&bettor, &100_000_000_000);
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt = BytesN::from_array(&env, &[3u8; 32]);
+    client.commit_bet(&pool_id, &bettor, &make_commitment(&env, 2, &salt), &10_000_000);
+
+    // Terms cannot change once bets are in.
+    assert_eq!(
+        client.try_set_pool_allow_draw(&pool_id, &true),
+        Err(Ok(Error::PoolNotOpen))
+    );
+
+    client.lock_pool(&pool_id);
+    assert_eq!(
+        client.try_reveal_bet(&pool_id, &bettor, &BetSide::Draw, &salt),
+        Err(Ok(Error::DrawNotAllowed))
+    );
+    assert_eq!(
+        client.try_settle_pool(&pool_id, &BetSide::Draw),
+        Err(Ok(Error::DrawNotAllowed))
+    );
+}
+
+#[test]
+fn test_multiple_markets_per_match() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let bettor = Address::generate(&env);
+    xlm.mint(&bettor, &100_000_000_000);
+
+    let mid = match_id(&env);
+    let winner_pool = client.create_pool(&mid, &0);
+    let moves_pool = client.create_market_pool(&mid, &Market::TotalMoves, &20, &0);
+    let blood_pool = client.create_market_pool(&mid, &Market::FirstBlood, &0, &0);
+
+    assert_eq!(
+        client.get_match_pools(&mid),
+        Vec::from_array(&env, [winner_pool, moves_pool, blood_pool])
+    );
+    assert_eq!(client.get_pool(&moves_pool).market, Market::TotalMoves);
+    assert_eq!(client.get_pool(&moves_pool).line, 20);
+
+    // Bet "over 20 moves" (Player1 side).
+    let salt = BytesN::from_array(&env, &[4u8; 32]);
+    client.commit_bet(&moves_pool, &bettor, &make_commitment(&env, 0, &salt), &10_000_000);
+    client.lock_pool(&moves_pool);
+    client.reveal_bet(&moves_pool, &bettor, &BetSide::Player1, &salt);
+
+    client.settle_market(&moves_pool, &23);
+    assert_eq!(client.get_pool(&moves_pool).winner_side, 0);
+    assert_eq!(client.claim_payout(&moves_pool, &bettor), 20_000_000);
+
+    client.lock_pool(&blood_pool);
+    assert_eq!(
+        client.try_settle_market(&blood_pool, &7),
+        Err(Ok(Error::InvalidOutcome))
+    );
+    client.settle_market(&blood_pool, &1);
+    assert_eq!(client.get_pool(&blood_pool).winner_side, 1);
+}
+
+#[test]
+fn test_over_under_line_settles_under() {
+    let (env, contract_id, _admin, _treasury, _xlm) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let pool_id = client.create_market_pool(&match_id(&env), &Market::RoundCount, &3, &0);
+    client.settle_market(&pool_id, &3);
+    assert_eq!(client.get_pool(&pool_id).winner_side, 1);
+}
+
+#[test]
+fn test_cancel_bet_refunds_while_open() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let xlm_client = token::Client::new(&env, &xlm_token);
+    let bettor = Address::generate(&env);
+    xlm.mint(&bettor, &100_000_000_000);
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt = BytesN::from_array(&env, &[5u8; 32]);
+    let commit = make_commitment(&env, 0, &salt);
+    let before = xlm_client.balance(&bettor);
+    client.commit_bet(&pool_id, &bettor, &commit, &10_000_000);
+
+    assert_eq!(client.cancel_bet(&pool_id, &bettor), 10_100_000);
+    assert_eq!(xlm_client.balance(&bettor), before);
+
+    let pool = client.get_pool(&pool_id);
+    assert_eq!(pool.bet_count, 0);
+    assert_eq!(pool.total_pool, 0);
+    assert_eq!(pool.total_fees, 0);
+    assert_eq!(client.get_open_exposure(), 0);
+    assert_eq!(client.try_get_bet(&pool_id, &bettor), Err(Ok(Error::BetNotFound)));
+
+    // The bettor can place a fresh bet, but not cancel once locked.
+    client.commit_bet(&pool_id, &bettor, &commit, &10_000_000);
+    client.lock_pool(&pool_id);
+    assert_eq!(
+        client.try_cancel_bet(&pool_id, &bettor),
+        Err(Ok(Error::PoolNotOpen))
+    );
+}
+
+#[test]
+fn test_increase_bet_keeps_side_and_pays_out_total() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let bettor = Address::generate(&env);
+    xlm.mint(&bettor, &100_000_000_000);
+
+    client.set_bet_limits(&25_000_000, &0);
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt = BytesN::from_array(&env, &[6u8; 32]);
+    client.commit_bet(&pool_id, &bettor, &make_commitment(&env, 1, &salt), &10_000_000);
+
+    client.increase_bet(&pool_id, &bettor, &5_000_000);
+    let bet = client.get_bet(&pool_id, &bettor);
+    assert_eq!(bet.amount, 15_000_000);
+    assert_eq!(bet.fee_paid, 150_000);
+    assert_eq!(client.get_pool(&pool_id).total_pool, 15_000_000);
+    assert_eq!(client.get_open_exposure(), 15_000_000);
+
+    assert_eq!(
+        client.try_increase_bet(&pool_id, &bettor, &20_000_000),
+        Err(Ok(Error::BetLimitExceeded))
+    );
+
+    client.lock_pool(&pool_id);
+    assert_eq!(
+        client.try_increase_bet(&pool_id, &bettor, &1_000_000),
+        Err(Ok(Error::PoolNotOpen))
+    );
+    client.reveal_bet(&pool_id, &bettor, &BetSide::Player2, &salt);
+    client.settle_pool(&pool_id, &BetSide::Player2);
+    assert_eq!(client.claim_payout(&pool_id, &bettor), 30_000_000);
+}
+
+#[test]
+fn test_unrevealed_bet_reclaim_after_grace() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let xlm_client = token::Client::new(&env, &xlm_token);
+    let bettor = Address::generate(&env);
+    xlm.mint(&bettor, &100_000_000_000);
+
+    env.ledger().set_timestamp(1_000);
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    client.set_reveal_policy(&pool_id, &2_000, &2_000);
+
+    let salt = BytesN::from_array(&env, &[7u8; 32]);
+    client.commit_bet(&pool_id, &bettor, &make_commitment(&env, 0, &salt), &10_000_000);
+    client.lock_pool(&pool_id);
+
+    env.ledger().set_timestamp(2_001);
+    assert_eq!(
+        client.try_reveal_bet(&pool_id, &bettor, &BetSide::Player1, &salt),
+        Err(Ok(Error::RevealDeadlinePassed))
+    );
+
+    let bankroll_before = client.get_bankroll();
+    client.settle_pool(&pool_id, &BetSide::Player1);
+    assert_eq!(client.get_bankroll(), bankroll_before);
+    assert_eq!(
+        client.try_claim_payout(&pool_id, &bettor),
+        Err(Ok(Error::NoPayout))
+    );
+    assert_eq!(
+        client.try_reclaim_unrevealed(&pool_id, &bettor),
+        Err(Ok(Error::ReclaimNotAvailable))
+    );
+
+    env.ledger().set_timestamp(2_001 + 86_400);
+    let fees_before = client.get_fee_accrued();
+    let before = xlm_client.balance(&bettor);
+    assert_eq!(client.reclaim_unrevealed(&pool_id, &bettor), 8_000_000);
+    assert_eq!(xlm_client.balance(&bettor) - before, 8_000_000);
+    assert_eq!(client.get_fee_accrued() - fees_before, 2_000_000);
+    assert_eq!(
+        client.try_reclaim_unrevealed(&pool_id, &bettor),
+        Err(Ok(Error::AlreadyClaimed))
+    );
+}
+
+#[test]
+fn test_unrevealed_bet_forfeited_by_default() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let bettor = Address::generate(&env);
+    xlm.mint(&bettor, &100_000_000_000);
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt = BytesN::from_array(&env, &[8u8; 32]);
+    client.commit_bet(&pool_id, &bettor, &make_commitment(&env, 0, &salt), &10_000_000);
+    client.lock_pool(&pool_id);
+    client.settle_pool(&pool_id, &BetSide::Player1);
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 86_400);
+    assert_eq!(
+        client.try_reclaim_unrevealed(&pool_id, &bettor),
+        Err(Ok(Error::ReclaimNotAvailable))
+    );
+}
+
+#[test]
+fn test_settle_from_match_reads_brawl_winner() {
+    let (env, contract_id, _admin, _treasury, _xlm) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let brawl = env.register(MockBrawlContract, ());
+    let brawl_client = MockBrawlContractClient::new(&env, &brawl);
+    client.set_brawl_contract(&brawl);
+
+    let mid = match_id(&env);
+    let winner_pool = client.create_pool(&mid, &0);
+    let moves_pool = client.create_market_pool(&mid, &Market::TotalMoves, &25, &0);
+    assert_eq!(
+        client.try_settle_from_match(&winner_pool),
+        Err(Ok(Error::SessionNotLinked))
+    );
+    brawl_client.set_match(&42, &brawl_match(&env, None));
+    client.link_pool_session(&winner_pool, &42);
+    client.link_pool_session(&moves_pool, &42);
+
+    assert_eq!(
+        client.try_settle_from_match(&winner_pool),
+        Err(Ok(Error::MatchNotFinished))
+    );
+
+    brawl_client.set_match(&42, &brawl_match(&env, Some(false)));
+    client.settle_from_match(&winner_pool);
+    client.settle_from_match(&moves_pool);
+
+    assert_eq!(client.get_pool(&winner_pool).winner_side, 1);
+    // 22 total moves is under the 25 line.
+    assert_eq!(client.get_pool(&moves_pool).winner_side, 1);
+}
+
+#[test]
+fn test_card_game_hooks_open_and_settle_pools() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    assert_eq!(
+        client.try_open_card_pool(&7, &0),
+        Err(Ok(Error::CardGameNotConfigured))
+    );
+
+    let card_game = Address::generate(&env);
+    client.set_card_game_contract(&card_game);
+    client.set_reveal_window(&600);
+
+    let won_pool = client.open_card_pool(&7, &0);
+    assert_eq!(env.auths()[0].0, card_game);
+    let push_pool = client.open_card_pool(&8, &0);
+
+    let bettor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &xlm_token).mint(&bettor, &1_000_000_000);
+    let salt = BytesN::from_array(&env, &[11u8; 32]);
+    client.commit_bet(&won_pool, &bettor, &make_commitment(&env, 1, &salt), &10_000_000);
+
+    // Reporting the result locks the open pool; the bet is unrevealed, so the
+    // result is recorded for later settlement.
+    client.settle_card_pool(&7, &Some(false));
+    assert_eq!(client.get_pool(&won_pool).status, PoolStatus::Locked);
+    assert_eq!(
+        client.try_settle_from_card_game(&won_pool),
+        Err(Ok(Error::RevealWindowOpen))
+    );
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 3_600);
+    client.settle_from_card_game(&won_pool);
+    assert_eq!(client.get_pool(&won_pool).winner_side, 1);
+
+    // A game without a winner refunds its pool; repeated reports are ignored.
+    client.settle_card_pool(&8, &None);
+    assert_eq!(client.get_pool(&push_pool).status, PoolStatus::Refunded);
+    client.settle_card_pool(&8, &Some(true));
+    assert_eq!(client.get_pool(&push_pool).status, PoolStatus::Refunded);
+}
+
+#[test]
+fn test_brawl_hooks_lock_pool_and_defer_settlement() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    assert_eq!(
+        client.try_open_brawl_pool(&7, &0),
+        Err(Ok(Error::BrawlNotConfigured))
+    );
+
+    let brawl = env.register(MockBrawlContract, ());
+    let brawl_client = MockBrawlContractClient::new(&env, &brawl);
+    client.set_brawl_contract(&brawl);
+
+    let pool_id = client.open_brawl_pool(&7, &0);
+    assert_eq!(env.auths()[0].0, brawl);
+    assert_eq!(client.get_pool(&pool_id).session_id, Some(7));
+
+    let bettor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &xlm_token).mint(&bettor, &1_000_000_000);
+    let salt = BytesN::from_array(&env, &[12u8; 32]);
+    client.commit_bet(&pool_id, &bettor, &make_commitment(&env, 1, &salt), &10_000_000);
+
+    // The match ends with a bet still unrevealed: the pool locks and waits.
+    assert_eq!(client.notify_match_ended(&7, &BetSide::Player1), 1);
+    assert_eq!(client.get_pool(&pool_id).status, PoolStatus::Locked);
+
+    brawl_client.set_match(&7, &brawl_match(&env, Some(true)));
+    assert_eq!(
+        client.try_settle_from_match(&pool_id),
+        Err(Ok(Error::RevealWindowOpen))
+    );
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 3_600);
+    client.settle_from_match(&pool_id);
+    assert_eq!(client.get_pool(&pool_id).winner_side, 0);
+}
+
+#[test]
+fn test_settle_from_match_handles_drawn_match() {
+    let (env, contract_id, _admin, _treasury, _xlm) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let brawl = env.register(MockBrawlContract, ());
+    let brawl_client = MockBrawlContractClient::new(&env, &brawl);
+    client.set_brawl_contract(&brawl);
+
+    let mid = match_id(&env);
+    let draw_pool = client.create_pool(&mid, &0);
+    client.set_pool_allow_draw(&draw_pool, &true);
+    let plain_pool = client.create_pool(&BytesN::from_array(&env, &[2u8; 32]), &0);
+    brawl_client.set_match(&42, &brawl_match(&env, None));
+    client.link_pool_session(&draw_pool, &42);
+    client.link_pool_session(&plain_pool, &42);
+
+    let mut game = brawl_match(&env, None);
+    game.winner = Some(Address::generate(&env));
+    brawl_client.set_match(&42, &game);
+
+    client.settle_from_match(&draw_pool);
+    assert_eq!(client.get_pool(&draw_pool).winner_side, 2);
+
+    client.settle_from_match(&plain_pool);
+    assert_eq!(client.get_pool(&plain_pool).status, PoolStatus::Refunded);
+}
+
+#[test]
+fn test_settle_from_match_rejects_cancelled_match() {
+    let (env, contract_id, _admin, _treasury, _xlm) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    assert_eq!(
+        client.try_link_pool_session(&pool_id, &7),
+        Err(Ok(Error::BrawlNotConfigured))
+    );
+
+    let brawl = env.register(MockBrawlContract, ());
+    let brawl_client = MockBrawlContractClient::new(&env, &brawl);
+    client.set_brawl_contract(&brawl);
+    brawl_client.set_match(&7, &brawl_match(&env, None));
+    client.link_pool_session(&pool_id, &7);
+
+    let mut game = brawl_match(&env, Some(true));
+    game.is_cancelled = true;
+    brawl_client.set_match(&7, &game);
+
+    assert_eq!(
+        client.try_settle_from_match(&pool_id),
+        Err(Ok(Error::MatchCancelled))
+    );
+}
+
+#[test]
+fn test_create_session_pool_validates_brawl_session() {
+    let (env, contract_id, _admin, _treasury, _xlm) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let brawl = env.register(MockBrawlContract, ());
+    let brawl_client = MockBrawlContractClient::new(&env, &brawl);
+    client.set_brawl_contract(&brawl);
+
+    let mid = match_id(&env);
+    assert_eq!(
+        client.try_create_session_pool(&mid, &3, &0),
+        Err(Ok(Error::MatchNotFound))
+    );
+
+    brawl_client.set_match(&3, &brawl_match(&env, Some(true)));
+    assert_eq!(
+        client.try_create_session_pool(&mid, &3, &0),
+        Err(Ok(Error::MatchAlreadyEnded))
+    );
+
+    brawl_client.set_match(&3, &brawl_match(&env, None));
+    let pool_id = client.create_session_pool(&mid, &3, &0);
+    assert_eq!(client.get_pool(&pool_id).session_id, Some(3));
+}
+
+#[test]
+fn test_admin_distribute_walks_pool_in_chunks() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let pool_id = client.create_pool(&match_id(&env), &0);
+
+    // 30 bettors: even indexes back Player1 (winners), odd back Player2.
+    let mut bettors = Vec::new(&env);
+    for i in 0..30u8 {
+        let bettor = Address::generate(&env);
+        xlm.mint(&bettor, &100_000_000);
+        let salt = BytesN::from_array(&env, &[i; 32]);
+        client.commit_bet(&pool_id, &bettor, &make_commitment(&env, i % 2, &salt), &1_000_000);
+        bettors.push_back((bettor, salt, (i % 2) as u32));
+    }
+    client.lock_pool(&pool_id);
+    for (bettor, salt, side) in bettors.iter() {
+        let side = if side == 0 { BetSide::Player1 } else { BetSide::Player2 };
+        client.reveal_bet(&pool_id, &bettor, &side, &salt);
+    }
+    client.settle_pool(&pool_id, &BetSide::Player1);
+
+    // Every other bettor wins, so the first walk stops after 8 payouts.
+    assert_eq!(client.admin_distribute(&pool_id, &Vec::new(&env)), 16_000_000);
+    assert_eq!(client.get_distribute_cursor(&pool_id), 15);
+    assert_eq!(client.admin_distribute(&pool_id, &Vec::new(&env)), 14_000_000);
+    assert_eq!(client.get_distribute_cursor(&pool_id), 30);
+    assert_eq!(client.admin_distribute(&pool_id, &Vec::new(&env)), 0);
+
+    let (first, _, _) = bettors.get(0).unwrap();
+    assert!(client.get_bet(&pool_id, &first).claimed);
+    assert_eq!(
+        client.admin_distribute(&pool_id, &Vec::from_array(&env, [first])),
+        0
+    );
+}
+
+#[test]
+fn test_open_index_prunes_pools_past_deadline() {
+    let (env, contract_id, _admin, _treasury, _xlm) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let now = env.ledger().timestamp();
+    let expiring = client.create_pool(&next_match_id(&env, &client), &(now + 100));
+    let lasting = client.create_pool(&next_match_id(&env, &client), &0);
+
+    env.ledger().set_timestamp(now + 101);
+    let fresh = client.create_pool(&next_match_id(&env, &client), &0);
+    assert_eq!(client.get_open_pools(), Vec::from_array(&env, [lasting, fresh]));
+    assert_eq!(client.get_pool(&expiring).status, PoolStatus::Open);
+}
+
+#[test]
+fn test_pool_listing_and_open_index() {
+    let (env, contract_id, _admin, _treasury, _xlm) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let p1 = client.create_pool(&next_match_id(&env, &client), &0);
+    let p2 = client.create_pool(&next_match_id(&env, &client), &0);
+    let p3 = client.create_pool(&next_match_id(&env, &client), &0);
+    assert_eq!(client.get_open_pools(), Vec::from_array(&env, [p1, p2, p3]));
+
+    client.lock_pool(&p1);
+    client.refund_pool(&p3);
+    assert_eq!(client.get_open_pools(), Vec::from_array(&env, [p2]));
+    client.settle_pool(&p2, &BetSide::Player1);
+    assert_eq!(client.get_open_pools().len(), 0);
+
+    let page = client.get_pools(&2, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().pool_id, p2);
+    assert_eq!(page.get(1).unwrap().status, PoolStatus::Refunded);
+    assert_eq!(client.get_pools(&0, &1).get(0).unwrap().pool_id, p1);
+    assert_eq!(client.get_pools(&4, &10).len(), 0);
+}
+
+#[test]
+fn test_bettor_history_is_paged() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let bettor = Address::generate(&env);
+    xlm.mint(&bettor, &100_000_000_000);
+
+    let salt = BytesN::from_array(&env, &[9u8; 32]);
+    let commit = make_commitment(&env, 0, &salt);
+    for _ in 0..22 {
+        let pool_id = client.create_pool(&next_match_id(&env, &client), &0);
+        client.commit_bet(&pool_id, &bettor, &commit, &1_000_000);
+    }
+
+    // Cancel and re-bet does not duplicate the entry.
+    client.cancel_bet(&1, &bettor);
+    client.commit_bet(&1, &bettor, &commit, &1_000_000);
+
+    let first = client.get_bettor_pools(&bettor, &0);
+    assert_eq!(first.len(), 20);
+    assert_eq!(first.get(0), Some(1));
+    assert_eq!(
+        client.get_bettor_pools(&bettor, &1),
+        Vec::from_array(&env, [21, 22])
+    );
+    assert_eq!(client.get_bettor_pools(&bettor, &2).len(), 0);
+    assert_eq!(client.get_bettor_pools(&Address::generate(&env), &0).len(), 0);
+}
+
+#[test]
+fn test_pool_fee_fixed_at_creation() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let bettor = Address::generate(&env);
+    xlm.mint(&bettor, &100_000_000_000);
+
+    let default_pool = client.create_pool(&next_match_id(&env, &client), &0);
+    client.set_fee_bps(&250);
+    let later_pool = client.create_pool(&next_match_id(&env, &client), &0);
+    let sponsored_pool = client.create_pool_with_config(
+        &next_match_id(&env, &client),
+        &0,
+        &PoolConfig { fee_bps: Some(0), ..Default::default() },
+    );
+
+    assert_eq!(client.get_pool(&default_pool).fee_bps, 100);
+    assert_eq!(client.get_pool(&later_pool).fee_bps, 250);
+    assert_eq!(client.get_pool(&sponsored_pool).fee_bps, 0);
+
+    let salt = BytesN::from_array(&env, &[10u8; 32]);
+    let commit = make_commitment(&env, 0, &salt);
+    client.commit_bet(&default_pool, &bettor, &commit, &10_000_000);
+    client.commit_bet(&later_pool, &bettor, &commit, &10_000_000);
+    client.commit_bet(&sponsored_pool, &bettor, &commit, &10_000_000);
+    assert_eq!(client.get_bet(&default_pool, &bettor).fee_paid, 100_000);
+    assert_eq!(client.get_bet(&later_pool, &bettor).fee_paid, 250_000);
+    assert_eq!(client.get_bet(&sponsored_pool, &bettor).fee_paid, 0);
+
+    assert_eq!(client.try_set_fee_bps(&1_001), Err(Ok(Error::InvalidAmount)));
+    let too_high = PoolConfig { fee_bps: Some(1_001), ..Default::default() };
+    assert_eq!(
+        client.try_create_pool_with_config(&next_match_id(&env, &client), &0, &too_high),
+        Err(Ok(Error::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_per_pool_min_and_max_bet() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let bettor = Address::generate(&env);
+    xlm.mint(&bettor, &100_000_000_000);
+
+    let micro = client.create_pool_with_config(
+        &next_match_id(&env, &client),
+        &0,
+        &PoolConfig { min_bet: 100_000, max_bet_per_bettor: 500_000, ..Default::default() },
+    );
+    let high_roller = client.create_pool_with_config(
+        &next_match_id(&env, &client),
+        &0,
+        &PoolConfig { min_bet: 1_000_000_000, ..Default::default() },
+    );
+    assert_eq!(client.get_pool(&client.create_pool(&next_match_id(&env, &client), &0)).min_bet, 1_000_000);
+
+    let salt = BytesN::from_array(&env, &[11u8; 32]);
+    let commit = make_commitment(&env, 1, &salt);
+    client.commit_bet(&micro, &bettor, &commit, &100_000);
+    assert_eq!(
+        client.try_increase_bet(&micro, &bettor, &500_000),
+        Err(Ok(Error::BetLimitExceeded))
+    );
+    assert_eq!(
+        client.try_commit_bet(&high_roller, &bettor, &commit, &999_999_999),
+        Err(Ok(Error::InvalidAmount))
+    );
+    client.commit_bet(&high_roller, &bettor, &commit, &1_000_000_000);
+
+    let inverted = PoolConfig { min_bet: 10, max_bet_per_bettor: 5, ..Default::default() };
+    assert_eq!(
+        client.try_create_pool_with_config(&next_match_id(&env, &client), &0, &inverted),
+        Err(Ok(Error::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_lock_pool_expired_is_permissionless_after_deadline() {
+    let (env, contract_id, _admin, _treasury, _xlm) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    env.ledger().set_timestamp(1_000);
+    let pool_id = client.create_pool(&next_match_id(&env, &client), &1_500);
+    let no_deadline = client.create_pool(&next_match_id(&env, &client), &0);
+
+    env.set_auths(&[]);
+    assert_eq!(
+        client.try_lock_pool_expired(&pool_id),
+        Err(Ok(Error::DeadlineNotReached))
+    );
+    assert_eq!(
+        client.try_lock_pool_expired(&no_deadline),
+        Err(Ok(Error::DeadlineNotReached))
+    );
+
+    env.ledger().set_timestamp(1_501);
+    client.lock_pool_expired(&pool_id);
+    assert_eq!(client.get_pool(&pool_id).status, PoolStatus::Locked);
+    assert_eq!(
+        client.try_lock_pool_expired(&pool_id),
+        Err(Ok(Error::PoolAlreadyLocked))
+    );
+}
+
+#[test]
+fn test_refund_pool_expired_after_abandonment() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let xlm_client = token::Client::new(&env, &xlm_token);
+    let bettor = Address::generate(&env);
+    xlm.mint(&bettor, &100_000_000_000);
+
+    env.ledger().set_timestamp(1_000);
+    let pool_id = client.create_pool(&match_id(&env), &2_000);
+    let salt = BytesN::from_array(&env, &[12u8; 32]);
+    let before = xlm_client.balance(&bettor);
+    client.commit_bet(&pool_id, &bettor, &make_commitment(&env, 0, &salt), &10_000_000);
+
+    env.set_auths(&[]);
+    env.ledger().set_timestamp(2_000 + 7 * 86_400);
+    assert_eq!(
+        client.try_refund_pool_expired(&pool_id),
+        Err(Ok(Error::DeadlineNotReached))
+    );
+
+    env.ledger().set_timestamp(2_001 + 7 * 86_400);
+    client.refund_pool_expired(&pool_id);
+    assert_eq!(client.get_pool(&pool_id).status, PoolStatus::Refunded);
+    assert_eq!(xlm_client.balance(&bettor), before);
+    assert_eq!(client.get_open_exposure(), 0);
+}
+
+#[test]
+fn test_liability_tracks_pool_lifecycle() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let bettor1 = Address::generate(&env);
+    let bettor2 = Address::generate(&env);
+    xlm.mint(&bettor1, &100_000_000_000);
+    xlm.mint(&bettor2, &100_000_000_000);
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt1 = BytesN::from_array(&env, &[13u8; 32]);
+    let salt2 = BytesN::from_array(&env, &[14u8; 32]);
+    client.commit_bet(&pool_id, &bettor1, &make_commitment(&env, 0, &salt1), &10_000_000);
+    client.commit_bet(&pool_id, &bettor2, &make_commitment(&env, 1, &salt2), &30_000_000);
+    assert_eq!(client.get_total_liability(), 80_000_000);
+    assert_eq!(client.get_pool(&pool_id).liability, 80_000_000);
+
+    client.lock_pool(&pool_id);
+    client.reveal_bet(&pool_id, &bettor1, &BetSide::Player1, &salt1);
+    client.reveal_bet(&pool_id, &bettor2, &BetSide::Player2, &salt2);
+    client.settle_pool(&pool_id, &BetSide::Player1);
+    assert_eq!(client.get_total_liability(), 20_000_000);
+
+    client.claim_payout(&pool_id, &bettor1);
+    assert_eq!(client.get_total_liability(), 0);
+    assert_eq!(client.get_pool(&pool_id).liability, 0);
+}
+
+#[test]
+fn test_commit_bet_rejected_when_liabilities_exceed_balance() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let bettor = Address::generate(&env);
+    xlm.mint(&bettor, &100_000_000_000);
+
+    // Drain the contract's XLM behind the bankroll's back so the ledger
+    // balance no longer covers a 2x payout.
+    let token_client = token::Client::new(&env, &xlm_token);
+    let drain = Address::generate(&env);
+    token_client.transfer(&contract_id, &drain, &token_client.balance(&contract_id));
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt = BytesN::from_array(&env, &[15u8; 32]);
+    assert_eq!(
+        client.try_commit_bet(&pool_id, &bettor, &make_commitment(&env, 0, &salt), &10_000_000),
+        Err(Ok(Error::InsufficientLiquidity))
+    );
+}
+
+#[test]
+fn test_house_balance_and_profit_withdrawal() {
+    let (env, contract_id, admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let xlm_client = token::Client::new(&env, &xlm_token);
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    let silent = Address::generate(&env);
+    for bettor in [&winner, &loser, &silent] {
+        xlm.mint(bettor, &100_000_000_000);
+    }
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt_w = BytesN::from_array(&env, &[16u8; 32]);
+    let salt_l = BytesN::from_array(&env, &[17u8; 32]);
+    let salt_s = BytesN::from_array(&env, &[18u8; 32]);
+    client.commit_bet(&pool_id, &winner, &make_commitment(&env, 0, &salt_w), &10_000_000);
+    client.commit_bet(&pool_id, &loser, &make_commitment(&env, 1, &salt_l), &30_000_000);
+    client.commit_bet(&pool_id, &silent, &make_commitment(&env, 1, &salt_s), &5_000_000);
+    client.lock_pool(&pool_id);
+    client.reveal_bet(&pool_id, &winner, &BetSide::Player1, &salt_w);
+    client.reveal_bet(&pool_id, &loser, &BetSide::Player2, &salt_l);
+    client.settle_pool(&pool_id, &BetSide::Player1);
+
+    // +30 losing +5 unrevealed -10 winner's house half.
+    assert_eq!(client.get_house_balance(), 25_000_000);
+
+    assert_eq!(
+        client.try_withdraw_house_profit(&admin, &25_000_001),
+        Err(Ok(Error::InsufficientBankroll))
+    );
+    let bankroll = client.get_bankroll();
+    let before = xlm_client.balance(&admin);
+    client.withdraw_house_profit(&admin, &25_000_000);
+    assert_eq!(xlm_client.balance(&admin) - before, 25_000_000);
+    assert_eq!(client.get_house_balance(), 0);
+    assert_eq!(client.get_bankroll(), bankroll - 25_000_000);
+
+    // Winner can still be paid in full.
+    assert_eq!(client.claim_payout(&pool_id, &winner), 20_000_000);
+}
+
+#[test]
+fn test_rake_share_credited_to_fighters() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let xlm_client = token::Client::new(&env, &xlm_token);
+    let bettor = Address::generate(&env);
+    xlm.mint(&bettor, &100_000_000_000);
+    let fighter1 = Address::generate(&env);
+    let fighter2 = Address::generate(&env);
+
+    client.set_rake_bps(&2_000);
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    client.set_pool_fighters(&pool_id, &fighter1, &fighter2);
+
+    let salt = BytesN::from_array(&env, &[19u8; 32]);
+    client.commit_bet(&pool_id, &bettor, &make_commitment(&env, 0, &salt), &100_000_000);
+    client.lock_pool(&pool_id);
+    client.settle_pool(&pool_id, &BetSide::Player2);
+
+    // 0.1 XLM fee, 20% rake split evenly.
+    assert_eq!(client.get_rake_balance(&fighter1), 100_000);
+    assert_eq!(client.get_rake_balance(&fighter2), 100_000);
+    assert_eq!(client.get_fee_accrued(), 800_000);
+
+    // Unclaimed rake is not free balance.
+    let balance = xlm_client.balance(&contract_id);
+    let free = || env.as_contract(&contract_id, || ZkBettingContract::free_balance(&env));
+    assert_eq!(free(), balance - 800_000 - 200_000);
+
+    assert_eq!(client.claim_rake(&fighter1), 100_000);
+    assert_eq!(free(), balance - 800_000 - 200_000);
+    assert_eq!(xlm_client.balance(&fighter1), 100_000);
+    assert_eq!(client.try_claim_rake(&fighter1), Err(Ok(Error::NothingToClaim)));
+    assert_eq!(client.try_set_rake_bps(&5_001), Err(Ok(Error::InvalidAmount)));
+}
+
+#[test]
+fn test_referral_rewards_credited_at_settlement() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let xlm_client = token::Client::new(&env, &xlm_token);
+    let bettor1 = Address::generate(&env);
+    let bettor2 = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    xlm.mint(&bettor1, &100_000_000_000);
+    xlm.mint(&bettor2, &100_000_000_000);
+
+    client.set_referral_bps(&1_000);
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt = BytesN::from_array(&env, &[20u8; 32]);
+    let commit = make_commitment(&env, 0, &salt);
+    client.commit_bet_with_referrer(&pool_id, &bettor1, &commit, &100_000_000, &referrer);
+    client.commit_bet_with_referrer(&pool_id, &bettor2, &commit, &100_000_000, &referrer);
+    assert_eq!(client.get_bet(&pool_id, &bettor1).referral_fee, 100_000);
+
+    // A cancelled bet's referral share is dropped with its fee.
+    client.cancel_bet(&pool_id, &bettor2);
+    client.increase_bet(&pool_id, &bettor1, &100_000_000);
+    assert_eq!(client.get_referral_balance(&referrer), 0);
+
+    client.lock_pool(&pool_id);
+    client.settle_pool(&pool_id, &BetSide::Player2);
+    assert_eq!(client.get_referral_balance(&referrer), 200_000);
+    assert_eq!(client.get_fee_accrued(), 1_800_000);
+
+    assert_eq!(client.claim_referral(&referrer), 200_000);
+    assert_eq!(xlm_client.balance(&referrer), 200_000);
+    assert_eq!(client.try_claim_referral(&referrer), Err(Ok(Error::NothingToClaim)));
+    assert_eq!(
+        client.try_commit_bet_with_referrer(&pool_id, &bettor1, &commit, &100_000_000, &bettor1),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_allowlist_only_pool() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let allowed = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    xlm.mint(&allowed, &100_000_000_000);
+    xlm.mint(&outsider, &100_000_000_000);
+
+    let gated = client.create_pool(&next_match_id(&env, &client), &0);
+    let open = client.create_pool(&next_match_id(&env, &client), &0);
+    client.set_pool_allowlist_only(&gated, &true);
+    client.add_allowlisted(&Vec::from_array(&env, [allowed.clone()]));
+
+    let salt = BytesN::from_array(&env, &[21u8; 32]);
+    let commit = make_commitment(&env, 0, &salt);
+    client.commit_bet(&gated, &allowed, &commit, &10_000_000);
+    assert_eq!(
+        client.try_commit_bet(&gated, &outsider, &commit, &10_000_000),
+        Err(Ok(Error::NotAllowlisted))
+    );
+    client.commit_bet(&open, &outsider, &commit, &10_000_000);
+
+    client.remove_allowlisted(&Vec::from_array(&env, [allowed.clone()]));
+    assert!(!client.is_allowlisted(&allowed));
+    assert_eq!(
+        client.try_commit_bet(&gated, &allowed, &commit, &10_000_000),
+        Err(Ok(Error::NotAllowlisted))
+    );
+    // A removed bettor cannot add to an existing bet either
+    assert_eq!(
+        client.try_increase_bet(&gated, &allowed, &5_000_000),
+        Err(Ok(Error::NotAllowlisted))
+    );
+    assert_eq!(client.get_bet(&gated, &allowed).amount, 10_000_000);
+}
+
+#[test]
+fn test_blacklisted_bettor_cannot_bet_or_claim() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let bettor = Address::generate(&env);
+    let fighter = Address::generate(&env);
+    xlm.mint(&bettor, &100_000_000_000);
+    xlm.mint(&fighter, &100_000_000_000);
+
+    client.add_blacklisted(&Vec::from_array(&env, [fighter.clone()]));
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt = BytesN::from_array(&env, &[22u8; 32]);
+    let commit = make_commitment(&env, 0, &salt);
+    assert_eq!(
+        client.try_commit_bet(&pool_id, &fighter, &commit, &10_000_000),
+        Err(Ok(Error::Blacklisted))
+    );
+
+    client.commit_bet(&pool_id, &bettor, &commit, &10_000_000);
+
+    // A blacklisted bettor cannot grow an existing bet
+    client.add_blacklisted(&Vec::from_array(&env, [bettor.clone()]));
+    assert_eq!(
+        client.try_increase_bet(&pool_id, &bettor, &5_000_000),
+        Err(Ok(Error::Blacklisted))
+    );
+    client.remove_blacklisted(&Vec::from_array(&env, [bettor.clone()]));
+    assert_eq!(client.get_bet(&pool_id, &bettor).amount, 10_000_000);
+
+    client.lock_pool(&pool_id);
+    client.reveal_bet(&pool_id, &bettor, &BetSide::Player1, &salt);
+    client.settle_pool(&pool_id, &BetSide::Player1);
+
+    client.add_blacklisted(&Vec::from_array(&env, [bettor.clone()]));
+    assert_eq!(
+        client.try_claim_payout(&pool_id, &bettor),
+        Err(Ok(Error::Blacklisted))
+    );
+    client.remove_blacklisted(&Vec::from_array(&env, [bettor.clone()]));
+    assert_eq!(client.claim_payout(&pool_id, &bettor), 20_000_000);
+}
+
+#[test]
+fn test_forfeit_blacklisted_payout_releases_liability() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let bettor = Address::generate(&env);
+    xlm.mint(&bettor, &100_000_000_000);
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt = BytesN::from_array(&env, &[23u8; 32]);
+    client.commit_bet(&pool_id, &bettor, &make_commitment(&env, 0, &salt), &10_000_000);
+    client.lock_pool(&pool_id);
+    client.reveal_bet(&pool_id, &bettor, &BetSide::Player1, &salt);
+    client.settle_pool(&pool_id, &BetSide::Player1);
+
+    assert_eq!(
+        client.try_forfeit_blacklisted_payout(&pool_id, &bettor),
+        Err(Ok(Error::NotBlacklisted))
+    );
+    client.add_blacklisted(&Vec::from_array(&env, [bettor.clone()]));
+    assert_eq!(client.get_total_liability(), 20_000_000);
+    assert_eq!(client.forfeit_blacklisted_payout(&pool_id, &bettor), 20_000_000);
+    assert_eq!(client.get_total_liability(), 0);
+
+    client.remove_blacklisted(&Vec::from_array(&env, [bettor.clone()]));
+    assert_eq!(
+        client.try_claim_payout(&pool_id, &bettor),
+        Err(Ok(Error::AlreadyClaimed))
+    );
+}
+
+#[test]
+fn test_reveal_event_carries_side_totals_and_odds() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let bettor1 = Address::generate(&env);
+    let bettor2 = Address::generate(&env);
+    xlm.mint(&bettor1, &100_000_000_000);
+    xlm.mint(&bettor2, &100_000_000_000);
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt1 = BytesN::from_array(&env, &[23u8; 32]);
+    let salt2 = BytesN::from_array(&env, &[24u8; 32]);
+    client.commit_bet(&pool_id, &bettor1, &make_commitment(&env, 0, &salt1), &30_000_000);
+    client.commit_bet(&pool_id, &bettor2, &make_commitment(&env, 1, &salt2), &10_000_000);
+    client.lock_pool(&pool_id);
+    client.reveal_bet(&pool_id, &bettor1, &BetSide::Player1, &salt1);
+    client.reveal_bet(&pool_id, &bettor2, &BetSide::Player2, &salt2);
+
+    // 30 vs 10 revealed: 75% / 25% implied.
+    assert_eq!(
+        env.events().all(),
+        Vec::from_array(
+            &env,
+            [(
+                contract_id.clone(),
+                (symbol_short!("reveal"), pool_id).into_val(&env),
+                (
+                    bettor2.clone(),
+                    1u32,
+                    (30_000_000_i128, 10_000_000_i128, 0_i128),
+                    (7_500u32, 2_500u32, 0u32),
+                )
+                    .into_val(&env),
+            )]
+        )
+    );
+}
+
+#[test]
+fn test_get_odds_from_revealed_state() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let bettor1 = Address::generate(&env);
+    let bettor2 = Address::generate(&env);
+    xlm.mint(&bettor1, &100_000_000_000);
+    xlm.mint(&bettor2, &100_000_000_000);
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt1 = BytesN::from_array(&env, &[25u8; 32]);
+    let salt2 = BytesN::from_array(&env, &[26u8; 32]);
+    client.commit_bet(&pool_id, &bettor1, &make_commitment(&env, 0, &salt1), &10_000_000);
+    client.commit_bet(&pool_id, &bettor2, &make_commitment(&env, 1, &salt2), &30_000_000);
+
+    let odds = client.get_odds(&pool_id);
+    assert_eq!(odds.player1_bps + odds.player2_bps, 0);
+    assert_eq!(odds.payout_multiplier_bps, 20_000);
+
+    client.lock_pool(&pool_id);
+    client.reveal_bet(&pool_id, &bettor1, &BetSide::Player1, &salt1);
+    client.reveal_bet(&pool_id, &bettor2, &BetSide::Player2, &salt2);
+
+    let odds = client.get_odds(&pool_id);
+    assert_eq!(odds.player1_total, 10_000_000);
+    assert_eq!(odds.player2_total, 30_000_000);
+    assert_eq!(odds.player1_bps, 2_500);
+    assert_eq!(odds.player2_bps, 7_500);
+    assert_eq!(odds.draw_bps, 0);
+}
+
+#[test]
+fn test_settle_from_oracle() {
+    let (env, contract_id, _admin, _treasury, _xlm) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let mid = match_id(&env);
+    let pool_id = client.create_market_pool(&mid, &Market::RoundCount, &2, &0);
+    assert_eq!(
+        client.try_settle_from_oracle(&pool_id),
+        Err(Ok(Error::OracleNotConfigured))
+    );
+
+    let oracle = env.register(MockOracleContract, ());
+    client.set_outcome_oracle(&oracle);
+    assert_eq!(
+        client.try_settle_from_oracle(&pool_id),
+        Err(Ok(Error::MatchNotFinished))
+    );
+
+    MockOracleContractClient::new(&env, &oracle).set_outcome(&mid, &(Market::RoundCount as u32), &3);
+    env.set_auths(&[]);
+    client.settle_from_oracle(&pool_id);
+
+    let pool = client.get_pool(&pool_id);
+    assert_eq!(pool.status, PoolStatus::Settled);
+    assert_eq!(pool.winner_side, 0);
+}
+
+#[test]
+fn test_pool_metadata_returned_with_info() {
+    let (env, contract_id, _admin, _treasury, _xlm) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    assert_eq!(client.get_pool_info(&pool_id).1, None);
+
+    let metadata = PoolMetadata {
+        player1_name_hash: BytesN::from_array(&env, &[1u8; 32]),
+        player2_name_hash: BytesN::from_array(&env, &[2u8; 32]),
+        scheduled_start_ts: 1_700_000_000,
+        stream_url_hash: BytesN::from_array(&env, &[3u8; 32]),
+    };
+    client.set_pool_metadata(&pool_id, &metadata);
+
+    let (pool, stored) = client.get_pool_info(&pool_id);
+    assert_eq!(pool.pool_id, pool_id);
+    assert_eq!(stored, Some(metadata.clone()));
+    assert_eq!(
+        client.try_set_pool_metadata(&99, &metadata),
+        Err(Ok(Error::PoolNotFound))
+    );
+}
+
+#[test]
+fn test_zk_bets_claim_without_reveal() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let verifier = env.register(MockVerifierAcceptContract, ());
+    client.set_zk_verifier(&verifier, &BytesN::from_array(&env, &[7u8; 32]));
+    let commit_tag = u32_to_bytes32(&env, ProofKind::CommitBet as u32);
+    let claim_tag = u32_to_bytes32(&env, ProofKind::ClaimBet as u32);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let winner = Address::generate(&env);
+    let loser = Address::generate(&env);
+    xlm.mint(&winner, &1_000_000_000);
+    xlm.mint(&loser, &1_000_000_000);
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let amount: i128 = 100_000_000;
+    let mut amount_input = [0u8; 32];
+    amount_input[16..].copy_from_slice(&amount.to_be_bytes());
+    let proof = Bytes::from_array(&env, &[5u8; 256]);
+
+    let winner_commit = BytesN::from_array(&env, &[3u8; 32]);
+    let loser_commit = BytesN::from_array(&env, &[4u8; 32]);
+    let commit_inputs = |tag: &BytesN<32>, commitment: &BytesN<32>| {
+        Vec::from_array(
+            &env,
+            [
+                tag.clone(),
+                commitment.clone(),
+                u32_to_bytes32(&env, pool_id),
+                BytesN::from_array(&env, &amount_input),
+            ],
+        )
+    };
+
+    // Each statement needs its own key, and a proof tagged for another
+    // statement is rejected.
+    let inputs = commit_inputs(&commit_tag, &winner_commit);
+    assert_eq!(
+        client.try_commit_bet_zk(&pool_id, &winner, &winner_commit, &amount, &proof, &inputs),
+        Err(Ok(Error::ZkVerifierNotConfigured))
+    );
+    client.set_proof_vk(&ProofKind::CommitBet, &BytesN::from_array(&env, &[8u8; 32]));
+    client.set_proof_vk(&ProofKind::ClaimBet, &BytesN::from_array(&env, &[9u8; 32]));
+    let inputs = commit_inputs(&claim_tag, &winner_commit);
+    assert_eq!(
+        client.try_commit_bet_zk(&pool_id, &winner, &winner_commit, &amount, &proof, &inputs),
+        Err(Ok(Error::PublicInputMismatch))
+    );
+
+    for (bettor, commitment) in [(&winner, &winner_commit), (&loser, &loser_commit)] {
+        let inputs = commit_inputs(&commit_tag, commitment);
+        client.commit_bet_zk(&pool_id, bettor, commitment, &amount, &proof, &inputs);
+    }
+
+    // The proof is bound to the deposited amount.
+    let other = Address::generate(&env);
+    xlm.mint(&other, &1_000_000_000);
+    let wrong_inputs = commit_inputs(&commit_tag, &winner_commit);
+    assert_eq!(
+        client.try_commit_bet_zk(
+            &pool_id,
+            &other,
+            &winner_commit,
+            &(amount * 2),
+            &proof,
+            &wrong_inputs
+        ),
+        Err(Ok(Error::PublicInputMismatch))
+    );
+
+    assert!(client.get_bet(&pool_id, &winner).zk_verified);
+    assert_eq!(client.get_pool(&pool_id).zk_total, amount * 2);
+
+    client.lock_pool(&pool_id);
+    client.settle_pool(&pool_id, &BetSide::Player1);
+    assert_eq!(client.get_pool(&pool_id).liability, amount * 4);
+    // Admin paths cannot forfeit a proof-verified bet.
+    assert_eq!(client.try_admin_claim_payout(&pool_id, &loser), Err(Ok(Error::NoPayout)));
+    assert!(!client.get_bet(&pool_id, &loser).claimed);
+
+    let claim_inputs = Vec::from_array(
+        &env,
+        [claim_tag, winner_commit, u32_to_bytes32(&env, pool_id), u32_to_bytes32(&env, 0)],
+    );
+    assert_eq!(client.claim_payout_zk(&pool_id, &winner, &proof, &claim_inputs), amount * 2);
+    assert_eq!(
+        client.try_release_zk_stakes(&pool_id),
+        Err(Ok(Error::ReclaimNotAvailable))
+    );
+
+    let bankroll_before = client.get_bankroll();
+    env.ledger().set_timestamp(86_400);
+    assert_eq!(client.release_zk_stakes(&pool_id), amount);
+    assert_eq!(client.get_bankroll(), bankroll_before + amount);
+    assert_eq!(client.get_pool(&pool_id).liability, 0);
+    assert_eq!(client.get_total_liability(), 0);
+}
+
+#[test]
+fn test_note_claimed_to_any_address_once() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let verifier = env.register(MockVerifierAcceptContract, ());
+    client.set_zk_verifier(&verifier, &BytesN::from_array(&env, &[7u8; 32]));
+    client.set_proof_vk(&ProofKind::CommitNote, &BytesN::from_array(&env, &[10u8; 32]));
+    client.set_proof_vk(&ProofKind::ClaimNote, &BytesN::from_array(&env, &[11u8; 32]));
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let depositor = Address::generate(&env);
+    xlm.mint(&depositor, &1_000_000_000);
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let amount: i128 = 100_000_000;
+    let mut amount_input = [0u8; 32];
+    amount_input[16..].copy_from_slice(&amount.to_be_bytes());
+    let amount_input = BytesN::from_array(&env, &amount_input);
+    let proof = Bytes::from_array(&env, &[5u8; 256]);
+
+    let note = BytesN::from_array(&env, &[9u8; 32]);
+    let inputs = Vec::from_array(
+        &env,
+        [
+            u32_to_bytes32(&env, ProofKind::CommitNote as u32),
+            note.clone(),
+            u32_to_bytes32(&env, pool_id),
+            amount_input.clone(),
+        ],
+    );
+    let mut bet_inputs = inputs.clone();
+    bet_inputs.set(0, u32_to_bytes32(&env, ProofKind::CommitBet as u32));
+    assert_eq!(
+        client.try_commit_note(&pool_id, &depositor, &note, &amount, &proof, &bet_inputs),
+        Err(Ok(Error::PublicInputMismatch))
+    );
+    client.commit_note(&pool_id, &depositor, &note, &amount, &proof, &inputs);
+
+    let pool = client.get_pool(&pool_id);
+    assert_eq!(pool.zk_total, amount);
+    assert_ne!(pool.notes_root, BytesN::from_array(&env, &[0u8; 32]));
+
+    client.lock_pool(&pool_id);
+    client.settle_pool(&pool_id, &BetSide::Player2);
+
+    let recipient = Address::generate(&env);
+    let nullifier = BytesN::from_array(&env, &[11u8; 32]);
+    let recipient_hash: BytesN<32> =
+        env.crypto().sha256(&recipient.clone().to_xdr(&env)).into();
+    let claim_inputs = Vec::from_array(
+        &env,
+        [
+            u32_to_bytes32(&env, ProofKind::ClaimNote as u32),
+            pool.notes_root,
+            u32_to_bytes32(&env, pool_id),
+            u32_to_bytes32(&env, 1),
+            amount_input,
+            nullifier.clone(),
+            recipient_hash,
+        ],
+    );
+
+    // Relayed by a third party: the proof binds the recipient.
+    env.set_auths(&[]);
+    let paid =
+        client.claim_note(&pool_id, &nullifier, &recipient, &amount, &proof, &claim_inputs);
+    assert_eq!(paid, amount * 2);
+    assert_eq!(token::Client::new(&env, &xlm_token).balance(&recipient), amount * 2);
+    assert!(client.is_nullifier_used(&nullifier));
+    assert_eq!(
+        client.try_claim_note(&pool_id, &nullifier, &recipient, &amount, &proof, &claim_inputs),
+        Err(Ok(Error::NullifierUsed))
+    );
+    assert_eq!(client.get_pool(&pool_id).liability, 0);
+}
+
+#[test]
+fn test_pool_history_records_transitions_and_actors() {
+    let (env, contract_id, admin, _treasury, _xlm) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    env.ledger().set_timestamp(100);
+    let pool_id = client.create_pool(&match_id(&env), &200);
+    env.ledger().set_timestamp(300);
+    client.lock_pool_expired(&pool_id);
+    env.ledger().set_timestamp(400);
+    client.settle_pool(&pool_id, &BetSide::Player2);
+
+    let history = client.get_pool_history(&pool_id);
+    assert_eq!(
+        history,
+        Vec::from_array(
+            &env,
+            [
+                StatusChange { status: PoolStatus::Open, ts: 100, actor: Some(admin.clone()) },
+                StatusChange { status: PoolStatus::Locked, ts: 300, actor: None },
+                StatusChange { status: PoolStatus::Settled, ts: 400, actor: Some(admin) },
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_pool_treasury_override_accrues_separately() {
+    let (env, contract_id, _admin, treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let bettor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &xlm_token).mint(&bettor, &1_000_000_000);
+    let partner = Address::generate(&env);
+
+    let default_pool = client.create_pool(&next_match_id(&env, &client), &0);
+    let partner_pool = client.create_pool_with_config(
+        &next_match_id(&env, &client),
+        &0,
+        &PoolConfig { treasury: Some(partner.clone()), ..Default::default() },
+    );
+
+    let salt = BytesN::from_array(&env, &[10u8; 32]);
+    let commit = make_commitment(&env, 0, &salt);
+    for pool_id in [default_pool, partner_pool] {
+        client.commit_bet(&pool_id, &bettor, &commit, &100_000_000);
+        client.lock_pool(&pool_id);
+        client.settle_pool(&pool_id, &BetSide::Player2);
+    }
+
+    assert_eq!(client.get_fee_accrued(), 1_000_000);
+    assert_eq!(client.get_treasury_fees(&partner), 1_000_000);
+
+    assert_eq!(client.sweep_treasury_fees(&partner), 1_000_000);
+    assert_eq!(client.sweep_treasury(), 1_000_000);
+    let xlm = token::Client::new(&env, &xlm_token);
+    assert_eq!(xlm.balance(&partner), 1_000_000);
+    assert_eq!(xlm.balance(&treasury), 1_000_000);
+    assert_eq!(
+        client.try_sweep_treasury_fees(&partner),
+        Err(Ok(Error::NothingToSweep))
+    );
+}
+
+#[test]
+fn test_bettor_stats_track_volume_wins_and_streaks() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let bettor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &xlm_token).mint(&bettor, &10_000_000_000);
+
+    let salt = BytesN::from_array(&env, &[10u8; 32]);
+    let commit = make_commitment(&env, 0, &salt);
+    // Wins, wins, loses, wins.
+    let winners = [BetSide::Player1, BetSide::Player1, BetSide::Player2, BetSide::Player1];
+    let mut pools = Vec::new(&env);
+    for _ in winners.iter() {
+        let pool_id = client.create_pool(&next_match_id(&env, &client), &0);
+        client.commit_bet(&pool_id, &bettor, &commit, &100_000_000);
+        pools.push_back(pool_id);
+    }
+    client.increase_bet(&pools.get(0).unwrap(), &bettor, &50_000_000);
+
+    for (i, winner) in winners.iter().enumerate() {
+        let pool_id = pools.get(i as u32).unwrap();
+        client.lock_pool(&pool_id);
+        client.reveal_bet(&pool_id, &bettor, &BetSide::Player1, &salt);
+        client.settle_pool(&pool_id, winner);
+        let _ = client.try_claim_payout(&pool_id, &bettor);
+
+        if i == 1 {
+            let stats = client.get_bettor_stats(&bettor);
+            assert_eq!((stats.wins, stats.current_streak), (2, 2));
+        }
+    }
+
+    let stats = client.get_bettor_stats(&bettor);
+    assert_eq!(stats.total_bets, 4);
+    assert_eq!(stats.volume, 450_000_000);
+    assert_eq!(stats.wins, 3);
+    assert_eq!(stats.current_streak, 1);
+    assert_eq!(stats.best_streak, 2);
+}
+
+#[test]
+fn test_pause_blocks_betting_but_not_refunds() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let bettor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &xlm_token).mint(&bettor, &1_000_000_000);
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt = BytesN::from_array(&env, &[10u8; 32]);
+    let commit = make_commitment(&env, 0, &salt);
+    client.commit_bet(&pool_id, &bettor, &commit, &100_000_000);
+
+    client.set_paused(&true);
+    assert!(client.is_paused());
+    assert_eq!(
+        client.try_create_pool(&match_id(&env), &0),
+        Err(Ok(Error::Paused))
+    );
+    let other = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &xlm_token).mint(&other, &1_000_000_000);
+    assert_eq!(
+        client.try_commit_bet(&pool_id, &other, &commit, &100_000_000),
+        Err(Ok(Error::Paused))
+    );
+    client.lock_pool(&pool_id);
+    assert_eq!(
+        client.try_reveal_bet(&pool_id, &bettor, &BetSide::Player1, &salt),
+        Err(Ok(Error::Paused))
+    );
+
+    client.refund_pool(&pool_id);
+    assert_eq!(token::Client::new(&env, &xlm_token).balance(&bettor), 1_000_000_000);
+
+    client.set_paused(&false);
+    client.create_pool(&match_id(&env), &0);
+}
+
+#[test]
+fn test_admin_transfer_requires_acceptance() {
+    let (env, contract_id, admin, _treasury, _xlm) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    assert_eq!(client.try_accept_admin(), Err(Ok(Error::NoPendingAdmin)));
+
+    let new_admin = Address::generate(&env);
+    client.set_admin(&new_admin);
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+
+    client.accept_admin();
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+}
+
+#[test]
+fn test_closed_pools_archived_and_pruned_after_retention() {
+    let (env, contract_id, _admin, _treasury, _xlm) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    env.ledger().set_timestamp(1_000);
+    let settled = client.create_pool(&next_match_id(&env, &client), &0);
+    let refunded = client.create_pool(&next_match_id(&env, &client), &0);
+    assert_eq!(client.try_get_archived_pool(&settled), Err(Ok(Error::PoolNotFound)));
+
+    client.lock_pool(&settled);
+    client.settle_pool(&settled, &BetSide::Player2);
+    client.refund_pool(&refunded);
+
+    let record = client.get_archived_pool(&settled);
+    assert_eq!(record.status, PoolStatus::Settled);
+    assert_eq!(record.winner_side, 1);
+    assert_eq!(record.closed_ts, 1_000);
+    assert_eq!(client.get_archived_pool(&refunded).status, PoolStatus::Refunded);
+    assert_eq!(client.get_pool_counter(), 2);
+
+    let ids = Vec::from_array(&env, [settled, refunded]);
+    assert_eq!(client.prune_archive(&ids), 0);
+    env.ledger().set_timestamp(1_000 + 365 * 86_400);
+    assert_eq!(client.prune_archive(&ids), 2);
+    assert_eq!(client.try_get_archived_pool(&settled), Err(Ok(Error::PoolNotFound)));
+}
+
+#[test]
+fn test_settlement_auto_sweeps_fees_over_threshold() {
+    let (env, contract_id, _admin, treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let bettor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &xlm_token).mint(&bettor, &1_000_000_000);
+    client.set_auto_sweep_threshold(&1_500_000);
+    assert_eq!(client.try_set_auto_sweep_threshold(&-1), Err(Ok(Error::InvalidAmount)));
+
+    let salt = BytesN::from_array(&env, &[10u8; 32]);
+    let commit = make_commitment(&env, 0, &salt);
+    let xlm = token::Client::new(&env, &xlm_token);
+    for expected_accrued in [1_000_000, 0] {
+        let pool_id = client.create_pool(&next_match_id(&env, &client), &0);
+        client.commit_bet(&pool_id, &bettor, &commit, &100_000_000);
+        client.lock_pool(&pool_id);
+        client.settle_pool(&pool_id, &BetSide::Player2);
+        assert_eq!(client.get_fee_accrued(), expected_accrued);
+    }
+
+    // Second settlement crossed 1.5M accrued and forwarded all 2M.
+    assert_eq!(xlm.balance(&treasury), 2_000_000);
+}
+
+#[test]
+fn test_pool_payout_multiplier_applies_at_claim() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let bettor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &xlm_token).mint(&bettor, &1_000_000_000);
+
+    let too_high = PoolConfig { payout_multiplier_bps: Some(50_001), ..Default::default() };
+    assert_eq!(
+        client.try_create_pool_with_config(&match_id(&env), &0, &too_high),
+        Err(Ok(Error::InvalidAmount))
+    );
+
+    let favorite = PoolConfig { payout_multiplier_bps: Some(18_000), ..Default::default() };
+    let pool_id = client.create_pool_with_config(&match_id(&env), &0, &favorite);
+    let salt = BytesN::from_array(&env, &[10u8; 32]);
+    client.commit_bet(&pool_id, &bettor, &make_commitment(&env, 0, &salt), &100_000_000);
+    assert_eq!(client.get_pool(&pool_id).liability, 180_000_000);
+    assert_eq!(client.get_open_exposure(), 80_000_000);
+    assert_eq!(client.get_odds(&pool_id).payout_multiplier_bps, 18_000);
+
+    client.lock_pool(&pool_id);
+    client.reveal_bet(&pool_id, &bettor, &BetSide::Player1, &salt);
+    let bankroll_before = client.get_bankroll();
+    client.settle_pool(&pool_id, &BetSide::Player1);
+    assert_eq!(client.get_bankroll(), bankroll_before - 80_000_000);
+    assert_eq!(client.get_open_exposure(), 0);
+
+    assert_eq!(client.claim_payout(&pool_id, &bettor), 180_000_000);
+    assert_eq!(client.get_total_liability(), 0);
+}
+
+#[test]
+fn test_underfunded_settlement_pays_winners_pro_rata() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let bettor1 = Address::generate(&env);
+    let bettor2 = Address::generate(&env);
+    xlm.mint(&bettor1, &1_000_000_000);
+    xlm.mint(&bettor2, &1_000_000_000);
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt = BytesN::from_array(&env, &[10u8; 32]);
+    let commit = make_commitment(&env, 0, &salt);
+    client.commit_bet(&pool_id, &bettor1, &commit, &100_000_000);
+    client.commit_bet(&pool_id, &bettor2, &commit, &100_000_000);
+    client.lock_pool(&pool_id);
+    client.reveal_bet(&pool_id, &bettor1, &BetSide::Player1, &salt);
+    client.reveal_bet(&pool_id, &bettor2, &BetSide::Player1, &salt);
+
+    // Funds leave the contract outside its accounting: only 300M of the
+    // 400M owed to winners remains besides the pool's 2M of fees.
+    let token_client = token::Client::new(&env, &xlm_token);
+    let drain = token_client.balance(&contract_id) - 302_000_000;
+    token_client.transfer(&contract_id, Address::generate(&env), &drain);
+
+    client.settle_pool(&pool_id, &BetSide::Player1);
+    assert_eq!(client.get_pool(&pool_id).haircut_bps, 7_500);
+
+    assert_eq!(client.claim_payout(&pool_id, &bettor1), 150_000_000);
+    assert_eq!(client.claim_payout(&pool_id, &bettor2), 150_000_000);
+    assert_eq!(client.get_total_liability(), 0);
+}
+
+#[test]
+fn test_unclaimed_winnings_expire_to_fees() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let claimer = Address::generate(&env);
+    let sleeper = Address::generate(&env);
+    xlm.mint(&claimer, &1_000_000_000);
+    xlm.mint(&sleeper, &1_000_000_000);
+
+    env.ledger().set_timestamp(1_000);
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt = BytesN::from_array(&env, &[10u8; 32]);
+    let commit = make_commitment(&env, 0, &salt);
+    client.commit_bet(&pool_id, &claimer, &commit, &100_000_000);
+    client.commit_bet(&pool_id, &sleeper, &commit, &100_000_000);
+    client.lock_pool(&pool_id);
+    client.reveal_bet(&pool_id, &claimer, &BetSide::Player1, &salt);
+    client.reveal_bet(&pool_id, &sleeper, &BetSide::Player1, &salt);
+    client.settle_pool(&pool_id, &BetSide::Player1);
+    client.claim_payout(&pool_id, &claimer);
+
+    let expires_ts = 1_000 + 14 * 86_400;
+    assert_eq!(client.get_unclaimed(&pool_id), (200_000_000, 200_000_000, expires_ts));
+    assert_eq!(client.try_reclaim_expired(&pool_id), Err(Ok(Error::DeadlineNotReached)));
+
+    env.ledger().set_timestamp(expires_ts);
+    assert_eq!(client.try_claim_payout(&pool_id, &sleeper), Err(Ok(Error::ClaimExpired)));
+    let fees_before = client.get_fee_accrued();
+    assert_eq!(client.reclaim_expired(&pool_id), 200_000_000);
+    assert_eq!(client.get_fee_accrued(), fees_before + 200_000_000);
+    assert_eq!(client.get_total_liability(), 0);
+    assert_eq!(client.try_reclaim_expired(&pool_id), Err(Ok(Error::NothingToClaim)));
+}
+
+#[test]
+fn test_top_predictors_ranked_by_streak_then_wins() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let streaker = Address::generate(&env);
+    let casual = Address::generate(&env);
+    xlm.mint(&streaker, &1_000_000_000);
+    xlm.mint(&casual, &1_000_000_000);
+
+    let p1_salt = BytesN::from_array(&env, &[10u8; 32]);
+    let p2_salt = BytesN::from_array(&env, &[20u8; 32]);
+    for _ in 0..2 {
+        let pool_id = client.create_pool(&next_match_id(&env, &client), &0);
+        client.commit_bet(&pool_id, &streaker, &make_commitment(&env, 0, &p1_salt), &10_000_000);
+        client.commit_bet(&pool_id, &casual, &make_commitment(&env, 1, &p2_salt), &10_000_000);
+        client.lock_pool(&pool_id);
+        client.reveal_bet(&pool_id, &streaker, &BetSide::Player1, &p1_salt);
+        client.reveal_bet(&pool_id, &casual, &BetSide::Player2, &p2_salt);
+        client.settle_pool(&pool_id, &BetSide::Player1);
+        client.claim_payout(&pool_id, &streaker);
+    }
+    let pool_id = client.create_pool(&next_match_id(&env, &client), &0);
+    client.commit_bet(&pool_id, &casual, &make_commitment(&env, 1, &p2_salt), &10_000_000);
+    client.lock_pool(&pool_id);
+    client.reveal_bet(&pool_id, &casual, &BetSide::Player2, &p2_salt);
+    client.settle_pool(&pool_id, &BetSide::Player2);
+    client.claim_payout(&pool_id, &casual);
+
+    let top = client.get_top_predictors(&10);
+    assert_eq!(top.len(), 2);
+    assert_eq!(top.get(0).unwrap().bettor, streaker);
+    assert_eq!(top.get(0).unwrap().best_streak, 2);
+    assert_eq!(top.get(1).unwrap().bettor, casual);
+    assert_eq!(client.get_top_predictors(&1).len(), 1);
+}
+
+#[test]
+fn test_commit_rate_limit_per_bettor() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let bettor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &xlm_token).mint(&bettor, &1_000_000_000);
+    client.set_rate_limit(&2, &600);
+
+    let commit = make_commitment(&env, 0, &BytesN::from_array(&env, &[10u8; 32]));
+    let mut pools = Vec::new(&env);
+    for _ in 0..4 {
+        pools.push_back(client.create_pool(&next_match_id(&env, &client), &0));
+    }
+
+    env.ledger().set_timestamp(6_000);
+    client.commit_bet(&pools.get(0).unwrap(), &bettor, &commit, &10_000_000);
+    client.commit_bet(&pools.get(1).unwrap(), &bettor, &commit, &10_000_000);
+    assert_eq!(
+        client.try_commit_bet(&pools.get(2).unwrap(), &bettor, &commit, &10_000_000),
+        Err(Ok(Error::RateLimited))
+    );
+
+    // Halfway into the next window, half of the previous window still counts.
+    env.ledger().set_timestamp(6_900);
+    client.commit_bet(&pools.get(2).unwrap(), &bettor, &commit, &10_000_000);
+    assert_eq!(
+        client.try_commit_bet(&pools.get(3).unwrap(), &bettor, &commit, &10_000_000),
+        Err(Ok(Error::RateLimited))
+    );
+
+    env.ledger().set_timestamp(8_000);
+    client.commit_bet(&pools.get(3).unwrap(), &bettor, &commit, &10_000_000);
+}
+
+#[test]
+fn test_one_canonical_pool_per_match_market() {
+    let (env, contract_id, _admin, _treasury, _xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let mid = match_id(&env);
+    let winner_pool = client.create_pool(&mid, &0);
+    let moves_pool = client.create_market_pool(&mid, &Market::TotalMoves, &20, &0);
+    assert_eq!(
+        client.try_create_market_pool(&mid, &Market::TotalMoves, &30, &0),
+        Err(Ok(Error::MarketExists))
+    );
+    assert_eq!(client.try_create_pool(&mid, &0), Err(Ok(Error::MarketExists)));
+
+    assert_eq!(client.get_pool_by_match(&mid, &Market::MatchWinner), winner_pool);
+    assert_eq!(client.get_pool_by_match(&mid, &Market::TotalMoves), moves_pool);
+    assert_eq!(
+        client.try_get_pool_by_match(&mid, &Market::FirstBlood),
+        Err(Ok(Error::PoolNotFound))
+    );
+
+    // A refunded pool frees the market for a replacement.
+    client.refund_pool(&winner_pool);
+    let replacement = client.create_pool(&mid, &0);
+    assert_eq!(client.get_pool_by_match(&mid, &Market::MatchWinner), replacement);
+}
+
+#[test]
+fn test_cancel_pool_records_reason_and_refunds() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let bettor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &xlm_token).mint(&bettor, &1_000_000_000);
+    let xlm = token::Client::new(&env, &xlm_token);
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let commit = make_commitment(&env, 0, &BytesN::from_array(&env, &[10u8; 32]));
+    client.commit_bet(&pool_id, &bettor, &commit, &50_000_000);
+    assert_eq!(client.get_cancel_reason(&pool_id), None);
+
+    client.cancel_pool(&pool_id, &CancelReason::IntegrityIssue);
+    assert_eq!(
+        env.events().all().filter_by_contract(&contract_id),
+        Vec::from_array(
+            &env,
+            [
+                (
+                    contract_id.clone(),
+                    (symbol_short!("pool_cxl"), pool_id).into_val(&env),
+                    (CancelReason::IntegrityIssue as u32).into_val(&env),
+                ),
+                (
+                    contract_id.clone(),
+                    (symbol_short!("refund"), pool_id).into_val(&env),
+                    1u32.into_val(&env),
+                ),
+            ]
+        )
+    );
+    assert_eq!(client.get_cancel_reason(&pool_id), Some(CancelReason::IntegrityIssue));
+    assert_eq!(client.get_pool(&pool_id).status, PoolStatus::Refunded);
+    assert_eq!(xlm.balance(&bettor), 1_000_000_000);
+
+    assert_eq!(
+        client.try_cancel_pool(&pool_id, &CancelReason::NoContest),
+        Err(Ok(Error::PoolAlreadySettled))
+    );
+    assert_eq!(client.get_cancel_reason(&pool_id), Some(CancelReason::IntegrityIssue));
+}
+
+#[test]
+fn test_fees_accounted_per_pool_and_day() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let bettor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &xlm_token).mint(&bettor, &1_000_000_000);
+    let commit = make_commitment(&env, 0, &BytesN::from_array(&env, &[10u8; 32]));
+
+    let mut pools = Vec::new(&env);
+    let bets = [
+        (86_400 + 10, 100_000_000),
+        (86_400 + 20, 50_000_000),
+        (2 * 86_400, 10_000_000),
+    ];
+    for (ts, amount) in bets {
+        env.ledger().set_timestamp(ts);
+        let pool_id = client.create_pool(&next_match_id(&env, &client), &0);
+        client.commit_bet(&pool_id, &bettor, &commit, &amount);
+        client.lock_pool(&pool_id);
+        client.settle_pool(&pool_id, &BetSide::Player2);
+        pools.push_back(pool_id);
+    }
+
+    assert_eq!(client.get_pool_fees(&pools.get(0).unwrap()), 1_000_000);
+    assert_eq!(client.get_pool_fees(&pools.get(1).unwrap()), 500_000);
+    assert_eq!(client.get_pool_fees(&pools.get(2).unwrap()), 100_000);
+    assert_eq!(client.get_daily_fees(&1), 1_500_000);
+    assert_eq!(client.get_daily_fees(&2), 100_000);
+    assert_eq!(client.get_daily_fees(&3), 0);
+    assert_eq!(client.get_fee_accrued(), 1_600_000);
+}
+
+#[test]
+fn test_audited_commit_stores_encrypted_side() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let bettor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &xlm_token).mint(&bettor, &1_000_000_000);
+
+    let pool_id = client.create_pool(&match_id(&env), &0);
+    let salt = BytesN::from_array(&env, &[10u8; 32]);
+    let commit = make_commitment(&env, 0, &salt);
+    let sealed = Bytes::from_array(&env, &[7u8; 48]);
+    assert_eq!(
+        client.try_commit_bet_audited(&pool_id, &bettor, &commit, &10_000_000, &sealed),
+        Err(Ok(Error::AuditorNotConfigured))
+    );
+
+    client.set_auditor_key(&BytesN::from_array(&env, &[5u8; 32]));
+    assert_eq!(
+        client.try_commit_bet_audited(
+            &pool_id,
+            &bettor,
+            &commit,
+            &10_000_000,
+            &Bytes::from_array(&env, &[7u8; 129]),
+        ),
+        Err(Ok(Error::EncryptedSideTooLong))
+    );
+    client.commit_bet_audited(&pool_id, &bettor, &commit, &10_000_000, &sealed);
+    let bet = client.get_bet(&pool_id, &bettor);
+    assert_eq!(bet.encrypted_side, Some(sealed));
+    assert_eq!(bet.side, SIDE_NONE);
+
+    // Reveal still goes through the public commitment.
+    client.lock_pool(&pool_id);
+    client.reveal_bet(&pool_id, &bettor, &BetSide::Player1, &salt);
+    assert_eq!(client.get_bet(&pool_id, &bettor).side, 0);
+}
+
+#[test]
+fn test_extend_deadline_only_later_while_open() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let bettor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &xlm_token).mint(&bettor, &1_000_000_000);
+
+    env.ledger().set_timestamp(1_000);
+    let pool_id = client.create_pool(&match_id(&env), &2_000);
+    assert_eq!(client.try_extend_deadline(&pool_id, &2_000), Err(Ok(Error::InvalidDeadline)));
+
+    client.extend_deadline(&pool_id, &5_000);
+    assert_eq!(
+        env.events().all().filter_by_contract(&contract_id),
+        Vec::from_array(
+            &env,
+            [(
+                contract_id.clone(),
+                (symbol_short!("deadline"), pool_id).into_val(&env),
+                (2_000u64, 5_000u64).into_val(&env),
+            )]
+        )
+    );
+    assert_eq!(client.get_pool(&pool_id).deadline_ts, 5_000);
+
+    // Bets past the original deadline are now accepted.
+    env.ledger().set_timestamp(3_000);
+    let commit = make_commitment(&env, 0, &BytesN::from_array(&env, &[10u8; 32]));
+    client.commit_bet(&pool_id, &bettor, &commit, &10_000_000);
+
+    let no_deadline = client.create_market_pool(&match_id(&env), &Market::FirstBlood, &0, &0);
+    assert_eq!(
+        client.try_extend_deadline(&no_deadline, &9_000),
+        Err(Ok(Error::InvalidDeadline))
+    );
+
+    client.lock_pool(&pool_id);
+    assert_eq!(client.try_extend_deadline(&pool_id, &9_000), Err(Ok(Error::PoolNotOpen)));
+}
+
+#[test]
+fn test_settlement_waits_for_reveal_window() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    let xlm = token::StellarAssetClient::new(&env, &xlm_token);
+    let revealer = Address::generate(&env);
+    let sleeper = Address::generate(&env);
+    xlm.mint(&revealer, &1_000_000_000);
+    xlm.mint(&sleeper, &1_000_000_000);
+
+    client.set_reveal_window(&600);
+    let pool_id = client.create_pool(&next_match_id(&env, &client), &0);
+    let instant = client.create_pool_with_config(
+        &next_match_id(&env, &client),
+        &0,
+        &PoolConfig { reveal_window_secs: Some(0), ..Default::default() },
+    );
+    assert_eq!(client.get_pool(&pool_id).reveal_window_secs, 600);
+
+    let salt = BytesN::from_array(&env, &[10u8; 32]);
+    let commit = make_commitment(&env, 0, &salt);
+    for id in [pool_id, instant] {
+        client.commit_bet(&id, &revealer, &commit, &10_000_000);
+        client.commit_bet(&id, &sleeper, &commit, &10_000_000);
+    }
+    assert_eq!(
+        client.try_settle_pool(&pool_id, &BetSide::Player1),
+        Err(Ok(Error::RevealWindowOpen))
+    );
+
+    env.ledger().set_timestamp(1_000);
+    client.lock_pool(&pool_id);
+    client.lock_pool(&instant);
+    client.settle_pool(&instant, &BetSide::Player1);
+    client.reveal_bet(&pool_id, &revealer, &BetSide::Player1, &salt);
+
+    env.ledger().set_timestamp(1_599);
+    assert_eq!(
+        client.try_settle_pool(&pool_id, &BetSide::Player1),
+        Err(Ok(Error::RevealWindowOpen))
+    );
+    env.ledger().set_timestamp(1_600);
+    client.settle_pool(&pool_id, &BetSide::Player1);
+}
+
+#[test]
+fn test_brawl_cancel_notification_refunds_linked_pools() {
+    let (env, contract_id, _admin, _treasury, xlm_token) = setup_env();
+    let client = ZkBettingContractClient::new(&env, &contract_id);
+
+    assert_eq!(
+        client.try_notify_match_cancelled(&3),
+        Err(Ok(Error::BrawlNotConfigured))
+    );
+
+    let brawl = env.register(MockBrawlContract, ());
+    let brawl_client = MockBrawlContractClient::new(&env, &brawl);
+    client.set_brawl_contract(&brawl);
+    brawl_client.set_match(&3, &brawl_match(&env, None));
+    brawl_client.set_match(&4, &brawl_match(&env, None));
+
+    let bettor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &xlm_token).mint(&bettor, &1_000_000_000);
+    let commit = make_commitment(&env, 0, &BytesN::from_array(&env, &[10u8; 32]));
+
+    let mid = match_id(&env);
+    let winner_pool = client.create_session_pool(&mid, &3, &0);
+    let moves_pool = client.create_market_pool(&mid, &Market::TotalMoves, &20, &0);
+    client.link_pool_session(&moves_pool, &3);
+    let other_session = client.create_session_pool(&next_match_id(&env, &client), &4, &0);
+    for pool_id in [winner_pool, moves_pool, other_session] {
+        client.commit_bet(&pool_id, &bettor, &commit, &10_000_000);
+    }
+    client.lock_pool(&moves_pool);
+
+    assert_eq!(client.notify_match_cancelled(&3), 2);
+    assert_eq!(env.auths()[0].0, brawl);
+    assert_eq!(client.get_pool(&winner_pool).status, PoolStatus::Refunded);
+    assert_eq!(client.get_pool(&moves_pool).status, PoolStatus::Refunded);
+    assert_eq!(client.get_pool(&other_session).status, PoolStatus::Open);
+
+    // Already-refunded pools are skipped on a repeated notification.
+    assert_eq!(client.notify_match_cancelled(&3), 0);
+}
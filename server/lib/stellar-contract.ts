@@ -472,6 +472,8 @@ export interface OnChainResult {
     txHash?: string;
     error?: string;
     sessionId?: number;
+    /** Action nonce the submission used; pass it back when retrying the same action. */
+    nonce?: number;
 }
 
 export interface SweepFeesResult extends OnChainResult {
@@ -588,6 +590,34 @@ function injectSignedAuthIntoTxEnvelope(
     return { updatedXdr: envelope.toXDR("base64"), replacedCount };
 }
 
+/**
+ * Read the action nonce (the trailing u32 argument) from a prepared
+ * submit_* transaction, so a rebuilt envelope matches the signed auth entry.
+ */
+function extractActionNonce(transactionXdr: string): number | null {
+    try {
+        const envelope = xdrLib.TransactionEnvelope.fromXDR(transactionXdr, "base64");
+        const envelopeType = envelope.switch().name;
+        const tx = envelopeType === "envelopeTypeTxFeeBump"
+            ? envelope.feeBump().tx().innerTx().v1().tx()
+            : envelope.v1().tx();
+
+        for (const op of tx.operations()) {
+            if (op.body().switch().name !== "invokeHostFunction") continue;
+            const fn = op.body().invokeHostFunctionOp().hostFunction();
+            if (fn.switch().name !== "hostFunctionTypeInvokeContract") continue;
+            const args = fn.invokeContract().args();
+            const last = args[args.length - 1];
+            if (last && last.switch().name === "scvU32") {
+                return last.u32();
+            }
+        }
+        return null;
+    } catch {
+        return null;
+    }
+}
+
 function countInvokeHostFunctionAuthEntries(transactionXdr: string): number {
     const envelope = xdrLib.TransactionEnvelope.fromXDR(transactionXdr, "base64");
     const envelopeType = envelope.switch().name;
@@ -899,6 +929,17 @@ export async function submitSignedRegistration(
 // submit_move — called for every combat move
 // =============================================================================
 
+/**
+ * Read the player's next per-match action nonce. submit_move, submit_power_surge
+ * and submit_zk_commit must pass it so retries are rejected instead of re-charged.
+ * Read it once per logical action: a retry must reuse the same nonce, or a
+ * submission that landed without a response would be charged twice.
+ */
+async function getActionNonce(client: contract.Client, sessionId: number, playerAddress: string): Promise<number> {
+    const tx = await (client as any).get_action_nonce({ session_id: sessionId, player: playerAddress });
+    return Number(tx?.result ?? 0);
+}
+
 /**
  * Record a combat move on-chain and transfer 0.0001 XLM from the player.
  * Called server-side after each move is submitted.
@@ -907,7 +948,9 @@ export async function submitMoveOnChain(
     matchId: string,
     playerAddress: string,
     moveType: string,
+    roundNumber: number,
     turn: number,
+    options?: { nonce?: number },
 ): Promise<OnChainResult> {
     if (!isStellarConfigured()) {
         return { success: false, error: "Stellar contract not configured" };
@@ -924,22 +967,26 @@ export async function submitMoveOnChain(
         return { success: false, error: `Unknown move type: ${moveType}` };
     }
 
+    let nonce = options?.nonce;
     try {
         const client = await createContractClient(keypair.publicKey(), createSigner(keypair));
+        if (nonce === undefined) nonce = await getActionNonce(client, sessionId, playerAddress);
         const tx = await (client as any).submit_move({
             session_id: sessionId,
             player: playerAddress,
             move_type: moveVal,
+            round: roundNumber,
             turn,
+            nonce,
         });
         const { txHash } = await signAndSendTx(tx);
 
-        console.log(`[Stellar] Move recorded: ${moveType} by ${playerAddress.slice(0, 8)}… turn=${turn}, TX: ${txHash || "n/a"}`);
-        return { success: true, txHash, sessionId };
+        console.log(`[Stellar] Move recorded: ${moveType} by ${playerAddress.slice(0, 8)}… round=${roundNumber} turn=${turn}, TX: ${txHash || "n/a"}`);
+        return { success: true, txHash, sessionId, nonce };
     } catch (err) {
         const message = err instanceof Error ? err.message : String(err);
         console.error(`[Stellar] Failed to submit move on-chain:`, message);
-        return { success: false, error: message, sessionId };
+        return { success: false, error: message, sessionId, nonce };
     }
 }
 
@@ -951,6 +998,7 @@ export async function submitPowerSurgeOnChain(
     playerAddress: string,
     roundNumber: number,
     cardId: PowerSurgeCardId,
+    options?: { nonce?: number },
 ): Promise<OnChainResult> {
     if (!isStellarConfigured()) {
         return { success: false, error: "Stellar contract not configured" };
@@ -967,24 +1015,27 @@ export async function submitPowerSurgeOnChain(
         return { success: false, error: `Unknown power surge card: ${cardId}` };
     }
 
+    let nonce = options?.nonce;
     try {
         const client = await createContractClient(keypair.publicKey(), createSigner(keypair));
+        if (nonce === undefined) nonce = await getActionNonce(client, sessionId, playerAddress);
         const tx = await (client as any).submit_power_surge({
             session_id: sessionId,
             player: playerAddress,
             round: roundNumber,
             card_code: cardCode,
+            nonce,
         });
         const { txHash } = await signAndSendTx(tx);
 
         console.log(
             `[Stellar] Power surge recorded: ${cardId} by ${playerAddress.slice(0, 8)}… round=${roundNumber}, TX: ${txHash || "n/a"}`,
         );
-        return { success: true, txHash, sessionId };
+        return { success: true, txHash, sessionId, nonce };
     } catch (err) {
         const message = err instanceof Error ? err.message : String(err);
         console.error(`[Stellar] Failed to submit power surge on-chain:`, message);
-        return { success: false, error: message, sessionId };
+        return { success: false, error: message, sessionId, nonce };
     }
 }
 
@@ -1030,6 +1081,7 @@ export async function prepareZkCommitOnChain(
         round: roundNumber,
         turn: turnNumber,
         commitment: commitmentBytes,
+        nonce: await getActionNonce(readOnlyClient, sessionId, playerAddress),
     });
 
     const authEntryXdr = getPlayerAuthEntryXdr(tx.simulationData?.result?.auth, playerAddress);
@@ -1080,6 +1132,9 @@ export async function submitSignedZkCommitOnChain(
 
         const commitmentBytes = normalizeCommitmentHexToBytes32(commitmentHex);
 
+        // The player signed over the prepared nonce; every rebuilt envelope must reuse it.
+        let actionNonce = extractActionNonce(transactionXdr);
+
         const buildFreshCommitXdr = async (): Promise<string> => {
             const feePayerClient = await createFeePayerContractClient(feePayerKeypair, options?.contractId || undefined);
             const submitZkCommit = (feePayerClient as any).submit_zk_commit;
@@ -1087,12 +1142,14 @@ export async function submitSignedZkCommitOnChain(
                 throw new Error("Deployed contract does not expose submit_zk_commit");
             }
 
+            if (actionNonce === null) actionNonce = await getActionNonce(feePayerClient, sessionId, playerAddress);
             const freshTx = await submitZkCommit({
                 session_id: sessionId,
                 player: playerAddress,
                 round: roundNumber,
                 turn: turnNumber,
                 commitment: commitmentBytes,
+                nonce: actionNonce,
             });
 
             return freshTx.toXDR();
@@ -1526,6 +1583,7 @@ export async function setMatchStakeOnChain(
                 const tx = await setMatchStake({
                     session_id: sessionId,
                     stake_amount_stroops: stakeAmountStroops,
+                    surge_price_bps: 0,
                 });
                 const { txHash } = await signAndSendTx(tx);
                 return { success: true, txHash, sessionId };
@@ -1646,7 +1704,7 @@ export async function submitZkCommitOnChain(
     roundNumber: number,
     turnNumber: number,
     commitmentHex: string,
-    options?: { contractId?: string; sessionId?: number },
+    options?: { contractId?: string; sessionId?: number; nonce?: number },
 ): Promise<OnChainResult> {
     if (!isStellarConfigured()) {
         return { success: false, error: "Stellar contract not configured" };
@@ -1664,6 +1722,7 @@ export async function submitZkCommitOnChain(
     const sessionId = options?.sessionId ?? matchIdToSessionId(matchId);
     const contractId = options?.contractId || undefined;
 
+    let nonce = options?.nonce;
     try {
         const commitmentBytes = normalizeCommitmentHexToBytes32(commitmentHex);
 
@@ -1677,19 +1736,21 @@ export async function submitZkCommitOnChain(
             };
         }
 
+        if (nonce === undefined) nonce = await getActionNonce(client, sessionId, playerAddress);
         const tx = await submitZkCommit({
             session_id: sessionId,
             player: playerAddress,
             round: roundNumber,
             turn: turnNumber,
             commitment: commitmentBytes,
+            nonce,
         });
         const { txHash } = await signAndSendTx(tx);
 
-        return { success: true, txHash, sessionId };
+        return { success: true, txHash, sessionId, nonce };
     } catch (err) {
         const message = err instanceof Error ? err.message : String(err);
-        return { success: false, error: message, sessionId };
+        return { success: false, error: message, sessionId, nonce };
     }
 }
 
@@ -1,4 +1,4 @@
-import { Client as DiceDuelClient, type Game } from './bindings';
+import { Client as DiceDuelClient, type Game, type TiePolicy } from './bindings';
 import { NETWORK_PASSPHRASE, RPC_URL, DEFAULT_METHOD_OPTIONS, DEFAULT_AUTH_TTL_MINUTES, MULTI_SIG_AUTH_TTL_MINUTES } from '@/utils/constants';
 import { contract, TransactionBuilder, StrKey, xdr, Address, authorizeEntry } from '@stellar/stellar-sdk';
 import { Buffer } from 'buffer';
@@ -8,6 +8,21 @@ import { injectSignedAuthEntry } from '@/utils/authEntryUtils';
 
 type ClientOptions = contract.ClientOptions;
 
+/** Games started here are a single roll that Player 1 wins on a tie */
+const DEFAULT_SERIES_LENGTH = 1;
+const DEFAULT_TIE_POLICY: TiePolicy = { tag: 'Player1', values: undefined };
+
+/**
+ * Decode a unit `TiePolicy` variant (encoded as a vec holding its name)
+ */
+function parseTiePolicy(scVal: xdr.ScVal): TiePolicy {
+  const tag = scVal.vec()?.[0]?.sym().toString();
+  if (tag !== 'Player1' && tag !== 'Reroll' && tag !== 'Draw' && tag !== 'HighDie') {
+    throw new Error(`Unknown tie policy: ${tag}`);
+  }
+  return { tag, values: undefined };
+}
+
 /**
  * Service for interacting with the DiceDuel game contract
  */
@@ -86,6 +101,8 @@ export class DiceDuelService {
       player2,
       player1_points: player1Points,
       player2_points: player2Points,
+      series_length: DEFAULT_SERIES_LENGTH,
+      tie_policy: DEFAULT_TIE_POLICY,
     }, DEFAULT_METHOD_OPTIONS);
 
     const validUntilLedgerSeq = authTtlMinutes
@@ -134,6 +151,8 @@ export class DiceDuelService {
       player2,
       player1_points: player1Points,
       player2_points: player2Points,
+      series_length: DEFAULT_SERIES_LENGTH,
+      tie_policy: DEFAULT_TIE_POLICY,
     }, DEFAULT_METHOD_OPTIONS);
     // NOTE: Contract methods automatically simulate - no need to call tx.simulate() again!
     console.log('[prepareStartGame] Transaction built and simulated, extracting auth entries');
@@ -235,11 +254,15 @@ export class DiceDuelService {
    * - Player address (from credentials)
    * - Session ID (arg 0)
    * - Player's points (arg 1)
+   * - Series length (arg 2)
+   * - Tie policy (arg 3)
    */
   parseAuthEntry(authEntryXdr: string): {
     sessionId: number;
     player1: string;
     player1Points: bigint;
+    seriesLength: number;
+    tiePolicy: TiePolicy;
     functionName: string;
   } {
     try {
@@ -281,26 +304,34 @@ export class DiceDuelService {
       // For start_game with require_auth_for_args, we have:
       // 0: session_id (u32)
       // 1: player_points (i128)
+      // 2: series_length (u32)
+      // 3: tie_policy (TiePolicy)
       const args = contractFn.args();
       console.log('[parseAuthEntry] Number of args:', args.length);
 
-      if (args.length !== 2) {
-        throw new Error(`Expected 2 arguments for start_game auth entry, got ${args.length}`);
+      if (args.length !== 4) {
+        throw new Error(`Expected 4 arguments for start_game auth entry, got ${args.length}`);
       }
 
       const sessionId = args[0].u32();
       const player1Points = args[1].i128().lo().toBigInt();
+      const seriesLength = args[2].u32();
+      const tiePolicy = parseTiePolicy(args[3]);
 
       console.log('[parseAuthEntry] Extracted:', {
         sessionId,
         player1,
         player1Points: player1Points.toString(),
+        seriesLength,
+        tiePolicy: tiePolicy.tag,
       });
 
       return {
         sessionId,
         player1,
         player1Points,
+        seriesLength,
+        tiePolicy,
         functionName,
       };
     } catch (err: any) {
@@ -369,6 +400,8 @@ export class DiceDuelService {
       player2: player2Address,             // Provided by Player 2
       player1_points: gameParams.player1Points, // From auth entry
       player2_points: player2Points,         // Provided by Player 2
+      series_length: gameParams.seriesLength, // From auth entry
+      tie_policy: gameParams.tiePolicy,       // From auth entry
     }, DEFAULT_METHOD_OPTIONS);
     // NOTE: Contract methods automatically simulate - no need to call tx.simulate() again!
 
@@ -503,6 +536,8 @@ export class DiceDuelService {
     player2: string;
     player1Points: bigint;
     player2Points: bigint;
+    seriesLength: number;
+    tiePolicy: TiePolicy;
     transactionSource: string;
     functionName: string;
   } {
@@ -535,13 +570,15 @@ export class DiceDuelService {
     // 2: player2 (Address)
     // 3: player1_points (i128)
     // 4: player2_points (i128)
+    // 5: series_length (u32)
+    // 6: tie_policy (TiePolicy)
 
     if (functionName !== 'start_game') {
       throw new Error(`Unexpected function: ${functionName}. Expected start_game.`);
     }
 
-    if (args.length !== 5) {
-      throw new Error(`Expected 5 arguments for start_game, got ${args.length}`);
+    if (args.length !== 7) {
+      throw new Error(`Expected 7 arguments for start_game, got ${args.length}`);
     }
 
     // Extract session_id (u32)
@@ -564,12 +601,18 @@ export class DiceDuelService {
     const player2PointsScVal = args[4];
     const player2Points = player2PointsScVal.i128().lo().toBigInt();
 
+    // Extract the series format
+    const seriesLength = args[5].u32();
+    const tiePolicy = parseTiePolicy(args[6]);
+
     return {
       sessionId,
       player1,
       player2,
       player1Points,
       player2Points,
+      seriesLength,
+      tiePolicy,
       transactionSource,
       functionName,
     };
@@ -721,8 +721,9 @@ export function TwentyOneGame({
         await loadGameState();
 
         const winner = (winnerResult as any).unwrap ? (winnerResult as any).unwrap() : winnerResult;
-        const isWinner = normalizeAddress(winner) === normalizedUserAddress;
-        setSuccess(isWinner ? '🎉 You won!' : 'Game complete! Winner revealed.');
+        // No round winner means the round was tied
+        const isWinner = !!winner && normalizeAddress(winner) === normalizedUserAddress;
+        setSuccess(isWinner ? '🎉 You won!' : winner ? 'Game complete! Winner revealed.' : 'Round tied.');
 
         onStandingsRefresh();
       } catch (err) {
@@ -68,6 +68,44 @@ export const Errors = {
 
 export type DataKey = {tag: "Game", values: readonly [u32]} | {tag: "GameHubAddress", values: void} | {tag: "Admin", values: void};
 
+/**
+ * How Aces are counted.
+ */
+export type AceMode = {tag: "Soft", values: void} | {tag: "Hard", values: void};
+
+
+/**
+ * Rules for a single game, passed to `start_game` so variants can share a deployment.
+ */
+export interface GameRules {
+  ace_mode: AceMode;
+  /**
+ * An unsplit hand reaching this many cards without busting wins the
+ * round (e.g. 5 for a five-card Charlie); 0 disables the rule.
+ */
+  charlie_cards: u32;
+  /**
+ * A hand reaching this many cards is stuck automatically (3 or more,
+ * and no fewer than `charlie_cards`); 0 means no limit.
+ */
+  max_hand_cards: u32;
+  /**
+ * Series length: the first player to win a majority of `max_rounds`
+ * decided rounds takes the game (1 = single round).
+ */
+  max_rounds: u32;
+  tie_policy: TiePolicy;
+  /**
+ * Time a player may idle before the opponent can claim the game.
+ */
+  timeout_seconds: u64;
+}
+
+/**
+ * What happens when a round ends in a tie.
+ */
+export type TiePolicy = {tag: "Redeal", values: void} | {tag: "Push", values: void} | {tag: "RedealLimit", values: readonly [u32]};
+
 export interface Client {
   /**
    * Construct and simulate a hit transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
@@ -152,7 +190,8 @@ export interface Client {
    * Construct and simulate a start_game transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
    * Start a new game between two players with points.
    * This creates a session in the Game Hub and locks points before starting the game.
-   * Each player is dealt 2 cards to start.
+   * Each player is dealt 2 cards to start; a natural (two-card 21) dealt
+   * to only one player wins the round immediately.
    * 
    * **CRITICAL:** This method requires authorization from THIS contract (not players).
    * The Game Hub will call `game_id.require_auth()` which checks this contract's address.
@@ -163,23 +202,38 @@ export interface Client {
    * * `player2` - Address of second player
    * * `player1_points` - Points amount committed by player 1
    * * `player2_points` - Points amount committed by player 2
+   * * `rules` - Rules for this game; `None` uses the contract defaults.
+   * Both players authorize the rules along with their points.
    */
-  start_game: ({session_id, player1, player2, player1_points, player2_points}: {session_id: u32, player1: string, player2: string, player1_points: i128, player2_points: i128}, options?: MethodOptions) => Promise<AssembledTransaction<Result<void>>>
+  start_game: ({session_id, player1, player2, player1_points, player2_points, rules}: {session_id: u32, player1: string, player2: string, player1_points: i128, player2_points: i128, rules: Option<GameRules>}, options?: MethodOptions) => Promise<AssembledTransaction<Result<void>>>
 
   /**
    * Construct and simulate a reveal_winner transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
-   * Reveal the winner of the game and submit outcome to GameHub.
+   * Reveal the winner of the round and submit outcome to GameHub once
+   * the series is decided.
    * Can only be called after both players have stuck.
    * This calculates hand values, determines the winner (closest to 21),
-   * and handles draws by dealing new hands.
+   * and handles ties according to the `TiePolicy`: by dealing new hands
+   * or ending the game as a push. With split hands, every hand is compared
+   * against each of the opponent's and the wins and losses are summed. In
+   * a best-of-N series an undecided game continues with a freshly dealt
+   * round.
+   * 
+   * Only the players or the admin may reveal, until both players have
+   * been stuck for the game's action timeout; after that anyone can, so
+   * a finished game cannot be left unrevealed. In a seeded house game the
+   * dealer's draws wait for the operator's next link, and the round is
+   * decided by `reveal_seed`.
    * 
    * # Arguments
    * * `session_id` - The session ID of the game
+   * * `caller` - Address revealing the winner
    * 
    * # Returns
-   * * `Address` - Address of the winning player
+   * * `Option<Address>` - Address of the round winner (check game.winner for
+   * the game), or `None` if the round was tied or waits for the dealer
    */
-  reveal_winner: ({session_id}: {session_id: u32}, options?: MethodOptions) => Promise<AssembledTransaction<Result<string>>>
+  reveal_winner: ({session_id, caller}: {session_id: u32, caller: string}, options?: MethodOptions) => Promise<AssembledTransaction<Result<Option<string>>>>
 
   /**
    * Construct and simulate a get_hand_value transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
@@ -217,6 +271,9 @@ export class Client extends ContractClient {
       new ContractSpec([ "AAAAAQAAAAAAAAAAAAAABEdhbWUAAAAKAAAAAAAAAAdwbGF5ZXIxAAAAABMAAAAAAAAADHBsYXllcjFfaGFuZAAAAA4AAAAAAAAADnBsYXllcjFfcG9pbnRzAAAAAAALAAAAAAAAAA1wbGF5ZXIxX3N0dWNrAAAAAAAAAQAAAAAAAAAHcGxheWVyMgAAAAATAAAAAAAAAAxwbGF5ZXIyX2hhbmQAAAAOAAAAAAAAAA5wbGF5ZXIyX3BvaW50cwAAAAAACwAAAAAAAAANcGxheWVyMl9zdHVjawAAAAAAAAEAAAAAAAAABXJvdW5kAAAAAAAABAAAAAAAAAAGd2lubmVyAAAAAAPoAAAAEw==",
         "AAAABAAAAAAAAAAAAAAABUVycm9yAAAAAAAACwAAAAAAAAAMR2FtZU5vdEZvdW5kAAAAAQAAAAAAAAAJTm90UGxheWVyAAAAAAAAAgAAAAAAAAAMQWxyZWFkeVN0dWNrAAAAAwAAAAAAAAAQR2FtZUFscmVhZHlFbmRlZAAAAAQAAAAAAAAADFBsYXllckJ1c3RlZAAAAAUAAAAAAAAAE0JvdGhQbGF5ZXJzTm90U3R1Y2sAAAAABgAAAAAAAAAQT3Bwb25lbnROb3RTdHVjawAAAAcAAAAAAAAABERyYXcAAAAIAAAAAAAAAAhTZWxmUGxheQAAAAkAAAAAAAAADVJvdW5kT3ZlcmZsb3cAAAAAAAAKAAAAAAAAAA9JbnZhbGlkSGFuZERhdGEAAAAACw==",
         "AAAAAgAAAAAAAAAAAAAAB0RhdGFLZXkAAAAAAwAAAAEAAAAAAAAABEdhbWUAAAABAAAABAAAAAAAAAAAAAAADkdhbWVIdWJBZGRyZXNzAAAAAAAAAAAAAAAAAAVBZG1pbgAAAA==",
+        "AAAAAgAAABVIb3cgQWNlcyBhcmUgY291bnRlZC4AAAAAAAAAAAAAB0FjZU1vZGUAAAAAAgAAAAAAAAA6T25lIEFjZSBjb3VudHMgYXMgMTEgdW5sZXNzIHRoZSBoYW5kIHdvdWxkIGJ1c3QgKGRlZmF1bHQpLgAAAAAABFNvZnQAAAAAAAAAF0FjZXMgYWx3YXlzIGNvdW50IGFzIDEuAAAAAARIYXJk",
+        "AAAAAQAAAFNSdWxlcyBmb3IgYSBzaW5nbGUgZ2FtZSwgcGFzc2VkIHRvIGBzdGFydF9nYW1lYCBzbyB2YXJpYW50cyBjYW4gc2hhcmUgYSBkZXBsb3ltZW50LgAAAAAAAAAACUdhbWVSdWxlcwAAAAAAAAYAAAAAAAAACGFjZV9tb2RlAAAH0AAAAAdBY2VNb2RlAAAAAH5BbiB1bnNwbGl0IGhhbmQgcmVhY2hpbmcgdGhpcyBtYW55IGNhcmRzIHdpdGhvdXQgYnVzdGluZyB3aW5zIHRoZQpyb3VuZCAoZS5nLiA1IGZvciBhIGZpdmUtY2FyZCBDaGFybGllKTsgMCBkaXNhYmxlcyB0aGUgcnVsZS4AAAAAAA1jaGFybGllX2NhcmRzAAAAAAAABAAAAHhBIGhhbmQgcmVhY2hpbmcgdGhpcyBtYW55IGNhcmRzIGlzIHN0dWNrIGF1dG9tYXRpY2FsbHkgKDMgb3IgbW9yZSwKYW5kIG5vIGZld2VyIHRoYW4gYGNoYXJsaWVfY2FyZHNgKTsgMCBtZWFucyBubyBsaW1pdC4AAAAObWF4X2hhbmRfY2FyZHMAAAAAAAQAAABzU2VyaWVzIGxlbmd0aDogdGhlIGZpcnN0IHBsYXllciB0byB3aW4gYSBtYWpvcml0eSBvZiBgbWF4X3JvdW5kc2AKZGVjaWRlZCByb3VuZHMgdGFrZXMgdGhlIGdhbWUgKDEgPSBzaW5nbGUgcm91bmQpLgAAAAAKbWF4X3JvdW5kcwAAAAAABAAAAAAAAAAKdGllX3BvbGljeQAAAAAH0AAAAAlUaWVQb2xpY3kAAAAAAAA+VGltZSBhIHBsYXllciBtYXkgaWRsZSBiZWZvcmUgdGhlIG9wcG9uZW50IGNhbiBjbGFpbSB0aGUgZ2FtZS4AAAAAAA90aW1lb3V0X3NlY29uZHMAAAAABg==",
+        "AAAAAgAAAChXaGF0IGhhcHBlbnMgd2hlbiBhIHJvdW5kIGVuZHMgaW4gYSB0aWUuAAAAAAAAAAlUaWVQb2xpY3kAAAAAAAADAAAAAAAAABtEZWFsIGEgbmV3IHJvdW5kIChkZWZhdWx0KS4AAAAABlJlZGVhbAAAAAAAAAAAACZFbmQgdGhlIGdhbWUgYXMgYSBwdXNoIHdpdGggbm8gd2lubmVyLgAAAAAABFB1c2gAAAABAAAAMlJlZGVhbCB1cCB0byB0aGUgZ2l2ZW4gbnVtYmVyIG9mIHRpbWVzLCB0aGVuIHB1c2guAAAAAAALUmVkZWFsTGltaXQAAAAAAQAAAAQ=",
         "AAAAAAAAANRQbGF5ZXIgZHJhd3MgYW5vdGhlciBjYXJkICgiaGl0IikuCklmIHRoZSBwbGF5ZXIncyBoYW5kIHZhbHVlIGV4Y2VlZHMgMjEsIHRoZXkgYnVzdCBhbmQgbG9zZSBpbW1lZGlhdGVseS4KCiMgQXJndW1lbnRzCiogYHNlc3Npb25faWRgIC0gVGhlIHNlc3Npb24gSUQgb2YgdGhlIGdhbWUKKiBgcGxheWVyYCAtIEFkZHJlc3Mgb2YgdGhlIHBsYXllciBkcmF3aW5nIGEgY2FyZAAAAANoaXQAAAAAAgAAAAAAAAAKc2Vzc2lvbl9pZAAAAAAABAAAAAAAAAAGcGxheWVyAAAAAAATAAAAAQAAA+kAAAPtAAAAAAAAAAM=",
         "AAAAAAAAANZQbGF5ZXIgY2hvb3NlcyB0byBzdGljayAoZW5kIHRoZWlyIHR1cm4gd2l0aCBjdXJyZW50IGhhbmQpLgpJZiBib3RoIHBsYXllcnMgaGF2ZSBzdHVjaywgdGhlIGdhbWUgY2FuIGJlIHJldmVhbGVkLgoKIyBBcmd1bWVudHMKKiBgc2Vzc2lvbl9pZGAgLSBUaGUgc2Vzc2lvbiBJRCBvZiB0aGUgZ2FtZQoqIGBwbGF5ZXJgIC0gQWRkcmVzcyBvZiB0aGUgcGxheWVyIHN0aWNraW5nAAAAAAAFc3RpY2sAAAAAAAACAAAAAAAAAApzZXNzaW9uX2lkAAAAAAAEAAAAAAAAAAZwbGF5ZXIAAAAAABMAAAABAAAD6QAAA+0AAAAAAAAAAw==",
         "AAAAAAAAAF5HZXQgdGhlIGN1cnJlbnQgR2FtZUh1YiBjb250cmFjdCBhZGRyZXNzCgojIFJldHVybnMKKiBgQWRkcmVzc2AgLSBUaGUgR2FtZUh1YiBjb250cmFjdCBhZGRyZXNzAAAAAAAHZ2V0X2h1YgAAAAAAAAAAAQAAABM=",
@@ -225,9 +282,9 @@ export class Client extends ContractClient {
         "AAAAAAAAAJ9HZXQgZ2FtZSBpbmZvcm1hdGlvbi4KCiMgQXJndW1lbnRzCiogYHNlc3Npb25faWRgIC0gVGhlIHNlc3Npb24gSUQgb2YgdGhlIGdhbWUKCiMgUmV0dXJucwoqIGBHYW1lYCAtIFRoZSBnYW1lIHN0YXRlIChpbmNsdWRlcyBoYW5kcyBhbmQgd2lubmVyIGFmdGVyIGdhbWUgZW5kcykAAAAACGdldF9nYW1lAAAAAQAAAAAAAAAKc2Vzc2lvbl9pZAAAAAAABAAAAAEAAAPpAAAH0AAAAARHYW1lAAAAAw==",
         "AAAAAAAAAEhHZXQgdGhlIGN1cnJlbnQgYWRtaW4gYWRkcmVzcwoKIyBSZXR1cm5zCiogYEFkZHJlc3NgIC0gVGhlIGFkbWluIGFkZHJlc3MAAAAJZ2V0X2FkbWluAAAAAAAAAAAAAAEAAAAT",
         "AAAAAAAAAEpTZXQgYSBuZXcgYWRtaW4gYWRkcmVzcwoKIyBBcmd1bWVudHMKKiBgbmV3X2FkbWluYCAtIFRoZSBuZXcgYWRtaW4gYWRkcmVzcwAAAAAACXNldF9hZG1pbgAAAAAAAAEAAAAAAAAACW5ld19hZG1pbgAAAAAAABMAAAAA",
-        "AAAAAAAAAlFTdGFydCBhIG5ldyBnYW1lIGJldHdlZW4gdHdvIHBsYXllcnMgd2l0aCBwb2ludHMuClRoaXMgY3JlYXRlcyBhIHNlc3Npb24gaW4gdGhlIEdhbWUgSHViIGFuZCBsb2NrcyBwb2ludHMgYmVmb3JlIHN0YXJ0aW5nIHRoZSBnYW1lLgpFYWNoIHBsYXllciBpcyBkZWFsdCAyIGNhcmRzIHRvIHN0YXJ0LgoKKipDUklUSUNBTDoqKiBUaGlzIG1ldGhvZCByZXF1aXJlcyBhdXRob3JpemF0aW9uIGZyb20gVEhJUyBjb250cmFjdCAobm90IHBsYXllcnMpLgpUaGUgR2FtZSBIdWIgd2lsbCBjYWxsIGBnYW1lX2lkLnJlcXVpcmVfYXV0aCgpYCB3aGljaCBjaGVja3MgdGhpcyBjb250cmFjdCdzIGFkZHJlc3MuCgojIEFyZ3VtZW50cwoqIGBzZXNzaW9uX2lkYCAtIFVuaXF1ZSBzZXNzaW9uIGlkZW50aWZpZXIgKHUzMikKKiBgcGxheWVyMWAgLSBBZGRyZXNzIG9mIGZpcnN0IHBsYXllcgoqIGBwbGF5ZXIyYCAtIEFkZHJlc3Mgb2Ygc2Vjb25kIHBsYXllcgoqIGBwbGF5ZXIxX3BvaW50c2AgLSBQb2ludHMgYW1vdW50IGNvbW1pdHRlZCBieSBwbGF5ZXIgMQoqIGBwbGF5ZXIyX3BvaW50c2AgLSBQb2ludHMgYW1vdW50IGNvbW1pdHRlZCBieSBwbGF5ZXIgMgAAAAAAAApzdGFydF9nYW1lAAAAAAAFAAAAAAAAAApzZXNzaW9uX2lkAAAAAAAEAAAAAAAAAAdwbGF5ZXIxAAAAABMAAAAAAAAAB3BsYXllcjIAAAAAEwAAAAAAAAAOcGxheWVyMV9wb2ludHMAAAAAAAsAAAAAAAAADnBsYXllcjJfcG9pbnRzAAAAAAALAAAAAQAAA+kAAAPtAAAAAAAAAAM=",
+        "AAAAAAAAAxxTdGFydCBhIG5ldyBnYW1lIGJldHdlZW4gdHdvIHBsYXllcnMgd2l0aCBwb2ludHMuClRoaXMgY3JlYXRlcyBhIHNlc3Npb24gaW4gdGhlIEdhbWUgSHViIGFuZCBsb2NrcyBwb2ludHMgYmVmb3JlIHN0YXJ0aW5nIHRoZSBnYW1lLgpFYWNoIHBsYXllciBpcyBkZWFsdCAyIGNhcmRzIHRvIHN0YXJ0OyBhIG5hdHVyYWwgKHR3by1jYXJkIDIxKSBkZWFsdAp0byBvbmx5IG9uZSBwbGF5ZXIgd2lucyB0aGUgcm91bmQgaW1tZWRpYXRlbHkuCgoqKkNSSVRJQ0FMOioqIFRoaXMgbWV0aG9kIHJlcXVpcmVzIGF1dGhvcml6YXRpb24gZnJvbSBUSElTIGNvbnRyYWN0IChub3QgcGxheWVycykuClRoZSBHYW1lIEh1YiB3aWxsIGNhbGwgYGdhbWVfaWQucmVxdWlyZV9hdXRoKClgIHdoaWNoIGNoZWNrcyB0aGlzIGNvbnRyYWN0J3MgYWRkcmVzcy4KCiMgQXJndW1lbnRzCiogYHNlc3Npb25faWRgIC0gVW5pcXVlIHNlc3Npb24gaWRlbnRpZmllciAodTMyKQoqIGBwbGF5ZXIxYCAtIEFkZHJlc3Mgb2YgZmlyc3QgcGxheWVyCiogYHBsYXllcjJgIC0gQWRkcmVzcyBvZiBzZWNvbmQgcGxheWVyCiogYHBsYXllcjFfcG9pbnRzYCAtIFBvaW50cyBhbW91bnQgY29tbWl0dGVkIGJ5IHBsYXllciAxCiogYHBsYXllcjJfcG9pbnRzYCAtIFBvaW50cyBhbW91bnQgY29tbWl0dGVkIGJ5IHBsYXllciAyCiogYHJ1bGVzYCAtIFJ1bGVzIGZvciB0aGlzIGdhbWU7IGBOb25lYCB1c2VzIHRoZSBjb250cmFjdCBkZWZhdWx0cy4KQm90aCBwbGF5ZXJzIGF1dGhvcml6ZSB0aGUgcnVsZXMgYWxvbmcgd2l0aCB0aGVpciBwb2ludHMuAAAACnN0YXJ0X2dhbWUAAAAAAAYAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAAAAAAAB3BsYXllcjEAAAAAEwAAAAAAAAAHcGxheWVyMgAAAAATAAAAAAAAAA5wbGF5ZXIxX3BvaW50cwAAAAAACwAAAAAAAAAOcGxheWVyMl9wb2ludHMAAAAAAAsAAAAAAAAABXJ1bGVzAAAAAAAD6AAAB9AAAAAJR2FtZVJ1bGVzAAAAAAAAAQAAA+kAAAACAAAAAw==",
         "AAAAAAAAAKNJbml0aWFsaXplIHRoZSBjb250cmFjdCB3aXRoIEdhbWVIdWIgYWRkcmVzcyBhbmQgYWRtaW4KCiMgQXJndW1lbnRzCiogYGFkbWluYCAtIEFkbWluIGFkZHJlc3MgKGNhbiB1cGdyYWRlIGNvbnRyYWN0KQoqIGBnYW1lX2h1YmAgLSBBZGRyZXNzIG9mIHRoZSBHYW1lSHViIGNvbnRyYWN0AAAAAA1fX2NvbnN0cnVjdG9yAAAAAAAAAgAAAAAAAAAFYWRtaW4AAAAAAAATAAAAAAAAAAhnYW1lX2h1YgAAABMAAAAA",
-        "AAAAAAAAAUpSZXZlYWwgdGhlIHdpbm5lciBvZiB0aGUgZ2FtZSBhbmQgc3VibWl0IG91dGNvbWUgdG8gR2FtZUh1Yi4KQ2FuIG9ubHkgYmUgY2FsbGVkIGFmdGVyIGJvdGggcGxheWVycyBoYXZlIHN0dWNrLgpUaGlzIGNhbGN1bGF0ZXMgaGFuZCB2YWx1ZXMsIGRldGVybWluZXMgdGhlIHdpbm5lciAoY2xvc2VzdCB0byAyMSksCmFuZCBoYW5kbGVzIGRyYXdzIGJ5IGRlYWxpbmcgbmV3IGhhbmRzLgoKIyBBcmd1bWVudHMKKiBgc2Vzc2lvbl9pZGAgLSBUaGUgc2Vzc2lvbiBJRCBvZiB0aGUgZ2FtZQoKIyBSZXR1cm5zCiogYEFkZHJlc3NgIC0gQWRkcmVzcyBvZiB0aGUgd2lubmluZyBwbGF5ZXIAAAAAAA1yZXZlYWxfd2lubmVyAAAAAAAAAQAAAAAAAAAKc2Vzc2lvbl9pZAAAAAAABAAAAAEAAAPpAAAAEwAAAAM=",
+        "AAAAAAAABABSZXZlYWwgdGhlIHdpbm5lciBvZiB0aGUgcm91bmQgYW5kIHN1Ym1pdCBvdXRjb21lIHRvIEdhbWVIdWIgb25jZQp0aGUgc2VyaWVzIGlzIGRlY2lkZWQuCkNhbiBvbmx5IGJlIGNhbGxlZCBhZnRlciBib3RoIHBsYXllcnMgaGF2ZSBzdHVjay4KVGhpcyBjYWxjdWxhdGVzIGhhbmQgdmFsdWVzLCBkZXRlcm1pbmVzIHRoZSB3aW5uZXIgKGNsb3Nlc3QgdG8gMjEpLAphbmQgaGFuZGxlcyB0aWVzIGFjY29yZGluZyB0byB0aGUgYFRpZVBvbGljeWA6IGJ5IGRlYWxpbmcgbmV3IGhhbmRzCm9yIGVuZGluZyB0aGUgZ2FtZSBhcyBhIHB1c2guIFdpdGggc3BsaXQgaGFuZHMsIGV2ZXJ5IGhhbmQgaXMgY29tcGFyZWQKYWdhaW5zdCBlYWNoIG9mIHRoZSBvcHBvbmVudCdzIGFuZCB0aGUgd2lucyBhbmQgbG9zc2VzIGFyZSBzdW1tZWQuIEluCmEgYmVzdC1vZi1OIHNlcmllcyBhbiB1bmRlY2lkZWQgZ2FtZSBjb250aW51ZXMgd2l0aCBhIGZyZXNobHkgZGVhbHQKcm91bmQuCgpPbmx5IHRoZSBwbGF5ZXJzIG9yIHRoZSBhZG1pbiBtYXkgcmV2ZWFsLCB1bnRpbCBib3RoIHBsYXllcnMgaGF2ZQpiZWVuIHN0dWNrIGZvciB0aGUgZ2FtZSdzIGFjdGlvbiB0aW1lb3V0OyBhZnRlciB0aGF0IGFueW9uZSBjYW4sIHNvCmEgZmluaXNoZWQgZ2FtZSBjYW5ub3QgYmUgbGVmdCB1bnJldmVhbGVkLiBJbiBhIHNlZWRlZCBob3VzZSBnYW1lIHRoZQpkZWFsZXIncyBkcmF3cyB3YWl0IGZvciB0aGUgb3BlcmF0b3IncyBuZXh0IGxpbmssIGFuZCB0aGUgcm91bmQgaXMKZGVjaWRlZCBieSBgcmV2ZWFsX3NlZWRgLgoKIyBBcmd1bWVudHMKKiBgc2Vzc2lvbl9pZGAgLSBUaGUgc2Vzc2lvbiBJRCBvZiB0aGUgZ2FtZQoqIGBjYWxsZXJgIC0gQWRkcmVzcyByZXZlYWxpbmcgdGhlIHdpbm5lcgoKIyBSZXR1cm5zCiogYE9wdGlvbjxBZGRyZXNzPmAgLSBBZGRyZXNzIG9mIHRoZSByb3VuZCB3aW5uZXIgKGNoZWNrIGdhbWUud2lubmVyIGZvcgp0aGUgZ2FtZSksIG9yIGBOb25lYCBpZiB0aGUgcm91bmQgd2FzIHRpZWQgb3Igd2FpdHMgAAAADXJldmVhbF93aW5uZXIAAAAAAAACAAAAAAAAAApzZXNzaW9uX2lkAAAAAAAEAAAAAAAAAAZjYWxsZXIAAAAAABMAAAABAAAD6QAAA+gAAAATAAAAAw==",
         "AAAAAAAAAL5HZXQgdGhlIGN1cnJlbnQgaGFuZCB2YWx1ZSBmb3IgYSBwbGF5ZXIuCgojIEFyZ3VtZW50cwoqIGBzZXNzaW9uX2lkYCAtIFRoZSBzZXNzaW9uIElEIG9mIHRoZSBnYW1lCiogYHBsYXllcmAgLSBBZGRyZXNzIG9mIHRoZSBwbGF5ZXIKCiMgUmV0dXJucwoqIGB1MzJgIC0gVGhlIHRvdGFsIHZhbHVlIG9mIHRoZSBwbGF5ZXIncyBoYW5kAAAAAAAOZ2V0X2hhbmRfdmFsdWUAAAAAAAIAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAAAAAAABnBsYXllcgAAAAAAEwAAAAEAAAPpAAAABAAAAAM=" ]),
       options
     )
@@ -242,7 +299,7 @@ export class Client extends ContractClient {
         get_admin: this.txFromJSON<string>,
         set_admin: this.txFromJSON<null>,
         start_game: this.txFromJSON<Result<void>>,
-        reveal_winner: this.txFromJSON<Result<string>>,
+        reveal_winner: this.txFromJSON<Result<Option<string>>>,
         get_hand_value: this.txFromJSON<Result<u32>>
   }
 }
\ No newline at end of file
@@ -113,6 +113,7 @@ export class TwentyOneService {
       player2,
       player1_points: player1Points,
       player2_points: player2Points,
+      rules: undefined,
     }, DEFAULT_METHOD_OPTIONS);
 
     console.log('[prepareStartGame] Transaction built and simulated');
@@ -225,6 +226,7 @@ export class TwentyOneService {
       player2,
       player1_points: gameParams.player1Points,
       player2_points: player2Points,
+      rules: undefined,
     }, DEFAULT_METHOD_OPTIONS);
 
     // Import Player 1's signed auth entry
@@ -274,6 +276,7 @@ export class TwentyOneService {
       // - signer address (from credentials)
       // - session_id (arg 0)
       // - signer points (arg 1)
+      // - rules (arg 2), always None (contract defaults) for games started here
       const credentials = authEntry.credentials();
       if (credentials.switch().name !== 'sorobanCredentialsAddress') {
         throw new Error(`Unsupported credentials type: ${credentials.switch().name}`);
@@ -290,8 +293,11 @@ export class TwentyOneService {
       }
 
       const args = contractFn.args();
-      if (args.length !== 2) {
-        throw new Error(`Invalid number of arguments: ${args.length}. Expected 2`);
+      if (args.length !== 3) {
+        throw new Error(`Invalid number of arguments: ${args.length}. Expected 3`);
+      }
+      if (args[2].switch().name !== 'scvVoid') {
+        throw new Error('Unsupported game rules: only default rules are supported');
       }
 
       const sessionId = args[0].u32();
@@ -365,6 +371,7 @@ export class TwentyOneService {
     const client = this.createSigningClient(player, signer);
     const tx = await client.reveal_winner({
       session_id: sessionId,
+      caller: player,
     }, DEFAULT_METHOD_OPTIONS);
 
     const validUntilLedgerSeq = await calculateValidUntilLedger(RPC_URL, DEFAULT_AUTH_TTL_MINUTES);